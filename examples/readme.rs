@@ -30,7 +30,7 @@ fn main() -> Result<(), Box<dyn Error>>{
     assert_delta_eq!(mylab, lab1, DE2000, 0.0, "mylab is not equal to lab1!");
 
     // Calculate DeltaE between two lab values
-    let de0 = DeltaE::new(&lab0, &lab1, DE2000);
+    let de0 = DeltaE::new(lab0, lab1, DE2000);
     // Use the Delta trait
     let de1 = lab0.delta(lab1, DE2000);
     assert_eq!(de0, de1);
@@ -39,8 +39,8 @@ fn main() -> Result<(), Box<dyn Error>>{
     let lch0 = LchValue::from(lab0);
     let xyz0 = XyzValue::from(lab1);
     // If DE2000 is less than 1.0, the colors are considered equivalent
-    assert!(lch0.delta_eq(&lab0, DE2000, 1.0));
-    assert!(xyz0.delta_eq(&lab1, DE2000, 1.0));
+    assert!(lch0.delta_eq(lab0, DE2000, 1.0));
+    assert!(xyz0.delta_eq(lab1, DE2000, 1.0));
 
     // Calculate DeltaE between different color types
     let de2 = lch0.delta(xyz0, DE2000);
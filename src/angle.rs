@@ -0,0 +1,136 @@
+//! Angles with their units in the type. The hue of an [`LchValue`] is in degrees, while the
+//! trigonometric functions of [`f32`] take radians. [`Degrees`] and [`Radians`] convert into each
+//! other, so a function that takes `impl Into<Radians>` accepts either. [`hue_diff`] is the
+//! difference between two hue angles the short way around the hue circle.
+//!
+//! # Examples
+//!
+//! ```
+//! use deltae::*;
+//!
+//! let mut lch = LchValue::new(50.0, 20.0, 90.0).unwrap();
+//! assert_eq!(lch.hue(), Degrees(90.0));
+//! assert!((Radians::from(lch.hue()).0 - std::f32::consts::FRAC_PI_2).abs() < 1e-6);
+//!
+//! // Hues are normalized to 0..360 degrees
+//! lch.set_hue(Degrees(-30.0));
+//! assert_eq!(lch.h, 330.0);
+//! lch.set_hue(Radians(std::f32::consts::PI));
+//! assert_eq!(lch.h, 180.0);
+//! ```
+
+use crate::*;
+
+/// An angle in degrees
+#[derive(Debug, Clone, Copy, PartialEq, PartialOrd, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Degrees(pub f32);
+
+/// An angle in radians
+#[derive(Debug, Clone, Copy, PartialEq, PartialOrd, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Radians(pub f32);
+
+impl Degrees {
+    /// The equivalent angle from 0° up to, but not including, 360°
+    /// ```
+    /// use deltae::*;
+    ///
+    /// assert_eq!(Degrees(370.0).normalized(), Degrees(10.0));
+    /// assert_eq!(Degrees(-90.0).normalized(), Degrees(270.0));
+    /// assert_eq!(Degrees(360.0).normalized(), Degrees(0.0));
+    /// ```
+    pub fn normalized(self) -> Degrees {
+        Degrees(normalize(self.0, 360.0))
+    }
+
+    /// The sine and cosine of the angle
+    pub fn sin_cos(self) -> (f32, f32) {
+        Radians::from(self).sin_cos()
+    }
+}
+
+impl Radians {
+    /// The equivalent angle from 0 up to, but not including, 2π
+    pub fn normalized(self) -> Radians {
+        Radians(normalize(self.0, std::f32::consts::TAU))
+    }
+
+    /// The sine and cosine of the angle
+    pub fn sin_cos(self) -> (f32, f32) {
+        self.0.sin_cos()
+    }
+}
+
+impl From<Radians> for Degrees {
+    fn from(radians: Radians) -> Degrees {
+        Degrees(radians.0.to_degrees())
+    }
+}
+
+impl From<Degrees> for Radians {
+    fn from(degrees: Degrees) -> Radians {
+        Radians(degrees.0.to_radians())
+    }
+}
+
+impl fmt::Display for Degrees {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}°", self.0)
+    }
+}
+
+impl fmt::Display for Radians {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{} rad", self.0)
+    }
+}
+
+impl LchValue {
+    /// The hue angle
+    pub fn hue(&self) -> Degrees {
+        Degrees(self.h)
+    }
+
+    /// The signed difference in hue angle from the color to another, along the shorter arc. See
+    /// [`hue_diff`].
+    /// ```
+    /// use deltae::*;
+    ///
+    /// let red = LchValue::new(50.0, 60.0, 20.0).unwrap();
+    /// let magenta = LchValue::new(50.0, 60.0, 340.0).unwrap();
+    /// assert_eq!(red.hue_delta(magenta), Degrees(-40.0));
+    /// assert_eq!(magenta.hue_delta(red), Degrees(40.0));
+    /// ```
+    pub fn hue_delta(&self, other: LchValue) -> Degrees {
+        Degrees(hue_diff(self.h, other.h))
+    }
+
+    /// Set the hue angle from [`Degrees`] or [`Radians`], normalized to 0..360°
+    pub fn set_hue<A: Into<Degrees>>(&mut self, hue: A) {
+        self.h = hue.into().normalized().0;
+    }
+}
+
+/// The signed difference from one hue angle to another in degrees, along the shorter arc of the
+/// hue circle: positive when `h2` is counter-clockwise of `h1`, in `-180.0..=180.0`. Hues exactly
+/// opposite each other are `180.0` apart.
+/// ```
+/// use deltae::*;
+///
+/// assert_eq!(hue_diff(10.0, 30.0), 20.0);
+/// assert_eq!(hue_diff(350.0, 10.0), 20.0);
+/// assert_eq!(hue_diff(10.0, 350.0), -20.0);
+/// assert_eq!(hue_diff(0.0, 180.0), 180.0);
+/// assert_eq!(hue_diff(-90.0, 450.0), 180.0);
+/// ```
+pub fn hue_diff(h1: f32, h2: f32) -> f32 {
+    let diff = normalize(h2 - h1, 360.0);
+    if diff > 180.0 { diff - 360.0 } else { diff }
+}
+
+// rem_euclid can round up to the full turn for tiny negative angles
+fn normalize(angle: f32, turn: f32) -> f32 {
+    let normalized = angle.rem_euclid(turn);
+    if normalized >= turn { 0.0 } else { normalized }
+}
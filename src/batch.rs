@@ -0,0 +1,220 @@
+//! Reading and writing lists of colors as CSV, and calculating [`DeltaE`] between two lists.
+//! Requires the `csv` feature.
+//!
+//! Each row is a color with an optional ID in the first column: `id,L,a,b` or `L,a,b`. A header
+//! row is detected and skipped when none of its color columns is a number.
+//!
+//! # Examples
+//!
+//! ```
+//! use deltae::*;
+//! use deltae::batch::{self, Pairing};
+//!
+//! let references = "id,L,a,b\nwhite,95.08,-0.17,-10.81\ngray,50,0,0\n";
+//! let samples = "id,L,a,b\ngray,51,0.5,-0.5\nwhite,89.73,1.88,-6.96\n";
+//!
+//! let references = batch::read::<LabValue, _>(references.as_bytes()).unwrap();
+//! let samples = batch::read::<LabValue, _>(samples.as_bytes()).unwrap();
+//!
+//! let comparisons = batch::compare(&references, &samples, DE2000, Pairing::Id).unwrap();
+//! assert_eq!(comparisons[0].id.as_deref(), Some("white"));
+//! assert_eq!(comparisons[0].delta.round_to(4), 5.3169);
+//!
+//! let mut csv = Vec::new();
+//! batch::write_comparisons(&mut csv, &comparisons).unwrap();
+//! ```
+
+use std::collections::HashMap;
+use std::io;
+use std::str::FromStr;
+
+use crate::*;
+
+/// A color read from a row of a CSV file
+#[derive(Debug, Clone, PartialEq)]
+pub struct Record<C> {
+    /// The ID in the first column, if the row has one
+    pub id: Option<String>,
+    /// The color
+    pub color: C,
+    /// The line number of the row, starting at 1
+    pub line: u64,
+}
+
+/// How the rows of a reference list are paired with the rows of a sample list
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Pairing {
+    /// Pair rows in the order they appear
+    #[default]
+    Position,
+    /// Pair rows with the same ID, in the order of the references
+    Id,
+}
+
+/// The [`DeltaE`] between a reference row and its paired sample row
+#[derive(Debug, Clone)]
+pub struct Comparison {
+    /// The ID of the reference row
+    pub id: Option<String>,
+    /// The color difference between the reference and the sample
+    pub delta: DeltaE,
+}
+
+/// The color types that can be written to CSV
+pub trait Components: ColorValue {
+    /// The names of the components, used as the header
+    const LABELS: [&'static str; 3];
+}
+
+macro_rules! impl_components {
+    ($t:ty, [$($label:expr),*]) => {
+        impl Components for $t {
+            const LABELS: [&'static str; 3] = [$($label),*];
+        }
+    }
+}
+
+impl_components!(LabValue, ["L", "a", "b"]);
+impl_components!(LchValue, ["L", "c", "h"]);
+impl_components!(XyzValue, ["X", "Y", "Z"]);
+#[cfg(feature = "rgb")]
+impl_components!(RgbValue, ["R", "G", "B"]);
+#[cfg(feature = "rgb")]
+impl_components!(Rgb16Value, ["R", "G", "B"]);
+#[cfg(feature = "rgb")]
+impl_components!(RgbNominalValue, ["R", "G", "B"]);
+
+/// Read a list of colors from CSV.
+/// Will return `Err()` if the CSV is malformed, a row does not have 3 or 4 columns, or a color is
+/// invalid.
+pub fn read<C, R>(reader: R) -> ValueResult<Vec<Record<C>>>
+where C: FromStr<Err = ValueError>, R: io::Read {
+    let mut reader = csv::ReaderBuilder::new()
+        .has_headers(false)
+        .flexible(true)
+        .trim(csv::Trim::All)
+        .from_reader(reader);
+
+    let mut records = Vec::new();
+    for (i, row) in reader.records().enumerate() {
+        let row = row?;
+        let line = row.position().map_or(i as u64 + 1, csv::Position::line);
+
+        let (id, components) = match row.len() {
+            3 => (None, [&row[0], &row[1], &row[2]]),
+            4 => (Some(row[0].to_string()), [&row[1], &row[2], &row[3]]),
+            _ => return Err(ValueError::Row { line, error: Box::new(ValueError::BadFormat) }),
+        };
+
+        if i == 0 && components.iter().all(|c| c.parse::<f32>().is_err()) {
+            continue;
+        }
+
+        let color = C::from_str(&components.join(","))
+            .map_err(|error| ValueError::Row { line, error: Box::new(error) })?;
+
+        records.push(Record { id, color, line });
+    }
+
+    Ok(records)
+}
+
+/// Write a list of colors to CSV, with a header row. The ID column is written if any of the
+/// records has an ID.
+pub fn write<C, W>(writer: W, records: &[Record<C>]) -> ValueResult<()>
+where C: Components, W: io::Write {
+    let mut writer = csv::Writer::from_writer(writer);
+    let with_id = records.iter().any(|r| r.id.is_some());
+
+    let mut header = if with_id { vec!["id"] } else { Vec::new() };
+    header.extend_from_slice(&C::LABELS);
+    writer.write_record(header)?;
+
+    for record in records {
+        let mut row = if with_id { vec![record.id.clone().unwrap_or_default()] } else { Vec::new() };
+        row.extend(record.color.components().iter().map(f32::to_string));
+        writer.write_record(row)?;
+    }
+
+    writer.flush()?;
+    Ok(())
+}
+
+/// Pair the rows of two lists and calculate the [`DeltaE`] of each pair.
+/// Will return `Err()` if a row has no counterpart in the other list, or when pairing by
+/// [`Pairing::Id`], if a row has no ID or a sample ID is repeated.
+pub fn compare<C, D>(
+    references: &[Record<C>],
+    samples: &[Record<D>],
+    method: DEMethod,
+    pairing: Pairing,
+) -> ValueResult<Vec<Comparison>>
+where C: Delta + Copy, D: Delta + Copy {
+    let unpaired = |id: &Option<String>, line: u64| ValueError::Unpaired { id: id.clone(), line };
+
+    let pairs: Vec<(&Record<C>, &Record<D>)> = match pairing {
+        Pairing::Position => {
+            if let Some(r) = references.get(samples.len()) {
+                return Err(unpaired(&r.id, r.line));
+            }
+            if let Some(s) = samples.get(references.len()) {
+                return Err(unpaired(&s.id, s.line));
+            }
+
+            references.iter().zip(samples).collect()
+        }
+        Pairing::Id => {
+            let mut by_id = HashMap::new();
+            for sample in samples {
+                let id = sample.id.as_deref().ok_or_else(|| unpaired(&sample.id, sample.line))?;
+                if by_id.insert(id, sample).is_some() {
+                    return Err(ValueError::Duplicate { id: id.to_string(), line: sample.line });
+                }
+            }
+
+            let pairs = references
+                .iter()
+                .map(|r| {
+                    r.id.as_deref()
+                        .and_then(|id| by_id.remove(id))
+                        .map(|s| (r, s))
+                        .ok_or_else(|| unpaired(&r.id, r.line))
+                })
+                .collect::<Result<Vec<_>, _>>()?;
+
+            if let Some(s) = by_id.values().min_by_key(|s| s.line) {
+                return Err(unpaired(&s.id, s.line));
+            }
+
+            pairs
+        }
+    };
+
+    Ok(pairs
+        .into_iter()
+        .map(|(r, s)| Comparison { id: r.id.clone(), delta: r.color.delta(s.color, method) })
+        .collect())
+}
+
+/// Write a list of [`Comparison`]s to CSV, with a header row. Each row holds the ID, the
+/// reference and sample [`LabValue`]s, the [`DEMethod`], and the [`DeltaE`].
+pub fn write_comparisons<W: io::Write>(writer: W, comparisons: &[Comparison]) -> ValueResult<()> {
+    let mut writer = csv::Writer::from_writer(writer);
+    writer.write_record([
+        "id", "reference_L", "reference_a", "reference_b",
+        "sample_L", "sample_a", "sample_b", "method", "delta_e",
+    ])?;
+
+    for comparison in comparisons {
+        let delta = &comparison.delta;
+        let mut row = vec![comparison.id.clone().unwrap_or_default()];
+        row.extend(delta.reference().components().iter().map(f32::to_string));
+        row.extend(delta.sample().components().iter().map(f32::to_string));
+        row.push(delta.method().to_string());
+        row.push(delta.value().to_string());
+        writer.write_record(row)?;
+    }
+
+    writer.flush()?;
+    Ok(())
+}
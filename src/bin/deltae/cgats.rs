@@ -0,0 +1,93 @@
+use crate::summary;
+use clap::{Args, ValueEnum};
+use deltae::cgats::{Cgats as CgatsFile, Patch};
+use deltae::*;
+use std::collections::HashMap;
+use std::error::Error;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::str::FromStr;
+
+/// Compare the Lab or XYZ patches of a reference and a sample CGATS file by SAMPLE_ID
+#[derive(Args)]
+pub struct Cgats {
+    /// CGATS file of reference measurements
+    reference: PathBuf,
+
+    /// CGATS file of sample measurements
+    sample: PathBuf,
+
+    /// Set DeltaE method: 2000, 1994, 1994T, CMC1, CMC2, CMC(l:c), or 1976
+    #[arg(short, long, default_value = "2000")]
+    method: DEMethod,
+
+    /// Set the order of the patches
+    #[arg(long, value_enum, default_value_t = Sort::Reference)]
+    sort: Sort,
+}
+
+/// The order of the patches in the table
+#[derive(Debug, Clone, Copy, PartialEq, ValueEnum)]
+pub enum Sort {
+    /// The order of the reference file
+    Reference,
+    /// Largest Delta E first
+    Delta,
+}
+
+impl Cgats {
+    /// Print the Delta E of each patch as a tab separated table, followed by the summary statistics
+    pub fn run(&self) -> Result<(), Box<dyn Error>> {
+        let references = read(&self.reference)?;
+        let samples = read(&self.sample)?;
+
+        // Pair the patches by SAMPLE_ID, in the order of the references
+        let mut by_id = HashMap::new();
+        for sample in &samples {
+            if by_id.insert(sample.id.as_str(), sample.color).is_some() {
+                return Err(format!("duplicate sample SAMPLE_ID {:?}", sample.id).into());
+            }
+        }
+
+        let mut rows = references
+            .iter()
+            .map(|r| match by_id.remove(r.id.as_str()) {
+                Some(sample) => Ok((r.id.as_str(), r.color.delta(sample, self.method))),
+                None => Err(format!("no sample for SAMPLE_ID {:?}", r.id)),
+            })
+            .collect::<Result<Vec<(&str, DeltaE)>, _>>()?;
+
+        if let Some(id) = samples.iter().map(|s| s.id.as_str()).find(|id| by_id.contains_key(id)) {
+            return Err(format!("no reference for SAMPLE_ID {:?}", id).into());
+        }
+
+        if self.sort == Sort::Delta {
+            rows.sort_by(|(_, a), (_, b)| b.value().total_cmp(a.value()));
+        }
+
+        println!("SAMPLE_ID\tDE\tDL\tDa\tDb\tDC\tDH");
+        for (id, delta) in &rows {
+            let report = delta.report(f32::INFINITY);
+            println!(
+                "{}\t{}\t{}\t{}\t{}\t{}\t{}",
+                id, report.delta_e, report.delta_l, report.delta_a, report.delta_b, report.delta_c,
+                report.delta_h,
+            );
+        }
+
+        println!();
+        let deltas: Vec<DeltaE> = rows.iter().map(|(_, delta)| *delta).collect();
+        summary::print(self.method, &deltas);
+
+        Ok(())
+    }
+}
+
+fn read(path: &Path) -> Result<Vec<Patch>, Box<dyn Error>> {
+    let s = fs::read_to_string(path).map_err(|e| format!("{}: {}", path.display(), e))?;
+    let patches = CgatsFile::from_str(&s)
+        .and_then(|cgats| cgats.patches())
+        .map_err(|e| format!("{}: {}", path.display(), e))?;
+
+    Ok(patches)
+}
@@ -0,0 +1,73 @@
+use crate::cgats::Cgats;
+use crate::conditions::{ColorType, Conditions};
+use crate::convert::Convert;
+use crate::output::Output;
+use crate::stats::Stats;
+use clap::{Args, Parser, Subcommand};
+use clap_complete::Shell;
+use deltae::*;
+
+#[derive(Parser)]
+#[command(name = "deltae", version, author, about, args_conflicts_with_subcommands = true)]
+pub struct Cli {
+    #[command(subcommand)]
+    pub command: Option<Command>,
+
+    #[command(flatten)]
+    pub compare: Compare,
+}
+
+#[derive(Subcommand)]
+pub enum Command {
+    Convert(Convert),
+    Stats(Stats),
+    Cgats(Cgats),
+    /// Print a shell completion script
+    Completions {
+        /// The shell to complete
+        #[arg(value_enum)]
+        shell: Shell,
+    },
+    /// Print the man page in roff format
+    Man,
+}
+
+/// Calculate Delta E between two colors, or between pairs of colors read from stdin
+#[derive(Args)]
+pub struct Compare {
+    /// Set DeltaE method: 2000, 1994, 1994T, CMC1, CMC2, CMC(l:c), or 1976
+    #[arg(short, long, default_value = "2000")]
+    pub method: DEMethod,
+
+    /// Reference color values, or - to read one per line from stdin. Without colors, pairs are
+    /// read from stdin as REFERENCE<TAB>SAMPLE. Colors may be prefixed with their type, as in
+    /// lab:50,2,-3 or rgb:#aabbcc
+    #[arg(requires = "color1")]
+    pub color0: Option<String>,
+
+    /// Sample color values, or - to read one per line from stdin
+    pub color1: Option<String>,
+
+    /// Set color type
+    #[arg(short, long, value_enum, default_value_t = ColorType::Lab, aliases = ["color", "type"])]
+    pub color_type: ColorType,
+
+    /// Set color type of the reference, overriding --color-type
+    #[arg(long, value_enum)]
+    pub type0: Option<ColorType>,
+
+    /// Set color type of the sample, overriding --color-type
+    #[arg(long, value_enum)]
+    pub type1: Option<ColorType>,
+
+    /// Show the colors as truecolor blocks in plain output
+    #[arg(short, long)]
+    pub swatch: bool,
+
+    /// Set output format
+    #[arg(short, long, value_enum, default_value_t = Output::Plain)]
+    pub output: Output,
+
+    #[command(flatten)]
+    pub conditions: Conditions,
+}
@@ -0,0 +1,84 @@
+use clap::{Args, ValueEnum};
+use deltae::*;
+use std::error::Error;
+use std::str::FromStr;
+
+/// The types of colors that can be read
+#[derive(Debug, Clone, Copy, PartialEq, ValueEnum)]
+pub enum ColorType {
+    Lab,
+    Lch,
+    Xyz,
+    Rgb,
+}
+
+/// The conditions colors are converted under: Lab, LCh, and XYZ values are relative to the white
+/// point of the illuminant for the observer, and RGB values are in the RGB system, adapted to and
+/// from its white point with the chromatic adaptation method.
+#[derive(Debug, Clone, Args)]
+pub struct Conditions {
+    /// Set the illuminant of the white point of Lab, LCh, and XYZ values: a, b, c, d50, d55, d65,
+    /// d75, e, f2, f7, f11, dci, or aces
+    #[arg(short, long, default_value = "d50")]
+    illuminant: Illuminant,
+
+    /// Set the standard observer of the white point of Lab, LCh, and XYZ values: 2 or 10
+    #[arg(long, default_value = "2")]
+    observer: Observer,
+
+    /// Set the RGB color space of RGB values, such as srgb, adobe1998, displayp3, or prophoto
+    #[arg(short, long, default_value = "srgb")]
+    rgb_system: RgbSystem,
+
+    /// Set the chromatic adaptation method used between the white point of Lab, LCh, and XYZ
+    /// values and the white point of the RGB color space
+    #[arg(long, default_value = "bradford")]
+    cat: ChromaticAdaptationMethod,
+}
+
+impl Conditions {
+    /// Parse a color of a type to an [`XyzValue`] relative to the reference white
+    pub fn xyz(&self, color_type: ColorType, s: &str) -> Result<XyzValue, Box<dyn Error>> {
+        Ok(match color_type {
+            ColorType::Lab | ColorType::Lch => {
+                XyzValue::from_lab_with_white_point(self.lab(color_type, s)?, self.white())
+            }
+            ColorType::Xyz => XyzValue::from_str(s)?,
+            ColorType::Rgb => self.xyz_from_rgb(RgbValue::from_str(s)?.into()),
+        })
+    }
+
+    /// Parse a color of a type to a [`LabValue`] relative to the reference white
+    pub fn lab(&self, color_type: ColorType, s: &str) -> Result<LabValue, Box<dyn Error>> {
+        Ok(match color_type {
+            ColorType::Lab => LabValue::from_str(s)?,
+            ColorType::Lch => LchValue::from_str(s)?.into(),
+            _ => self.lab_from_xyz(self.xyz(color_type, s)?),
+        })
+    }
+
+    pub fn lab_from_xyz(&self, xyz: XyzValue) -> LabValue {
+        LabValue::from_xyz_with_white_point(xyz, self.white())
+    }
+
+    pub fn xyz_from_rgb(&self, rgb: RgbNominalValue) -> XyzValue {
+        let white = self.rgb_system.white_point().white_point();
+        let xyz = XyzValue::from_rgb(rgb, self.rgb_system.clone(), None);
+        xyz.chrom_adapt_white_point(self.cat, white, self.white())
+    }
+
+    pub fn rgb_from_lab(&self, lab: LabValue) -> RgbValue {
+        self.rgb_from_xyz(XyzValue::from_lab_with_white_point(lab, self.white()))
+    }
+
+    pub fn rgb_from_xyz(&self, xyz: XyzValue) -> RgbValue {
+        let white = self.rgb_system.white_point().white_point();
+        let xyz = xyz.chrom_adapt_white_point(self.cat, self.white(), white);
+        RgbValue::from(RgbNominalValue::from_xyz(xyz, self.rgb_system.clone(), None))
+    }
+
+    // The white point of the illuminant for the observer
+    fn white(&self) -> XyzValue {
+        self.illuminant.white_point_for(self.observer)
+    }
+}
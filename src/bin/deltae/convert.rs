@@ -0,0 +1,57 @@
+use crate::conditions::{ColorType, Conditions};
+use clap::Args;
+use deltae::*;
+use std::error::Error;
+use std::io::{self, BufRead};
+
+/// Convert a color from one color type to another
+#[derive(Args)]
+pub struct Convert {
+    /// Color values, or - to read one per line from stdin
+    color: String,
+
+    /// Set color type of the input
+    #[arg(short, long, value_enum, default_value_t = ColorType::Lab)]
+    from: ColorType,
+
+    /// Set color type of the output
+    #[arg(short, long, value_enum)]
+    to: ColorType,
+
+    #[command(flatten)]
+    conditions: Conditions,
+}
+
+impl Convert {
+    /// Print the conversion of the color, or of each non-empty line of stdin if the color is `-`
+    pub fn run(&self) -> Result<(), Box<dyn Error>> {
+        if self.color != "-" {
+            println!("{}", self.convert(&self.color)?);
+            return Ok(());
+        }
+
+        for (i, line) in io::stdin().lock().lines().enumerate() {
+            let line = line?;
+            if line.trim().is_empty() {
+                continue;
+            }
+
+            let converted = self.convert(&line).map_err(|e| format!("stdin line {}: {}", i + 1, e))?;
+            println!("{}", converted);
+        }
+
+        Ok(())
+    }
+
+    // Convert through XYZ relative to the reference white of the conditions
+    fn convert(&self, s: &str) -> Result<String, Box<dyn Error>> {
+        let xyz = self.conditions.xyz(self.from, s)?;
+
+        Ok(match self.to {
+            ColorType::Lab => self.conditions.lab_from_xyz(xyz).to_string(),
+            ColorType::Lch => LchValue::from(self.conditions.lab_from_xyz(xyz)).to_string(),
+            ColorType::Xyz => xyz.to_string(),
+            ColorType::Rgb => self.conditions.rgb_from_xyz(xyz).to_string(),
+        })
+    }
+}
@@ -0,0 +1,109 @@
+use clap::error::ErrorKind;
+use clap::{CommandFactory, Parser, ValueEnum};
+use deltae::*;
+use std::error::Error;
+use std::io::{self, BufRead, IsTerminal};
+
+mod cgats;
+mod cli;
+mod conditions;
+mod convert;
+mod output;
+mod stats;
+mod summary;
+
+use cli::{Cli, Command};
+use conditions::{ColorType, Conditions};
+use output::Output;
+
+fn main() -> Result<(), Box<dyn Error>> {
+    //Parse command line arguments with clap
+    let cli = Cli::parse();
+
+    match cli.command {
+        Some(Command::Convert(convert)) => return convert.run(),
+        Some(Command::Stats(stats)) => return stats.run(),
+        Some(Command::Cgats(cgats)) => return cgats.run(),
+        Some(Command::Completions { shell }) => {
+            clap_complete::generate(shell, &mut Cli::command(), "deltae", &mut io::stdout());
+            return Ok(());
+        }
+        Some(Command::Man) => {
+            clap_mangen::Man::new(Cli::command()).render(&mut io::stdout())?;
+            return Ok(());
+        }
+        None => (),
+    }
+
+    let args = cli.compare;
+
+    // Pairs are read from stdin without colors, which would wait for input in a terminal
+    if args.color0.is_none() && io::stdin().is_terminal() {
+        let message = "no colors given, and stdin is a terminal rather than REFERENCE<TAB>SAMPLE pairs";
+        Cli::command().error(ErrorKind::MissingRequiredArgument, message).exit();
+    }
+
+    let types = [args.type0.unwrap_or(args.color_type), args.type1.unwrap_or(args.color_type)];
+    let (output, swatch) = (args.output, args.swatch.then_some(&args.conditions));
+    let delta = |color0: &str, color1: &str| delta(&args.conditions, types, color0, color1, args.method);
+    output.header();
+
+    match (args.color0.as_deref(), args.color1.as_deref()) {
+        // Pairs of colors from each line of stdin
+        (Some("-"), Some("-")) | (None, _) => {
+            for_each_line(output, swatch, |line| {
+                let mut split = line.split('\t');
+                match (split.next(), split.next(), split.next()) {
+                    (Some(color0), Some(color1), None) => delta(color0, color1),
+                    _ => Err("expected REFERENCE<TAB>SAMPLE".into()),
+                }
+            })?;
+        }
+        // A color from each line of stdin compared to a fixed color
+        (Some("-"), Some(color1)) => for_each_line(output, swatch, |line| delta(line, color1))?,
+        (Some(color0), Some("-")) => for_each_line(output, swatch, |line| delta(color0, line))?,
+        (Some(color0), Some(color1)) => output.print(&delta(color0, color1)?, swatch),
+        (Some(_), None) => unreachable!("COLOR0 requires COLOR1"),
+    }
+
+    Ok(())
+}
+
+fn delta(
+    conditions: &Conditions,
+    types: [ColorType; 2],
+    color0: &str,
+    color1: &str,
+    method: DEMethod,
+) -> Result<DeltaE, Box<dyn Error>> {
+    Ok(color(conditions, types[0], color0)?.delta(color(conditions, types[1], color1)?, method))
+}
+
+// Parse a color of a type, unless the color has a type prefix ("lab:50,2,-3", "rgb:#aabbcc")
+fn color(conditions: &Conditions, color_type: ColorType, s: &str) -> Result<LabValue, Box<dyn Error>> {
+    let (color_type, s) = match s.split_once(':') {
+        Some((prefix, color)) => match ColorType::from_str(prefix.trim(), true) {
+            Ok(color_type) => (color_type, color),
+            Err(_) => (color_type, s),
+        },
+        None => (color_type, s),
+    };
+
+    conditions.lab(color_type, s)
+}
+
+// Calculate and print a DeltaE for each non-empty line of stdin
+fn for_each_line<F>(output: Output, swatch: Option<&Conditions>, mut f: F) -> Result<(), Box<dyn Error>>
+where F: FnMut(&str) -> Result<DeltaE, Box<dyn Error>> {
+    for (i, line) in io::stdin().lock().lines().enumerate() {
+        let line = line?;
+        if line.trim().is_empty() {
+            continue;
+        }
+
+        let delta = f(&line).map_err(|e| format!("stdin line {}: {}", i + 1, e))?;
+        output.print(&delta, swatch);
+    }
+
+    Ok(())
+}
@@ -0,0 +1,82 @@
+use crate::conditions::Conditions;
+use clap::ValueEnum;
+use deltae::*;
+
+const FIELDS: [&str; 13] = [
+    "reference_L", "reference_a", "reference_b",
+    "sample_L", "sample_a", "sample_b",
+    "method", "delta_e", "delta_l", "delta_a", "delta_b", "delta_c", "delta_h",
+];
+
+/// The format of the printed results
+#[derive(Debug, Clone, Copy, PartialEq, ValueEnum)]
+pub enum Output {
+    // `{method}: {value}`
+    Plain,
+    // One JSON object per line
+    Json,
+    // Comma separated values with a header
+    Csv,
+    // Tab separated values with a header
+    Tsv,
+}
+
+impl Output {
+    /// Print the header, if the format has one
+    pub fn header(self) {
+        match self {
+            Output::Csv => println!("{}", FIELDS.join(",")),
+            Output::Tsv => println!("{}", FIELDS.join("\t")),
+            Output::Plain | Output::Json => (),
+        }
+    }
+
+    /// Print a [`DeltaE`], preceded in plain output by truecolor swatches of the reference and
+    /// sample in the RGB system of the conditions, if there are any
+    pub fn print(self, delta: &DeltaE, swatch: Option<&Conditions>) {
+        // The report is only used for its component differences
+        let report = delta.report(f32::INFINITY);
+        let fields = [
+            report.reference.l.to_string(),
+            report.reference.a.to_string(),
+            report.reference.b.to_string(),
+            report.sample.l.to_string(),
+            report.sample.a.to_string(),
+            report.sample.b.to_string(),
+            report.method.to_string(),
+            report.delta_e.to_string(),
+            report.delta_l.to_string(),
+            report.delta_a.to_string(),
+            report.delta_b.to_string(),
+            report.delta_c.to_string(),
+            report.delta_h.to_string(),
+        ];
+
+        match (self, swatch) {
+            (Output::Plain, Some(conditions)) => {
+                let reference = ansi_swatch(conditions.rgb_from_lab(*delta.reference()));
+                let sample = ansi_swatch(conditions.rgb_from_lab(*delta.sample()));
+                println!("{} {} {}: {}", reference, sample, delta.method(), delta.value());
+            }
+            (Output::Plain, None) => println!("{}: {}", delta.method(), delta.value()),
+            (Output::Csv, _) => println!("{}", fields.join(",")),
+            (Output::Tsv, _) => println!("{}", fields.join("\t")),
+            (Output::Json, _) => {
+                let members: Vec<String> = FIELDS
+                    .iter()
+                    .zip(&fields)
+                    .map(|(name, value)| match *name {
+                        "method" => format!("\"{}\":\"{}\"", name, value),
+                        _ => format!("\"{}\":{}", name, value),
+                    })
+                    .collect();
+                println!("{{{}}}", members.join(","));
+            }
+        }
+    }
+}
+
+// A block of an RGB color as an ANSI truecolor escape sequence
+fn ansi_swatch(rgb: RgbValue) -> String {
+    format!("\x1b[48;2;{};{};{}m    \x1b[0m", rgb.r, rgb.g, rgb.b)
+}
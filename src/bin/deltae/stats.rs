@@ -0,0 +1,88 @@
+use crate::conditions::{ColorType, Conditions};
+use crate::summary;
+use clap::Args;
+use deltae::batch::{self, Comparison, Pairing, Record};
+use deltae::*;
+use std::error::Error;
+use std::fs::File;
+use std::path::{Path, PathBuf};
+
+/// Summarize Delta E between the rows of a reference and a sample CSV file
+#[derive(Args)]
+pub struct Stats {
+    /// CSV file of reference colors, one per row as L,a,b or id,L,a,b
+    reference: PathBuf,
+
+    /// CSV file of sample colors, one per row as L,a,b or id,L,a,b
+    sample: PathBuf,
+
+    /// Set DeltaE method: 2000, 1994, 1994T, CMC1, CMC2, CMC(l:c), or 1976
+    #[arg(short, long, default_value = "2000")]
+    method: DEMethod,
+
+    /// Set color type of both files
+    #[arg(short, long, value_enum, default_value_t = ColorType::Lab)]
+    color_type: ColorType,
+
+    /// Pair rows by the ID in their first column instead of by position
+    #[arg(long)]
+    by_id: bool,
+
+    /// Set the number of worst rows to list
+    #[arg(short, long, default_value_t = 5)]
+    worst: usize,
+
+    #[command(flatten)]
+    conditions: Conditions,
+}
+
+impl Stats {
+    /// Print the count, mean, maximum, standard deviation, and 95th percentile of Delta E, followed
+    /// by the rows with the largest Delta E
+    pub fn run(&self) -> Result<(), Box<dyn Error>> {
+        let pairing = if self.by_id { Pairing::Id } else { Pairing::Position };
+        let references = self.read(&self.reference)?;
+        let samples = self.read(&self.sample)?;
+        let comparisons = batch::compare(&references, &samples, self.method, pairing)?;
+
+        let deltas: Vec<DeltaE> = comparisons.iter().map(|c| c.delta).collect();
+        summary::print(self.method, &deltas);
+
+        let mut rows: Vec<(usize, &Comparison)> = comparisons.iter().enumerate().collect();
+        rows.sort_by(|(_, a), (_, b)| b.delta.value().total_cmp(a.delta.value()));
+
+        if self.worst > 0 && !rows.is_empty() {
+            println!("worst:");
+            for (i, comparison) in rows.into_iter().take(self.worst) {
+                let id = comparison.id.clone().unwrap_or_else(|| format!("row {}", i + 1));
+                println!("  {}: {}", id, comparison.delta.value());
+            }
+        }
+
+        Ok(())
+    }
+
+    // Read a CSV file of colors as Lab under the conditions
+    fn read(&self, path: &Path) -> Result<Vec<Record<LabValue>>, Box<dyn Error>> {
+        let conditions = &self.conditions;
+        let file = File::open(path).map_err(|e| format!("{}: {}", path.display(), e))?;
+
+        let records = match self.color_type {
+            ColorType::Lab => batch::read::<LabValue, _>(file),
+            ColorType::Lch => batch::read::<LchValue, _>(file).map(|r| to_lab(r, LabValue::from)),
+            ColorType::Xyz => batch::read::<XyzValue, _>(file)
+                .map(|r| to_lab(r, |xyz| conditions.lab_from_xyz(xyz))),
+            ColorType::Rgb => batch::read::<RgbValue, _>(file)
+                .map(|r| to_lab(r, |rgb| conditions.lab_from_xyz(conditions.xyz_from_rgb(rgb.into())))),
+        };
+
+        Ok(records.map_err(|e| format!("{}: {}", path.display(), e))?)
+    }
+}
+
+fn to_lab<C, F: Fn(C) -> LabValue>(records: Vec<Record<C>>, f: F) -> Vec<Record<LabValue>> {
+    records
+        .into_iter()
+        .map(|r| Record { id: r.id, color: f(r.color), line: r.line })
+        .collect()
+}
@@ -0,0 +1,14 @@
+use deltae::*;
+
+/// Print the count, mean, maximum, standard deviation, and 95th percentile of a batch of Delta E
+pub fn print(method: DEMethod, deltas: &[DeltaE]) {
+    let stats: DeltaEStats = deltas.iter().cloned().collect();
+    let value = |value: Option<f32>| value.unwrap_or(0.0);
+
+    println!("method:          {}", method);
+    println!("count:           {}", stats.count());
+    println!("mean:            {}", value(stats.mean()));
+    println!("max:             {}", value(stats.max()));
+    println!("std dev:         {}", value(stats.std_dev()));
+    println!("95th percentile: {}", value(stats.percentile(95.0)));
+}
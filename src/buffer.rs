@@ -0,0 +1,108 @@
+//! Converting whole buffers of colors into caller-provided output buffers, such as the planes of an
+//! image, without allocating for each color. [`convert_into`] converts a slice of one color type
+//! into a slice of another, and [`convert_packed_into`] converts colors packed as consecutive
+//! triples of [`f32`] components.
+//!
+//! # Examples
+//!
+//! ```
+//! use deltae::*;
+//!
+//! let labs = [
+//!     LabValue::new(50.0, 20.0, -10.0).unwrap(),
+//!     LabValue::new(80.0, 0.0, 0.0).unwrap(),
+//! ];
+//! let mut lchs = [LchValue::default(); 2];
+//! convert_into(&labs, &mut lchs).unwrap();
+//! assert_eq!(lchs[1], LchValue::from(labs[1]));
+//!
+//! // L, a, b, L, a, b, ...
+//! let plane = [50.0, 20.0, -10.0, 80.0, 0.0, 0.0];
+//! let mut xyz = vec![0.0; plane.len()];
+//! convert_packed_into::<LabValue, XyzValue>(&plane, &mut xyz).unwrap();
+//! assert_eq!(&xyz[3..], &XyzValue::from(labs[1]).components());
+//!
+//! // The buffers must hold the same number of colors
+//! assert!(convert_into(&labs, &mut lchs[..1]).is_err());
+//! ```
+
+use crate::*;
+
+/// A color that can be built from the values of its components, in the order of
+/// [`ColorValue::components`]. The values are not validated, as with a struct literal.
+pub trait FromComponents: ColorValue {
+    /// New color from the values of its components
+    fn from_components(components: [f32; 3]) -> Self;
+}
+
+impl FromComponents for LabValue {
+    fn from_components([l, a, b]: [f32; 3]) -> LabValue {
+        LabValue { l, a, b }
+    }
+}
+
+impl FromComponents for LchValue {
+    fn from_components([l, c, h]: [f32; 3]) -> LchValue {
+        LchValue { l, c, h }
+    }
+}
+
+impl FromComponents for XyzValue {
+    fn from_components([x, y, z]: [f32; 3]) -> XyzValue {
+        XyzValue { x, y, z }
+    }
+}
+
+#[cfg(feature = "rgb")]
+impl FromComponents for RgbNominalValue {
+    fn from_components([r, g, b]: [f32; 3]) -> RgbNominalValue {
+        RgbNominalValue { r, g, b }
+    }
+}
+
+/// Convert each color of `input` into the color at the same index of `output`.
+/// Will return `Err()` if the slices are of unequal length.
+pub fn convert_into<A, B>(input: &[A], output: &mut [B]) -> ValueResult<()>
+where A: Copy + Into<B> {
+    if input.len() != output.len() {
+        return Err(ValueError::BadFormat);
+    }
+
+    for (out, &color) in output.iter_mut().zip(input) {
+        *out = color.into();
+    }
+
+    Ok(())
+}
+
+/// Convert colors of type `A` packed as consecutive triples of components in `input` into colors
+/// of type `B` packed the same way in `output`.
+/// Will return `Err()` if the slices are of unequal length, or if their length is not a multiple
+/// of 3.
+/// ```
+/// # #[cfg(feature = "rgb")] {
+/// use deltae::*;
+///
+/// // Nominal sRGB planes to Lab
+/// let rgb = [1.0, 1.0, 1.0, 0.0, 0.0, 0.0];
+/// let mut lab = [0.0; 6];
+/// convert_packed_into::<RgbNominalValue, LabValue>(&rgb, &mut lab).unwrap();
+/// assert!((lab[0] - 100.0).abs() < 1e-2);
+/// assert_eq!(lab[3], 0.0);
+///
+/// assert!(convert_packed_into::<RgbNominalValue, LabValue>(&rgb[..4], &mut lab[..4]).is_err());
+/// # }
+/// ```
+pub fn convert_packed_into<A, B>(input: &[f32], output: &mut [f32]) -> ValueResult<()>
+where A: FromComponents + Into<B>, B: ColorValue {
+    if input.len() != output.len() || input.len() % 3 != 0 {
+        return Err(ValueError::BadFormat);
+    }
+
+    for (src, dst) in input.chunks_exact(3).zip(output.chunks_exact_mut(3)) {
+        let color: B = A::from_components([src[0], src[1], src[2]]).into();
+        dst.copy_from_slice(&color.components());
+    }
+
+    Ok(())
+}
@@ -0,0 +1,172 @@
+//! Reading measurement data from CGATS.17 text files, such as the IT8.7 and ECI2002 charts
+//! measured for proof certification. Requires the `cgats` feature.
+//!
+//! A CGATS file is a list of keywords followed by a table of named fields. Only the first table of
+//! a file is read. Patches are identified by their `SAMPLE_ID` (or `SAMPLE_NAME`), and their
+//! colors are read from the `LAB_L`, `LAB_A`, and `LAB_B` fields, or the `XYZ_X`, `XYZ_Y`, and
+//! `XYZ_Z` fields, which are scaled from `0..100` to `0..1` and converted to Lab relative to
+//! [`Illuminant::D50`].
+//!
+//! # Examples
+//!
+//! ```
+//! use deltae::*;
+//! use deltae::cgats::Cgats;
+//!
+//! let cgats: Cgats = "CGATS.17
+//! ORIGINATOR \"deltae\"
+//! NUMBER_OF_FIELDS 4
+//! BEGIN_DATA_FORMAT
+//! SAMPLE_ID LAB_L LAB_A LAB_B
+//! END_DATA_FORMAT
+//! NUMBER_OF_SETS 2
+//! BEGIN_DATA
+//! A1 95.08 -0.17 -10.81
+//! A2 54.29 80.8 69.89
+//! END_DATA
+//! ".parse().unwrap();
+//!
+//! assert_eq!(cgats.keyword("ORIGINATOR"), Some("deltae"));
+//!
+//! let patches = cgats.patches().unwrap();
+//! assert_eq!(patches[1].id, "A2");
+//! assert_eq!(patches[1].color, LabValue::new(54.29, 80.8, 69.89).unwrap());
+//! ```
+
+use std::str::FromStr;
+
+use crate::*;
+
+/// The keywords and first table of a CGATS file
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct Cgats {
+    /// The keywords and their values, in the order they appear. Quotes are removed from values.
+    pub keywords: Vec<(String, String)>,
+    /// The names of the fields of the table
+    pub fields: Vec<String>,
+    /// The rows of the table. Each row has a value for every field.
+    pub rows: Vec<Vec<String>>,
+}
+
+/// A measured patch of a CGATS file
+#[derive(Debug, Clone, PartialEq)]
+pub struct Patch {
+    /// The `SAMPLE_ID` or `SAMPLE_NAME` of the patch, or its row number starting at 1 if the file
+    /// has neither
+    pub id: String,
+    /// The color of the patch
+    pub color: LabValue,
+}
+
+impl Cgats {
+    /// Returns the value of the first keyword with a name
+    pub fn keyword(&self, name: &str) -> Option<&str> {
+        self.keywords.iter().find(|(k, _)| k == name).map(|(_, v)| v.as_str())
+    }
+
+    /// Returns the index of the field with a name
+    pub fn field(&self, name: &str) -> Option<usize> {
+        self.fields.iter().position(|f| f == name)
+    }
+
+    /// Returns the patches of the table.
+    /// Will return `Err()` if the table has no Lab or XYZ fields, or a color is invalid.
+    pub fn patches(&self) -> ValueResult<Vec<Patch>> {
+        let fields = |names: [&str; 3]| -> Option<[usize; 3]> {
+            Some([self.field(names[0])?, self.field(names[1])?, self.field(names[2])?])
+        };
+
+        let (columns, xyz) = match fields(["LAB_L", "LAB_A", "LAB_B"]) {
+            Some(columns) => (columns, false),
+            None => (fields(["XYZ_X", "XYZ_Y", "XYZ_Z"]).ok_or(ValueError::BadFormat)?, true),
+        };
+        let id = self.field("SAMPLE_ID").or_else(|| self.field("SAMPLE_NAME"));
+
+        self.rows
+            .iter()
+            .enumerate()
+            .map(|(i, row)| {
+                // Some locales write a decimal comma
+                let v = |n: usize| {
+                    let value = row[columns[n]].replace(',', ".");
+                    value.parse::<f32>().map_err(|_| ValueError::BadFormat)
+                };
+
+                let color = if xyz {
+                    let xyz = XyzValue { x: v(0)? / 100.0, y: v(1)? / 100.0, z: v(2)? / 100.0 };
+                    LabValue::from(xyz.validate()?)
+                } else {
+                    LabValue { l: v(0)?, a: v(1)?, b: v(2)? }.validate()?
+                };
+                let id = id.map_or_else(|| (i + 1).to_string(), |id| row[id].clone());
+
+                Ok(Patch { id, color })
+            })
+            .collect()
+    }
+}
+
+impl FromStr for Cgats {
+    type Err = ValueError;
+    fn from_str(s: &str) -> ValueResult<Cgats> {
+        let mut cgats = Cgats::default();
+        let mut lines = s.lines().map(str::trim).filter(|l| !l.is_empty() && !l.starts_with('#'));
+
+        // The first line identifies the file type, such as "CGATS.17" or "IT8.7/2"
+        lines.next().ok_or(ValueError::BadFormat)?;
+
+        while let Some(line) = lines.next() {
+            match line {
+                "BEGIN_DATA_FORMAT" => {
+                    for line in lines.by_ref().take_while(|l| *l != "END_DATA_FORMAT") {
+                        cgats.fields.extend(tokens(line)?);
+                    }
+                }
+                "BEGIN_DATA" => {
+                    for line in lines.by_ref().take_while(|l| *l != "END_DATA") {
+                        let row = tokens(line)?;
+                        if row.len() != cgats.fields.len() {
+                            return Err(ValueError::BadFormat);
+                        }
+                        cgats.rows.push(row);
+                    }
+                    // Only the first table is read
+                    break;
+                }
+                _ => {
+                    let mut split = line.splitn(2, char::is_whitespace);
+                    let keyword = split.next().unwrap_or_default().to_string();
+                    let value = split.next().map(str::trim).unwrap_or_default().trim_matches('"');
+                    cgats.keywords.push((keyword, value.to_string()));
+                }
+            }
+        }
+
+        if cgats.fields.is_empty() {
+            return Err(ValueError::BadFormat);
+        }
+
+        Ok(cgats)
+    }
+}
+
+// Split a line into whitespace separated values. Quoted values may contain whitespace.
+fn tokens(line: &str) -> ValueResult<Vec<String>> {
+    let mut tokens = Vec::new();
+    let mut rest = line.trim_start();
+
+    while !rest.is_empty() {
+        let end = if let Some(quoted) = rest.strip_prefix('"') {
+            let close = quoted.find('"').ok_or(ValueError::BadFormat)?;
+            tokens.push(quoted[..close].to_string());
+            close + 2
+        } else {
+            let end = rest.find(char::is_whitespace).unwrap_or(rest.len());
+            tokens.push(rest[..end].to_string());
+            end
+        };
+        rest = rest[end..].trim_start();
+    }
+
+    Ok(tokens)
+}
@@ -0,0 +1,377 @@
+//! Chromatic adaptation of [`XyzValue`]s from one reference white to another.
+//!
+//! Further Reading: [http://www.brucelindbloom.com/index.html?Eqn_ChromAdapt.html](http://www.brucelindbloom.com/index.html?Eqn_ChromAdapt.html)
+//!
+//! # Examples
+//!
+//! ```
+//! use deltae::*;
+//!
+//! let d65 = Illuminant::D65.white_point();
+//! let d50 = d65.chrom_adapt(ChromaticAdaptationMethod::Bradford, Illuminant::D65, Illuminant::D50);
+//!
+//! assert_eq!(d50.round_to(4), Illuminant::D50.white_point().round_to(4));
+//! ```
+
+use std::fmt;
+
+use crate::*;
+
+/// The method used to transform [`XyzValue`]s into a cone response domain for chromatic
+/// adaptation
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub enum ChromaticAdaptationMethod {
+    /// The Bradford transform. The most widely used method, and the default.
+    #[default]
+    Bradford,
+    /// The von Kries transform
+    VonKries,
+    /// Simple scaling of the XYZ values. Generally considered the least accurate method.
+    XyzScaling,
+    /// The CIECAM02 transform, as used by ICC v4 profiles
+    Cat02,
+    /// The CAM16 transform, a revision of [`Cat02`](ChromaticAdaptationMethod::Cat02)
+    Cat16,
+    /// The spectrally sharpened transform of Süsstrunk, Holm, and Finlayson
+    Sharp,
+    /// The spectrally sharpened transform of Fairchild, from "A Revision of CIECAM97s for
+    /// Practical Applications" (2001). Not the Hunt-Pointer-Estévez transform of RLAB.
+    Fairchild,
+    /// The CMCCAT2000 transform, with a degree of adaptation
+    CmcCat2000 {
+        /// The degree of adaptation, from `0.0` (no adaptation) to `1.0` (complete adaptation).
+        /// See [`ChromaticAdaptationMethod::cmccat2000_degree`].
+        d: f32,
+    },
+    /// A user supplied cone response matrix. The inverse is calculated at runtime. A singular
+    /// matrix has no inverse and will fail [`Validate`].
+    Custom(Matrix3x3),
+}
+
+impl Eq for ChromaticAdaptationMethod {}
+
+impl ChromaticAdaptationMethod {
+    /// Returns the cone response matrix of the [`ChromaticAdaptationMethod`]
+    pub const fn matrix(&self) -> Matrix3x3 {
+        match self {
+            ChromaticAdaptationMethod::Bradford   => BRADFORD,
+            ChromaticAdaptationMethod::VonKries   => VON_KRIES,
+            ChromaticAdaptationMethod::XyzScaling => XYZ_SCALING,
+            ChromaticAdaptationMethod::Cat02      => CAT02,
+            ChromaticAdaptationMethod::Cat16      => CAT16,
+            ChromaticAdaptationMethod::Sharp      => SHARP,
+            ChromaticAdaptationMethod::Fairchild  => FAIRCHILD,
+            ChromaticAdaptationMethod::CmcCat2000 { .. } => CMCCAT2000,
+            ChromaticAdaptationMethod::Custom(matrix)    => *matrix,
+        }
+    }
+
+    /// Returns the inverse of the cone response matrix of the [`ChromaticAdaptationMethod`]. The
+    /// inverse of a singular [`Custom`](ChromaticAdaptationMethod::Custom) matrix is filled with
+    /// `NaN`.
+    pub const fn inverse(&self) -> Matrix3x3 {
+        match self {
+            ChromaticAdaptationMethod::Bradford   => BRADFORD_INV,
+            ChromaticAdaptationMethod::VonKries   => VON_KRIES_INV,
+            ChromaticAdaptationMethod::XyzScaling => XYZ_SCALING,
+            ChromaticAdaptationMethod::Cat02      => CAT02_INV,
+            ChromaticAdaptationMethod::Cat16      => CAT16_INV,
+            ChromaticAdaptationMethod::Sharp      => SHARP_INV,
+            ChromaticAdaptationMethod::Fairchild  => FAIRCHILD_INV,
+            ChromaticAdaptationMethod::CmcCat2000 { .. } => CMCCAT2000_INV,
+            ChromaticAdaptationMethod::Custom(matrix)    => match matrix.inverse() {
+                Some(inverse) => inverse,
+                None => Matrix3x3::new([f32::NAN; 9]),
+            },
+        }
+    }
+
+    /// Returns the degree of adaptation of the [`ChromaticAdaptationMethod`], clamped between
+    /// `0.0` and `1.0`. All methods other than
+    /// [`CmcCat2000`](ChromaticAdaptationMethod::CmcCat2000) adapt completely.
+    pub const fn degree_of_adaptation(&self) -> f32 {
+        match self {
+            // f32::clamp is only const since Rust 1.85
+            ChromaticAdaptationMethod::CmcCat2000 { d } if *d < 0.0 => 0.0,
+            ChromaticAdaptationMethod::CmcCat2000 { d } if *d > 1.0 => 1.0,
+            ChromaticAdaptationMethod::CmcCat2000 { d } => *d,
+            _ => 1.0,
+        }
+    }
+
+    /// Calculate the CMCCAT2000 degree of adaptation from the surround factor `f` (`1.0` for
+    /// average viewing conditions, `0.8` for dim and dark surrounds) and the luminance of the
+    /// adapting fields of the source and destination viewing conditions in cd/m².
+    /// ```
+    /// use deltae::*;
+    ///
+    /// // A lightbooth at 2000 lux compared with an office at 500 lux
+    /// let d = ChromaticAdaptationMethod::cmccat2000_degree(1.0, 2000.0 / 5.0, 500.0 / 5.0);
+    /// let method = ChromaticAdaptationMethod::CmcCat2000 { d };
+    /// assert!(method.degree_of_adaptation() < 1.0);
+    /// ```
+    pub fn cmccat2000_degree(f: f32, la_source: f32, la_destination: f32) -> f32 {
+        let d = f * (
+            0.08 * (0.5 * (la_source + la_destination)).log10()
+          + 0.76
+          - 0.45 * (la_source - la_destination) / (la_source + la_destination)
+        );
+
+        d.clamp(0.0, 1.0)
+    }
+}
+
+impl fmt::Display for ChromaticAdaptationMethod {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            ChromaticAdaptationMethod::CmcCat2000 { d } => write!(f, "CmcCat2000(D:{:0.2})", d),
+            ChromaticAdaptationMethod::Custom(_) => write!(f, "Custom"),
+            _ => write!(f, "{:?}", self)
+        }
+    }
+}
+
+/// A chromatic adaptation from a source [`Illuminant`] to a destination [`Illuminant`] with the
+/// adaptation matrix calculated once, for adapting many values efficiently.
+/// ```
+/// use deltae::*;
+///
+/// let cat = ChromaticAdaptationTransform::new(
+///     ChromaticAdaptationMethod::Bradford,
+///     Illuminant::D65,
+///     Illuminant::D50,
+/// );
+///
+/// let pixels = [
+///     XyzValue::new(0.1, 0.2, 0.3).unwrap(),
+///     XyzValue::new(0.4, 0.5, 0.6).unwrap(),
+/// ];
+///
+/// for xyz in pixels.iter() {
+///     let adapted = cat.apply(*xyz);
+///     assert_eq!(adapted, xyz.chrom_adapt(cat.method(), Illuminant::D65, Illuminant::D50));
+/// }
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct ChromaticAdaptationTransform {
+    method: ChromaticAdaptationMethod,
+    source: Illuminant,
+    destination: Illuminant,
+    matrix: Matrix3x3,
+}
+
+impl ChromaticAdaptationTransform {
+    /// Calculate the adaptation matrix `M⁻¹·S·M` for a [`ChromaticAdaptationMethod`] from a
+    /// source [`Illuminant`] to a destination [`Illuminant`]. This is a `const fn`, so a transform
+    /// may be calculated at compile time.
+    /// ```
+    /// use deltae::*;
+    ///
+    /// const D65_TO_D50: ChromaticAdaptationTransform = ChromaticAdaptationTransform::new(
+    ///     ChromaticAdaptationMethod::Bradford,
+    ///     Illuminant::D65,
+    ///     Illuminant::D50,
+    /// );
+    ///
+    /// let white = D65_TO_D50.apply(Illuminant::D65.white_point());
+    /// assert_eq!(white.round_to(4), Illuminant::D50.white_point());
+    /// ```
+    pub const fn new(
+        method: ChromaticAdaptationMethod,
+        source: Illuminant,
+        destination: Illuminant,
+    ) -> ChromaticAdaptationTransform {
+        ChromaticAdaptationTransform {
+            method,
+            source,
+            destination,
+            matrix: adaptation_matrix(method, source.white_point(), destination.white_point()),
+        }
+    }
+
+    /// Adapt an [`XyzValue`] from the source [`Illuminant`] to the destination [`Illuminant`]
+    #[inline]
+    pub const fn apply(&self, xyz: XyzValue) -> XyzValue {
+        let [x, y, z] = self.matrix.mul_column(&matrix![xyz.x; xyz.y; xyz.z]).to_array();
+        XyzValue { x, y, z }
+    }
+
+    /// Return the [`ChromaticAdaptationMethod`] of the transform
+    pub const fn method(&self) -> ChromaticAdaptationMethod {
+        self.method
+    }
+
+    /// Return the source [`Illuminant`] of the transform
+    pub const fn source(&self) -> Illuminant {
+        self.source
+    }
+
+    /// Return the destination [`Illuminant`] of the transform
+    pub const fn destination(&self) -> Illuminant {
+        self.destination
+    }
+
+    /// Return a reference to the adaptation matrix `M⁻¹·S·M`
+    pub const fn matrix(&self) -> &Matrix3x3 {
+        &self.matrix
+    }
+}
+
+impl XyzValue {
+    /// Adapt an [`XyzValue`] measured under a source [`Illuminant`] to the corresponding color
+    /// under a destination [`Illuminant`]. When adapting many values, use a
+    /// [`ChromaticAdaptationTransform`] instead.
+    /// ```
+    /// use deltae::*;
+    ///
+    /// let xyz = XyzValue::new(0.5, 0.4, 0.3).unwrap();
+    /// let adapted = xyz.chrom_adapt(ChromaticAdaptationMethod::VonKries, Illuminant::D50, Illuminant::D65);
+    /// let restored = adapted.chrom_adapt(ChromaticAdaptationMethod::VonKries, Illuminant::D65, Illuminant::D50);
+    ///
+    /// assert_eq!(xyz.round_to(4), restored.round_to(4));
+    /// ```
+    pub fn chrom_adapt(
+        self,
+        method: ChromaticAdaptationMethod,
+        source: Illuminant,
+        destination: Illuminant,
+    ) -> XyzValue {
+        ChromaticAdaptationTransform::new(method, source, destination).apply(self)
+    }
+
+    /// Adapt an [`XyzValue`] from a source white point to a destination white point. Use this
+    /// when a white point is not the 2° white point of an [`Illuminant`], such as the white point
+    /// for another [`Observer`].
+    /// ```
+    /// use deltae::*;
+    ///
+    /// let d65 = Illuminant::D65.white_point();
+    /// let d50 = Illuminant::D50.white_point_for(Observer::Cie1964);
+    /// let adapted = d65.chrom_adapt_white_point(ChromaticAdaptationMethod::Bradford, d65, d50);
+    ///
+    /// assert_eq!(adapted.round_to(4), d50.round_to(4));
+    /// ```
+    pub fn chrom_adapt_white_point(
+        self,
+        method: ChromaticAdaptationMethod,
+        source: XyzValue,
+        destination: XyzValue,
+    ) -> XyzValue {
+        XyzValue::from(adaptation_matrix(method, source, destination) * Matrix3x1::from(self))
+    }
+}
+
+// The adaptation matrix M⁻¹·S·M from a source white point to a destination white point
+pub(crate) const fn adaptation_matrix(
+    method: ChromaticAdaptationMethod,
+    source: XyzValue,
+    destination: XyzValue,
+) -> Matrix3x3 {
+    let ma = method.matrix();
+    let src = ma.mul_column(&matrix![source.x; source.y; source.z]).to_array();
+    let dst = ma.mul_column(&matrix![destination.x; destination.y; destination.z]).to_array();
+    let d = method.degree_of_adaptation();
+    let scale = Matrix3x3::diagonal([
+        d * dst[0] / src[0] + 1.0 - d,
+        d * dst[1] / src[1] + 1.0 - d,
+        d * dst[2] / src[2] + 1.0 - d,
+    ]);
+
+    method.inverse().mul_matrix(&scale).mul_matrix(&ma)
+}
+
+impl LabValue {
+    /// Adapt a [`LabValue`] relative to a source [`Illuminant`] to the corresponding
+    /// [`LabValue`] relative to a destination [`Illuminant`]. The conversion is done in XYZ.
+    /// ```
+    /// use deltae::*;
+    ///
+    /// let lab = LabValue::new(50.0, 20.0, -30.0).unwrap();
+    /// let adapted = lab.chrom_adapt(ChromaticAdaptationMethod::Bradford, Illuminant::D50, Illuminant::D65);
+    /// let restored = adapted.chrom_adapt(ChromaticAdaptationMethod::Bradford, Illuminant::D65, Illuminant::D50);
+    ///
+    /// assert_eq!(lab.round_to(3), restored.round_to(3));
+    /// ```
+    pub fn chrom_adapt(
+        self,
+        method: ChromaticAdaptationMethod,
+        source: Illuminant,
+        destination: Illuminant,
+    ) -> LabValue {
+        let xyz = XyzValue::from_lab_with_white(self, source)
+            .chrom_adapt(method, source, destination);
+
+        LabValue::from_xyz_with_white(xyz, destination)
+    }
+}
+
+impl LchValue {
+    /// Adapt an [`LchValue`] relative to a source [`Illuminant`] to the corresponding
+    /// [`LchValue`] relative to a destination [`Illuminant`]. The conversion is done in XYZ.
+    pub fn chrom_adapt(
+        self,
+        method: ChromaticAdaptationMethod,
+        source: Illuminant,
+        destination: Illuminant,
+    ) -> LchValue {
+        LchValue::from(LabValue::from(self).chrom_adapt(method, source, destination))
+    }
+}
+
+const BRADFORD: Matrix3x3 = matrix![
+     0.8951,     0.2664,    -0.1614;
+    -0.7502,     1.7135,     0.0367;
+     0.0389,    -0.0685,     1.0296
+];
+
+const BRADFORD_INV: Matrix3x3 = BRADFORD.expect_inverse();
+
+const VON_KRIES: Matrix3x3 = matrix![
+     0.40024,    0.7076,    -0.08081;
+    -0.2263,     1.16532,    0.0457;
+     0.0,        0.0,        0.91822
+];
+
+const VON_KRIES_INV: Matrix3x3 = VON_KRIES.expect_inverse();
+
+const CAT02: Matrix3x3 = matrix![
+     0.7328,     0.4296,    -0.1624;
+    -0.7036,     1.6975,     0.0061;
+     0.003,      0.0136,     0.9834
+];
+
+const CAT02_INV: Matrix3x3 = CAT02.expect_inverse();
+
+const CAT16: Matrix3x3 = matrix![
+     0.401288,   0.650173,  -0.051461;
+    -0.250268,   1.204414,   0.045854;
+    -0.002079,   0.048952,   0.953127
+];
+
+const CAT16_INV: Matrix3x3 = CAT16.expect_inverse();
+
+const SHARP: Matrix3x3 = matrix![
+     1.2694,    -0.0988,    -0.1706;
+    -0.8364,     1.8006,     0.0357;
+     0.0297,    -0.0315,     1.0018
+];
+
+const SHARP_INV: Matrix3x3 = SHARP.expect_inverse();
+
+const FAIRCHILD: Matrix3x3 = matrix![
+     0.8562,     0.3372,    -0.1934;
+    -0.836,      1.8327,     0.0033;
+     0.0357,    -0.0469,     1.0112
+];
+
+const FAIRCHILD_INV: Matrix3x3 = FAIRCHILD.expect_inverse();
+
+const CMCCAT2000: Matrix3x3 = matrix![
+     0.7982,     0.3389,    -0.1371;
+    -0.5918,     1.5512,     0.0406;
+     0.0008,     0.0239,     0.9753
+];
+
+const CMCCAT2000_INV: Matrix3x3 = CMCCAT2000.expect_inverse();
+
+const XYZ_SCALING: Matrix3x3 = Matrix3x3::IDENTITY;
@@ -0,0 +1,287 @@
+//! Grouping colors into clusters of similar colors. [`cluster`] collapses near-duplicates, such as
+//! the swatches sampled from the same area of an image, into clusters whose members are all within
+//! a [`DeltaE`] of the cluster's centroid. [`kmeans`] extracts a palette of a fixed number of
+//! colors.
+//!
+//! # Examples
+//!
+//! ```
+//! use deltae::*;
+//!
+//! let swatches = [
+//!     LabValue::new(50.0, 20.0, 20.0).unwrap(),
+//!     LabValue::new(80.0, -10.0, 40.0).unwrap(),
+//!     LabValue::new(50.4, 20.3, 19.8).unwrap(),
+//!     LabValue::new(79.7, -10.2, 40.1).unwrap(),
+//!     LabValue::new(50.1, 19.6, 20.2).unwrap(),
+//! ];
+//!
+//! let clusters = cluster(swatches.iter().copied(), 1.0, DE2000);
+//! assert_eq!(clusters.len(), 2);
+//! assert_eq!(clusters[0].members, vec![0, 2, 4]);
+//! assert_eq!(clusters[1].members, vec![1, 3]);
+//!
+//! let unique = dedupe(swatches.iter().copied(), 1.0, DE2000);
+//! assert_eq!(unique, vec![swatches[0], swatches[1]]);
+//! ```
+
+use crate::*;
+#[cfg(feature = "illuminants")]
+use crate::interpolate::{lab_to_oklab, oklab_to_lab};
+
+/// The space the centroid of a [`Cluster`] is averaged in
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum CentroidSpace {
+    /// The mean of the L\*, a\*, and b\* values
+    #[default]
+    Lab,
+    /// The mean in [Oklab](https://bottosson.github.io/posts/oklab/), as in
+    /// [`LabValue::mix_oklab`]
+    #[cfg(feature = "illuminants")]
+    Oklab,
+}
+
+impl CentroidSpace {
+    fn to_coordinates(self, lab: LabValue) -> Matrix3x1 {
+        match self {
+            CentroidSpace::Lab => matrix![lab.l; lab.a; lab.b],
+            #[cfg(feature = "illuminants")]
+            CentroidSpace::Oklab => lab_to_oklab(lab),
+        }
+    }
+
+    fn to_lab(self, coordinates: Matrix3x1) -> LabValue {
+        match self {
+            CentroidSpace::Lab => LabValue { l: coordinates[0], a: coordinates[1], b: coordinates[2] },
+            #[cfg(feature = "illuminants")]
+            CentroidSpace::Oklab => oklab_to_lab(coordinates),
+        }
+    }
+}
+
+/// # Cluster
+///
+/// A group of similar colors, identified by their indices in the list of colors they were
+/// clustered from
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Cluster {
+    /// The mean of the members in Lab
+    pub centroid: LabValue,
+    /// The indices of the members, in ascending order
+    pub members: Vec<usize>,
+    /// The index of the member nearest to the centroid
+    pub representative: usize,
+}
+
+impl Cluster {
+    /// The number of members in the cluster
+    pub fn len(&self) -> usize {
+        self.members.len()
+    }
+
+    /// Return true if the cluster has no members
+    pub fn is_empty(&self) -> bool {
+        self.members.is_empty()
+    }
+}
+
+/// Group colors into clusters whose members are all within `threshold` of the cluster's centroid
+/// by the [`DEMethod`]. The centroid is the reference color of each [`DeltaE`].
+///
+/// Colors are added in order to the cluster with the nearest centroid, as long as all of its
+/// members would still be within the threshold of its new centroid, and otherwise start a new
+/// cluster. Clusters are in the order of their first members.
+pub fn cluster<C, I>(colors: I, threshold: f32, method: DEMethod) -> Vec<Cluster>
+where C: Into<LabValue>, I: IntoIterator<Item = C> {
+    let colors: Vec<LabValue> = colors.into_iter().map(Into::into).collect();
+    let mut clusters: Vec<Cluster> = Vec::new();
+
+    for (index, &color) in colors.iter().enumerate() {
+        let mut candidates: Vec<(usize, f32)> = clusters.iter()
+            .enumerate()
+            .map(|(i, cluster)| (i, *cluster.centroid.delta(color, method).value()))
+            .filter(|&(_, value)| value <= threshold)
+            .collect();
+        candidates.sort_by(|a, b| a.1.total_cmp(&b.1));
+
+        let joined = candidates.into_iter().find_map(|(i, _)| {
+            let cluster = &clusters[i];
+            let centroid = mean(&colors, &cluster.members, Some(index));
+            let fits = cluster.members.iter()
+                .chain(std::iter::once(&index))
+                .all(|&m| *centroid.delta(colors[m], method).value() <= threshold);
+            if fits { Some((i, centroid)) } else { None }
+        });
+
+        match joined {
+            Some((i, centroid)) => {
+                clusters[i].centroid = centroid;
+                clusters[i].members.push(index);
+            }
+            None => clusters.push(Cluster { centroid: color, members: vec![index], representative: index }),
+        }
+    }
+
+    for cluster in clusters.iter_mut() {
+        cluster.representative = representative(&colors, cluster, method);
+    }
+
+    clusters
+}
+
+/// Collapse near-duplicate colors into the representative of each [`Cluster`], in the order of
+/// their first appearance
+pub fn dedupe<C, I>(colors: I, threshold: f32, method: DEMethod) -> Vec<LabValue>
+where C: Into<LabValue>, I: IntoIterator<Item = C> {
+    let colors: Vec<LabValue> = colors.into_iter().map(Into::into).collect();
+    cluster(colors.iter().copied(), threshold, method)
+        .into_iter()
+        .map(|cluster| colors[cluster.representative])
+        .collect()
+}
+
+/// Partition colors into at most `k` clusters by
+/// [k-means](https://en.wikipedia.org/wiki/K-means_clustering). Each color is assigned to the
+/// cluster with the nearest centroid by the [`DEMethod`], with the centroid as the reference
+/// color, and each centroid is the mean of its
+/// members in the [`CentroidSpace`]. Clusters are sorted by their number of members, largest
+/// first.
+///
+/// The first centroid is the color nearest to the mean of all the colors, and each following one
+/// is the color furthest from the centroids before it, so the result is deterministic. There are
+/// fewer than `k` clusters when there are fewer than `k` distinct colors.
+/// ```
+/// use deltae::*;
+///
+/// let pixels = [
+///     LabValue::new(52.0, 60.0, 40.0).unwrap(),
+///     LabValue::new(20.0, 10.0, -45.0).unwrap(),
+///     LabValue::new(50.0, 62.0, 38.0).unwrap(),
+///     LabValue::new(54.0, 58.0, 42.0).unwrap(),
+///     LabValue::new(22.0, 12.0, -43.0).unwrap(),
+/// ];
+///
+/// let palette = kmeans(pixels.iter().copied(), 2, DE2000, CentroidSpace::Lab);
+/// assert_eq!(palette.len(), 2);
+/// assert_eq!(palette[0].centroid, LabValue::new(52.0, 60.0, 40.0).unwrap());
+/// assert_eq!(palette[0].members, vec![0, 2, 3]);
+/// assert_eq!(palette[1].len(), 2);
+/// ```
+pub fn kmeans<C, I>(colors: I, k: usize, method: DEMethod, space: CentroidSpace) -> Vec<Cluster>
+where C: Into<LabValue>, I: IntoIterator<Item = C> {
+    const MAX_ITERATIONS: usize = 100;
+
+    let colors: Vec<LabValue> = colors.into_iter().map(Into::into).collect();
+    if colors.is_empty() || k == 0 {
+        return Vec::new();
+    }
+
+    let coordinates: Vec<Matrix3x1> = colors.iter().map(|&lab| space.to_coordinates(lab)).collect();
+    let everything: Vec<usize> = (0..colors.len()).collect();
+    let mean_color = space.to_lab(mean_coordinates(&coordinates, &everything));
+
+    // Farthest-point initialization
+    let mut centroids = vec![colors[nearest(&colors, mean_color, method)]];
+    let mut distances: Vec<f32> = colors.iter()
+        .map(|&color| *centroids[0].delta(color, method).value())
+        .collect();
+    while centroids.len() < k {
+        let (furthest, &distance) = distances.iter()
+            .enumerate()
+            .max_by(|a, b| a.1.total_cmp(b.1).then(b.0.cmp(&a.0)))
+            .expect("colors is not empty");
+        if distance <= 0.0 {
+            break;
+        }
+
+        let centroid = colors[furthest];
+        centroids.push(centroid);
+        for (distance, &color) in distances.iter_mut().zip(colors.iter()) {
+            *distance = distance.min(*centroid.delta(color, method).value());
+        }
+    }
+
+    let mut assignments: Vec<usize> = Vec::new();
+    for _ in 0..MAX_ITERATIONS {
+        let assigned: Vec<usize> = colors.iter()
+            .map(|&color| {
+                centroids.iter()
+                    .map(|centroid| *centroid.delta(color, method).value())
+                    .enumerate()
+                    .min_by(|a, b| a.1.total_cmp(&b.1))
+                    .map_or(0, |(c, _)| c)
+            })
+            .collect();
+        if assigned == assignments {
+            break;
+        }
+        assignments = assigned;
+
+        for (c, centroid) in centroids.iter_mut().enumerate() {
+            let members: Vec<usize> = (0..colors.len()).filter(|&i| assignments[i] == c).collect();
+            if !members.is_empty() {
+                *centroid = space.to_lab(mean_coordinates(&coordinates, &members));
+            }
+        }
+    }
+
+    let mut clusters: Vec<Cluster> = centroids.into_iter()
+        .enumerate()
+        .map(|(c, centroid)| Cluster {
+            centroid,
+            members: (0..colors.len()).filter(|&i| assignments[i] == c).collect(),
+            representative: 0,
+        })
+        .filter(|cluster| !cluster.is_empty())
+        .collect();
+
+    for cluster in clusters.iter_mut() {
+        cluster.representative = representative(&colors, cluster, method);
+    }
+    clusters.sort_by_key(|cluster| std::cmp::Reverse(cluster.len()));
+
+    clusters
+}
+
+// The index of the color nearest to a reference color, the first one if there is a tie
+fn nearest(colors: &[LabValue], reference: LabValue, method: DEMethod) -> usize {
+    colors.iter()
+        .map(|&color| *reference.delta(color, method).value())
+        .enumerate()
+        .min_by(|a, b| a.1.total_cmp(&b.1))
+        .map(|(i, _)| i)
+        .unwrap_or(0)
+}
+
+fn mean_coordinates(coordinates: &[Matrix3x1], members: &[usize]) -> Matrix3x1 {
+    let sum = members.iter().fold(matrix![0.0; 0.0; 0.0], |sum, &i| sum + coordinates[i]);
+    sum / members.len() as f32
+}
+
+// The mean in Lab of the colors at the given indices, and optionally one more
+fn mean(colors: &[LabValue], members: &[usize], extra: Option<usize>) -> LabValue {
+    let indices = members.iter().copied().chain(extra);
+    let (mut l, mut a, mut b, mut n) = (0.0, 0.0, 0.0, 0.0);
+    for i in indices {
+        l += colors[i].l;
+        a += colors[i].a;
+        b += colors[i].b;
+        n += 1.0;
+    }
+
+    LabValue { l: l / n, a: a / n, b: b / n }
+}
+
+// The member nearest to the centroid, the first one if there is a tie
+fn representative(colors: &[LabValue], cluster: &Cluster, method: DEMethod) -> usize {
+    cluster.members.iter()
+        .copied()
+        .min_by(|&i, &j| {
+            let de_i = *cluster.centroid.delta(colors[i], method).value();
+            let de_j = *cluster.centroid.delta(colors[j], method).value();
+            de_i.total_cmp(&de_j)
+        })
+        .unwrap_or(cluster.representative)
+}
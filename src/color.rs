@@ -3,6 +3,7 @@
 //! # Examples
 //!
 //! ```
+//! # #[cfg(feature = "parse")] {
 //! use deltae::*;
 //!
 //! let lab0: LabValue = "95.08, -0.17, -10.81".parse().unwrap();
@@ -19,11 +20,17 @@
 //!
 //! println!("{}", lch0); // [L:89.73, c:7.2094, h:285.1157]
 //!
+//! // The alternate form can be parsed back
+//! let lch1: LchValue = format!("{:#}", lch0).parse().unwrap();
+//! assert_eq!(lch0, lch1);
+//!
 //! assert_eq!(lab0.round_to(4), lab2.round_to(4));
+//! # }
 //! ```
 
 use std::fmt;
 use std::error::Error;
+use std::hash::{Hash, Hasher};
 
 use crate::*;
 
@@ -38,6 +45,7 @@ use crate::*;
 /// | `b*`    | `Blue  <---> Yellow`  | `-128.0 <---> 128.0` |
 ///
 #[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct LabValue {
     /// Lightness
     pub l: f32,
@@ -61,9 +69,12 @@ impl Default for LabValue {
     }
 }
 
+/// Displays as `[L:50, a:-2.5, b:10]`, or `50,-2.5,10` in the alternate form (`{:#}`). The
+/// precision applies to each value, and the width, fill, and alignment to the whole, so
+/// `{:>#18.2}` lines up a column of colors. The other color types display the same way.
 impl fmt::Display for LabValue {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
-        write!(f, "[L:{}, a:{}, b:{}]", self.l, self.a, self.b)
+        fmt_components(f, ["L", "a", "b"], [self.l, self.a, self.b])
     }
 }
 
@@ -76,6 +87,7 @@ impl fmt::Display for LabValue {
 /// | `h`     | `Hue (Degrees)`            | `0.0 <---> 360.0°`     |
 ///
 #[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct LchValue {
     /// Lightness
     pub l: f32,
@@ -106,7 +118,7 @@ impl Default for LchValue {
 
 impl fmt::Display for LchValue {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
-        write!(f, "[L:{}, c:{}, h:{}]", self.l, self.c, self.h)
+        fmt_components(f, ["L", "c", "h"], [self.l, self.c, self.h])
     }
 }
 
@@ -121,6 +133,7 @@ impl fmt::Display for LchValue {
 /// | `Z`     | `Blue`      | `0.0 <---> 1.0` |
 ///
 #[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct XyzValue {
     /// X Value
     pub x: f32,
@@ -146,32 +159,414 @@ impl Default for XyzValue {
 
 impl fmt::Display for XyzValue {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
-        write!(f, "[X:{}, Y:{}, Z:{}]", self.x, self.y, self.z)
+        fmt_components(f, ["X", "Y", "Z"], [self.x, self.y, self.z])
     }
 }
 
-#[derive(Debug)]
-/// Value validation Error type
-pub enum ValueError {
-    /// The value is outside the acceptable range
-    OutOfBounds,
-    /// The value is formatted incorrectly
-    BadFormat,
+/// # Color
+///
+/// A color of any of the crate types, for holding lists of colors of mixed types. A [`Color`]
+/// displays and parses with a tag naming its type, followed by the values of the color:
+/// `lab:50,2,-3`, `lch:50,3.6,303.7`, `xyz:0.2,0.18,0.15`, or `rgb:#aabbcc`.
+///
+/// ```
+/// # #[cfg(all(feature = "rgb", feature = "parse"))] {
+/// use deltae::*;
+///
+/// let colors: Vec<Color> = ["lab:50,2,-3", "rgb:#808080", "xyz:0.18, 0.19, 0.16"]
+///     .iter()
+///     .map(|s| s.parse().unwrap())
+///     .collect();
+///
+/// assert_eq!(colors[0], Color::Lab(LabValue { l: 50.0, a: 2.0, b: -3.0 }));
+/// assert_eq!(colors[0].to_string(), "lab:50,2,-3");
+///
+/// for color in &colors {
+///     println!("{}", color.delta(colors[0], DE2000));
+/// }
+/// # }
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum Color {
+    /// A CIE L\*a\*b\* color
+    Lab(LabValue),
+    /// A CIE L\*C\*h° color
+    Lch(LchValue),
+    /// A CIE XYZ color
+    Xyz(XyzValue),
+    /// An 8-bit sRGB color
+    #[cfg(feature = "rgb")]
+    Rgb(RgbValue),
+}
+
+impl Color {
+    /// The tag of the type of the color: `"lab"`, `"lch"`, `"xyz"`, or `"rgb"`
+    pub fn tag(&self) -> &'static str {
+        match self {
+            Color::Lab(_) => "lab",
+            Color::Lch(_) => "lch",
+            Color::Xyz(_) => "xyz",
+            #[cfg(feature = "rgb")]
+            Color::Rgb(_) => "rgb",
+        }
+    }
 }
 
-impl fmt::Display for ValueError {
+impl fmt::Display for Color {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
-        write!(f, "{}", self.description())
+        let precision = f.precision();
+        let values = match self {
+            Color::Lab(lab) => alternate(lab, precision),
+            Color::Lch(lch) => alternate(lch, precision),
+            Color::Xyz(xyz) => alternate(xyz, precision),
+            #[cfg(feature = "rgb")]
+            Color::Rgb(rgb) => alternate(rgb, precision),
+        };
+        pad(f, &format!("{}:{}", self.tag(), values))
+    }
+}
+
+// The alternate form of a value, with a precision if there is one
+fn alternate<T: fmt::Display>(value: &T, precision: Option<usize>) -> String {
+    match precision {
+        Some(p) => format!("{:#.*}", p, value),
+        None => format!("{:#}", value),
+    }
+}
+
+// Write the three components of a color with the precision of the formatter, if any: labeled in
+// brackets, or only separated by commas in the alternate form. The result is padded to the width
+// of the formatter.
+pub(crate) fn fmt_components<T>(f: &mut fmt::Formatter, labels: [&str; 3], values: [T; 3]) -> fmt::Result
+where T: fmt::Display {
+    let values: Vec<String> = values.iter()
+        .map(|v| match f.precision() {
+            Some(p) => format!("{:.*}", p, v),
+            None => format!("{}", v),
+        })
+        .collect();
+
+    let s = if f.alternate() {
+        values.join(",")
+    } else {
+        format!("[{}:{}, {}:{}, {}:{}]", labels[0], values[0], labels[1], values[1], labels[2], values[2])
+    };
+
+    pad(f, &s)
+}
+
+// Write a string with the fill, alignment, and width of the formatter, left-aligned by default.
+// Unlike Formatter::pad, the precision does not truncate the string.
+pub(crate) fn pad(f: &mut fmt::Formatter, s: &str) -> fmt::Result {
+    let padding = f.width().unwrap_or(0).saturating_sub(s.chars().count());
+    let (before, after) = match f.align() {
+        Some(fmt::Alignment::Right) => (padding, 0),
+        Some(fmt::Alignment::Center) => (padding / 2, padding - padding / 2),
+        _ => (0, padding),
+    };
+
+    let fill = f.fill();
+    for _ in 0..before {
+        write!(f, "{}", fill)?;
+    }
+    f.write_str(s)?;
+    for _ in 0..after {
+        write!(f, "{}", fill)?;
+    }
+
+    Ok(())
+}
+
+/// # Color Value
+///
+/// The interface shared by the color types: their components, the white point they are relative
+/// to, and conversion to [`LabValue`] and [`XyzValue`]. Every [`ColorValue`] implements [`Delta`]
+/// and [`DeltaEq`], so generic code can accept any color.
+///
+/// ```
+/// # #[cfg(feature = "rgb")] {
+/// use deltae::*;
+///
+/// fn describe<C: ColorValue>(color: C) -> String {
+///     let [v0, v1, v2] = color.components();
+///     let [l0, l1, l2] = color.labels();
+///     format!("{}={} {}={} {}={} ({})", l0, v0, l1, v1, l2, v2, color.white_point())
+/// }
+///
+/// assert_eq!(describe(LabValue { l: 50.0, a: 2.0, b: -3.0 }), "L=50 a=2 b=-3 (D50)");
+/// assert_eq!(describe(RgbValue::new(255, 0, 0)), "R=255 G=0 B=0 (D65)");
+///
+/// let red = RgbValue::new(255, 0, 0);
+/// assert!(red.delta_eq(red.to_lab(), DE2000, 0.0));
+/// # }
+/// ```
+pub trait ColorValue: Copy + Into<LabValue> {
+    /// The values of the components
+    fn components(&self) -> [f32; 3];
+
+    /// The names of the components, as they appear in the `Display` output
+    fn labels(&self) -> [&'static str; 3];
+
+    /// The [`Illuminant`] of the white point the color is relative to
+    #[cfg(feature = "illuminants")]
+    fn white_point(&self) -> Illuminant;
+
+    /// Convert the color to a [`LabValue`] relative to [`Illuminant::D50`]
+    fn to_lab(&self) -> LabValue {
+        (*self).into()
+    }
+
+    /// Convert the color to an [`XyzValue`] relative to [`Illuminant::D50`]
+    fn to_xyz(&self) -> XyzValue {
+        XyzValue::from(self.to_lab())
+    }
+}
+
+macro_rules! impl_color_value {
+    ($t:ty, [$($label:expr),*], [$($field:ident),*], $white:expr) => {
+        impl ColorValue for $t {
+            fn components(&self) -> [f32; 3] {
+                [$(self.$field as f32),*]
+            }
+
+            fn labels(&self) -> [&'static str; 3] {
+                [$($label),*]
+            }
+
+            #[cfg(feature = "illuminants")]
+            fn white_point(&self) -> Illuminant {
+                $white
+            }
+        }
+    }
+}
+
+impl_color_value!(LabValue, ["L", "a", "b"], [l, a, b], Illuminant::D50);
+impl_color_value!(LchValue, ["L", "c", "h"], [l, c, h], Illuminant::D50);
+#[cfg(feature = "rgb")]
+impl_color_value!(RgbValue, ["R", "G", "B"], [r, g, b], RgbSystem::Srgb.white_point());
+#[cfg(feature = "rgb")]
+impl_color_value!(Rgb16Value, ["R", "G", "B"], [r, g, b], RgbSystem::Srgb.white_point());
+#[cfg(feature = "rgb")]
+impl_color_value!(RgbNominalValue, ["R", "G", "B"], [r, g, b], RgbSystem::Srgb.white_point());
+
+impl ColorValue for XyzValue {
+    fn components(&self) -> [f32; 3] {
+        [self.x, self.y, self.z]
+    }
+
+    fn labels(&self) -> [&'static str; 3] {
+        ["X", "Y", "Z"]
+    }
+
+    #[cfg(feature = "illuminants")]
+    fn white_point(&self) -> Illuminant {
+        Illuminant::D50
+    }
+
+    fn to_xyz(&self) -> XyzValue {
+        *self
     }
 }
 
-impl ValueError {
-    fn description(&self) -> &str {
+impl ColorValue for Color {
+    fn components(&self) -> [f32; 3] {
+        match self {
+            Color::Lab(lab) => lab.components(),
+            Color::Lch(lch) => lch.components(),
+            Color::Xyz(xyz) => xyz.components(),
+            #[cfg(feature = "rgb")]
+            Color::Rgb(rgb) => rgb.components(),
+        }
+    }
+
+    fn labels(&self) -> [&'static str; 3] {
+        match self {
+            Color::Lab(lab) => lab.labels(),
+            Color::Lch(lch) => lch.labels(),
+            Color::Xyz(xyz) => xyz.labels(),
+            #[cfg(feature = "rgb")]
+            Color::Rgb(rgb) => rgb.labels(),
+        }
+    }
+
+    #[cfg(feature = "illuminants")]
+    fn white_point(&self) -> Illuminant {
+        match self {
+            Color::Lab(lab) => lab.white_point(),
+            Color::Lch(lch) => lch.white_point(),
+            Color::Xyz(xyz) => xyz.white_point(),
+            #[cfg(feature = "rgb")]
+            Color::Rgb(rgb) => rgb.white_point(),
+        }
+    }
+
+    fn to_xyz(&self) -> XyzValue {
         match self {
-            ValueError::OutOfBounds => "Value is out of range!",
-            ValueError::BadFormat   => "Value is malformed!",
+            Color::Xyz(xyz) => *xyz,
+            _ => XyzValue::from(self.to_lab()),
+        }
+    }
+}
+
+/// # Hash Key
+///
+/// A color that implements [`Hash`] and [`Eq`], as the key of a `HashMap` or the member of a
+/// `HashSet`. Colors are equal when they have the same component labels and the same components,
+/// bit for bit, except that `0.0` equals `-0.0`, and every NaN equals every other NaN.
+/// ```
+/// use deltae::*;
+/// use std::collections::HashMap;
+///
+/// let mut cache: HashMap<HashKey<LabValue>, XyzValue> = HashMap::new();
+/// let lab = LabValue::new(50.0, -0.0, 10.0).unwrap();
+/// cache.insert(HashKey(lab), XyzValue::from(lab));
+///
+/// assert!(cache.contains_key(&HashKey(LabValue::new(50.0, 0.0, 10.0).unwrap())));
+/// assert!(!cache.contains_key(&HashKey(LabValue::new(50.0, 0.0, 10.01).unwrap())));
+/// ```
+#[derive(Debug, Clone, Copy)]
+pub struct HashKey<C>(pub C);
+
+impl<C: ColorValue> HashKey<C> {
+    fn canonical_bits(&self) -> [u32; 3] {
+        let canonical = |v: f32| if v.is_nan() {
+            f32::NAN.to_bits()
+        } else if v == 0.0 {
+            0
+        } else {
+            v.to_bits()
+        };
+        let [v0, v1, v2] = self.0.components();
+        [canonical(v0), canonical(v1), canonical(v2)]
+    }
+}
+
+impl<C: ColorValue> PartialEq for HashKey<C> {
+    fn eq(&self, other: &HashKey<C>) -> bool {
+        self.0.labels() == other.0.labels() && self.canonical_bits() == other.canonical_bits()
+    }
+}
+
+impl<C: ColorValue> Eq for HashKey<C> {}
+
+impl<C: ColorValue> Hash for HashKey<C> {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.0.labels().hash(state);
+        self.canonical_bits().hash(state);
+    }
+}
+
+impl<C: ColorValue> From<C> for HashKey<C> {
+    fn from(color: C) -> HashKey<C> {
+        HashKey(color)
+    }
+}
+
+/// # Parse Error
+///
+/// Describes where and why parsing a color from a string failed: the component that failed, the
+/// offending token, and the byte position of that token in the input string.
+///
+/// ```
+/// # #[cfg(feature = "parse")] {
+/// use deltae::*;
+///
+/// let err = "89.73, 1.88, derp".parse::<LabValue>().unwrap_err();
+/// assert_eq!(err.to_string(), r#"invalid number "derp" for b at position 13"#);
+///
+/// if let ValueError::Parse(e) = err {
+///     assert_eq!(e.kind(), ParseErrorKind::Malformed);
+///     assert_eq!(e.component(), Some("b"));
+///     assert_eq!(e.token(), "derp");
+///     assert_eq!(e.position(), 13);
+/// }
+/// # }
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ParseError {
+    kind: ParseErrorKind,
+    component: Option<&'static str>,
+    token: String,
+    position: usize,
+}
+
+#[cfg(feature = "parse")]
+impl ParseError {
+    pub(crate) fn new(
+        kind: ParseErrorKind,
+        component: Option<&'static str>,
+        token: &str,
+        position: usize,
+    ) -> ParseError {
+        ParseError { kind, component, token: token.to_string(), position }
+    }
+
+    // Move the position of the error when the parsed string was a slice of the input
+    pub(crate) fn offset(self, by: usize) -> ParseError {
+        ParseError { position: self.position + by, ..self }
+    }
+
+    /// The reason parsing failed
+    pub fn kind(&self) -> ParseErrorKind {
+        self.kind
+    }
+
+    /// The name of the component that failed, as it appears in the `Display` output of the color
+    /// (`"L"`, `"a"`, `"b"`, ...), or `None` if the failure does not belong to one component
+    pub fn component(&self) -> Option<&'static str> {
+        self.component
+    }
+
+    /// The offending token. Empty if a component is missing.
+    pub fn token(&self) -> &str {
+        &self.token
+    }
+
+    /// The byte offset of the token in the input string
+    pub fn position(&self) -> usize {
+        self.position
+    }
+}
+
+impl fmt::Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self.kind {
+            ParseErrorKind::Malformed      => write!(f, "invalid number {:?}", self.token)?,
+            ParseErrorKind::OutOfRange     => write!(f, "value {:?} out of range", self.token)?,
+            ParseErrorKind::Missing        => write!(f, "missing value")?,
+            ParseErrorKind::Unexpected     => write!(f, "unexpected {:?}", self.token)?,
+            ParseErrorKind::UnknownLabel   => write!(f, "unknown label {:?}", self.token)?,
+            ParseErrorKind::DuplicateLabel => write!(f, "duplicate label {:?}", self.token)?,
+            ParseErrorKind::UnknownName    => write!(f, "unknown name {:?}", self.token)?,
+        }
+
+        if let Some(component) = self.component {
+            write!(f, " for {}", component)?;
         }
+
+        write!(f, " at position {}", self.position)
     }
 }
 
-impl Error for ValueError {}
+impl Error for ParseError {}
+
+/// The reason a [`ParseError`] occurred
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ParseErrorKind {
+    /// The token is not a valid number
+    Malformed,
+    /// The number is outside the acceptable range of the component
+    OutOfRange,
+    /// The input ended before all of the components were found
+    Missing,
+    /// The input has more tokens than components
+    Unexpected,
+    /// A component label does not belong to the color type
+    UnknownLabel,
+    /// A component label appears more than once
+    DuplicateLabel,
+    /// The string is not the name of an illuminant, observer, or other named value
+    UnknownName,
+}
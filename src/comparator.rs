@@ -0,0 +1,194 @@
+//! Comparing many pairs of colors under the same conditions. A [`Comparator`] holds the
+//! [`DEMethod`], the [`Tolerance`], and the conditions colors are converted under, so they do not
+//! have to be passed to every comparison.
+//!
+//! # Examples
+//!
+//! ```
+//! # #[cfg(feature = "rgb")] {
+//! use deltae::*;
+//!
+//! let comparator = DeltaE::builder()
+//!     .method(DE1976)
+//!     .tolerance(2.0)
+//!     .illuminant(Illuminant::D65)
+//!     .rgb_system(RgbSystem::Adobe);
+//!
+//! let pairs = [
+//!     (Color::from(RgbValue::new(10, 200, 30)), Color::from(RgbValue::new(12, 200, 30))),
+//!     (Color::from(RgbValue::new(10, 200, 30)), Color::from(RgbValue::new(40, 200, 30))),
+//! ];
+//!
+//! let reports = comparator.compare_all(pairs.iter().copied());
+//! assert!(reports[0].pass);
+//! assert!(!reports[1].pass);
+//! # }
+//! ```
+
+use crate::*;
+
+/// # Comparator
+///
+/// The settings of a comparison between colors of any type:
+///
+/// - Lab and LCh values are relative to the white point of the [`Illuminant`] for the
+///   [`Observer`]. XYZ values are converted to Lab relative to the same white point.
+/// - RGB values are in the [`RgbSystem`], and are adapted from its white point to the white point
+///   of the illuminant with the [`ChromaticAdaptationMethod`].
+///
+/// The default is [`DE2000`] with a tolerance of `1.0`, under [`Illuminant::D50`] and the 2°
+/// observer, with sRGB values adapted by [`ChromaticAdaptationMethod::Bradford`].
+#[derive(Debug, Clone, PartialEq)]
+pub struct Comparator {
+    method: DEMethod,
+    tolerance: f32,
+    components: Option<ComponentTolerance>,
+    #[cfg(feature = "illuminants")]
+    illuminant: Illuminant,
+    #[cfg(feature = "illuminants")]
+    observer: Observer,
+    #[cfg(feature = "rgb")]
+    rgb_system: RgbSystem,
+    #[cfg(feature = "rgb")]
+    adaptation: ChromaticAdaptationMethod,
+}
+
+impl Default for Comparator {
+    fn default() -> Comparator {
+        Comparator {
+            method: DE2000,
+            tolerance: 1.0,
+            components: None,
+            #[cfg(feature = "illuminants")]
+            illuminant: Illuminant::D50,
+            #[cfg(feature = "illuminants")]
+            observer: Observer::Cie1931,
+            #[cfg(feature = "rgb")]
+            rgb_system: RgbSystem::Srgb,
+            #[cfg(feature = "rgb")]
+            adaptation: ChromaticAdaptationMethod::Bradford,
+        }
+    }
+}
+
+impl DeltaE {
+    /// Return a default [`Comparator`] to set the method, tolerance, and conditions of
+    /// comparisons
+    pub fn builder() -> Comparator {
+        Comparator::default()
+    }
+}
+
+impl Comparator {
+    /// Set the [`DEMethod`]
+    pub fn method(mut self, method: DEMethod) -> Comparator {
+        self.method = method;
+        self
+    }
+
+    /// Set the [`Tolerance`]
+    pub fn tolerance<T: Tolerance>(mut self, tolerance: T) -> Comparator {
+        self.tolerance = tolerance.tolerance();
+        self
+    }
+
+    /// Set a [`ComponentTolerance`] that samples must also be within
+    pub fn components(mut self, components: ComponentTolerance) -> Comparator {
+        self.components = Some(components);
+        self
+    }
+
+    /// Set the [`Illuminant`] of the white point of Lab, LCh, and XYZ values
+    #[cfg(feature = "illuminants")]
+    pub fn illuminant(mut self, illuminant: Illuminant) -> Comparator {
+        self.illuminant = illuminant;
+        self
+    }
+
+    /// Set the [`Observer`] of the white point of Lab, LCh, and XYZ values
+    #[cfg(feature = "illuminants")]
+    pub fn observer(mut self, observer: Observer) -> Comparator {
+        self.observer = observer;
+        self
+    }
+
+    /// Set the [`RgbSystem`] of RGB values
+    #[cfg(feature = "rgb")]
+    pub fn rgb_system(mut self, rgb_system: RgbSystem) -> Comparator {
+        self.rgb_system = rgb_system;
+        self
+    }
+
+    /// Set the [`ChromaticAdaptationMethod`] between the white point of the [`RgbSystem`] and the
+    /// white point of the [`Illuminant`]
+    #[cfg(feature = "rgb")]
+    pub fn adaptation(mut self, adaptation: ChromaticAdaptationMethod) -> Comparator {
+        self.adaptation = adaptation;
+        self
+    }
+
+    /// The white point of the [`Illuminant`] for the [`Observer`]. Without the `illuminants`
+    /// feature, it is the white point of D50 for the 2° observer.
+    pub fn white_point(&self) -> XyzValue {
+        #[cfg(feature = "illuminants")]
+        return self.illuminant.white_point_for(self.observer);
+        #[cfg(not(feature = "illuminants"))]
+        return crate::convert::D50;
+    }
+
+    /// Convert a color to a [`LabValue`] under the conditions of the comparator
+    /// ```
+    /// # #[cfg(feature = "rgb")] {
+    /// use deltae::*;
+    ///
+    /// let lab = LabValue::new(50.0, 2.0, -3.0).unwrap();
+    /// assert_eq!(DeltaE::builder().illuminant(Illuminant::D65).to_lab(lab), lab);
+    ///
+    /// let white = DeltaE::builder().to_lab(RgbValue::new(255, 255, 255));
+    /// assert_eq!(white.round_to(2), LabValue::new(100.0, 0.0, 0.0).unwrap());
+    /// # }
+    /// ```
+    pub fn to_lab<C: Into<Color>>(&self, color: C) -> LabValue {
+        let white = self.white_point();
+        let xyz = match color.into() {
+            Color::Lab(lab) => return lab,
+            Color::Lch(lch) => return lch.into(),
+            Color::Xyz(xyz) => xyz,
+            #[cfg(feature = "rgb")]
+            Color::Rgb(rgb) => {
+                let xyz = XyzValue::from_rgb(rgb.into(), self.rgb_system.clone(), None);
+                let source = self.rgb_system.white_point().white_point();
+                xyz.chrom_adapt_white_point(self.adaptation, source, white)
+            }
+        };
+
+        LabValue::from_xyz_with_white_point(xyz, white)
+    }
+
+    /// Calculate the [`DeltaE`] between a reference and a sample color
+    pub fn delta<A: Into<Color>, B: Into<Color>>(&self, reference: A, sample: B) -> DeltaE {
+        self.to_lab(reference).delta(self.to_lab(sample), self.method)
+    }
+
+    /// Return whether a sample color is within the tolerance of a reference color, and within the
+    /// [`ComponentTolerance`] if one is set
+    pub fn delta_eq<A: Into<Color>, B: Into<Color>>(&self, reference: A, sample: B) -> bool {
+        self.report(reference, sample).pass
+    }
+
+    /// Return a [`DeltaReport`] of the comparison of a reference and a sample color against the
+    /// tolerance
+    pub fn report<A: Into<Color>, B: Into<Color>>(&self, reference: A, sample: B) -> DeltaReport {
+        let delta = self.delta(reference, sample);
+        match self.components {
+            Some(components) => DeltaReport::new(delta, (self.tolerance, components)),
+            None => DeltaReport::new(delta, self.tolerance),
+        }
+    }
+
+    /// Return a [`DeltaReport`] for each pair of reference and sample colors
+    pub fn compare_all<A, B, I>(&self, pairs: I) -> Vec<DeltaReport>
+    where A: Into<Color>, B: Into<Color>, I: IntoIterator<Item = (A, B)> {
+        pairs.into_iter().map(|(reference, sample)| self.report(reference, sample)).collect()
+    }
+}
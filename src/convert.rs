@@ -1,6 +1,5 @@
 use super::*;
 use std::convert::TryFrom;
-use std::str::FromStr;
 
 // To Lab /////////////////////////////////////////////////////////////////////
 impl From<LchValue> for LabValue {
@@ -27,15 +26,7 @@ impl From<&LabValue> for LabValue {
 
 impl From<XyzValue> for LabValue {
     fn from(xyz: XyzValue) -> LabValue {
-        let x = xyz_to_lab_map(xyz.x / 0.9642);
-        let y = xyz_to_lab_map(xyz.y);
-        let z = xyz_to_lab_map(xyz.z / 0.8251);
-
-        LabValue {
-            l: (116.0 * y) - 16.0,
-            a: 500.0 * (x - y),
-            b: 200.0 * (y - z),
-        }
+        LabValue::from_xyz_with_white_point(xyz, D50)
     }
 }
 
@@ -143,30 +134,7 @@ impl TryFrom<&(f32, f32, f32)> for LchValue {
 // To Xyz /////////////////////////////////////////////////////////////////////
 impl From<LabValue> for XyzValue {
     fn from(lab: LabValue) -> XyzValue {
-        let fy = (lab.l + 16.0) / 116.0;
-        let fx = (lab.a / 500.0) + fy;
-        let fz = fy - (lab.b / 200.0);
-        let xr = if fx > CBRT_EPSILON as f32 {
-            fx.powi(3)
-        } else {
-            ((fx * 116.0) - 16.0) / KAPPA
-        };
-        let yr = if lab.l > EPSILON * KAPPA {
-            fy.powi(3)
-        } else {
-            lab.l / KAPPA
-        };
-        let zr = if fz > CBRT_EPSILON as f32 {
-            fz.powi(3)
-        } else {
-            ((fz * 116.0) - 16.0) / KAPPA
-        };
-
-        XyzValue {
-            x: xr * 0.9642,
-            y: yr,
-            z: zr * 0.8251,
-        }
+        XyzValue::from_lab_with_white_point(lab, D50)
     }
 }
 
@@ -221,66 +189,228 @@ impl TryFrom<&(f32, f32, f32)> for XyzValue {
     }
 }
 
-// FromStr ////////////////////////////////////////////////////////////////////
-impl FromStr for DEMethod {
-    type Err = std::io::Error;
-    fn from_str(s: &str) -> Result<DEMethod, Self::Err> {
-        match s.to_lowercase().trim() {
-            "de2000"  | "de00"  | "2000"  | "00"  => Ok(DEMethod::DE2000),
-            "de1976"  | "de76"  | "1976"  | "76"  => Ok(DEMethod::DE1976),
-            "de1994"  | "de94"  | "1994"  | "94" |
-            "de1994g" | "de94g" | "1994g" | "94g" => Ok(DEMethod::DE1994G),
-            "de1994t" | "de94t" | "1994t" | "94t" => Ok(DEMethod::DE1994T),
-            "decmc"   | "decmc1"| "cmc1"  | "cmc" => Ok(DEMethod::DECMC(1.0, 1.0)),
-            "decmc2"  | "cmc2"                    => Ok(DEMethod::DECMC(2.0, 1.0)),
-            _ => Err(io::Error::from(io::ErrorKind::InvalidInput)),
+// RGB ////////////////////////////////////////////////////////////////////////
+// Conversions between channel depths go through the nominal values
+macro_rules! impl_rgb_depth_from {
+    ($from:ty, $to:ty) => {
+        #[cfg(feature = "rgb")]
+        impl From<$from> for $to {
+            fn from(rgb: $from) -> $to {
+                rgb.to_depth()
+            }
         }
+
+        #[cfg(feature = "rgb")]
+        impl From<&$from> for $to {
+            fn from(rgb: &$from) -> $to {
+                rgb.to_depth()
+            }
+        }
+    };
+}
+
+impl_rgb_depth_from!(RgbValue, RgbNominalValue);
+impl_rgb_depth_from!(RgbNominalValue, RgbValue);
+impl_rgb_depth_from!(Rgb16Value, RgbNominalValue);
+impl_rgb_depth_from!(RgbNominalValue, Rgb16Value);
+impl_rgb_depth_from!(RgbValue, Rgb16Value);
+impl_rgb_depth_from!(Rgb16Value, RgbValue);
+
+// Conversions between RGB and the CIE types assume sRGB adapted to D50 with Bradford
+#[cfg(feature = "rgb")]
+impl<C: Channel> From<Rgb<C>> for LabValue {
+    fn from(rgb: Rgb<C>) -> LabValue {
+        LabValue::from_rgb(
+            rgb.to_nominal(),
+            RgbSystem::Srgb,
+            Some(ChromaticAdaptationMethod::Bradford),
+        )
     }
 }
 
-impl FromStr for LabValue {
-    type Err = ValueError;
-    fn from_str(s: &str) -> ValueResult<LabValue> {
-        let split = parse_str_to_vecf32(s, 3)?;
+#[cfg(feature = "rgb")]
+impl<C: Channel> From<&Rgb<C>> for LabValue {
+    fn from(rgb: &Rgb<C>) -> LabValue {
+        LabValue::from(*rgb)
+    }
+}
 
-        LabValue {
-            l: split[0],
-            a: split[1],
-            b: split[2],
-        }.validate()
+#[cfg(feature = "rgb")]
+impl<C: Channel> From<Rgb<C>> for LchValue {
+    fn from(rgb: Rgb<C>) -> LchValue {
+        LchValue::from(LabValue::from(rgb))
     }
 }
 
-impl FromStr for LchValue {
-    type Err = ValueError;
-    fn from_str(s: &str) -> ValueResult<LchValue> {
-        let split = parse_str_to_vecf32(s, 3)?;
+#[cfg(feature = "rgb")]
+impl<C: Channel> From<Rgb<C>> for XyzValue {
+    fn from(rgb: Rgb<C>) -> XyzValue {
+        XyzValue::from_rgb(
+            rgb.to_nominal(),
+            RgbSystem::Srgb,
+            Some(ChromaticAdaptationMethod::Bradford),
+        )
+    }
+}
 
-        LchValue {
-            l: split[0],
-            c: split[1],
-            h: split[2],
-        }.validate()
+#[cfg(feature = "rgb")]
+impl<C: Channel> From<LabValue> for Rgb<C> {
+    fn from(lab: LabValue) -> Rgb<C> {
+        Rgb::from_nominal(RgbNominalValue::from_lab(
+            lab,
+            RgbSystem::Srgb,
+            Some(ChromaticAdaptationMethod::Bradford),
+        ))
     }
 }
 
-impl FromStr for XyzValue {
-    type Err = ValueError;
-    fn from_str(s: &str) -> ValueResult<XyzValue> {
-        let split = parse_str_to_vecf32(s, 3)?;
+#[cfg(feature = "rgb")]
+impl<C: Channel> From<LchValue> for Rgb<C> {
+    fn from(lch: LchValue) -> Rgb<C> {
+        Rgb::from(LabValue::from(lch))
+    }
+}
 
-        XyzValue {
-            x: split[0],
-            y: split[1],
-            z: split[2],
-        }.validate()
+#[cfg(feature = "rgb")]
+impl<C: Channel> From<XyzValue> for Rgb<C> {
+    fn from(xyz: XyzValue) -> Rgb<C> {
+        Rgb::from_nominal(RgbNominalValue::from_xyz(
+            xyz,
+            RgbSystem::Srgb,
+            Some(ChromaticAdaptationMethod::Bradford),
+        ))
     }
+}
 
+// Color ///////////////////////////////////////////////////////////////////////
+macro_rules! impl_color_from {
+    ($variant:ident, $t:ty) => {
+        impl From<$t> for Color {
+            fn from(value: $t) -> Color {
+                Color::$variant(value)
+            }
+        }
+    }
+}
+
+impl_color_from!(Lab, LabValue);
+impl_color_from!(Lch, LchValue);
+impl_color_from!(Xyz, XyzValue);
+#[cfg(feature = "rgb")]
+impl_color_from!(Rgb, RgbValue);
+
+macro_rules! impl_from_color {
+    ($t:ty) => {
+        impl From<Color> for $t {
+            fn from(color: Color) -> $t {
+                match color {
+                    Color::Lab(lab) => lab.into(),
+                    Color::Lch(lch) => lch.into(),
+                    Color::Xyz(xyz) => xyz.into(),
+                    #[cfg(feature = "rgb")]
+                    Color::Rgb(rgb) => rgb.into(),
+                }
+            }
+        }
+    }
+}
+
+impl_from_color!(LabValue);
+impl_from_color!(LchValue);
+impl_from_color!(XyzValue);
+#[cfg(feature = "rgb")]
+impl_from_color!(RgbValue);
+
+// Reference White ////////////////////////////////////////////////////////////
+impl LabValue {
+    /// Convert an [`XyzValue`] to a [`LabValue`] relative to the white point of an [`Illuminant`].
+    /// The [`From`] implementations assume [`Illuminant::D50`].
+    /// ```
+    /// use deltae::*;
+    ///
+    /// let white = Illuminant::D65.white_point();
+    /// let lab = LabValue::from_xyz_with_white(white, Illuminant::D65);
+    /// assert_eq!(lab.round_to(4), LabValue { l: 100.0, a: 0.0, b: 0.0 });
+    /// ```
+    #[cfg(feature = "illuminants")]
+    pub fn from_xyz_with_white(xyz: XyzValue, illuminant: Illuminant) -> LabValue {
+        LabValue::from_xyz_with_white_point(xyz, illuminant.white_point())
+    }
+
+    /// Convert an [`XyzValue`] to a [`LabValue`] relative to a white point, such as the white point
+    /// of an [`Illuminant`] for a standard [`Observer`]
+    /// ```
+    /// use deltae::*;
+    ///
+    /// // D65 for the CIE 1964 10° observer
+    /// let white = XyzValue { x: 0.94811, y: 1.0, z: 1.07304 };
+    /// let lab = LabValue::from_xyz_with_white_point(white, white);
+    /// assert_eq!(lab.round_to(4), LabValue { l: 100.0, a: 0.0, b: 0.0 });
+    /// ```
+    pub fn from_xyz_with_white_point(xyz: XyzValue, white: XyzValue) -> LabValue {
+        let x = xyz_to_lab_map(xyz.x / white.x);
+        let y = xyz_to_lab_map(xyz.y / white.y);
+        let z = xyz_to_lab_map(xyz.z / white.z);
+
+        LabValue {
+            l: (116.0 * y) - 16.0,
+            a: 500.0 * (x - y),
+            b: 200.0 * (y - z),
+        }
+    }
+}
+
+impl XyzValue {
+    /// Convert a [`LabValue`] relative to the white point of an [`Illuminant`] to an
+    /// [`XyzValue`]. The [`From`] implementations assume [`Illuminant::D50`].
+    /// ```
+    /// use deltae::*;
+    ///
+    /// let lab = LabValue { l: 100.0, a: 0.0, b: 0.0 };
+    /// let xyz = XyzValue::from_lab_with_white(lab, Illuminant::D65);
+    /// assert_eq!(xyz, Illuminant::D65.white_point());
+    /// ```
+    #[cfg(feature = "illuminants")]
+    pub fn from_lab_with_white(lab: LabValue, illuminant: Illuminant) -> XyzValue {
+        XyzValue::from_lab_with_white_point(lab, illuminant.white_point())
+    }
+
+    /// Convert a [`LabValue`] relative to a white point, such as the white point of an
+    /// [`Illuminant`] for a standard [`Observer`], to an [`XyzValue`]
+    pub fn from_lab_with_white_point(lab: LabValue, white: XyzValue) -> XyzValue {
+        let fy = (lab.l + 16.0) / 116.0;
+        let fx = (lab.a / 500.0) + fy;
+        let fz = fy - (lab.b / 200.0);
+        let xr = if fx > CBRT_EPSILON as f32 {
+            fx.powi(3)
+        } else {
+            ((fx * 116.0) - 16.0) / KAPPA
+        };
+        let yr = if lab.l > EPSILON * KAPPA {
+            fy.powi(3)
+        } else {
+            lab.l / KAPPA
+        };
+        let zr = if fz > CBRT_EPSILON as f32 {
+            fz.powi(3)
+        } else {
+            ((fz * 116.0) - 16.0) / KAPPA
+        };
+
+        XyzValue {
+            x: xr * white.x,
+            y: yr * white.y,
+            z: zr * white.z,
+        }
+    }
 }
 
 // Helper Functions ////////////////////////////////////////////////////////////
-const KAPPA: f32 = 24389.0 / 27.0; // CIE Standard: 903.3
-const EPSILON: f32 = 216.0 / 24389.0; // CIE Standard: 0.008856
+// The white point of Illuminant::D50 for the 2° observer, the reference white of the From
+// implementations
+pub(crate) const D50: XyzValue = XyzValue { x: 0.9642, y: 1.0, z: 0.8251 };
+pub(crate) const KAPPA: f32 = 24389.0 / 27.0; // CIE Standard: 903.3
+pub(crate) const EPSILON: f32 = 216.0 / 24389.0; // CIE Standard: 0.008856
 const CBRT_EPSILON: f64 = 0.20689655172413796;
 
 pub fn get_h_prime(a: f32, b: f32) -> f32 {
@@ -292,29 +422,6 @@ pub fn get_h_prime(a: f32, b: f32) -> f32 {
     }
 }
 
-// Validate and convert strings to `LabValue`.
-// Split string by comma (92.5,33.5,-18.8).
-fn parse_str_to_vecf32(s: &str, length: usize) -> ValueResult<Vec<f32>> {
-    let collection: Vec<&str> = s.split(',').collect();
-
-    // Allow extraneous whitespace ("92.5, 33.5, -18.8")
-    let mut v: Vec<&str> = Vec::new();
-    for item in collection.iter() {
-        if !item.is_empty() {
-            v.push(item.trim());
-        }
-    }
-    // Parse the f32's into a Vec
-    let split: Vec<f32> = v.iter().filter_map(|s| s.parse().ok()).collect();
-
-    // Check if it's the right number of items
-    if v.len() != length || split.len() != length {
-        return Err(ValueError::BadFormat);
-    }
-
-    Ok(split)
-}
-
 #[inline]
 fn xyz_to_lab_map(c: f32) -> f32 {
     if c > EPSILON {
@@ -0,0 +1,390 @@
+//! The CIE color rendering index (CRI) of a light source, from its spectral power distribution.
+//!
+//! The colors of a set of test color samples lit by the source are compared to their colors lit by
+//! a reference illuminant of the same correlated color temperature: a blackbody below 5000K, and
+//! CIE daylight from 5000K. Each sample gives a special color rendering index `Ri`, and the general
+//! color rendering index `Ra` is the mean of the first eight. An index of `100` renders the samples
+//! exactly like the reference.
+//!
+//! The reflectance of the fourteen test color samples TCS01 to TCS14 of CIE 13.3 is included, see
+//! [`test_color_samples`]. Other samples can be used with [`color_rendering_index_with`].
+//!
+//! # Examples
+//!
+//! ```
+//! use deltae::*;
+//!
+//! // An incandescent lamp is a blackbody, so it renders colors like its reference
+//! let incandescent = SpectralCurve::blackbody(2700.0, 380.0, 5.0, 81).unwrap();
+//! let cri = color_rendering_index(&incandescent).unwrap();
+//! assert!((cri.cct() - 2700.0).abs() < 1.0);
+//! assert_eq!(cri.ra().round(), 100.0);
+//! assert_eq!(cri.special().len(), 14);
+//!
+//! // A lamp with three narrow bands renders the colors between them poorly
+//! let triband = SpectralCurve::new(380.0, 5.0, (0..81).map(|j| {
+//!     let wavelength = 380.0 + 5.0 * j as f32;
+//!     [450.0, 545.0, 610.0].iter()
+//!         .map(|peak| (-((wavelength - peak) / 10.0).powi(2)).exp())
+//!         .sum()
+//! }).collect()).unwrap();
+//! let cri = color_rendering_index(&triband).unwrap();
+//! assert!(cri.ra() < 90.0);
+//! ```
+
+use crate::*;
+
+/// # Color Rendering Index
+///
+/// The color rendering of a light source, calculated by [`color_rendering_index`]
+#[derive(Debug, Clone, PartialEq)]
+pub struct ColorRenderingIndex {
+    cct: f32,
+    duv: f32,
+    ra: f32,
+    special: Vec<f32>,
+}
+
+impl ColorRenderingIndex {
+    /// The correlated color temperature of the source in kelvin
+    pub fn cct(&self) -> f32 {
+        self.cct
+    }
+
+    /// The distance of the source from the blackbody locus in the CIE 1960 UCS, positive above
+    /// it. CIE 13.3 considers the index meaningful when the distance is under `0.0054`.
+    pub fn duv(&self) -> f32 {
+        self.duv
+    }
+
+    /// The general color rendering index `Ra`, the mean of `R1` to `R8`
+    pub fn ra(&self) -> f32 {
+        self.ra
+    }
+
+    /// The special color rendering indices `R1`, `R2`, and so on, one for each test color sample
+    pub fn special(&self) -> &[f32] {
+        &self.special
+    }
+}
+
+/// Calculate the [`ColorRenderingIndex`] of a light source from its relative spectral power, as
+/// specified by CIE 13.3: with the fourteen [`test_color_samples`] and the CIE 1931 2° standard
+/// observer. Will return `Err()` if the source has no power in the visible range.
+pub fn color_rendering_index(source: &SpectralCurve) -> ValueResult<ColorRenderingIndex> {
+    color_rendering_index_with(source, &test_color_samples(), &ColorMatchingFunctions::cie1931())
+}
+
+/// Calculate the [`ColorRenderingIndex`] of a light source from its relative spectral power,
+/// with the reflectance of other test color samples, in order, and another observer. The first
+/// eight samples give the general index `Ra`.
+/// Will return `Err()` if there are fewer than eight samples, or if the source has no power in
+/// the range of the samples.
+pub fn color_rendering_index_with(
+    source: &SpectralCurve,
+    samples: &[SpectralCurve],
+    observer: &ColorMatchingFunctions,
+) -> ValueResult<ColorRenderingIndex> {
+    if samples.len() < 8 {
+        return Err(ValueError::BadFormat);
+    }
+
+    let (cct, duv) = correlated_color_temperature(source, observer);
+    if !duv.is_finite() {
+        return Err(ValueError::NotFinite);
+    }
+    let reference = if cct < 5000.0 {
+        SpectralCurve::blackbody(cct, source.start(), source.interval(), source.len())?
+    } else {
+        SpectralCurve::daylight(cct)?
+    };
+
+    let special: Vec<f32> = samples.iter().map(|sample| {
+        let white = sample.map(|_| 1.0);
+        let source_white = uv(white.to_xyz(source, observer));
+        let reference_white = uv(white.to_xyz(&reference, observer));
+
+        let test = sample.to_xyz(source, observer);
+        let rendered = sample.to_xyz(&reference, observer);
+        let adapted = adapt(uv(test), source_white, reference_white);
+        let [u0, v0, w0] = uvw(test.y, adapted, reference_white);
+        let [u1, v1, w1] = uvw(rendered.y, uv(rendered), reference_white);
+        let delta = ((u0 - u1).powi(2) + (v0 - v1).powi(2) + (w0 - w1).powi(2)).sqrt();
+
+        100.0 - 4.6 * delta
+    }).collect();
+
+    let ra = special[..8].iter().sum::<f32>() / 8.0;
+
+    Ok(ColorRenderingIndex { cct, duv, ra, special })
+}
+
+/// The reflectance of the test color samples TCS01 to TCS14 of CIE 13.3, from 380nm to 780nm at
+/// 5nm intervals. TCS01 to TCS08 are moderately saturated Munsell colors of equal lightness
+/// around the hue circle and give the general index `Ra`. TCS09 to TCS12 are saturated red,
+/// yellow, green and blue, TCS13 is a human complexion and TCS14 is leaf green.
+/// ```
+/// use deltae::*;
+///
+/// let samples = test_color_samples();
+/// assert_eq!(samples.len(), 14);
+/// // The strong red TCS09 reflects far more at 700nm than at 500nm
+/// assert!(samples[8].at(700.0).unwrap() > 10.0 * samples[8].at(500.0).unwrap());
+/// ```
+pub fn test_color_samples() -> Vec<SpectralCurve> {
+    TEST_COLOR_SAMPLES.iter()
+        .map(|reflectance| {
+            SpectralCurve::new(380.0, 5.0, reflectance.to_vec())
+                .expect("the test color samples are finite")
+        })
+        .collect()
+}
+
+/// The correlated color temperature of a light source in kelvin, from its relative spectral
+/// power: the temperature of the nearest blackbody in the CIE 1960 UCS, from 1000K to 25000K. Also
+/// returns the distance from the blackbody, positive above it.
+/// ```
+/// use deltae::*;
+///
+/// let observer = ColorMatchingFunctions::cie1931();
+/// let d65 = SpectralCurve::daylight(6504.0).unwrap();
+/// let (cct, duv) = correlated_color_temperature(&d65, &observer);
+/// assert!((cct - 6500.0).abs() < 50.0);
+/// assert!(duv > 0.0);
+/// ```
+pub fn correlated_color_temperature(
+    source: &SpectralCurve,
+    observer: &ColorMatchingFunctions,
+) -> (f32, f32) {
+    let (u, v) = uv(source.map(|_| 1.0).to_xyz(source, observer));
+    let locus = |mired: f32| {
+        SpectralCurve::blackbody(1e6 / mired, source.start(), source.interval(), source.len())
+            .map(|blackbody| uv(source.map(|_| 1.0).to_xyz(&blackbody, observer)))
+            .unwrap_or((f32::NAN, f32::NAN))
+    };
+    let distance = |mired: f32| {
+        let (lu, lv) = locus(mired);
+        (u - lu).hypot(v - lv)
+    };
+
+    // Search for the nearest point in steps of 5 mired, then refine by golden section
+    let nearest = (8..=200)
+        .map(|step| step as f32 * 5.0)
+        .min_by(|a, b| distance(*a).total_cmp(&distance(*b)))
+        .unwrap_or(200.0);
+    let (mut low, mut high) = ((nearest - 5.0).max(40.0), (nearest + 5.0).min(1000.0));
+    let ratio = (5f32.sqrt() - 1.0) / 2.0;
+    for _ in 0..32 {
+        let a = high - ratio * (high - low);
+        let b = low + ratio * (high - low);
+        if distance(a) < distance(b) {
+            high = b;
+        } else {
+            low = a;
+        }
+    }
+
+    let mired = (low + high) / 2.0;
+    let (_, lv) = locus(mired);
+    (1e6 / mired, distance(mired).copysign(v - lv))
+}
+
+// The CIE 1960 UCS chromaticity of an XyzValue
+fn uv(xyz: XyzValue) -> (f32, f32) {
+    let denominator = xyz.x + 15.0 * xyz.y + 3.0 * xyz.z;
+    (4.0 * xyz.x / denominator, 6.0 * xyz.y / denominator)
+}
+
+// The von Kries adaptation of CIE 13.3 of a chromaticity from the white of the source to the white
+// of the reference illuminant
+fn adapt(uv: (f32, f32), source_white: (f32, f32), reference_white: (f32, f32)) -> (f32, f32) {
+    let cd = |(u, v): (f32, f32)| ((4.0 - u - 10.0 * v) / v, (1.708 * v + 0.404 - 1.481 * u) / v);
+    let (ci, di) = cd(uv);
+    let (ck, dk) = cd(source_white);
+    let (cr, dr) = cd(reference_white);
+
+    let c = cr / ck * ci;
+    let d = dr / dk * di;
+    let denominator = 16.518 + 1.481 * c - d;
+    ((10.872 + 0.404 * c - 4.0 * d) / denominator, 5.520 / denominator)
+}
+
+// The CIE 1964 U*V*W* of a luminance factor and chromaticity, relative to a white chromaticity
+fn uvw(y: f32, (u, v): (f32, f32), (white_u, white_v): (f32, f32)) -> [f32; 3] {
+    let w = 25.0 * (100.0 * y).cbrt() - 17.0;
+    [13.0 * w * (u - white_u), 13.0 * w * (v - white_v), w]
+}
+
+// The reflectance of the CIE 13.3 test color samples from 380nm to 780nm at 5nm intervals
+// Measured reflectances, some of which happen to be close to 1/π
+#[allow(clippy::approx_constant)]
+const TEST_COLOR_SAMPLES: [[f32; 81]; 14] = [
+    // TCS01 7.5R 6/4, light greyish red
+    [
+        0.219, 0.239, 0.252, 0.256, 0.256, 0.254, 0.252, 0.248, 0.244, 0.240,
+        0.237, 0.232, 0.230, 0.226, 0.225, 0.222, 0.220, 0.218, 0.216, 0.214,
+        0.214, 0.214, 0.216, 0.218, 0.223, 0.225, 0.226, 0.226, 0.225, 0.225,
+        0.227, 0.230, 0.236, 0.245, 0.253, 0.262, 0.272, 0.283, 0.298, 0.318,
+        0.341, 0.367, 0.390, 0.409, 0.424, 0.435, 0.442, 0.448, 0.450, 0.451,
+        0.451, 0.451, 0.451, 0.451, 0.450, 0.450, 0.451, 0.451, 0.453, 0.454,
+        0.455, 0.457, 0.458, 0.460, 0.462, 0.463, 0.464, 0.465, 0.466, 0.466,
+        0.466, 0.466, 0.467, 0.467, 0.467, 0.467, 0.467, 0.467, 0.467, 0.467,
+        0.467,
+    ],
+    // TCS02 5Y 6/4, dark greyish yellow
+    [
+        0.070, 0.079, 0.089, 0.101, 0.111, 0.116, 0.118, 0.120, 0.121, 0.122,
+        0.122, 0.122, 0.123, 0.124, 0.127, 0.128, 0.131, 0.134, 0.138, 0.143,
+        0.150, 0.159, 0.174, 0.190, 0.207, 0.225, 0.242, 0.253, 0.260, 0.264,
+        0.267, 0.269, 0.272, 0.276, 0.282, 0.289, 0.299, 0.309, 0.322, 0.329,
+        0.335, 0.339, 0.341, 0.341, 0.342, 0.342, 0.342, 0.341, 0.341, 0.339,
+        0.339, 0.338, 0.338, 0.337, 0.336, 0.335, 0.334, 0.332, 0.332, 0.331,
+        0.331, 0.330, 0.329, 0.328, 0.328, 0.327, 0.326, 0.325, 0.324, 0.324,
+        0.324, 0.323, 0.322, 0.321, 0.320, 0.318, 0.316, 0.315, 0.315, 0.314,
+        0.314,
+    ],
+    // TCS03 5GY 6/8, strong yellow green
+    [
+        0.065, 0.068, 0.070, 0.072, 0.073, 0.073, 0.074, 0.074, 0.074, 0.073,
+        0.073, 0.073, 0.073, 0.073, 0.074, 0.075, 0.077, 0.080, 0.085, 0.094,
+        0.109, 0.126, 0.148, 0.172, 0.198, 0.221, 0.241, 0.260, 0.278, 0.302,
+        0.339, 0.370, 0.392, 0.399, 0.400, 0.393, 0.380, 0.365, 0.349, 0.332,
+        0.315, 0.299, 0.285, 0.272, 0.264, 0.257, 0.252, 0.247, 0.241, 0.235,
+        0.229, 0.224, 0.220, 0.217, 0.216, 0.216, 0.219, 0.224, 0.230, 0.238,
+        0.251, 0.269, 0.288, 0.312, 0.340, 0.366, 0.390, 0.412, 0.431, 0.447,
+        0.460, 0.472, 0.481, 0.488, 0.493, 0.497, 0.500, 0.502, 0.505, 0.510,
+        0.516,
+    ],
+    // TCS04 2.5G 6/6, moderate yellowish green
+    [
+        0.074, 0.083, 0.093, 0.105, 0.116, 0.121, 0.124, 0.126, 0.128, 0.131,
+        0.135, 0.139, 0.144, 0.151, 0.161, 0.172, 0.186, 0.205, 0.229, 0.254,
+        0.281, 0.308, 0.332, 0.352, 0.370, 0.383, 0.390, 0.394, 0.395, 0.392,
+        0.385, 0.377, 0.367, 0.354, 0.341, 0.327, 0.312, 0.296, 0.280, 0.263,
+        0.247, 0.229, 0.214, 0.198, 0.185, 0.175, 0.169, 0.164, 0.160, 0.156,
+        0.154, 0.152, 0.151, 0.149, 0.148, 0.148, 0.148, 0.149, 0.151, 0.154,
+        0.158, 0.162, 0.165, 0.168, 0.170, 0.171, 0.170, 0.168, 0.166, 0.164,
+        0.164, 0.165, 0.168, 0.172, 0.177, 0.181, 0.185, 0.189, 0.192, 0.194,
+        0.195,
+    ],
+    // TCS05 10BG 6/4, light bluish green
+    [
+        0.295, 0.306, 0.310, 0.312, 0.313, 0.315, 0.319, 0.322, 0.326, 0.330,
+        0.334, 0.339, 0.346, 0.352, 0.360, 0.369, 0.381, 0.394, 0.403, 0.410,
+        0.415, 0.418, 0.419, 0.417, 0.413, 0.409, 0.403, 0.396, 0.389, 0.381,
+        0.372, 0.363, 0.353, 0.342, 0.331, 0.320, 0.308, 0.296, 0.284, 0.271,
+        0.260, 0.247, 0.232, 0.220, 0.210, 0.200, 0.194, 0.189, 0.185, 0.183,
+        0.180, 0.177, 0.176, 0.175, 0.175, 0.175, 0.175, 0.177, 0.180, 0.183,
+        0.186, 0.189, 0.192, 0.195, 0.199, 0.200, 0.199, 0.198, 0.196, 0.195,
+        0.195, 0.196, 0.197, 0.200, 0.203, 0.205, 0.208, 0.212, 0.215, 0.217,
+        0.219,
+    ],
+    // TCS06 5PB 6/8, light blue
+    [
+        0.151, 0.203, 0.265, 0.339, 0.410, 0.464, 0.492, 0.508, 0.517, 0.524,
+        0.531, 0.538, 0.544, 0.551, 0.556, 0.556, 0.554, 0.549, 0.541, 0.531,
+        0.519, 0.504, 0.488, 0.469, 0.450, 0.431, 0.414, 0.395, 0.377, 0.358,
+        0.341, 0.325, 0.309, 0.293, 0.279, 0.265, 0.253, 0.241, 0.234, 0.227,
+        0.225, 0.222, 0.221, 0.220, 0.220, 0.220, 0.220, 0.220, 0.223, 0.227,
+        0.233, 0.239, 0.244, 0.251, 0.258, 0.263, 0.268, 0.273, 0.278, 0.281,
+        0.283, 0.286, 0.291, 0.296, 0.302, 0.313, 0.325, 0.338, 0.351, 0.364,
+        0.376, 0.389, 0.401, 0.413, 0.425, 0.436, 0.447, 0.458, 0.469, 0.477,
+        0.485,
+    ],
+    // TCS07 2.5P 6/8, light violet
+    [
+        0.378, 0.459, 0.524, 0.546, 0.551, 0.555, 0.559, 0.560, 0.561, 0.558,
+        0.556, 0.551, 0.544, 0.535, 0.522, 0.506, 0.488, 0.469, 0.448, 0.429,
+        0.408, 0.385, 0.363, 0.341, 0.324, 0.311, 0.301, 0.291, 0.283, 0.273,
+        0.265, 0.260, 0.257, 0.257, 0.259, 0.260, 0.260, 0.258, 0.256, 0.254,
+        0.254, 0.259, 0.270, 0.284, 0.302, 0.324, 0.344, 0.362, 0.377, 0.389,
+        0.400, 0.410, 0.420, 0.429, 0.438, 0.445, 0.452, 0.457, 0.462, 0.466,
+        0.468, 0.470, 0.473, 0.477, 0.483, 0.489, 0.496, 0.503, 0.511, 0.518,
+        0.525, 0.532, 0.539, 0.546, 0.553, 0.559, 0.565, 0.570, 0.575, 0.578,
+        0.581,
+    ],
+    // TCS08 10P 6/8, light reddish purple
+    [
+        0.104, 0.129, 0.170, 0.240, 0.319, 0.416, 0.462, 0.482, 0.490, 0.488,
+        0.482, 0.473, 0.462, 0.450, 0.439, 0.426, 0.413, 0.397, 0.382, 0.367,
+        0.352, 0.337, 0.325, 0.310, 0.299, 0.289, 0.283, 0.276, 0.270, 0.262,
+        0.256, 0.251, 0.250, 0.251, 0.254, 0.258, 0.264, 0.269, 0.272, 0.274,
+        0.278, 0.284, 0.295, 0.316, 0.348, 0.384, 0.434, 0.482, 0.528, 0.568,
+        0.604, 0.629, 0.648, 0.663, 0.676, 0.685, 0.693, 0.700, 0.705, 0.709,
+        0.712, 0.715, 0.717, 0.719, 0.721, 0.720, 0.719, 0.722, 0.725, 0.727,
+        0.729, 0.730, 0.730, 0.730, 0.730, 0.730, 0.730, 0.730, 0.730, 0.730,
+        0.730,
+    ],
+    // TCS09 4.5R 4/13, strong red
+    [
+        0.066, 0.062, 0.058, 0.055, 0.052, 0.052, 0.051, 0.050, 0.050, 0.049,
+        0.048, 0.047, 0.046, 0.044, 0.042, 0.041, 0.038, 0.035, 0.033, 0.031,
+        0.030, 0.029, 0.028, 0.028, 0.028, 0.029, 0.030, 0.030, 0.031, 0.031,
+        0.032, 0.032, 0.033, 0.034, 0.035, 0.037, 0.041, 0.044, 0.047, 0.050,
+        0.054, 0.060, 0.068, 0.083, 0.104, 0.133, 0.166, 0.208, 0.248, 0.293,
+        0.338, 0.383, 0.423, 0.459, 0.490, 0.515, 0.537, 0.554, 0.569, 0.582,
+        0.593, 0.604, 0.612, 0.620, 0.626, 0.631, 0.634, 0.638, 0.641, 0.644,
+        0.647, 0.649, 0.650, 0.652, 0.653, 0.654, 0.655, 0.656, 0.656, 0.657,
+        0.657,
+    ],
+    // TCS10 5Y 8/10, strong yellow
+    [
+        0.050, 0.054, 0.059, 0.063, 0.066, 0.067, 0.068, 0.069, 0.069, 0.070,
+        0.072, 0.073, 0.076, 0.078, 0.083, 0.088, 0.095, 0.103, 0.113, 0.125,
+        0.142, 0.162, 0.189, 0.219, 0.262, 0.305, 0.365, 0.416, 0.465, 0.509,
+        0.546, 0.581, 0.610, 0.634, 0.658, 0.674, 0.690, 0.702, 0.713, 0.723,
+        0.731, 0.739, 0.744, 0.749, 0.753, 0.757, 0.760, 0.762, 0.764, 0.766,
+        0.767, 0.769, 0.770, 0.771, 0.772, 0.773, 0.774, 0.775, 0.776, 0.776,
+        0.777, 0.778, 0.778, 0.779, 0.780, 0.781, 0.782, 0.782, 0.782, 0.782,
+        0.783, 0.783, 0.783, 0.783, 0.783, 0.783, 0.783, 0.783, 0.783, 0.783,
+        0.783,
+    ],
+    // TCS11 4.5G 5/8, strong green
+    [
+        0.111, 0.121, 0.127, 0.129, 0.127, 0.121, 0.116, 0.112, 0.108, 0.105,
+        0.104, 0.104, 0.105, 0.106, 0.110, 0.115, 0.123, 0.134, 0.148, 0.167,
+        0.192, 0.219, 0.252, 0.291, 0.325, 0.347, 0.356, 0.353, 0.346, 0.333,
+        0.314, 0.294, 0.271, 0.248, 0.227, 0.206, 0.188, 0.170, 0.153, 0.138,
+        0.125, 0.114, 0.106, 0.100, 0.096, 0.092, 0.090, 0.087, 0.085, 0.083,
+        0.082, 0.080, 0.079, 0.078, 0.078, 0.078, 0.078, 0.081, 0.083, 0.088,
+        0.093, 0.102, 0.112, 0.125, 0.141, 0.161, 0.182, 0.203, 0.223, 0.242,
+        0.257, 0.270, 0.282, 0.292, 0.302, 0.310, 0.314, 0.317, 0.323, 0.330,
+        0.334,
+    ],
+    // TCS12 3PB 3/11, strong blue
+    [
+        0.120, 0.103, 0.090, 0.082, 0.076, 0.068, 0.064, 0.065, 0.075, 0.093,
+        0.123, 0.160, 0.207, 0.256, 0.300, 0.331, 0.346, 0.347, 0.341, 0.328,
+        0.307, 0.282, 0.257, 0.230, 0.204, 0.178, 0.154, 0.129, 0.109, 0.090,
+        0.075, 0.062, 0.051, 0.041, 0.035, 0.029, 0.025, 0.022, 0.019, 0.017,
+        0.017, 0.017, 0.016, 0.016, 0.016, 0.016, 0.016, 0.016, 0.018, 0.018,
+        0.018, 0.018, 0.019, 0.020, 0.023, 0.024, 0.026, 0.030, 0.035, 0.043,
+        0.056, 0.074, 0.097, 0.128, 0.166, 0.210, 0.257, 0.305, 0.354, 0.401,
+        0.446, 0.485, 0.520, 0.551, 0.577, 0.599, 0.618, 0.633, 0.645, 0.656,
+        0.666,
+    ],
+    // TCS13 5YR 8/4, light yellowish pink (human complexion)
+    [
+        0.190, 0.224, 0.260, 0.291, 0.306, 0.312, 0.313, 0.313, 0.318, 0.324,
+        0.330, 0.335, 0.339, 0.342, 0.345, 0.346, 0.346, 0.347, 0.347, 0.350,
+        0.353, 0.357, 0.363, 0.371, 0.383, 0.398, 0.415, 0.433, 0.452, 0.468,
+        0.481, 0.492, 0.502, 0.515, 0.526, 0.538, 0.549, 0.559, 0.570, 0.581,
+        0.590, 0.598, 0.605, 0.612, 0.619, 0.625, 0.631, 0.636, 0.640, 0.644,
+        0.648, 0.652, 0.656, 0.659, 0.663, 0.666, 0.669, 0.672, 0.675, 0.678,
+        0.681, 0.684, 0.686, 0.688, 0.690, 0.693, 0.695, 0.697, 0.698, 0.699,
+        0.700, 0.701, 0.702, 0.703, 0.704, 0.705, 0.705, 0.705, 0.706, 0.707,
+        0.707,
+    ],
+    // TCS14 5GY 4/4, moderate olive green (leaf green)
+    [
+        0.036, 0.036, 0.036, 0.036, 0.036, 0.036, 0.037, 0.038, 0.039, 0.039,
+        0.040, 0.041, 0.042, 0.042, 0.043, 0.044, 0.044, 0.045, 0.045, 0.046,
+        0.047, 0.048, 0.050, 0.052, 0.055, 0.057, 0.062, 0.067, 0.075, 0.083,
+        0.092, 0.100, 0.108, 0.121, 0.133, 0.142, 0.150, 0.154, 0.155, 0.152,
+        0.147, 0.140, 0.133, 0.125, 0.118, 0.112, 0.106, 0.101, 0.098, 0.095,
+        0.093, 0.090, 0.089, 0.087, 0.086, 0.085, 0.084, 0.084, 0.084, 0.084,
+        0.085, 0.087, 0.092, 0.096, 0.102, 0.110, 0.123, 0.137, 0.152, 0.169,
+        0.188, 0.207, 0.226, 0.243, 0.260, 0.277, 0.294, 0.310, 0.325, 0.339,
+        0.353,
+    ],
+];
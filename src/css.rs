@@ -0,0 +1,245 @@
+//! Parsing of colors in the syntax of the
+//! [CSS Color Module Level 4](https://www.w3.org/TR/css-color-4/).
+//!
+//! # Examples
+//!
+//! ```
+//! use deltae::*;
+//!
+//! let red: CssColor = "rgb(255 0 0)".parse().unwrap();
+//! let also_red: CssColor = "hsl(0deg 100% 50%)".parse().unwrap();
+//! assert_eq!(red, also_red);
+//!
+//! // Any CssColor can be compared with any other color type
+//! let oklab: CssColor = "oklab(0.628 0.2249 0.1258)".parse().unwrap();
+//! assert!(red.clone().delta(oklab, DE2000).value() < &0.1);
+//!
+//! let lab = LabValue::new(54.29, 80.8, 69.89).unwrap();
+//! assert!(red.delta(lab, DE2000).value() < &0.1);
+//! ```
+
+use std::str::FromStr;
+
+use crate::*;
+use crate::interpolate::{OKLAB_LAB2LMS, OKLAB_LMS2XYZ};
+
+/// # CSS Color
+///
+/// A color parsed from one of the CSS color functions, in the crate type that best represents it:
+///
+/// | `Syntax`                                                  | `Variant`                         |
+/// |:----------------------------------------------------------|:----------------------------------|
+/// | `#RRGGBB`, `rgb()`, `rgba()`, `hsl()`, `hsla()`           | [`CssColor::Rgb`] in sRGB         |
+/// | Named colors (`named-colors` feature)                     | [`CssColor::Rgb`] in sRGB         |
+/// | `color(srgb\|display-p3\|a98-rgb\|prophoto-rgb\|rec2020)` | [`CssColor::Rgb`]                 |
+/// | `lab()`                                                   | [`CssColor::Lab`]                 |
+/// | `lch()`                                                   | [`CssColor::Lch`]                 |
+/// | `oklab()`, `oklch()`, `color(xyz-d50\|xyz-d65)`           | [`CssColor::Xyz`] relative to D50 |
+///
+/// Both the modern space separated syntax and the legacy comma separated syntax are accepted, as
+/// are percentages, hue units, and the `none` keyword. An alpha value is validated but ignored,
+/// since it has no bearing on color difference. Values outside of the range of the crate types
+/// are an error rather than being clamped.
+#[derive(Debug, Clone, PartialEq)]
+pub enum CssColor {
+    /// An RGB color and the [`RgbSystem`] it is relative to
+    Rgb(RgbNominalValue, RgbSystem),
+    /// A CIE L\*a\*b\* color relative to D50
+    Lab(LabValue),
+    /// A CIE L\*C\*h° color relative to D50
+    Lch(LchValue),
+    /// A CIE XYZ color relative to D50. Colors specified relative to D65 are adapted with the
+    /// Bradford transform, as in CSS.
+    Xyz(XyzValue),
+}
+
+impl From<CssColor> for LabValue {
+    fn from(css: CssColor) -> LabValue {
+        match css {
+            CssColor::Rgb(rgb, system) => {
+                LabValue::from_rgb(rgb, system, Some(ChromaticAdaptationMethod::Bradford))
+            }
+            CssColor::Lab(lab) => lab,
+            CssColor::Lch(lch) => LabValue::from(lch),
+            CssColor::Xyz(xyz) => LabValue::from(xyz),
+        }
+    }
+}
+
+impl FromStr for CssColor {
+    type Err = ValueError;
+    fn from_str(s: &str) -> ValueResult<CssColor> {
+        let s = s.trim().to_lowercase();
+
+        if s.starts_with('#') {
+            let rgb = RgbValue::from_str(&s)?;
+            return Ok(CssColor::Rgb(RgbNominalValue::from(rgb), RgbSystem::Srgb));
+        }
+
+        #[cfg(feature = "named-colors")]
+        if let Some(rgb) = RgbValue::from_name(&s) {
+            return Ok(CssColor::Rgb(RgbNominalValue::from(rgb), RgbSystem::Srgb));
+        }
+
+        let open = s.find('(').ok_or(ValueError::BadFormat)?;
+        let args = s[open + 1..].strip_suffix(')').ok_or(ValueError::BadFormat)?;
+        let (mut components, alpha) = split_args(args)?;
+
+        if let Some(alpha) = alpha {
+            if !(0.0..=1.0).contains(&number(alpha, 1.0)?) {
+                return Err(ValueError::OutOfBounds);
+            }
+        }
+
+        let space = match &s[..open] {
+            "color" if !components.is_empty() => components.remove(0),
+            name => name,
+        };
+
+        if components.len() != 3 {
+            return Err(ValueError::BadFormat);
+        }
+        let [c0, c1, c2] = [components[0], components[1], components[2]];
+
+        match space {
+            "rgb" | "rgba" => {
+                let [r, g, b] = [number(c0, 255.0)?, number(c1, 255.0)?, number(c2, 255.0)?];
+                rgb(r / 255.0, g / 255.0, b / 255.0, RgbSystem::Srgb)
+            }
+            "hsl" | "hsla" => {
+                let h = hue(c0)?;
+                let [s, l] = [number(c1, 100.0)? / 100.0, number(c2, 100.0)? / 100.0];
+                let nominal = HslValue::new(h, s, l)?.to_rgb();
+                rgb(nominal.r, nominal.g, nominal.b, RgbSystem::Srgb)
+            }
+            "lab" => {
+                LabValue {
+                    l: number(c0, 100.0)?,
+                    a: number(c1, 125.0)?,
+                    b: number(c2, 125.0)?,
+                }.validate().map(CssColor::Lab)
+            }
+            "lch" => {
+                LchValue {
+                    l: number(c0, 100.0)?,
+                    c: number(c1, 150.0)?,
+                    h: hue(c2)?,
+                }.validate().map(CssColor::Lch)
+            }
+            "oklab" => oklab(number(c0, 1.0)?, number(c1, 0.4)?, number(c2, 0.4)?),
+            "oklch" => {
+                let (c, h) = (number(c1, 0.4)?, hue(c2)?.to_radians());
+                oklab(number(c0, 1.0)?, c * h.cos(), c * h.sin())
+            }
+            "srgb" | "display-p3" | "a98-rgb" | "prophoto-rgb" | "rec2020" => {
+                let system = match space {
+                    "display-p3"   => RgbSystem::DisplayP3,
+                    "a98-rgb"      => RgbSystem::Adobe,
+                    "prophoto-rgb" => RgbSystem::ProPhoto,
+                    "rec2020"      => RgbSystem::Rec2020,
+                    _              => RgbSystem::Srgb,
+                };
+
+                rgb(number(c0, 1.0)?, number(c1, 1.0)?, number(c2, 1.0)?, system)
+            }
+            "xyz-d50" => {
+                XyzValue {
+                    x: number(c0, 1.0)?,
+                    y: number(c1, 1.0)?,
+                    z: number(c2, 1.0)?,
+                }.validate().map(CssColor::Xyz)
+            }
+            "xyz" | "xyz-d65" => {
+                let xyz = XyzValue {
+                    x: number(c0, 1.0)?,
+                    y: number(c1, 1.0)?,
+                    z: number(c2, 1.0)?,
+                };
+
+                d65_to_d50(xyz).validate().map(CssColor::Xyz)
+            }
+            _ => Err(ValueError::BadFormat),
+        }
+    }
+}
+
+// Split the arguments of a color function into its components and an optional alpha.
+// Accepts the modern syntax (`255 0 0 / 50%`) and the legacy syntax (`255, 0, 0, 0.5`).
+fn split_args(args: &str) -> ValueResult<(Vec<&str>, Option<&str>)> {
+    if args.contains(',') {
+        if args.contains('/') {
+            return Err(ValueError::BadFormat);
+        }
+
+        let mut components: Vec<&str> = args.split(',').map(str::trim).collect();
+        let alpha = if components.len() == 4 { components.pop() } else { None };
+        return Ok((components, alpha));
+    }
+
+    let mut split = args.split('/');
+    let components = split.next().unwrap_or_default().split_whitespace().collect();
+    let alpha = match (split.next().map(str::trim), split.next()) {
+        (None, _) => None,
+        (Some(alpha), None) if !alpha.is_empty() && !alpha.contains(char::is_whitespace) => {
+            Some(alpha)
+        }
+        _ => return Err(ValueError::BadFormat),
+    };
+
+    Ok((components, alpha))
+}
+
+// Parse a number or a percentage, where `100%` is equal to `full`.
+// The `none` keyword is zero.
+fn number(s: &str, full: f32) -> ValueResult<f32> {
+    if s == "none" {
+        return Ok(0.0);
+    }
+
+    let value = match s.strip_suffix('%') {
+        Some(pct) => pct.parse::<f32>().map(|v| v * full / 100.0),
+        None => s.parse::<f32>(),
+    };
+
+    value.ok().filter(|v| v.is_finite()).ok_or(ValueError::BadFormat)
+}
+
+// Parse a hue angle with an optional unit to degrees in the range 0..360
+fn hue(s: &str) -> ValueResult<f32> {
+    let (value, scale) = if let Some(v) = s.strip_suffix("deg") {
+        (v, 1.0)
+    } else if let Some(v) = s.strip_suffix("grad") {
+        (v, 0.9)
+    } else if let Some(v) = s.strip_suffix("rad") {
+        (v, 180.0 / std::f32::consts::PI)
+    } else if let Some(v) = s.strip_suffix("turn") {
+        (v, 360.0)
+    } else {
+        (s, 1.0)
+    };
+
+    if value.ends_with('%') {
+        return Err(ValueError::BadFormat);
+    }
+
+    Ok((number(value, 1.0)? * scale).rem_euclid(360.0))
+}
+
+fn rgb(r: f32, g: f32, b: f32, system: RgbSystem) -> ValueResult<CssColor> {
+    let rgb = RgbNominalValue { r, g, b }.validate()?;
+    Ok(CssColor::Rgb(rgb, system))
+}
+
+// https://bottosson.github.io/posts/oklab/
+fn oklab(l: f32, a: f32, b: f32) -> ValueResult<CssColor> {
+    let mut lms = OKLAB_LAB2LMS * matrix![l; a; b];
+    lms.iter_mut().for_each(|v| *v = v.powi(3));
+
+    d65_to_d50(XyzValue::from(OKLAB_LMS2XYZ * lms)).validate().map(CssColor::Xyz)
+}
+
+// Adapt to D50, rounding away the error of the transform so that white stays in range
+fn d65_to_d50(xyz: XyzValue) -> XyzValue {
+    xyz.chrom_adapt(ChromaticAdaptationMethod::Bradford, Illuminant::D65, Illuminant::D50)
+        .round_to(6)
+}
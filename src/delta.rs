@@ -1,4 +1,6 @@
 use super::*;
+use std::iter::Sum;
+use std::ops::{Add, Mul, Neg, Sub};
 
 /// Trait to determine color difference between various types.
 /// As long as the type can be converted to Lab, we can calculate DeltaE.
@@ -45,7 +47,7 @@ fn delta_e_1994(lab_0: &LabValue, lab_1: &LabValue, textiles: bool) -> f32 {
     let delta_chroma = chroma_0 - chroma_1;
     let delta_a = lab_0.a - lab_1.a;
     let delta_b = lab_0.b - lab_1.b;
-    let delta_hue = (delta_a.powi(2) + delta_b.powi(2) - delta_chroma.powi(2)).sqrt();
+    let delta_hue = (delta_a.powi(2) + delta_b.powi(2) - delta_chroma.powi(2)).max(0.0).sqrt();
 
     let (kl, k1, k2) = match textiles {
         true => (2.0, 0.048, 0.014),
@@ -82,7 +84,7 @@ fn delta_e_2000(lab_0: &LabValue, lab_1: &LabValue) -> f32 {
     let h_prime_1 = convert::get_h_prime(a_prime_1, lab_1.b);
 
     let h_bar_prime = if (h_prime_0 - h_prime_1).abs() > 180.0 {
-        if (h_prime_0 - h_prime_1) < 360.0 {
+        if (h_prime_0 + h_prime_1) < 360.0 {
             (h_prime_0 + h_prime_1 + 360.0) / 2.0
         } else {
             (h_prime_0 + h_prime_1 - 360.0) / 2.0
@@ -96,12 +98,7 @@ fn delta_e_2000(lab_0: &LabValue, lab_1: &LabValue) -> f32 {
                 + 0.32 * ((3.0 * h_bar_prime +  6.0).to_radians()).cos()
                 - 0.20 * ((4.0 * h_bar_prime - 63.0).to_radians()).cos();
 
-    let mut delta_h = h_prime_1 - h_prime_0;
-    if delta_h > 180.0 && h_prime_1 <= h_prime_0 {
-        delta_h += 360.0;
-    } else if delta_h > 180.0 {
-        delta_h -= 360.0;
-    };
+    let delta_h = hue_diff(h_prime_0, h_prime_1);
 
     let delta_l_prime = lab_1.l - lab_0.l;
     let delta_c_prime = c_prime_1 - c_prime_0;
@@ -141,21 +138,36 @@ fn delta_e_cmc(lab0: &LabValue, lab1: &LabValue, tolerance_l: f32, tolerance_c:
     let delta_a = lab0.a - lab1.a;
     let delta_b = lab0.b - lab1.b;
 
-    let delta_h = (delta_a.powi(2) + delta_b.powi(2) - delta_c.powi(2)).sqrt();
+    let delta_h = (delta_a.powi(2) + delta_b.powi(2) - delta_c.powi(2)).max(0.0).sqrt();
+
+    let (s_l, s_c, s_h) = cmc_weights(lab0);
+
+    (
+        (delta_l / (tolerance_l * s_l)).powi(2)
+      + (delta_c / (tolerance_c * s_c)).powi(2)
+      + (delta_h / s_h).powi(2)
+    )
+    .sqrt()
+}
+
+/// The CMC weighting functions of lightness, chroma, and hue (S<sub>L</sub>, S<sub>C</sub>, and
+/// S<sub>H</sub>) at a reference color
+pub(crate) fn cmc_weights(lab: &LabValue) -> (f32, f32, f32) {
+    let chroma = (lab.a.powi(2) + lab.b.powi(2)).sqrt();
 
-    let s_l = if lab0.l < 16.0 {
+    let s_l = if lab.l < 16.0 {
         0.511
     } else {
-        (0.040975 * lab0.l) / (1.0 + (0.01765 * lab0.l))
+        (0.040975 * lab.l) / (1.0 + (0.01765 * lab.l))
     };
 
-    let s_c = ((0.0638 * chroma_0) / (1.0 + (0.0131 * chroma_0))) + 0.638;
+    let s_c = ((0.0638 * chroma) / (1.0 + (0.0131 * chroma))) + 0.638;
 
-    let h = lab0.b.atan2(lab0.a).to_degrees();
+    let h = lab.b.atan2(lab.a).to_degrees();
 
     let h_1 = if h >= 0.0 { h } else { h + 360.0 };
 
-    let f = (chroma_0.powi(4) / (chroma_0.powi(4) + 1900.0)).sqrt();
+    let f = (chroma.powi(4) / (chroma.powi(4) + 1900.0)).sqrt();
 
     let t = if (164.0..345.0).contains(&h_1) {
         0.56 + (0.2 * (h_1 + 168.0).to_radians().cos()).abs()
@@ -165,10 +177,302 @@ fn delta_e_cmc(lab0: &LabValue, lab1: &LabValue, tolerance_l: f32, tolerance_c:
 
     let s_h = s_c * (f * t + 1.0 - f);
 
-    (
-        (delta_l / (tolerance_l * s_l)).powi(2)
-      + (delta_c / (tolerance_c * s_c)).powi(2)
-      + (delta_h / s_h).powi(2)
-    )
-    .sqrt()
+    (s_l, s_c, s_h)
+}
+
+/// The semi-axes of the CMC(l:c) acceptability ellipsoid around a reference color, scaled by a
+/// commercial factor `cf`: the largest ΔL\*, ΔC\*, and ΔH\* a sample may have along each axis and
+/// still be accepted. The ellipsoid is aligned with the lightness, chroma, and hue directions of
+/// the reference.
+/// ```
+/// use deltae::*;
+///
+/// let reference = LabValue::new(50.0, 0.0, 0.0).unwrap();
+/// let [l, c, h] = cmc_ellipse_axes(reference, 2.0, 1.0, 1.0);
+/// assert!((l - 2.1766).abs() < 1e-4);
+/// assert_eq!([c, h], [0.638, 0.638]);
+/// ```
+pub fn cmc_ellipse_axes<R: Into<LabValue>>(reference: R, l: f32, c: f32, cf: f32) -> [f32; 3] {
+    let (s_l, s_c, s_h) = cmc_weights(&reference.into());
+    [cf * l * s_l, cf * c * s_c, cf * s_h]
+}
+
+/// Return true if a sample color is inside the CMC(l:c) acceptability ellipsoid around a reference
+/// color, scaled by a commercial factor `cf`. This is the pass/fail test of textile contracts,
+/// where `cf` is the agreed tolerance in CMC units. A sample on the surface of the ellipsoid is
+/// accepted.
+/// ```
+/// use deltae::*;
+///
+/// let reference = LabValue::new(50.0, 20.0, 10.0).unwrap();
+/// let sample = LabValue::new(51.0, 20.5, 10.0).unwrap();
+///
+/// assert!(is_within_cmc_ellipse(reference, sample, 2.0, 1.0, 1.0));
+/// assert!(!is_within_cmc_ellipse(reference, sample, 1.0, 1.0, 0.5));
+/// ```
+pub fn is_within_cmc_ellipse<R, S>(reference: R, sample: S, l: f32, c: f32, cf: f32) -> bool
+where R: Into<LabValue>, S: Into<LabValue> {
+    let (reference, sample) = (reference.into(), sample.into());
+    let [axis_l, axis_c, axis_h] = cmc_ellipse_axes(reference, l, c, 1.0);
+
+    let chroma_0 = (reference.a.powi(2) + reference.b.powi(2)).sqrt();
+    let chroma_1 = (sample.a.powi(2) + sample.b.powi(2)).sqrt();
+    let delta_l = sample.l - reference.l;
+    let delta_c = chroma_1 - chroma_0;
+    let delta_h_squared = (reference.a - sample.a).powi(2) + (reference.b - sample.b).powi(2)
+        - delta_c.powi(2);
+
+    // Compared in CMC units, so that a commercial factor of 0 accepts only identical colors
+    let distance = (
+        (delta_l / axis_l).powi(2)
+      + (delta_c / axis_c).powi(2)
+      + delta_h_squared.max(0.0) / axis_h.powi(2)
+    ).sqrt();
+
+    distance <= cf
+}
+
+/// The CIE special metamerism index for a change in illuminant, from the Lab values of two samples
+/// under a reference illuminant and under a test illuminant, each given as `[reference, test]`.
+/// A metameric pair matches under the reference illuminant, such as a daylight booth, but not under
+/// the test illuminant, such as store lighting.
+///
+/// The pair needn't match exactly under the reference illuminant: the mismatch is subtracted from
+/// the second sample under the test illuminant (the additive correction of CIE 15), so the index is
+/// the difference caused by the change of illuminant alone. See [`metamerism_index`] to calculate
+/// the index from spectral data.
+/// ```
+/// use deltae::*;
+///
+/// let lab = |l, a, b| LabValue::new(l, a, b).unwrap();
+///
+/// let sample_a = [lab(50.0, 10.0, 10.0), lab(51.0, 13.0, 14.0)];
+/// let sample_b = [lab(50.0, 10.0, 10.0), lab(51.0, 10.0, 10.0)];
+/// assert_eq!(metamerism_index_lab(sample_a, sample_b, DE1976), 5.0);
+///
+/// // A pair that differs by the same amount under both illuminants is not metameric
+/// let lighter = [lab(51.0, 10.0, 10.0), lab(52.0, 13.0, 14.0)];
+/// assert_eq!(metamerism_index_lab(sample_a, lighter, DE1976), 0.0);
+/// ```
+pub fn metamerism_index_lab(
+    sample_a: [LabValue; 2],
+    sample_b: [LabValue; 2],
+    method: DEMethod,
+) -> DeltaE {
+    let ([a_reference, a_test], [b_reference, b_test]) = (sample_a, sample_b);
+    let corrected = LabValue {
+        l: b_test.l + a_reference.l - b_reference.l,
+        a: b_test.a + a_reference.a - b_reference.a,
+        b: b_test.b + a_reference.b - b_reference.b,
+    };
+
+    a_test.delta(corrected, method)
+}
+
+/// Iterator adaptors that calculate [`DeltaE`] lazily
+/// ```
+/// use deltae::*;
+///
+/// let reference = LabValue::new(50.0, 0.0, 0.0).unwrap();
+/// let samples = [
+///     LabValue::new(51.0, 0.0, 0.0).unwrap(),
+///     LabValue::new(50.0, 3.0, 4.0).unwrap(),
+/// ];
+///
+/// let deltas: Vec<f32> = samples.iter()
+///     .deltas_against(&reference, DE1976)
+///     .map(|de| *de.value())
+///     .collect();
+/// assert_eq!(deltas, vec![1.0, 5.0]);
+///
+/// let worst = samples.iter()
+///     .zip(samples.iter().rev())
+///     .deltas(DE1976)
+///     .map(|de| *de.value())
+///     .fold(0.0, f32::max);
+/// assert!((worst - 5.0990).abs() < 1e-4);
+/// ```
+pub trait DeltaIterator: Iterator + Sized {
+    /// Calculate the [`DeltaE`] of each sample color against the same reference color
+    fn deltas_against<R: Into<LabValue>>(self, reference: R, method: DEMethod) -> DeltasAgainst<Self>
+    where Self::Item: Into<LabValue> {
+        DeltasAgainst { iter: self, reference: reference.into(), method }
+    }
+
+    /// Calculate the [`DeltaE`] of each pair of reference and sample colors
+    fn deltas<A, B>(self, method: DEMethod) -> Deltas<Self>
+    where Self: Iterator<Item = (A, B)>, A: Into<LabValue>, B: Into<LabValue> {
+        Deltas { iter: self, method }
+    }
+}
+
+impl<I: Iterator> DeltaIterator for I {}
+
+/// An iterator of the [`DeltaE`] of sample colors against a reference color. See
+/// [`DeltaIterator::deltas_against`].
+#[derive(Debug, Clone)]
+pub struct DeltasAgainst<I> {
+    iter: I,
+    reference: LabValue,
+    method: DEMethod,
+}
+
+impl<I: Iterator> Iterator for DeltasAgainst<I> where I::Item: Into<LabValue> {
+    type Item = DeltaE;
+
+    fn next(&mut self) -> Option<DeltaE> {
+        self.iter.next().map(|sample| self.reference.delta(sample, self.method))
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.iter.size_hint()
+    }
+}
+
+impl<I: DoubleEndedIterator> DoubleEndedIterator for DeltasAgainst<I> where I::Item: Into<LabValue> {
+    fn next_back(&mut self) -> Option<DeltaE> {
+        self.iter.next_back().map(|sample| self.reference.delta(sample, self.method))
+    }
+}
+
+impl<I: ExactSizeIterator> ExactSizeIterator for DeltasAgainst<I> where I::Item: Into<LabValue> {}
+
+/// An iterator of the [`DeltaE`] of pairs of reference and sample colors. See
+/// [`DeltaIterator::deltas`].
+#[derive(Debug, Clone)]
+pub struct Deltas<I> {
+    iter: I,
+    method: DEMethod,
+}
+
+impl<A, B, I> Iterator for Deltas<I>
+where I: Iterator<Item = (A, B)>, A: Into<LabValue>, B: Into<LabValue> {
+    type Item = DeltaE;
+
+    fn next(&mut self) -> Option<DeltaE> {
+        self.iter.next().map(|(reference, sample)| reference.delta(sample, self.method))
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.iter.size_hint()
+    }
+}
+
+impl<A, B, I> DoubleEndedIterator for Deltas<I>
+where I: DoubleEndedIterator<Item = (A, B)>, A: Into<LabValue>, B: Into<LabValue> {
+    fn next_back(&mut self) -> Option<DeltaE> {
+        self.iter.next_back().map(|(reference, sample)| reference.delta(sample, self.method))
+    }
+}
+
+impl<A, B, I> ExactSizeIterator for Deltas<I>
+where I: ExactSizeIterator<Item = (A, B)>, A: Into<LabValue>, B: Into<LabValue> {}
+
+/// # Lab Difference
+///
+/// The signed difference between two colors in each Lab component: the direction of the
+/// difference as well as its size. A positive `db` is a sample that is yellower than its
+/// reference, and a negative `dl` one that is darker.
+/// ```
+/// use deltae::*;
+///
+/// let reference = LabValue::new(60.0, 10.0, 20.0).unwrap();
+/// let sample = LabValue::new(59.0, 10.0, 24.0).unwrap();
+///
+/// let drift = reference.difference(sample);
+/// assert_eq!(drift, LabDelta { dl: -1.0, da: 0.0, db: 4.0 });
+/// assert_eq!(drift.magnitude(), *reference.delta(sample, DE1976).value());
+/// assert_eq!(drift.direction(), 90.0); // toward yellow
+/// assert_eq!(reference + drift, sample);
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct LabDelta {
+    /// Difference in lightness (ΔL\*)
+    pub dl: f32,
+    /// Difference in red/green (Δa\*)
+    pub da: f32,
+    /// Difference in blue/yellow (Δb\*)
+    pub db: f32,
+}
+
+impl LabDelta {
+    /// The length of the difference, which is [`DE1976`]
+    pub fn magnitude(&self) -> f32 {
+        (self.dl.powi(2) + self.da.powi(2) + self.db.powi(2)).sqrt()
+    }
+
+    /// The length of the difference in the a\*b\* plane, ignoring lightness
+    pub fn chromatic_magnitude(&self) -> f32 {
+        (self.da.powi(2) + self.db.powi(2)).sqrt()
+    }
+
+    /// The angle of the difference in the a\*b\* plane, in degrees counter-clockwise from +a\*,
+    /// like a hue angle: 90° is toward yellow and 270° toward blue. Differences in lightness alone
+    /// have a direction of 0°.
+    pub fn direction(&self) -> f32 {
+        self.db.atan2(self.da).to_degrees().rem_euclid(360.0)
+    }
+}
+
+impl LabValue {
+    /// The signed [`LabDelta`] from the color to another: `other` minus `self`
+    pub fn difference<L: Into<LabValue>>(&self, other: L) -> LabDelta {
+        let other = other.into();
+        LabDelta { dl: other.l - self.l, da: other.a - self.a, db: other.b - self.b }
+    }
+}
+
+impl Add for LabDelta {
+    type Output = LabDelta;
+    fn add(self, rhs: LabDelta) -> LabDelta {
+        LabDelta { dl: self.dl + rhs.dl, da: self.da + rhs.da, db: self.db + rhs.db }
+    }
+}
+
+impl Sub for LabDelta {
+    type Output = LabDelta;
+    fn sub(self, rhs: LabDelta) -> LabDelta {
+        self + -rhs
+    }
+}
+
+impl Neg for LabDelta {
+    type Output = LabDelta;
+    fn neg(self) -> LabDelta {
+        LabDelta { dl: -self.dl, da: -self.da, db: -self.db }
+    }
+}
+
+impl Mul<f32> for LabDelta {
+    type Output = LabDelta;
+    fn mul(self, rhs: f32) -> LabDelta {
+        LabDelta { dl: self.dl * rhs, da: self.da * rhs, db: self.db * rhs }
+    }
+}
+
+impl Sum for LabDelta {
+    fn sum<I: Iterator<Item = LabDelta>>(iter: I) -> LabDelta {
+        iter.fold(LabDelta::default(), Add::add)
+    }
+}
+
+impl Add<LabDelta> for LabValue {
+    type Output = LabValue;
+    fn add(self, rhs: LabDelta) -> LabValue {
+        LabValue { l: self.l + rhs.dl, a: self.a + rhs.da, b: self.b + rhs.db }
+    }
+}
+
+impl Sub<LabDelta> for LabValue {
+    type Output = LabValue;
+    fn sub(self, rhs: LabDelta) -> LabValue {
+        self + -rhs
+    }
+}
+
+impl fmt::Display for LabDelta {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        fmt_components(f, ["ΔL", "Δa", "Δb"], [self.dl, self.da, self.db])
+    }
 }
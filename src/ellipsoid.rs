@@ -0,0 +1,211 @@
+//! Tolerances in the shape of an ellipsoid around a reference color, rather than a sphere of
+//! [`DeltaE`]. A [`ToleranceEllipsoid`] is centered on a color in Lab space, and a
+//! [`MacAdamEllipse`] is centered on a chromaticity in the CIE 1931 xy diagram, as in LED binning
+//! and display uniformity specifications.
+//!
+//! Both are also [`Tolerance`]s for [`DeltaEq::delta_eq`], moved to the reference color: a sample
+//! is within tolerance if it is inside the ellipsoid or ellipse centered on the reference.
+//!
+//! # Examples
+//!
+//! ```
+//! use deltae::*;
+//!
+//! // A 4-step ellipse around the chromaticity of a white LED
+//! let bin = MacAdamEllipse::new([0.3818, 0.3797], 0.00313, 0.00134, 53.72).scaled(4.0);
+//!
+//! assert!(bin.contains(XyzValue::from_chromaticity([0.3818, 0.3797], 1.0).unwrap()));
+//! assert!(!bin.contains(XyzValue::from_chromaticity([0.3818, 0.3700], 1.0).unwrap()));
+//!
+//! // An ellipsoid twice as long in lightness as in chroma, as a tolerance around a reference
+//! let reference = LabValue::new(50.0, 20.0, 0.0).unwrap();
+//! let ellipsoid = ToleranceEllipsoid::new(reference, [2.0, 1.0, 1.0], 0.0);
+//! assert!(reference.delta_eq(LabValue::new(51.5, 20.0, 0.0).unwrap(), DE2000, ellipsoid));
+//! assert!(!reference.delta_eq(LabValue::new(50.0, 21.5, 0.0).unwrap(), DE2000, ellipsoid));
+//! ```
+
+use crate::*;
+
+/// # Tolerance Ellipsoid
+///
+/// An ellipsoid in Lab space. One semi-axis is along L\*, and the other two are in the a\*b\*
+/// plane, the first of which is rotated counter-clockwise from the a\* axis.
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct ToleranceEllipsoid {
+    /// The center of the ellipsoid
+    pub center: LabValue,
+    /// The semi-axes along L\*, along the rotated a\* axis, and along the rotated b\* axis
+    pub semi_axes: [f32; 3],
+    /// The rotation of the semi-axes in the a\*b\* plane, in degrees
+    pub rotation: f32,
+}
+
+impl ToleranceEllipsoid {
+    /// New [`ToleranceEllipsoid`] from a center, semi-axes, and a rotation in degrees
+    pub fn new(center: LabValue, semi_axes: [f32; 3], rotation: f32) -> ToleranceEllipsoid {
+        ToleranceEllipsoid { center, semi_axes, rotation }
+    }
+
+    /// Return the [`ToleranceEllipsoid`] with its semi-axes multiplied by a factor
+    pub fn scaled(self, factor: f32) -> ToleranceEllipsoid {
+        let [l, a, b] = self.semi_axes;
+        ToleranceEllipsoid { semi_axes: [l * factor, a * factor, b * factor], ..self }
+    }
+
+    /// The distance of a color from the center, in units of the semi-axes. A color on the surface
+    /// of the ellipsoid is at a distance of `1.0`.
+    /// ```
+    /// use deltae::*;
+    ///
+    /// let center = LabValue::new(50.0, 0.0, 0.0).unwrap();
+    /// let ellipsoid = ToleranceEllipsoid::new(center, [2.0, 1.0, 0.5], 90.0);
+    ///
+    /// assert_eq!(ellipsoid.distance(LabValue::new(49.0, 0.0, 0.0).unwrap()), 0.5);
+    /// assert!((ellipsoid.distance(LabValue::new(50.0, 0.0, 1.0).unwrap()) - 1.0).abs() < 1e-6);
+    /// assert!((ellipsoid.distance(LabValue::new(50.0, 1.0, 0.0).unwrap()) - 2.0).abs() < 1e-6);
+    /// ```
+    pub fn distance<C: Into<LabValue>>(&self, color: C) -> f32 {
+        self.distance_from(self.center, color.into())
+    }
+
+    /// Return true if a color is inside or on the surface of the ellipsoid
+    pub fn contains<C: Into<LabValue>>(self, color: C) -> bool {
+        self.distance(color) <= 1.0
+    }
+
+    // The distance of a color from another center, in units of the semi-axes
+    fn distance_from(&self, center: LabValue, lab: LabValue) -> f32 {
+        let (sin, cos) = self.rotation.to_radians().sin_cos();
+        let (da, db) = (lab.a - center.a, lab.b - center.b);
+
+        let l = (lab.l - center.l) / self.semi_axes[0];
+        let a = (da * cos + db * sin) / self.semi_axes[1];
+        let b = (db * cos - da * sin) / self.semi_axes[2];
+
+        (l.powi(2) + a.powi(2) + b.powi(2)).sqrt()
+    }
+}
+
+/// The ellipsoid centered on the reference of the [`DeltaE`] contains the sample. Like a
+/// [`ComponentTolerance`], its [`Tolerance::tolerance`] is [`f32::INFINITY`].
+impl Tolerance for ToleranceEllipsoid {
+    fn tolerance(self) -> f32 {
+        f32::INFINITY
+    }
+
+    fn contains(self, delta: &DeltaE) -> bool {
+        self.distance_from(*delta.reference(), *delta.sample()) <= 1.0
+    }
+}
+
+/// # MacAdam Ellipse
+///
+/// An ellipse in the CIE 1931 xy chromaticity diagram, given by its center, its semi-major and
+/// semi-minor axes, and the angle of the semi-major axis counter-clockwise from the x axis. When
+/// the axes are those of a 1-step MacAdam ellipse, [`MacAdamEllipse::steps`] counts MacAdam steps.
+/// Luminance is ignored.
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct MacAdamEllipse {
+    /// The x and y chromaticity of the center
+    pub center: [f32; 2],
+    /// The semi-major axis
+    pub a: f32,
+    /// The semi-minor axis
+    pub b: f32,
+    /// The angle of the semi-major axis, in degrees
+    pub theta: f32,
+}
+
+impl MacAdamEllipse {
+    /// New [`MacAdamEllipse`] from a center, semi-axes, and an angle in degrees
+    pub fn new(center: [f32; 2], a: f32, b: f32, theta: f32) -> MacAdamEllipse {
+        MacAdamEllipse { center, a, b, theta }
+    }
+
+    /// Return the [`MacAdamEllipse`] with its axes multiplied by a number of steps
+    pub fn scaled(self, steps: f32) -> MacAdamEllipse {
+        MacAdamEllipse { a: self.a * steps, b: self.b * steps, ..self }
+    }
+
+    /// The distance of the chromaticity of a color from the center, in units of the axes. A color
+    /// on the ellipse is at a distance of `1.0`.
+    pub fn steps<C: Into<XyzValue>>(&self, color: C) -> f32 {
+        self.steps_from(self.center, color.into())
+    }
+
+    /// Return true if the chromaticity of a color is inside or on the ellipse
+    pub fn contains<C: Into<XyzValue>>(self, color: C) -> bool {
+        self.steps(color) <= 1.0
+    }
+
+    // The distance of the chromaticity of a color from another center, in units of the axes
+    fn steps_from(&self, center: [f32; 2], xyz: XyzValue) -> f32 {
+        let [x, y] = xyz.chromaticity();
+        let (sin, cos) = self.theta.to_radians().sin_cos();
+        let (dx, dy) = (x - center[0], y - center[1]);
+
+        let major = (dx * cos + dy * sin) / self.a;
+        let minor = (dy * cos - dx * sin) / self.b;
+
+        (major.powi(2) + minor.powi(2)).sqrt()
+    }
+}
+
+/// The ellipse centered on the chromaticity of the reference of the [`DeltaE`] contains the
+/// chromaticity of the sample. Like a [`ComponentTolerance`], its [`Tolerance::tolerance`] is
+/// [`f32::INFINITY`].
+impl Tolerance for MacAdamEllipse {
+    fn tolerance(self) -> f32 {
+        f32::INFINITY
+    }
+
+    fn contains(self, delta: &DeltaE) -> bool {
+        let center = XyzValue::from(*delta.reference()).chromaticity();
+        self.steps_from(center, XyzValue::from(*delta.sample())) <= 1.0
+    }
+}
+
+impl XyzValue {
+    /// The x and y chromaticity coordinates of the color. Black has the chromaticity of
+    /// [`Illuminant::E`].
+    /// ```
+    /// use deltae::*;
+    ///
+    /// // The white point of D65
+    /// let white = XyzValue { x: 0.95047, y: 1.0, z: 1.08883 };
+    /// let [x, y] = white.chromaticity();
+    /// assert!((x - 0.3127).abs() < 1e-4 && (y - 0.3290).abs() < 1e-4);
+    /// ```
+    pub fn chromaticity(&self) -> [f32; 2] {
+        let sum = self.x + self.y + self.z;
+        if sum == 0.0 {
+            return [1.0 / 3.0, 1.0 / 3.0];
+        }
+
+        [self.x / sum, self.y / sum]
+    }
+
+    /// Returns a result of a new [`XyzValue`] from x and y chromaticity coordinates and a
+    /// luminance `Y`. Will return `Err()` if `y` is zero.
+    /// ```
+    /// use deltae::*;
+    ///
+    /// let xyz = XyzValue::from_chromaticity([0.25, 0.5], 0.5).unwrap();
+    /// assert_eq!(xyz, XyzValue { x: 0.25, y: 0.5, z: 0.25 });
+    /// assert!(XyzValue::from_chromaticity([0.25, 0.0], 0.5).is_err());
+    /// ```
+    pub fn from_chromaticity(xy: [f32; 2], luminance: f32) -> ValueResult<XyzValue> {
+        let [x, y] = xy;
+        if y == 0.0 {
+            return Err(ValueError::OutOfBounds);
+        }
+
+        Ok(XyzValue {
+            x: x * luminance / y,
+            y: luminance,
+            z: (1.0 - x - y) * luminance / y,
+        })
+    }
+}
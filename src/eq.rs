@@ -72,7 +72,7 @@ use crate::*;
 pub trait DeltaEq<D: Delta + Copy>: Delta + Copy {
     /// Return true if the value is less than or equal to the [`Tolerance`]
     fn delta_eq<T: Tolerance>(&self, other: D, method: DEMethod, tolerance: T) -> bool {
-        self.delta(other, method).value() <= &tolerance.tolerance()
+        tolerance.contains(&self.delta(other, method))
     }
 }
 
@@ -100,6 +100,13 @@ macro_rules! assert_delta_eq {
 pub trait Tolerance {
     /// Return a tolerance value
     fn tolerance(self) -> f32;
+
+    /// Return true if a [`DeltaE`] is within the tolerance. By default, the value of the
+    /// [`DeltaE`] must be less than or equal to [`Tolerance::tolerance`].
+    fn contains(self, delta: &DeltaE) -> bool
+    where Self: Sized {
+        delta.value() <= &self.tolerance()
+    }
 }
 
 impl Tolerance for f32 {
@@ -120,12 +127,148 @@ impl Tolerance for DeltaE {
     }
 }
 
-macro_rules! impl_delta_eq {
-    ($t:ty) => {
-        impl<D: Delta + Copy> DeltaEq<D> for $t {}
+/// A pair of tolerances that must both contain a [`DeltaE`]. Combine a limit on the total
+/// [`DeltaE`] with a [`ComponentTolerance`] this way.
+/// ```
+/// use deltae::*;
+///
+/// let lab0 = LabValue::new(50.0, 0.0, 0.0).unwrap();
+/// let lab1 = LabValue::new(51.5, 0.0, 0.0).unwrap();
+/// let lightness = ComponentTolerance::lch(1.0, 2.0, 2.0);
+///
+/// assert!(lab0.delta_eq(lab1, DE2000, 2.0));
+/// assert!(!lab0.delta_eq(lab1, DE2000, (2.0, lightness)));
+/// ```
+impl<A: Tolerance, B: Tolerance> Tolerance for (A, B) {
+    fn tolerance(self) -> f32 {
+        self.0.tolerance().min(self.1.tolerance())
+    }
+
+    fn contains(self, delta: &DeltaE) -> bool {
+        self.0.contains(delta) && self.1.contains(delta)
+    }
+}
+
+/// A combination of tolerances that must all contain a [`DeltaE`]. Its [`Tolerance::tolerance`]
+/// is the smallest of the tolerances, or [`f32::INFINITY`] if there are none.
+///
+/// Tolerances of different types may be combined as a tuple, which also requires both to
+/// contain the [`DeltaE`].
+/// ```
+/// use deltae::*;
+///
+/// let lab0 = LabValue::new(50.0, 20.0, 0.0).unwrap();
+/// let lab1 = LabValue::new(51.0, 20.0, 1.0).unwrap();
+///
+/// let hue = ComponentTolerance::lch(f32::INFINITY, f32::INFINITY, 0.5);
+/// let lightness = ComponentTolerance::lch(1.0, f32::INFINITY, f32::INFINITY);
+///
+/// assert!(!lab0.delta_eq(lab1, DE2000, AllOf([hue, lightness])));
+/// assert!(lab0.delta_eq(lab1, DE2000, AnyOf([hue, lightness])));
+/// assert!(lab0.delta_eq(lab1, DE2000, (2.0, AnyOf([hue, lightness]))));
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct AllOf<I>(pub I);
+
+/// A combination of tolerances of which at least one must contain a [`DeltaE`]. Its
+/// [`Tolerance::tolerance`] is the largest of the tolerances, or [`f32::NEG_INFINITY`] if there
+/// are none.
+///
+/// See [`AllOf`] for an example.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct AnyOf<I>(pub I);
+
+impl<I> Tolerance for AllOf<I>
+where I: IntoIterator, I::Item: Tolerance {
+    fn tolerance(self) -> f32 {
+        self.0.into_iter().map(Tolerance::tolerance).fold(f32::INFINITY, f32::min)
+    }
+
+    fn contains(self, delta: &DeltaE) -> bool {
+        self.0.into_iter().all(|tolerance| tolerance.contains(delta))
+    }
+}
+
+impl<I> Tolerance for AnyOf<I>
+where I: IntoIterator, I::Item: Tolerance {
+    fn tolerance(self) -> f32 {
+        self.0.into_iter().map(Tolerance::tolerance).fold(f32::NEG_INFINITY, f32::max)
+    }
+
+    fn contains(self, delta: &DeltaE) -> bool {
+        self.0.into_iter().any(|tolerance| tolerance.contains(delta))
+    }
+}
+
+/// A box tolerance: limits on the absolute difference in each component between the reference
+/// and the sample, rather than on the total [`DeltaE`]. Print specifications often limit ΔL\* or
+/// ΔH\* in addition to ΔE. Pair it with a total limit as a tuple: `(2.0, component_tolerance)`.
+///
+/// On its own, its [`Tolerance::tolerance`] is [`f32::INFINITY`], since it does not limit the
+/// total [`DeltaE`].
+/// ```
+/// use deltae::*;
+///
+/// let lab0 = LabValue::new(50.0, 10.0, 0.0).unwrap();
+/// let lab1 = LabValue::new(51.0, 10.0, 1.0).unwrap();
+///
+/// assert!(lab0.delta_eq(lab1, DE2000, ComponentTolerance::lab(1.0, 1.0, 1.0)));
+/// assert!(!lab0.delta_eq(lab1, DE2000, ComponentTolerance::lab(1.0, 1.0, 0.5)));
+/// assert!(!lab0.delta_eq(lab1, DE2000, ComponentTolerance::lch(2.0, 2.0, 0.5)));
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum ComponentTolerance {
+    /// Limits on lightness, chroma, and hue
+    Lch {
+        /// Limit of ΔL\*
+        dl: f32,
+        /// Limit of ΔC\*
+        dc: f32,
+        /// Limit of the metric hue difference ΔH\*
+        dh: f32,
+    },
+    /// Limits on lightness and the a\* and b\* axes
+    Lab {
+        /// Limit of ΔL\*
+        dl: f32,
+        /// Limit of Δa\*
+        da: f32,
+        /// Limit of Δb\*
+        db: f32,
+    },
+}
+
+impl ComponentTolerance {
+    /// New [`ComponentTolerance::Lch`] from limits of ΔL\*, ΔC\*, and ΔH\*
+    pub fn lch(dl: f32, dc: f32, dh: f32) -> ComponentTolerance {
+        ComponentTolerance::Lch { dl, dc, dh }
+    }
+
+    /// New [`ComponentTolerance::Lab`] from limits of ΔL\*, Δa\*, and Δb\*
+    pub fn lab(dl: f32, da: f32, db: f32) -> ComponentTolerance {
+        ComponentTolerance::Lab { dl, da, db }
+    }
+}
+
+impl Tolerance for ComponentTolerance {
+    fn tolerance(self) -> f32 {
+        f32::INFINITY
+    }
+
+    fn contains(self, delta: &DeltaE) -> bool {
+        let report = DeltaReport::new(*delta, f32::INFINITY);
+        let (limits, differences) = match self {
+            ComponentTolerance::Lch { dl, dc, dh } => {
+                ([dl, dc, dh], [report.delta_l, report.delta_c, report.delta_h])
+            }
+            ComponentTolerance::Lab { dl, da, db } => {
+                ([dl, da, db], [report.delta_l, report.delta_a, report.delta_b])
+            }
+        };
+
+        limits.iter().zip(&differences).all(|(limit, difference)| difference.abs() <= *limit)
     }
 }
 
-impl_delta_eq!(LabValue);
-impl_delta_eq!(LchValue);
-impl_delta_eq!(XyzValue);
+impl<C: ColorValue, D: Delta + Copy> DeltaEq<D> for C {}
@@ -0,0 +1,157 @@
+//! The error type of the crate. Every fallible operation returns an [`Error`], so applications
+//! can match on the cause of a failure wherever it came from. [`ValueError`] is another name for
+//! [`Error`].
+//!
+//! # Examples
+//!
+//! ```
+//! # #[cfg(all(feature = "rgb", feature = "parse"))] {
+//! use deltae::*;
+//!
+//! match "D99".parse::<Illuminant>() {
+//!     Err(Error::Parse(e)) => assert_eq!(e.kind(), ParseErrorKind::UnknownName),
+//!     other => panic!("{:?}", other),
+//! }
+//!
+//! let singular = RgbSystem::Custom {
+//!     rgb_to_xyz: Matrix3x3::new([1.0; 9]),
+//!     white: Illuminant::D65,
+//!     curves: [ToneCurve::Gamma(2.2), ToneCurve::Gamma(1.8), ToneCurve::Gamma(2.2)],
+//! };
+//! assert!(matches!(singular.validate(), Err(Error::SingularMatrix)));
+//! # }
+//! ```
+
+use std::error;
+use std::fmt;
+use std::io;
+use crate::*;
+
+/// # Error
+///
+/// The ways an operation of the crate can fail. More variants may be added in the future.
+#[derive(Debug)]
+#[non_exhaustive]
+pub enum Error {
+    /// The value is outside the acceptable range
+    OutOfBounds,
+    /// The value is formatted incorrectly
+    BadFormat,
+    /// The value is NaN or infinite
+    NotFinite,
+    /// A matrix that needs to be inverted is singular
+    SingularMatrix,
+    /// The value can not be converted to the requested type, such as CMYK to RGB
+    UnsupportedConversion,
+    /// A color could not be parsed from a string. See [`ParseError`].
+    Parse(ParseError),
+    /// A string is not the name of a [`DEMethod`]
+    Method(ParseMethodError),
+    /// Reading or writing failed
+    Io(io::Error),
+    /// A CSV list could not be read or written. Requires the `csv` feature.
+    #[cfg(feature = "csv")]
+    Csv(csv::Error),
+    /// The color on a row of a list is missing or invalid
+    Row {
+        /// The line number of the row, starting at 1
+        line: u64,
+        /// The reason the color is invalid
+        error: Box<Error>,
+    },
+    /// A row of a list has no counterpart in the other list
+    Unpaired {
+        /// The ID of the row
+        id: Option<String>,
+        /// The line number of the row
+        line: u64,
+    },
+    /// An ID appears more than once in a list
+    Duplicate {
+        /// The repeated ID
+        id: String,
+        /// The line number of the repeated row
+        line: u64,
+    },
+}
+
+/// The previous name of [`Error`], from when it only described invalid values
+pub type ValueError = Error;
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Error::Parse(e) => write!(f, "{}", e),
+            Error::Method(e) => write!(f, "{}", e),
+            Error::Io(e) => write!(f, "{}", e),
+            #[cfg(feature = "csv")]
+            Error::Csv(e) => write!(f, "{}", e),
+            Error::Row { line, error } => write!(f, "line {}: {}", line, error),
+            Error::Unpaired { id: Some(id), line } => write!(f, "line {}: no match for {:?}", line, id),
+            Error::Unpaired { id: None, line } => write!(f, "line {}: no match", line),
+            Error::Duplicate { id, line } => write!(f, "line {}: duplicate id {:?}", line, id),
+            _ => write!(f, "{}", self.description()),
+        }
+    }
+}
+
+impl Error {
+    fn description(&self) -> &str {
+        match self {
+            Error::OutOfBounds           => "Value is out of range!",
+            Error::BadFormat             => "Value is malformed!",
+            Error::NotFinite             => "Value is not a finite number!",
+            Error::SingularMatrix        => "Matrix is singular!",
+            Error::UnsupportedConversion => "Value can not be converted!",
+            Error::Parse(_)              => "Value could not be parsed!",
+            Error::Method(_)             => "Unknown DeltaE method!",
+            Error::Io(_)                 => "Input or output failed!",
+            #[cfg(feature = "csv")]
+            Error::Csv(_)                => "CSV could not be read or written!",
+            Error::Row { .. }            => "Row is invalid!",
+            Error::Unpaired { .. }       => "Row has no match!",
+            Error::Duplicate { .. }      => "ID is repeated!",
+        }
+    }
+}
+
+// The wrapped errors are transparent: `Display` already prints them, so `source` skips to their
+// own source instead of repeating the same message in an error chain.
+impl error::Error for Error {
+    fn source(&self) -> Option<&(dyn error::Error + 'static)> {
+        match self {
+            Error::Parse(e) => e.source(),
+            Error::Method(e) => e.source(),
+            Error::Io(e) => e.source(),
+            #[cfg(feature = "csv")]
+            Error::Csv(e) => e.source(),
+            Error::Row { error, .. } => error.source(),
+            _ => None,
+        }
+    }
+}
+
+impl From<ParseError> for Error {
+    fn from(e: ParseError) -> Error {
+        Error::Parse(e)
+    }
+}
+
+impl From<ParseMethodError> for Error {
+    fn from(e: ParseMethodError) -> Error {
+        Error::Method(e)
+    }
+}
+
+impl From<io::Error> for Error {
+    fn from(e: io::Error) -> Error {
+        Error::Io(e)
+    }
+}
+
+#[cfg(feature = "csv")]
+impl From<csv::Error> for Error {
+    fn from(e: csv::Error) -> Error {
+        Error::Csv(e)
+    }
+}
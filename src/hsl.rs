@@ -0,0 +1,209 @@
+//! The HSL and HSV representations of RGB colors, as used by color pickers and CSS.
+//!
+//! HSL and HSV are rearrangements of the nominal values of an [`RgbNominalValue`], not color
+//! spaces of their own: an [`HslValue`] or [`HsvValue`] is in the same [`RgbSystem`] as the RGB
+//! color it came from. Convert back to RGB to compare colors in a CIE space.
+//!
+//! # Examples
+//!
+//! ```
+//! use deltae::*;
+//!
+//! let rebeccapurple = RgbValue::new(102, 51, 153);
+//! assert_eq!(rebeccapurple.to_hsl().round_to(3), HslValue { h: 270.0, s: 0.5, l: 0.4 });
+//! assert_eq!(rebeccapurple.to_hsv().round_to(3), HsvValue { h: 270.0, s: 0.667, v: 0.6 });
+//!
+//! // Lighten in HSL, and compare in Lab
+//! let mut hsl = rebeccapurple.to_hsl();
+//! hsl.l += 0.1;
+//! let lighter = RgbValue::from(hsl.to_rgb());
+//! assert!(*LabValue::from(rebeccapurple).delta(LabValue::from(lighter), DE2000).value() > 5.0);
+//! ```
+
+use std::fmt;
+
+use crate::*;
+use crate::color::fmt_components;
+
+/// # HSL
+///
+/// | `Value` | `Color`      | `Range`           |
+/// |:-------:|:------------:|:-----------------:|
+/// | `H`     | `Hue`        | `0.0 <---> 360.0` |
+/// | `S`     | `Saturation` | `0.0 <---> 1.0`   |
+/// | `L`     | `Lightness`  | `0.0 <---> 1.0`   |
+///
+/// The hue of a gray is `0.0`.
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct HslValue {
+    /// Hue in degrees
+    pub h: f32,
+    /// Saturation
+    pub s: f32,
+    /// Lightness
+    pub l: f32,
+}
+
+/// # HSV
+///
+/// | `Value` | `Color`      | `Range`           |
+/// |:-------:|:------------:|:-----------------:|
+/// | `H`     | `Hue`        | `0.0 <---> 360.0` |
+/// | `S`     | `Saturation` | `0.0 <---> 1.0`   |
+/// | `V`     | `Value`      | `0.0 <---> 1.0`   |
+///
+/// Also known as HSB. The hue of a gray is `0.0`.
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct HsvValue {
+    /// Hue in degrees
+    pub h: f32,
+    /// Saturation
+    pub s: f32,
+    /// Value
+    pub v: f32,
+}
+
+impl HslValue {
+    /// Returns a result of an HslValue from 3 `f32`s.
+    /// Will return `Err()` if the values are out of range as determined by the [`Validate`] trait.
+    pub fn new(h: f32, s: f32, l: f32) -> ValueResult<HslValue> {
+        HslValue { h, s, l }.validate()
+    }
+
+    /// Convert to an [`RgbNominalValue`] in the same [`RgbSystem`]
+    /// ```
+    /// use deltae::*;
+    ///
+    /// let red = HslValue::new(0.0, 1.0, 0.5).unwrap();
+    /// assert_eq!(red.to_rgb(), RgbNominalValue { r: 1.0, g: 0.0, b: 0.0 });
+    /// ```
+    pub fn to_rgb(self) -> RgbNominalValue {
+        // https://www.w3.org/TR/css-color-4/#hsl-to-rgb
+        let h = Degrees(self.h).normalized().0;
+        let a = self.s * self.l.min(1.0 - self.l);
+        let f = |n: f32| {
+            let k = (n + h / 30.0) % 12.0;
+            self.l - a * (k - 3.0).min(9.0 - k).clamp(-1.0, 1.0)
+        };
+
+        RgbNominalValue { r: f(0.0), g: f(8.0), b: f(4.0) }
+    }
+}
+
+impl HsvValue {
+    /// Returns a result of an HsvValue from 3 `f32`s.
+    /// Will return `Err()` if the values are out of range as determined by the [`Validate`] trait.
+    pub fn new(h: f32, s: f32, v: f32) -> ValueResult<HsvValue> {
+        HsvValue { h, s, v }.validate()
+    }
+
+    /// Convert to an [`RgbNominalValue`] in the same [`RgbSystem`]
+    /// ```
+    /// use deltae::*;
+    ///
+    /// let navy = HsvValue::new(240.0, 1.0, 0.5).unwrap();
+    /// assert_eq!(navy.to_rgb(), RgbNominalValue { r: 0.0, g: 0.0, b: 0.5 });
+    /// ```
+    pub fn to_rgb(self) -> RgbNominalValue {
+        let h = Degrees(self.h).normalized().0;
+        let f = |n: f32| {
+            let k = (n + h / 60.0) % 6.0;
+            self.v - self.v * self.s * k.min(4.0 - k).clamp(0.0, 1.0)
+        };
+
+        RgbNominalValue { r: f(5.0), g: f(3.0), b: f(1.0) }
+    }
+}
+
+impl<C: Channel> Rgb<C> {
+    /// Convert to an [`HslValue`] in the same [`RgbSystem`]
+    pub fn to_hsl(self) -> HslValue {
+        let (h, max, min) = hue_max_min(self.to_nominal());
+        let l = (max + min) / 2.0;
+        let s = if l <= 0.0 || l >= 1.0 {
+            0.0
+        } else {
+            (max - l) / l.min(1.0 - l)
+        };
+
+        HslValue { h, s, l }
+    }
+
+    /// Convert to an [`HsvValue`] in the same [`RgbSystem`]
+    pub fn to_hsv(self) -> HsvValue {
+        let (h, max, min) = hue_max_min(self.to_nominal());
+        let s = if max > 0.0 { (max - min) / max } else { 0.0 };
+
+        HsvValue { h, s, v: max }
+    }
+}
+
+// The hue shared by HSL and HSV, and the largest and smallest channels
+fn hue_max_min(rgb: RgbNominalValue) -> (f32, f32, f32) {
+    let RgbNominalValue { r, g, b } = rgb;
+    let max = r.max(g).max(b);
+    let min = r.min(g).min(b);
+    let chroma = max - min;
+
+    let h = if chroma <= 0.0 {
+        0.0
+    } else if max == r {
+        (g - b) / chroma
+    } else if max == g {
+        (b - r) / chroma + 2.0
+    } else {
+        (r - g) / chroma + 4.0
+    };
+
+    (Degrees(h * 60.0).normalized().0, max, min)
+}
+
+impl From<RgbNominalValue> for HslValue {
+    fn from(rgb: RgbNominalValue) -> HslValue {
+        rgb.to_hsl()
+    }
+}
+
+impl From<RgbNominalValue> for HsvValue {
+    fn from(rgb: RgbNominalValue) -> HsvValue {
+        rgb.to_hsv()
+    }
+}
+
+impl From<HslValue> for RgbNominalValue {
+    fn from(hsl: HslValue) -> RgbNominalValue {
+        hsl.to_rgb()
+    }
+}
+
+impl From<HsvValue> for RgbNominalValue {
+    fn from(hsv: HsvValue) -> RgbNominalValue {
+        hsv.to_rgb()
+    }
+}
+
+impl From<HslValue> for HsvValue {
+    fn from(hsl: HslValue) -> HsvValue {
+        hsl.to_rgb().to_hsv()
+    }
+}
+
+impl From<HsvValue> for HslValue {
+    fn from(hsv: HsvValue) -> HslValue {
+        hsv.to_rgb().to_hsl()
+    }
+}
+
+impl fmt::Display for HslValue {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        fmt_components(f, ["H", "S", "L"], [self.h, self.s, self.l])
+    }
+}
+
+impl fmt::Display for HsvValue {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        fmt_components(f, ["H", "S", "V"], [self.h, self.s, self.v])
+    }
+}
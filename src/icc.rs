@@ -0,0 +1,197 @@
+//! Reading matrix/TRC RGB systems from ICC profiles. Requires the `icc` feature.
+//!
+//! Only the tags of a matrix/TRC display or working space profile are read: the media white
+//! point (`wtpt`), the colorants (`rXYZ`, `gXYZ`, `bXYZ`), and the tone reproduction curves
+//! (`rTRC`, `gTRC`, `bTRC`). The colorants of an ICC profile are relative to the D50 Profile
+//! Connection Space, so the resulting [`RgbSystem::Custom`] always has a white point of
+//! [`Illuminant::D50`].
+//!
+//! Further Reading: [https://www.color.org/specification/ICC.1-2022-05.pdf](https://www.color.org/specification/ICC.1-2022-05.pdf)
+
+use std::convert::TryInto;
+
+use crate::*;
+
+/// # ICC Profile
+///
+/// The RGB system and media white point of a matrix/TRC ICC profile.
+///
+/// Sampled `curv` tone curves are read as a [`ToneCurve::Sampled`] table, or a
+/// [`ToneCurve::Gamma`] if the curve is a single exponent. Curves in the parametric `para` form
+/// are read exactly.
+#[derive(Debug, Clone, PartialEq)]
+pub struct IccProfile {
+    media_white: XyzValue,
+    system: RgbSystem,
+}
+
+impl IccProfile {
+    /// Returns a result of an [`IccProfile`] from the bytes of an ICC profile.
+    /// Will return `Err()` if the profile is malformed, is not an RGB profile, or is missing any
+    /// of the colorant and tone curve tags.
+    pub fn from_bytes(bytes: &[u8]) -> ValueResult<IccProfile> {
+        if bytes.len() < 132 || &bytes[36..40] != b"acsp" {
+            return Err(ValueError::BadFormat);
+        }
+
+        if &bytes[16..20] != b"RGB " || &bytes[20..24] != b"XYZ " {
+            return Err(ValueError::BadFormat);
+        }
+
+        let tags = Tags::new(bytes)?;
+
+        let [r, g, b] = [tags.xyz(b"rXYZ")?, tags.xyz(b"gXYZ")?, tags.xyz(b"bXYZ")?];
+        let rgb_to_xyz = Matrix3x3::from_columns([r, g, b]);
+
+        let curves = [tags.curve(b"rTRC")?, tags.curve(b"gTRC")?, tags.curve(b"bTRC")?];
+
+        let system = RgbSystem::Custom { rgb_to_xyz, white: Illuminant::D50, curves }.validate()?;
+
+        // The media white point is required, but some profiles in the wild omit it
+        let media_white = match tags.xyz(b"wtpt") {
+            Ok([x, y, z]) => XyzValue { x, y, z },
+            Err(_) => Illuminant::D50.white_point(),
+        };
+
+        Ok(IccProfile { media_white, system })
+    }
+
+    /// The media white point (`wtpt`) of the profile. For display profiles this is usually
+    /// [`Illuminant::D50`], since the white point of the display is adapted to the Profile
+    /// Connection Space.
+    pub fn media_white_point(&self) -> XyzValue {
+        self.media_white
+    }
+
+    /// The [`RgbSystem::Custom`] described by the profile
+    pub fn rgb_system(&self) -> RgbSystem {
+        self.system.clone()
+    }
+}
+
+impl From<IccProfile> for RgbSystem {
+    fn from(profile: IccProfile) -> RgbSystem {
+        profile.system
+    }
+}
+
+impl RgbSystem {
+    /// Returns a result of an [`RgbSystem::Custom`] from the bytes of a matrix/TRC ICC profile.
+    /// See [`IccProfile::from_bytes`]. Requires the `icc` feature.
+    pub fn from_icc(bytes: &[u8]) -> ValueResult<RgbSystem> {
+        IccProfile::from_bytes(bytes).map(RgbSystem::from)
+    }
+}
+
+// The tag table of a profile
+struct Tags<'a> {
+    bytes: &'a [u8],
+    count: usize,
+}
+
+impl<'a> Tags<'a> {
+    fn new(bytes: &'a [u8]) -> ValueResult<Tags<'a>> {
+        let count = u32_at(bytes, 128)? as usize;
+        if bytes.len() < 132 + count * 12 {
+            return Err(ValueError::BadFormat);
+        }
+
+        Ok(Tags { bytes, count })
+    }
+
+    // Returns the data of a tag by signature
+    fn find(&self, signature: &[u8; 4]) -> ValueResult<&'a [u8]> {
+        (0..self.count)
+            .map(|i| 132 + i * 12)
+            .find(|&entry| &self.bytes[entry..entry + 4] == signature)
+            .ok_or(ValueError::BadFormat)
+            .and_then(|entry| {
+                let offset = u32_at(self.bytes, entry + 4)? as usize;
+                let size = u32_at(self.bytes, entry + 8)? as usize;
+                let end = offset.checked_add(size).ok_or(ValueError::BadFormat)?;
+                self.bytes.get(offset..end).ok_or(ValueError::BadFormat)
+            })
+    }
+
+    // Read an XYZType tag
+    fn xyz(&self, signature: &[u8; 4]) -> ValueResult<[f32; 3]> {
+        let data = self.find(signature)?;
+        if data.get(0..4) != Some(b"XYZ ") {
+            return Err(ValueError::BadFormat);
+        }
+
+        Ok([s15_fixed16_at(data, 8)?, s15_fixed16_at(data, 12)?, s15_fixed16_at(data, 16)?])
+    }
+
+    // Read a curveType or parametricCurveType tag
+    fn curve(&self, signature: &[u8; 4]) -> ValueResult<ToneCurve> {
+        let data = self.find(signature)?;
+
+        match data.get(0..4) {
+            Some(b"curv") => {
+                let count = u32_at(data, 8)? as usize;
+                let table = (0..count)
+                    .map(|i| u16_at(data, 12 + i * 2))
+                    .collect::<ValueResult<Vec<u16>>>()?;
+
+                match table.as_slice() {
+                    [] => Ok(ToneCurve::Gamma(1.0)),
+                    [gamma] => Ok(ToneCurve::Gamma(*gamma as f32 / 256.0)),
+                    _ => Ok(ToneCurve::Sampled(sampled(&table).into())),
+                }
+            }
+            Some(b"para") => {
+                let p = |i: usize| s15_fixed16_at(data, 12 + i * 4);
+
+                let (g, a, b, c, d, e, f) = match u16_at(data, 8)? {
+                    0 => (p(0)?, 1.0, 0.0, 0.0, 0.0, 0.0, 0.0),
+                    1 => {
+                        let (g, a, b) = (p(0)?, p(1)?, p(2)?);
+                        (g, a, b, 0.0, -b / a, 0.0, 0.0)
+                    }
+                    2 => {
+                        let (g, a, b, c) = (p(0)?, p(1)?, p(2)?, p(3)?);
+                        (g, a, b, 0.0, -b / a, c, c)
+                    }
+                    3 => (p(0)?, p(1)?, p(2)?, p(3)?, p(4)?, 0.0, 0.0),
+                    4 => (p(0)?, p(1)?, p(2)?, p(3)?, p(4)?, p(5)?, p(6)?),
+                    _ => return Err(ValueError::BadFormat),
+                };
+
+                if !(g > 0.0 && a != 0.0 && d.is_finite()) {
+                    return Err(ValueError::OutOfBounds);
+                }
+
+                Ok(ToneCurve::Parametric { g, a, b, c, d, e, f })
+            }
+            _ => Err(ValueError::BadFormat),
+        }
+    }
+}
+
+// Scale a curv table to 0-1. Some profiles have small dips in their tables from rounding, so
+// each sample is kept at or above the previous one to keep the curve invertible.
+fn sampled(table: &[u16]) -> Vec<f32> {
+    let mut max = 0;
+    table
+        .iter()
+        .map(|&v| {
+            max = max.max(v);
+            max as f32 / 65535.0
+        })
+        .collect()
+}
+
+fn u16_at(bytes: &[u8], i: usize) -> ValueResult<u16> {
+    let b = bytes.get(i..i + 2).ok_or(ValueError::BadFormat)?;
+    Ok(u16::from_be_bytes(b.try_into().unwrap()))
+}
+
+fn u32_at(bytes: &[u8], i: usize) -> ValueResult<u32> {
+    let b = bytes.get(i..i + 4).ok_or(ValueError::BadFormat)?;
+    Ok(u32::from_be_bytes(b.try_into().unwrap()))
+}
+
+fn s15_fixed16_at(bytes: &[u8], i: usize) -> ValueResult<f32> {
+    Ok(u32_at(bytes, i)? as i32 as f32 / 65536.0)
+}
@@ -0,0 +1,152 @@
+//! Standard illuminants and their reference white points
+//!
+//! CIE L\*a\*b\* is always relative to a reference white. By default, this crate uses
+//! [`Illuminant::D50`], the white point of the ICC Profile Connection Space. Other reference whites
+//! may be used with [`LabValue::from_xyz_with_white`] and [`XyzValue::from_lab_with_white`].
+//!
+//! # Examples
+//!
+//! ```
+//! use deltae::*;
+//!
+//! let xyz = XyzValue::new(0.5, 0.5, 0.5).unwrap();
+//!
+//! // D50 is the default reference white
+//! let lab_d50 = LabValue::from(xyz);
+//! assert_eq!(lab_d50, LabValue::from_xyz_with_white(xyz, Illuminant::D50));
+//!
+//! // D65 referenced Lab
+//! let lab_d65 = LabValue::from_xyz_with_white(xyz, Illuminant::D65);
+//! let xyz_d65 = XyzValue::from_lab_with_white(lab_d65, Illuminant::D65);
+//! assert_eq!(xyz.round_to(4), xyz_d65.round_to(4));
+//! ```
+
+use std::fmt;
+
+use crate::*;
+use crate::convert::D50;
+
+/// # Standard Illuminants
+///
+/// White points are given for the CIE 1931 2° standard observer, normalized to `Y = 1.0`. The
+/// white points for the CIE 1964 10° observer are given by [`Illuminant::white_point_for`].
+///
+/// Further Reading: [http://www.brucelindbloom.com/index.html?Eqn_ChromAdapt.html](http://www.brucelindbloom.com/index.html?Eqn_ChromAdapt.html)
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum Illuminant {
+    /// Incandescent / Tungsten (2856K)
+    A,
+    /// Direct sunlight at noon (obsolete)
+    B,
+    /// Average daylight (obsolete)
+    C,
+    /// Horizon light (5003K). The ICC Profile Connection Space white point.
+    #[default]
+    D50,
+    /// Mid-morning / mid-afternoon daylight (5503K)
+    D55,
+    /// Noon daylight (6504K). The white point of sRGB and most displays.
+    D65,
+    /// North sky daylight (7504K)
+    D75,
+    /// Equal energy
+    E,
+    /// Cool white fluorescent (4230K)
+    F2,
+    /// Broadband daylight fluorescent (6500K)
+    F7,
+    /// Narrow band white fluorescent (4000K)
+    F11,
+    /// The DCI-P3 theater projector white (about 6300K). It is defined by its chromaticity of
+    /// `(0.314, 0.351)` rather than by a spectrum, so it has the same white point for both
+    /// observers.
+    Dci,
+    /// The ACES white point (about 6000K), close to D60. Like [`Illuminant::Dci`], it is defined
+    /// by its chromaticity of `(0.32168, 0.33767)`.
+    Aces,
+}
+
+impl Illuminant {
+    /// Returns the reference white point of the [`Illuminant`] as an [`XyzValue`]
+    /// ```
+    /// use deltae::*;
+    ///
+    /// let white = Illuminant::D65.white_point();
+    /// assert_eq!(white.y, 1.0);
+    /// ```
+    pub const fn white_point(&self) -> XyzValue {
+        self.white_point_for(Observer::Cie1931)
+    }
+
+    /// Returns the reference white point of the [`Illuminant`] for a standard [`Observer`]
+    /// ```
+    /// use deltae::*;
+    ///
+    /// let white = Illuminant::D65.white_point_for(Observer::Cie1964);
+    /// assert_eq!(white, XyzValue { x: 0.94811, y: 1.0, z: 1.07304 });
+    /// ```
+    pub const fn white_point_for(&self, observer: Observer) -> XyzValue {
+        let (x, y, z) = match observer {
+            Observer::Cie1931 => match self {
+                Illuminant::A    => (1.09850, 1.0, 0.35585),
+                Illuminant::B    => (0.99072, 1.0, 0.85223),
+                Illuminant::C    => (0.98074, 1.0, 1.18232),
+                Illuminant::D50  => (D50.x, D50.y, D50.z),
+                Illuminant::D55  => (0.95682, 1.0, 0.92149),
+                Illuminant::D65  => (0.95047, 1.0, 1.08883),
+                Illuminant::D75  => (0.94972, 1.0, 1.22638),
+                Illuminant::E    => (1.0,     1.0, 1.0    ),
+                Illuminant::F2   => (0.99186, 1.0, 0.67393),
+                Illuminant::F7   => (0.95041, 1.0, 1.08747),
+                Illuminant::F11  => (1.00962, 1.0, 0.64350),
+                Illuminant::Dci  => (0.89459, 1.0, 0.95442),
+                Illuminant::Aces => (0.95265, 1.0, 1.00883),
+            },
+            Observer::Cie1964 => match self {
+                Illuminant::A    => (1.11144, 1.0, 0.35200),
+                Illuminant::B    => (0.99178, 1.0, 0.84349),
+                Illuminant::C    => (0.97285, 1.0, 1.16145),
+                Illuminant::D50  => (0.96720, 1.0, 0.81427),
+                Illuminant::D55  => (0.95799, 1.0, 0.90926),
+                Illuminant::D65  => (0.94811, 1.0, 1.07304),
+                Illuminant::D75  => (0.94416, 1.0, 1.20641),
+                Illuminant::E    => (1.0,     1.0, 1.0    ),
+                Illuminant::F2   => (1.03279, 1.0, 0.69027),
+                Illuminant::F7   => (0.95792, 1.0, 1.07686),
+                Illuminant::F11  => (1.03863, 1.0, 0.65607),
+                Illuminant::Dci  => (0.89459, 1.0, 0.95442),
+                Illuminant::Aces => (0.95265, 1.0, 1.00883),
+            },
+        };
+
+        XyzValue { x, y, z }
+    }
+}
+
+impl fmt::Display for Illuminant {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{:?}", self)
+    }
+}
+
+/// # Standard Observers
+///
+/// The field of view of the color matching functions used to measure a color. White points differ
+/// slightly between observers, so colors measured with the 10° observer should be compared using
+/// its white points.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum Observer {
+    /// The CIE 1931 2° standard observer, the default
+    #[default]
+    Cie1931,
+    /// The CIE 1964 10° supplementary standard observer
+    Cie1964,
+}
+
+impl fmt::Display for Observer {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{:?}", self)
+    }
+}
@@ -0,0 +1,432 @@
+//! Interpolating between two colors. Each color type mixes in its own space: [`LabValue::mix`]
+//! is a straight line in Lab, [`LchValue::mix`] follows the shorter arc of the hue circle, and
+//! [`LabValue::mix_oklab`] is a straight line in [Oklab](https://bottosson.github.io/posts/oklab/),
+//! which keeps the hue of blues more constant than Lab. A [`Gradient`] yields evenly spaced steps
+//! between two colors in any of these spaces.
+//!
+//! # Examples
+//!
+//! ```
+//! use deltae::*;
+//!
+//! let red = LabValue::new(54.29, 80.8, 69.89).unwrap();
+//! let blue = LabValue::new(29.57, 68.3, -112.03).unwrap();
+//!
+//! let middle = red.mix(blue, 0.5);
+//! assert_eq!(middle.round_to(2), LabValue::new(41.93, 74.55, -21.07).unwrap());
+//!
+//! // The halves are equally different from the ends in DE1976
+//! assert_eq!(
+//!     red.delta(middle, DE1976).round_to(3),
+//!     middle.delta(blue, DE1976).round_to(3),
+//! );
+//! ```
+
+use crate::*;
+
+/// The space two colors are interpolated in
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum InterpolationSpace {
+    /// A straight line in Lab, as in [`LabValue::mix`]
+    #[default]
+    Lab,
+    /// Lightness and chroma linearly and hue around the hue circle, as in
+    /// [`LchValue::mix_hue`]
+    Lch(HueInterpolation),
+    /// A straight line in Oklab, as in [`LabValue::mix_oklab`]
+    #[cfg(feature = "illuminants")]
+    Oklab,
+    /// A straight line in XYZ relative to [`Illuminant::D50`], as in [`XyzValue::mix`]
+    Xyz,
+}
+
+impl InterpolationSpace {
+    /// Interpolate between two colors in the space. `t` is the fraction of the way from `start`
+    /// to `end`.
+    pub fn mix(self, start: LabValue, end: LabValue, t: f32) -> LabValue {
+        match self {
+            InterpolationSpace::Lab => start.mix(end, t),
+            InterpolationSpace::Lch(hue) => {
+                LchValue::from(start).mix_hue(end.into(), t, hue).into()
+            }
+            #[cfg(feature = "illuminants")]
+            InterpolationSpace::Oklab => start.mix_oklab(end, t),
+            InterpolationSpace::Xyz => XyzValue::from(start).mix(end.into(), t).into(),
+        }
+    }
+}
+
+/// The way hue is interpolated around the hue circle, as in the `hue-interpolation-method` of
+/// [CSS Color 4](https://www.w3.org/TR/css-color-4/#hue-interpolation)
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum HueInterpolation {
+    /// Along the shorter arc between the hues
+    #[default]
+    Shorter,
+    /// Along the longer arc between the hues
+    Longer,
+    /// Counter-clockwise, with the hue angle increasing
+    Increasing,
+    /// Clockwise, with the hue angle decreasing
+    Decreasing,
+}
+
+impl HueInterpolation {
+    /// Interpolate between two hue angles in degrees. The result is in `0.0..360.0`.
+    /// ```
+    /// use deltae::*;
+    ///
+    /// assert_eq!(HueInterpolation::Shorter.mix(350.0, 10.0, 0.25), 355.0);
+    /// assert_eq!(HueInterpolation::Longer.mix(350.0, 10.0, 0.25), 265.0);
+    /// assert_eq!(HueInterpolation::Increasing.mix(10.0, 350.0, 0.5), 180.0);
+    /// assert_eq!(HueInterpolation::Decreasing.mix(10.0, 350.0, 0.5), 0.0);
+    /// ```
+    pub fn mix(self, start: f32, end: f32, t: f32) -> f32 {
+        let (mut start, mut end) = (start.rem_euclid(360.0), end.rem_euclid(360.0));
+        let delta = end - start;
+
+        match self {
+            HueInterpolation::Shorter if delta > 180.0 => start += 360.0,
+            HueInterpolation::Shorter if delta < -180.0 => end += 360.0,
+            HueInterpolation::Longer if 0.0 < delta && delta < 180.0 => start += 360.0,
+            HueInterpolation::Longer if -180.0 < delta && delta <= 0.0 => end += 360.0,
+            HueInterpolation::Increasing if end < start => end += 360.0,
+            HueInterpolation::Decreasing if start < end => start += 360.0,
+            _ => (),
+        }
+
+        lerp(start, end, t).rem_euclid(360.0)
+    }
+}
+
+/// # Gradient
+///
+/// An iterator over a number of evenly spaced steps from one color to another, including both
+/// ends, interpolated in an [`InterpolationSpace`]. A gradient of one step yields only the start.
+/// ```
+/// use deltae::*;
+///
+/// let black = LabValue::new(0.0, 0.0, 0.0).unwrap();
+/// let white = LabValue::new(100.0, 0.0, 0.0).unwrap();
+///
+/// let ramp: Vec<LabValue> = Gradient::new(black, white, 5, InterpolationSpace::Lab).collect();
+/// let lightness: Vec<f32> = ramp.iter().map(|lab| lab.l).collect();
+/// assert_eq!(lightness, [0.0, 25.0, 50.0, 75.0, 100.0]);
+///
+/// // Check that no step is too large
+/// assert!(ramp.windows(2).all(|pair| pair[0].delta_eq(pair[1], DE2000, 30.0)));
+/// ```
+#[derive(Debug, Clone)]
+pub struct Gradient {
+    start: LabValue,
+    end: LabValue,
+    space: InterpolationSpace,
+    steps: usize,
+    // The fraction of the way from start to end of each step, if they are not evenly spaced
+    positions: Option<Vec<f32>>,
+    front: usize,
+    back: usize,
+}
+
+impl Gradient {
+    /// New [`Gradient`] of a number of steps from a start to an end color
+    pub fn new<S, E>(start: S, end: E, steps: usize, space: InterpolationSpace) -> Gradient
+    where S: Into<LabValue>, E: Into<LabValue> {
+        Gradient {
+            start: start.into(),
+            end: end.into(),
+            space,
+            steps,
+            positions: None,
+            front: 0,
+            back: steps,
+        }
+    }
+
+    /// New [`Gradient`] whose steps are placed so that each pair of consecutive steps has
+    /// approximately the same [`DeltaE`] in a [`DEMethod`]. Evenly spaced steps in Lab are not
+    /// evenly spaced perceptually: DE2000 shrinks the steps of saturated colors, so even steps
+    /// bunch up there.
+    ///
+    /// The steps still follow the path of the [`InterpolationSpace`]. They are found by
+    /// repeatedly respacing them by the [`DeltaE`] between them, until the largest and smallest
+    /// differ by less than 0.1%, or after 50 iterations.
+    /// ```
+    /// use deltae::*;
+    ///
+    /// let gray = LabValue::new(50.0, 0.0, 0.0).unwrap();
+    /// let red = LabValue::new(50.0, 80.0, 60.0).unwrap();
+    ///
+    /// let ramp: Vec<LabValue> = Gradient::uniform(gray, red, 6, InterpolationSpace::Lab, DE2000)
+    ///     .collect();
+    /// let deltas: Vec<f32> = ramp.windows(2).map(|w| *w[0].delta(w[1], DE2000).value()).collect();
+    ///
+    /// let min = deltas.iter().cloned().fold(f32::INFINITY, f32::min);
+    /// let max = deltas.iter().cloned().fold(0.0, f32::max);
+    /// assert!(max / min < 1.001);
+    /// ```
+    pub fn uniform<S, E>(
+        start: S,
+        end: E,
+        steps: usize,
+        space: InterpolationSpace,
+        method: DEMethod,
+    ) -> Gradient
+    where S: Into<LabValue>, E: Into<LabValue> {
+        let mut gradient = Gradient::new(start, end, steps, space);
+        if steps < 3 {
+            return gradient;
+        }
+
+        let mut positions: Vec<f32> = (0..steps).map(|i| i as f32 / (steps - 1) as f32).collect();
+        for _ in 0..50 {
+            let colors: Vec<LabValue> =
+                positions.iter().map(|&t| space.mix(gradient.start, gradient.end, t)).collect();
+            let deltas: Vec<f32> =
+                colors.windows(2).map(|pair| *pair[0].delta(pair[1], method).value()).collect();
+
+            let max = deltas.iter().cloned().fold(0.0, f32::max);
+            let min = deltas.iter().cloned().fold(f32::INFINITY, f32::min);
+            if max - min <= max * 0.001 {
+                break;
+            }
+
+            positions = respace(&positions, &deltas);
+        }
+
+        gradient.positions = Some(positions);
+        gradient
+    }
+
+    // The color of a step
+    fn step(&self, i: usize) -> LabValue {
+        if self.steps == 1 {
+            return self.start;
+        }
+
+        let t = match &self.positions {
+            Some(positions) => positions[i],
+            None => i as f32 / (self.steps - 1) as f32,
+        };
+
+        self.space.mix(self.start, self.end, t)
+    }
+}
+
+impl Iterator for Gradient {
+    type Item = LabValue;
+
+    fn next(&mut self) -> Option<LabValue> {
+        if self.front == self.back {
+            return None;
+        }
+
+        self.front += 1;
+        Some(self.step(self.front - 1))
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let len = self.back - self.front;
+        (len, Some(len))
+    }
+}
+
+impl DoubleEndedIterator for Gradient {
+    fn next_back(&mut self) -> Option<LabValue> {
+        if self.front == self.back {
+            return None;
+        }
+
+        self.back -= 1;
+        Some(self.step(self.back))
+    }
+}
+
+impl ExactSizeIterator for Gradient {}
+
+impl LabValue {
+    /// Interpolate linearly in Lab between the color and another. `t` is the fraction of the way
+    /// to the other color: `0.0` returns `self` and `1.0` returns `other`. Values of `t` outside
+    /// of `0.0..=1.0` extrapolate.
+    pub fn mix(self, other: LabValue, t: f32) -> LabValue {
+        LabValue {
+            l: lerp(self.l, other.l, t),
+            a: lerp(self.a, other.a, t),
+            b: lerp(self.b, other.b, t),
+        }
+    }
+
+    /// Interpolate linearly in Oklab between the color and another. The Lab values are relative
+    /// to [`Illuminant::D50`], and are adapted to D65 for Oklab with
+    /// [`ChromaticAdaptationMethod::Bradford`].
+    /// ```
+    /// use deltae::*;
+    ///
+    /// let white = LabValue::new(100.0, 0.0, 0.0).unwrap();
+    /// let black = LabValue::new(0.0, 0.0, 0.0).unwrap();
+    /// let gray = white.mix_oklab(black, 0.5);
+    ///
+    /// // Oklab lightness is closer to Lab lightness cubed than to Lab lightness
+    /// assert!(gray.l < 50.0);
+    /// assert!(gray.a.abs() < 1e-3 && gray.b.abs() < 1e-3);
+    /// ```
+    #[cfg(feature = "illuminants")]
+    pub fn mix_oklab(self, other: LabValue, t: f32) -> LabValue {
+        let (start, end) = (lab_to_oklab(self), lab_to_oklab(other));
+        let mixed = matrix![
+            lerp(start[0], end[0], t);
+            lerp(start[1], end[1], t);
+            lerp(start[2], end[2], t)
+        ];
+
+        oklab_to_lab(mixed)
+    }
+}
+
+impl LchValue {
+    /// Interpolate between the color and another in LCh. Lightness and chroma are interpolated
+    /// linearly, and hue along the shorter arc between the two hue angles. When one of the colors
+    /// is achromatic its hue is meaningless, so the hue of the other color is used.
+    /// ```
+    /// use deltae::*;
+    ///
+    /// let red = LchValue::new(50.0, 60.0, 350.0).unwrap();
+    /// let orange = LchValue::new(70.0, 80.0, 30.0).unwrap();
+    ///
+    /// // The hue crosses 0° rather than passing through green and blue
+    /// let mixed = red.mix(orange, 0.5);
+    /// assert_eq!(mixed.round_to(4), LchValue::new(60.0, 70.0, 10.0).unwrap());
+    /// ```
+    pub fn mix(self, other: LchValue, t: f32) -> LchValue {
+        self.mix_hue(other, t, HueInterpolation::Shorter)
+    }
+
+    /// Interpolate between the color and another in LCh, with hue interpolated by a
+    /// [`HueInterpolation`]. When one of the colors is achromatic its hue is meaningless, so the
+    /// hue of the other color is used.
+    /// ```
+    /// use deltae::*;
+    ///
+    /// let red = LchValue::new(50.0, 60.0, 350.0).unwrap();
+    /// let orange = LchValue::new(50.0, 60.0, 30.0).unwrap();
+    ///
+    /// // The long way around, through blue and green
+    /// let mixed = red.mix_hue(orange, 0.5, HueInterpolation::Longer);
+    /// assert_eq!(mixed.round_to(4).h, 190.0);
+    /// ```
+    pub fn mix_hue(self, other: LchValue, t: f32, hue: HueInterpolation) -> LchValue {
+        let (h0, h1) = match (self.c == 0.0, other.c == 0.0) {
+            (true, false) => (other.h, other.h),
+            (false, true) => (self.h, self.h),
+            _ => (self.h, other.h),
+        };
+
+        LchValue {
+            l: lerp(self.l, other.l, t),
+            c: lerp(self.c, other.c, t),
+            h: hue.mix(h0, h1, t),
+        }
+    }
+}
+
+impl XyzValue {
+    /// Interpolate linearly in XYZ between the color and another. This is the additive mixture
+    /// of two lights.
+    pub fn mix(self, other: XyzValue, t: f32) -> XyzValue {
+        XyzValue {
+            x: lerp(self.x, other.x, t),
+            y: lerp(self.y, other.y, t),
+            z: lerp(self.z, other.z, t),
+        }
+    }
+}
+
+// Move the positions so that the length of the path between them, estimated by the deltas
+// between them, is equal. The path is treated as linear in position between each pair, and a
+// target that falls on a segment of zero length, between duplicate colors, is its start.
+pub(crate) fn respace(positions: &[f32], deltas: &[f32]) -> Vec<f32> {
+    let total: f32 = deltas.iter().sum();
+    let intervals = deltas.len();
+    if total == 0.0 {
+        return positions.to_vec();
+    }
+
+    let mut respaced = Vec::with_capacity(positions.len());
+    respaced.push(positions[0]);
+
+    let (mut segment, mut length) = (0, 0.0);
+    for k in 1..intervals {
+        let target = total * k as f32 / intervals as f32;
+        while length + deltas[segment] < target && segment + 1 < intervals {
+            length += deltas[segment];
+            segment += 1;
+        }
+
+        let fraction = if deltas[segment] > 0.0 {
+            ((target - length) / deltas[segment]).min(1.0)
+        } else {
+            0.0
+        };
+        respaced.push(lerp(positions[segment], positions[segment + 1], fraction));
+    }
+
+    respaced.push(positions[intervals]);
+    respaced
+}
+
+fn lerp(start: f32, end: f32, t: f32) -> f32 {
+    start + (end - start) * t
+}
+
+// https://bottosson.github.io/posts/oklab/
+#[cfg(feature = "illuminants")]
+pub(crate) fn lab_to_oklab(lab: LabValue) -> Matrix3x1 {
+    let xyz = XyzValue::from(lab).chrom_adapt(
+        ChromaticAdaptationMethod::Bradford,
+        Illuminant::D50,
+        Illuminant::D65,
+    );
+
+    let mut lms = OKLAB_XYZ2LMS * Matrix3x1::from(xyz);
+    lms.iter_mut().for_each(|v| *v = v.cbrt());
+
+    OKLAB_LMS2LAB * lms
+}
+
+#[cfg(feature = "illuminants")]
+pub(crate) fn oklab_to_lab(oklab: Matrix3x1) -> LabValue {
+    let mut lms = OKLAB_LAB2LMS * oklab;
+    lms.iter_mut().for_each(|v| *v = v.powi(3));
+
+    let xyz = XyzValue::from(OKLAB_LMS2XYZ * lms).chrom_adapt(
+        ChromaticAdaptationMethod::Bradford,
+        Illuminant::D65,
+        Illuminant::D50,
+    );
+
+    LabValue::from(xyz)
+}
+
+// Oklab to cube-root LMS, and linear LMS to XYZ relative to D65
+#[cfg(feature = "illuminants")]
+pub(crate) const OKLAB_LAB2LMS: Matrix3x3 = matrix![
+     1.0,        0.3963378,  0.2158038;
+     1.0,       -0.1055613, -0.0638542;
+     1.0,       -0.0894842, -1.2914855
+];
+
+#[cfg(feature = "illuminants")]
+pub(crate) const OKLAB_LMS2XYZ: Matrix3x3 = matrix![
+     1.22688,   -0.557815,   0.281391;
+    -0.0405757,  1.1122868, -0.0717111;
+    -0.0763729, -0.4214933,  1.586924
+];
+
+// The inverses of the matrices above, which CSS `oklab()` is parsed with, so that colors round
+// trip
+#[cfg(feature = "illuminants")]
+const OKLAB_XYZ2LMS: Matrix3x3 = OKLAB_LMS2XYZ.expect_inverse();
+#[cfg(feature = "illuminants")]
+const OKLAB_LMS2LAB: Matrix3x3 = OKLAB_LAB2LMS.expect_inverse();
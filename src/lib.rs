@@ -8,6 +8,8 @@
 //! use std::error::Error;
 //! use deltae::*;
 //!
+//! # #[cfg(not(feature = "parse"))] fn main() {}
+//! # #[cfg(feature = "parse")]
 //! fn main() -> Result<(), Box<dyn Error>>{
 //!     // Lab from a string
 //!     let lab0: LabValue = "89.73, 1.88, -6.96".parse()?;
@@ -73,27 +75,96 @@
 //! }
 //! ```
 
+pub mod angle;
+#[cfg(feature = "csv")]
+pub mod batch;
+pub mod buffer;
+#[cfg(feature = "cgats")]
+pub mod cgats;
+#[cfg(feature = "illuminants")]
+pub mod chromatic_adaptation;
+pub mod cluster;
 pub mod color;
+pub mod comparator;
 mod convert;
+#[cfg(feature = "spectral")]
+pub mod cri;
+#[cfg(all(feature = "rgb", feature = "parse"))]
+pub mod css;
 mod delta;
+pub mod ellipsoid;
 pub mod eq;
+pub mod error;
+#[cfg(feature = "rgb")]
+pub mod hsl;
+#[cfg(feature = "icc")]
+pub mod icc;
+#[cfg(feature = "illuminants")]
+pub mod illuminant;
+pub mod interpolate;
+pub mod matrix;
+#[cfg(feature = "named-colors")]
+mod named;
+pub mod palette;
+#[cfg(feature = "parse")]
+mod parse;
+#[cfg(feature = "python")]
+pub mod python;
+pub mod report;
+#[cfg(feature = "rgb")]
+pub mod rgb;
 mod round;
+pub mod stats;
+pub mod sort;
+#[cfg(feature = "spectral")]
+pub mod spectral;
+#[cfg(feature = "swatches")]
+pub mod swatch;
 mod validate;
+#[cfg(feature = "wasm")]
+pub mod wasm;
 
 #[cfg(test)]
 mod tests;
 
 pub use DEMethod::*;
+pub use angle::*;
+pub use buffer::*;
+#[cfg(feature = "illuminants")]
+pub use chromatic_adaptation::*;
+pub use cluster::*;
 pub use color::*;
+pub use comparator::*;
+#[cfg(feature = "spectral")]
+pub use cri::*;
+#[cfg(all(feature = "rgb", feature = "parse"))]
+pub use css::*;
 pub use delta::*;
+pub use ellipsoid::*;
 pub use eq::*;
+pub use error::*;
+#[cfg(feature = "rgb")]
+pub use hsl::*;
+#[cfg(feature = "icc")]
+pub use icc::*;
+#[cfg(feature = "illuminants")]
+pub use illuminant::*;
+pub use interpolate::*;
+pub use matrix::*;
+pub use palette::*;
+pub use report::*;
+#[cfg(feature = "rgb")]
+pub use rgb::*;
 pub use round::*;
+pub use stats::*;
+pub use sort::*;
+#[cfg(feature = "spectral")]
+pub use spectral::*;
 pub use validate::*;
 
 use std::fmt;
-use std::io;
 
-pub(crate) type ValueResult<T> = Result<T, color::ValueError>;
+pub(crate) type ValueResult<T> = Result<T, error::Error>;
 
 /// ## The measured difference between two colors
 ///
@@ -102,6 +173,7 @@ pub(crate) type ValueResult<T> = Result<T, color::ValueError>;
 /// between two colors. Regardless of the [`DEMethod`] used, [`DeltaE`] is always calculated based on the
 /// [`LabValue`]s of the two colors.
 #[derive(Debug, Clone, Copy)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct DeltaE {
     /// The mathematical method used for calculating color difference
     method: DEMethod,
@@ -155,7 +227,17 @@ impl DeltaE {
     }
 
     /// Return a reference to the reference [`LabValue`] used in the calculation. A reference color
-    /// is the base color to which the sample color is being compared.
+    /// is the base color to which the sample color is being compared. Colors of other types are
+    /// kept as the [`LabValue`] they were compared as.
+    /// ```
+    /// use deltae::*;
+    ///
+    /// let lch = LchValue::new(60.0, 30.0, 0.0).unwrap();
+    /// let lab = LabValue::new(60.0, 28.0, 2.0).unwrap();
+    /// let de = lch.delta(lab, DE2000);
+    /// assert_eq!(de.reference().round_to(4), LabValue::new(60.0, 30.0, 0.0).unwrap());
+    /// assert_eq!(de.sample(), &lab);
+    /// ```
     pub fn reference(&self) -> &LabValue {
         &self.reference
     }
@@ -167,9 +249,10 @@ impl DeltaE {
     }
 }
 
+/// Displays the value with all of the formatting options of an [`f32`], such as `{:>8.2}`
 impl fmt::Display for DeltaE {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
-        write!(f, "{}", &self.value)
+        fmt::Display::fmt(&self.value, f)
     }
 }
 
@@ -195,9 +278,15 @@ impl PartialOrd for DeltaE {
 }
 
 /// The most common DeltaE methods
-#[derive(Debug, PartialEq, Clone, Copy)]
+///
+/// More methods may be added in the future, so matches on [`DEMethod`] outside of this crate need a
+/// wildcard arm.
+#[derive(Debug, PartialEq, Clone, Copy, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[non_exhaustive]
 pub enum DEMethod{
     /// The default DeltaE method
+    #[default]
     DE2000,
     /// An implementation of DeltaE with separate tolerances for Lightness and Chroma
     DECMC(
@@ -221,26 +310,106 @@ pub const DECMC2: DEMethod = DECMC(2.0, 1.0);
 
 impl Eq for DEMethod {}
 
-impl Default for DEMethod {
-    fn default() -> DEMethod {
-        DEMethod::DE2000
+impl DEMethod {
+    // The name and the lowercase aliases of each method with a fixed name, the first of which is
+    // canonical. This is the single source of the names for Display, FromStr, and
+    // ParseMethodError.
+    pub(crate) const NAMES: &'static [(DEMethod, &'static str, &'static [&'static str])] = &[
+        (DE2000, "DE2000", &["de2000", "de00", "2000", "00"]),
+        (DE1994G, "DE1994G", &["de1994g", "de94g", "1994g", "94g", "de1994", "de94", "1994", "94"]),
+        (DE1994T, "DE1994T", &["de1994t", "de94t", "1994t", "94t"]),
+        (DECMC1, "DECMC1", &["decmc1", "cmc1", "decmc", "cmc"]),
+        (DECMC2, "DECMC2", &["decmc2", "cmc2"]),
+        (DE1976, "DE1976", &["de1976", "de76", "1976", "76"]),
+    ];
+
+    /// The name of the method, as it is displayed. [`DECMC`] with tolerances other than those of
+    /// [`DECMC1`] and [`DECMC2`] is named `"DECMC"`, and displays with its tolerances.
+    /// ```
+    /// use deltae::*;
+    ///
+    /// assert_eq!(DE2000.as_str(), "DE2000");
+    /// assert_eq!(DECMC(2.0, 1.0).as_str(), "DECMC2");
+    /// assert_eq!(DECMC(1.5, 1.0).as_str(), "DECMC");
+    /// assert_eq!(DECMC(1.5, 1.0).to_string(), "DECMC(1.50:1.00)");
+    /// ```
+    pub fn as_str(&self) -> &'static str {
+        DEMethod::NAMES.iter()
+            .find(|(method, _, _)| method == self)
+            .map_or("DECMC", |&(_, name, _)| name)
     }
 }
 
 impl fmt::Display for DEMethod {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
         match self {
-            DECMC(tl, tc) => {
-                if (tl, tc) == (&1.0, &1.0) {
-                    write!(f, "DECMC1")
-                } else if (tl, tc) == (&2.0, &1.0) {
-                    write!(f, "DECMC2")
-                } else {
-                    write!(f, "DECMC({:0.2}:{:0.2})", tl, tc)
-                }
+            DECMC(tl, tc) if self.as_str() == "DECMC" => {
+                f.pad(&format!("{}({:0.2}:{:0.2})", self.as_str(), tl, tc))
             }
-            _ => write!(f, "{:?}", self)
+            _ => f.pad(self.as_str()),
+        }
+    }
+}
+
+/// The error returned when a string is not the name of a [`DEMethod`]
+/// ```
+/// # #[cfg(feature = "parse")] {
+/// use deltae::*;
+///
+/// let err = "de3000".parse::<DEMethod>().unwrap_err();
+/// assert_eq!(err.token(), "de3000");
+/// assert!(err.to_string().starts_with(r#"unknown DeltaE method "de3000", expected one of: de2000"#));
+/// # }
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ParseMethodError {
+    token: String,
+}
+
+impl ParseMethodError {
+    /// The accepted names of each [`DEMethod`], in a canonical form, followed by the form of
+    /// [`DECMC`] with tolerances. Names are case insensitive, and shorter aliases such as `"2000"`,
+    /// `"de94"`, and `"cmc"` are also accepted.
+    pub const ACCEPTED: &'static [&'static str] = &ParseMethodError::accepted();
+
+    // The canonical alias of each of DEMethod::NAMES, and the DECMC form
+    const fn accepted() -> [&'static str; DEMethod::NAMES.len() + 1] {
+        let mut accepted = [""; DEMethod::NAMES.len() + 1];
+        let mut i = 0;
+        while i < DEMethod::NAMES.len() {
+            accepted[i] = DEMethod::NAMES[i].2[0];
+            i += 1;
         }
+        accepted[i] = "decmc(l:c)";
+        accepted
+    }
+
+    #[cfg(feature = "parse")]
+    pub(crate) fn new(token: &str) -> ParseMethodError {
+        ParseMethodError { token: token.to_string() }
+    }
+
+    /// The unrecognized string
+    pub fn token(&self) -> &str {
+        &self.token
     }
 }
 
+impl fmt::Display for ParseMethodError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let aliases: Vec<&str> = DEMethod::NAMES.iter()
+            .flat_map(|(_, _, aliases)| aliases[1..].iter().copied())
+            .collect();
+
+        write!(
+            f,
+            "unknown DeltaE method {:?}, expected one of: {} (or the aliases {})",
+            self.token,
+            ParseMethodError::ACCEPTED.join(", "),
+            aliases.join(", ")
+        )
+    }
+}
+
+impl std::error::Error for ParseMethodError {}
+
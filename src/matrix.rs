@@ -0,0 +1,540 @@
+//! 3x3 and 3x1 matrices used in color space conversions.
+//!
+//! [`Matrix3x3`] and [`Matrix3x1`] hold `f32`s, like the rest of the crate. [`Matrix3x3d`] and
+//! [`Matrix3x1d`] hold `f64`s and share the same API, for long chains of matrix operations where
+//! the accumulated error of `f32` becomes visible.
+//!
+//! # Examples
+//!
+//! ```
+//! use deltae::*;
+//!
+//! let m = matrix![
+//!     1.0, 0.0, 0.0;
+//!     0.0, 2.0, 0.0;
+//!     0.0, 0.0, 3.0
+//! ];
+//! let v = matrix![1.0; 1.0; 1.0];
+//!
+//! assert_eq!(m * v, matrix![1.0; 2.0; 3.0]);
+//! assert_eq!(m[(1, 1)], 2.0);
+//!
+//! // Scalar and element-wise arithmetic
+//! assert_eq!(v * 2.0 - v, v);
+//! assert_eq!((m + m) / 2.0, m);
+//!
+//! // Iteration in row-major order
+//! assert_eq!(m.into_iter().sum::<f32>(), 6.0);
+//! for value in &v {
+//!     assert_eq!(*value, 1.0);
+//! }
+//!
+//! // Display with aligned columns
+//! assert_eq!(format!("{:.1}", v * -1.0), "[-1.0]\n[-1.0]\n[-1.0]");
+//! assert_eq!(format!("{:.2}", m).lines().next(), Some("[1.00, 0.00, 0.00]"));
+//!
+//! // Double precision
+//! let md = Matrix3x3d::from(m);
+//! assert_eq!(md.inverse().unwrap()[(2, 2)], 1.0 / 3.0);
+//! assert_eq!(md.to_f32(), m);
+//! ```
+
+use std::array;
+use std::convert::TryFrom;
+use std::fmt;
+use std::ops::{Add, Div, Index, IndexMut, Mul, Sub};
+use std::slice;
+
+use crate::*;
+
+/// Construct a [`Matrix3x3`] from rows separated by semicolons, or a [`Matrix3x1`] from a single
+/// column of values separated by semicolons.
+/// ```
+/// use deltae::*;
+///
+/// let m3x3 = matrix![
+///     1.0, 2.0, 3.0;
+///     4.0, 5.0, 6.0;
+///     7.0, 8.0, 9.0
+/// ];
+/// let m3x1 = matrix![1.0; 2.0; 3.0];
+///
+/// assert_eq!(m3x3[(1, 2)], 6.0);
+/// assert_eq!(m3x1[2], 3.0);
+/// ```
+#[macro_export]
+macro_rules! matrix {
+    (
+        $a:expr, $b:expr, $c:expr;
+        $d:expr, $e:expr, $f:expr;
+        $g:expr, $h:expr, $i:expr $(;)?
+    ) => {
+        $crate::Matrix3x3::new([$a, $b, $c, $d, $e, $f, $g, $h, $i])
+    };
+    ($x:expr; $y:expr; $z:expr $(;)?) => {
+        $crate::Matrix3x1::new([$x, $y, $z])
+    };
+}
+
+/// A 3x3 matrix of `f32`s, stored in row-major order.
+///
+/// Flat arrays and slices are always interpreted as row-major, and nested arrays as an array of
+/// rows. Use [`Matrix3x3::from_columns`] or [`Matrix3x3::transpose`] for column-major data, such as
+/// the `rXYZ`, `gXYZ`, and `bXYZ` colorant tags of an ICC profile.
+/// ```
+/// use deltae::*;
+///
+/// let m = Matrix3x3::from_columns([
+///     [1.0, 4.0, 7.0],
+///     [2.0, 5.0, 8.0],
+///     [3.0, 6.0, 9.0],
+/// ]);
+/// assert_eq!(m, Matrix3x3::from([[1.0, 2.0, 3.0], [4.0, 5.0, 6.0], [7.0, 8.0, 9.0]]));
+/// assert_eq!(m.transpose()[(0, 1)], 4.0);
+/// assert_eq!(m.trace(), 15.0);
+///
+/// // Singular matrices have no inverse
+/// assert_eq!(m.determinant(), 0.0);
+/// assert_eq!(m.inverse(), None);
+///
+/// let d = Matrix3x3::diagonal([2.0, 4.0, 8.0]);
+/// assert_eq!(d.inverse(), Some(Matrix3x3::diagonal([0.5, 0.25, 0.125])));
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Matrix3x3 {
+    inner: [f32; 9],
+}
+
+/// A 3x1 column matrix of `f32`s
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Matrix3x1 {
+    inner: [f32; 3],
+}
+
+/// A 3x3 matrix of `f64`s, stored in row-major order. See [`Matrix3x3`].
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Matrix3x3d {
+    inner: [f64; 9],
+}
+
+/// A 3x1 column matrix of `f64`s. See [`Matrix3x1`].
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Matrix3x1d {
+    inner: [f64; 3],
+}
+
+// The shared API of a 3x3 and 3x1 matrix pair of the same float type
+macro_rules! impl_matrix {
+    ($m3x3:ident, $m3x1:ident, $float:ty) => {
+        impl $m3x3 {
+            /// The identity matrix
+            pub const IDENTITY: $m3x3 = $m3x3::diagonal([1.0, 1.0, 1.0]);
+
+            /// Returns a new matrix with all values set to zero
+            pub const fn zero() -> $m3x3 {
+                $m3x3 { inner: [0.0; 9] }
+            }
+
+            /// Returns a new matrix from 9 values in row-major order
+            pub const fn new(inner: [$float; 9]) -> $m3x3 {
+                $m3x3 { inner }
+            }
+
+            /// Returns a new matrix from an array of columns
+            pub const fn from_columns(columns: [[$float; 3]; 3]) -> $m3x3 {
+                let [[a, d, g], [b, e, h], [c, f, i]] = columns;
+                $m3x3 { inner: [a, b, c, d, e, f, g, h, i] }
+            }
+
+            /// Returns a new diagonal matrix, with all other values set to zero
+            pub const fn diagonal(d: [$float; 3]) -> $m3x3 {
+                $m3x3 {
+                    inner: [
+                        d[0], 0.0,  0.0,
+                        0.0,  d[1], 0.0,
+                        0.0,  0.0,  d[2],
+                    ],
+                }
+            }
+
+            /// Returns the 9 values of the matrix in row-major order
+            pub const fn to_array(self) -> [$float; 9] {
+                self.inner
+            }
+
+            /// Returns an iterator over the values of the matrix in row-major order
+            pub fn iter(&self) -> slice::Iter<'_, $float> {
+                self.inner.iter()
+            }
+
+            /// Returns an iterator that allows modifying the values of the matrix in row-major
+            /// order
+            pub fn iter_mut(&mut self) -> slice::IterMut<'_, $float> {
+                self.inner.iter_mut()
+            }
+
+            /// Returns the determinant of the matrix
+            pub const fn determinant(&self) -> $float {
+                let [a, b, c, d, e, f, g, h, i] = self.inner;
+                a * (e * i - f * h) - b * (d * i - f * g) + c * (d * h - e * g)
+            }
+
+            /// Returns the transpose of the matrix
+            pub const fn transpose(&self) -> $m3x3 {
+                let [a, b, c, d, e, f, g, h, i] = self.inner;
+                $m3x3 { inner: [a, d, g, b, e, h, c, f, i] }
+            }
+
+            /// Returns the trace (the sum of the diagonal) of the matrix
+            pub const fn trace(&self) -> $float {
+                self.inner[0] + self.inner[4] + self.inner[8]
+            }
+
+            /// Returns the product of the matrix and another matrix, the same as `self * rhs`.
+            /// This is a `const fn`, so matrices may be combined at compile time.
+            pub const fn mul_matrix(&self, rhs: &$m3x3) -> $m3x3 {
+                let (a, b) = (&self.inner, &rhs.inner);
+                let mut inner = [0.0; 9];
+                let mut i = 0;
+                while i < 9 {
+                    let (row, col) = (i / 3 * 3, i % 3);
+                    inner[i] = a[row] * b[col] + a[row + 1] * b[col + 3] + a[row + 2] * b[col + 6];
+                    i += 1;
+                }
+
+                $m3x3 { inner }
+            }
+
+            /// Returns the product of the matrix and a column matrix, the same as `self * rhs`
+            pub const fn mul_column(&self, rhs: &$m3x1) -> $m3x1 {
+                let [a, b, c, d, e, f, g, h, i] = self.inner;
+                let [x, y, z] = rhs.inner;
+                $m3x1 { inner: [a * x + b * y + c * z, d * x + e * y + f * z, g * x + h * y + i * z] }
+            }
+
+            /// Returns `true` if the matrix is exactly the identity matrix
+            pub fn is_identity(&self) -> bool {
+                *self == $m3x3::IDENTITY
+            }
+
+            /// Returns `true` if the matrix has an inverse
+            pub const fn is_invertible(&self) -> bool {
+                self.inverse().is_some()
+            }
+
+            /// Returns the inverse of the matrix, or `None` if the matrix is singular. This is a
+            /// `const fn`, so inverse matrices may be calculated at compile time.
+            pub const fn inverse(&self) -> Option<$m3x3> {
+                let [a, b, c, d, e, f, g, h, i] = self.inner;
+
+                let det = self.determinant();
+                if !det.is_normal() {
+                    return None;
+                }
+
+                Some($m3x3 {
+                    inner: [
+                        (e * i - f * h) / det, (c * h - b * i) / det, (b * f - c * e) / det,
+                        (f * g - d * i) / det, (a * i - c * g) / det, (c * d - a * f) / det,
+                        (d * h - e * g) / det, (b * g - a * h) / det, (a * e - b * d) / det,
+                    ],
+                })
+            }
+        }
+
+        impl Index<usize> for $m3x3 {
+            type Output = $float;
+            fn index(&self, i: usize) -> &$float {
+                &self.inner[i]
+            }
+        }
+
+        impl IndexMut<usize> for $m3x3 {
+            fn index_mut(&mut self, i: usize) -> &mut $float {
+                &mut self.inner[i]
+            }
+        }
+
+        /// Index by `(row, column)`
+        impl Index<(usize, usize)> for $m3x3 {
+            type Output = $float;
+            fn index(&self, (row, col): (usize, usize)) -> &$float {
+                assert!(
+                    row < 3 && col < 3,
+                    "{} index out of bounds: ({}, {})", stringify!($m3x3), row, col
+                );
+                &self.inner[row * 3 + col]
+            }
+        }
+
+        /// Index by `(row, column)`
+        impl IndexMut<(usize, usize)> for $m3x3 {
+            fn index_mut(&mut self, (row, col): (usize, usize)) -> &mut $float {
+                assert!(
+                    row < 3 && col < 3,
+                    "{} index out of bounds: ({}, {})", stringify!($m3x3), row, col
+                );
+                &mut self.inner[row * 3 + col]
+            }
+        }
+
+        impl Mul<$m3x1> for $m3x3 {
+            type Output = $m3x1;
+            fn mul(self, rhs: $m3x1) -> $m3x1 {
+                self.mul_column(&rhs)
+            }
+        }
+
+        impl Mul for $m3x3 {
+            type Output = $m3x3;
+            fn mul(self, rhs: $m3x3) -> $m3x3 {
+                self.mul_matrix(&rhs)
+            }
+        }
+
+        /// Interpreted as row-major
+        impl From<[$float; 9]> for $m3x3 {
+            fn from(inner: [$float; 9]) -> $m3x3 {
+                $m3x3 { inner }
+            }
+        }
+
+        /// Interpreted as an array of rows
+        impl From<[[$float; 3]; 3]> for $m3x3 {
+            fn from(rows: [[$float; 3]; 3]) -> $m3x3 {
+                let [[a, b, c], [d, e, f], [g, h, i]] = rows;
+                $m3x3 { inner: [a, b, c, d, e, f, g, h, i] }
+            }
+        }
+
+        /// Interpreted as row-major. Will return `Err()` if the slice does not contain exactly 9
+        /// values.
+        impl TryFrom<&[$float]> for $m3x3 {
+            type Error = ValueError;
+            fn try_from(slice: &[$float]) -> ValueResult<$m3x3> {
+                let inner = <[$float; 9]>::try_from(slice).map_err(|_| ValueError::BadFormat)?;
+                Ok($m3x3 { inner })
+            }
+        }
+
+        impl $m3x1 {
+            /// Returns a new column matrix from 3 values
+            pub const fn new(inner: [$float; 3]) -> $m3x1 {
+                $m3x1 { inner }
+            }
+
+            /// Returns the 3 values of the column matrix
+            pub const fn to_array(self) -> [$float; 3] {
+                self.inner
+            }
+
+            /// Returns an iterator over the values of the column matrix
+            pub fn iter(&self) -> slice::Iter<'_, $float> {
+                self.inner.iter()
+            }
+
+            /// Returns an iterator that allows modifying the values of the column matrix
+            pub fn iter_mut(&mut self) -> slice::IterMut<'_, $float> {
+                self.inner.iter_mut()
+            }
+        }
+
+        impl Index<usize> for $m3x1 {
+            type Output = $float;
+            fn index(&self, i: usize) -> &$float {
+                &self.inner[i]
+            }
+        }
+
+        impl IndexMut<usize> for $m3x1 {
+            fn index_mut(&mut self, i: usize) -> &mut $float {
+                &mut self.inner[i]
+            }
+        }
+
+        /// Rows are written on separate lines with the columns aligned. The precision of the
+        /// formatter, if any, is applied to each value.
+        impl fmt::Display for $m3x3 {
+            fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+                fmt_grid(f, &self.inner, 3)
+            }
+        }
+
+        impl fmt::Display for $m3x1 {
+            fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+                fmt_grid(f, &self.inner, 1)
+            }
+        }
+
+        impl_matrix_iter!($m3x3, $float, 9);
+        impl_matrix_iter!($m3x1, $float, 3);
+        impl_matrix_arithmetic!($m3x3, $float);
+        impl_matrix_arithmetic!($m3x1, $float);
+    }
+}
+
+// Write values as a grid of rows, right-aligning each column to the widest value
+fn fmt_grid<T: fmt::Display>(f: &mut fmt::Formatter, values: &[T], columns: usize) -> fmt::Result {
+    let cells: Vec<String> = values.iter()
+        .map(|v| match f.precision() {
+            Some(p) => format!("{:.*}", p, v),
+            None => format!("{}", v),
+        })
+        .collect();
+    let width = cells.iter().map(|c| c.chars().count()).max().unwrap_or(0);
+
+    for (i, row) in cells.chunks(columns).enumerate() {
+        if i > 0 {
+            writeln!(f)?;
+        }
+        let row: Vec<String> = row.iter().map(|c| format!("{:>1$}", c, width)).collect();
+        write!(f, "[{}]", row.join(", "))?;
+    }
+
+    Ok(())
+}
+
+// Owned and borrowed iteration in row-major order, without allocation
+macro_rules! impl_matrix_iter {
+    ($t:ty, $float:ty, $len:expr) => {
+        impl IntoIterator for $t {
+            type Item = $float;
+            type IntoIter = array::IntoIter<$float, $len>;
+            fn into_iter(self) -> Self::IntoIter {
+                IntoIterator::into_iter(self.inner)
+            }
+        }
+
+        impl<'a> IntoIterator for &'a $t {
+            type Item = &'a $float;
+            type IntoIter = slice::Iter<'a, $float>;
+            fn into_iter(self) -> Self::IntoIter {
+                self.inner.iter()
+            }
+        }
+
+        impl<'a> IntoIterator for &'a mut $t {
+            type Item = &'a mut $float;
+            type IntoIter = slice::IterMut<'a, $float>;
+            fn into_iter(self) -> Self::IntoIter {
+                self.inner.iter_mut()
+            }
+        }
+    }
+}
+
+// Scalar and element-wise arithmetic
+macro_rules! impl_matrix_arithmetic {
+    ($t:ty, $float:ty) => {
+        impl Mul<$float> for $t {
+            type Output = $t;
+            fn mul(mut self, rhs: $float) -> $t {
+                self.inner.iter_mut().for_each(|v| *v *= rhs);
+                self
+            }
+        }
+
+        impl Div<$float> for $t {
+            type Output = $t;
+            fn div(mut self, rhs: $float) -> $t {
+                self.inner.iter_mut().for_each(|v| *v /= rhs);
+                self
+            }
+        }
+
+        impl Add for $t {
+            type Output = $t;
+            fn add(mut self, rhs: $t) -> $t {
+                self.inner.iter_mut().zip(rhs.inner.iter()).for_each(|(v, r)| *v += r);
+                self
+            }
+        }
+
+        impl Sub for $t {
+            type Output = $t;
+            fn sub(mut self, rhs: $t) -> $t {
+                self.inner.iter_mut().zip(rhs.inner.iter()).for_each(|(v, r)| *v -= r);
+                self
+            }
+        }
+    }
+}
+
+impl_matrix!(Matrix3x3, Matrix3x1, f32);
+impl_matrix!(Matrix3x3d, Matrix3x1d, f64);
+
+impl Matrix3x3 {
+    // The inverse of a constant matrix, calculated at compile time. A singular matrix fails the
+    // build.
+    #[cfg(feature = "illuminants")]
+    pub(crate) const fn expect_inverse(self) -> Matrix3x3 {
+        match self.inverse() {
+            Some(inverse) => inverse,
+            None => panic!("Constant matrix is singular!"),
+        }
+    }
+
+    /// Returns a double precision copy of the [`Matrix3x3`]
+    pub fn to_f64(&self) -> Matrix3x3d {
+        Matrix3x3d::from(*self)
+    }
+}
+
+impl Matrix3x3d {
+    /// Returns a single precision copy of the [`Matrix3x3d`], rounding each value to the nearest
+    /// `f32`
+    pub fn to_f32(&self) -> Matrix3x3 {
+        Matrix3x3 { inner: self.inner.map(|v| v as f32) }
+    }
+}
+
+impl Matrix3x1 {
+    /// Returns a double precision copy of the [`Matrix3x1`]
+    pub fn to_f64(&self) -> Matrix3x1d {
+        Matrix3x1d::from(*self)
+    }
+}
+
+impl Matrix3x1d {
+    /// Returns a single precision copy of the [`Matrix3x1d`], rounding each value to the nearest
+    /// `f32`
+    pub fn to_f32(&self) -> Matrix3x1 {
+        Matrix3x1 { inner: self.inner.map(|v| v as f32) }
+    }
+}
+
+impl From<Matrix3x3> for Matrix3x3d {
+    fn from(m: Matrix3x3) -> Matrix3x3d {
+        Matrix3x3d { inner: m.inner.map(f64::from) }
+    }
+}
+
+impl From<Matrix3x1> for Matrix3x1d {
+    fn from(m: Matrix3x1) -> Matrix3x1d {
+        Matrix3x1d { inner: m.inner.map(f64::from) }
+    }
+}
+
+impl From<XyzValue> for Matrix3x1 {
+    fn from(xyz: XyzValue) -> Matrix3x1 {
+        Matrix3x1 { inner: [xyz.x, xyz.y, xyz.z] }
+    }
+}
+
+impl From<Matrix3x1> for XyzValue {
+    fn from(m: Matrix3x1) -> XyzValue {
+        XyzValue { x: m[0], y: m[1], z: m[2] }
+    }
+}
+
+impl From<XyzValue> for Matrix3x1d {
+    fn from(xyz: XyzValue) -> Matrix3x1d {
+        Matrix3x1::from(xyz).to_f64()
+    }
+}
+
+impl From<Matrix3x1d> for XyzValue {
+    fn from(m: Matrix3x1d) -> XyzValue {
+        XyzValue::from(m.to_f32())
+    }
+}
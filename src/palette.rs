@@ -0,0 +1,251 @@
+//! Searching a palette of colors for the nearest colors to a query color. A [`Palette`] indexes
+//! its colors in a k-d tree in Lab space, so a search does not have to calculate [`DeltaE`] against
+//! every color in the palette.
+//!
+//! # Examples
+//!
+//! ```
+//! use deltae::*;
+//!
+//! let palette: Palette = [
+//!     LabValue::new(50.0, 0.0, 0.0).unwrap(),
+//!     LabValue::new(60.0, 40.0, 20.0).unwrap(),
+//!     LabValue::new(30.0, -20.0, -40.0).unwrap(),
+//! ].iter().copied().collect();
+//!
+//! let query = LabValue::new(58.0, 35.0, 18.0).unwrap();
+//! let (index, delta) = palette.nearest(query, DE2000).unwrap();
+//! assert_eq!(index, 1);
+//! assert_eq!(delta, query.delta(palette[1], DE2000));
+//!
+//! let nearest: Vec<usize> = palette.k_nearest(query, 2, DE2000)
+//!     .into_iter()
+//!     .map(|(index, _)| index)
+//!     .collect();
+//! assert_eq!(nearest, vec![1, 0]);
+//! ```
+
+use crate::*;
+use std::iter::FromIterator;
+use std::ops::Index;
+
+/// # Palette
+///
+/// A list of colors, indexed for nearest-color searches by any [`DEMethod`]. The query color of a
+/// search is the reference color of each [`DeltaE`], and the colors of the palette are the samples.
+///
+/// The k-d tree is searched by the distance along each of the L\*, a\*, and b\* axes, which is a
+/// lower bound of [`DE1976`]. The other methods weight the components of the difference, so that
+/// bound is scaled by the largest weight the method could apply between the query and the palette.
+/// Searches return the same colors as comparing against every color, but methods that weight
+/// differences more heavily, such as [`DE2000`] around saturated colors, prune less of the tree.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct Palette {
+    colors: Vec<LabValue>,
+    tree: Vec<usize>,
+    min_l: f32,
+    max_l: f32,
+    max_chroma: f32,
+}
+
+impl Palette {
+    /// New [`Palette`] from a list of colors
+    pub fn new<C: Into<LabValue>, I: IntoIterator<Item = C>>(colors: I) -> Palette {
+        let colors: Vec<LabValue> = colors.into_iter().map(Into::into).collect();
+
+        let mut tree: Vec<usize> = (0..colors.len()).collect();
+        build(&colors, &mut tree, 0);
+
+        let min_l = colors.iter().map(|lab| lab.l).fold(f32::INFINITY, f32::min);
+        let max_l = colors.iter().map(|lab| lab.l).fold(f32::NEG_INFINITY, f32::max);
+        let max_chroma = colors.iter().map(chroma).fold(0.0, f32::max);
+
+        Palette { colors, tree, min_l, max_l, max_chroma }
+    }
+
+    /// The number of colors in the palette
+    pub fn len(&self) -> usize {
+        self.colors.len()
+    }
+
+    /// Return true if the palette has no colors
+    pub fn is_empty(&self) -> bool {
+        self.colors.is_empty()
+    }
+
+    /// The colors of the palette, in the order they were given
+    pub fn colors(&self) -> &[LabValue] {
+        &self.colors
+    }
+
+    /// Return the index of the nearest color in the palette and its [`DeltaE`], or `None` if the
+    /// palette is empty
+    pub fn nearest<C: Into<LabValue>>(&self, color: C, method: DEMethod) -> Option<(usize, DeltaE)> {
+        self.k_nearest(color, 1, method).pop()
+    }
+
+    /// Return the indices of the `k` nearest colors in the palette and their [`DeltaE`]s, nearest
+    /// first. Colors at the same distance are in the order of the palette.
+    /// ```
+    /// use deltae::*;
+    ///
+    /// let grays = Palette::new((0..=10).map(|l| LabValue::new(l as f32 * 10.0, 0.0, 0.0).unwrap()));
+    /// let query = LabValue::new(42.0, 0.0, 0.0).unwrap();
+    ///
+    /// let nearest: Vec<usize> = grays.k_nearest(query, 3, DE1976)
+    ///     .into_iter()
+    ///     .map(|(index, _)| index)
+    ///     .collect();
+    /// assert_eq!(nearest, vec![4, 5, 3]);
+    /// ```
+    pub fn k_nearest<C: Into<LabValue>>(&self, color: C, k: usize, method: DEMethod) -> Vec<(usize, DeltaE)> {
+        let query = color.into();
+        let mut search = Search {
+            colors: &self.colors,
+            query,
+            method,
+            k,
+            bound: self.bound(&query, method),
+            best: Vec::with_capacity(k + 1),
+        };
+
+        if k > 0 {
+            search.visit(&self.tree, 0);
+        }
+
+        search.best
+            .into_iter()
+            .map(|(index, _)| (index, query.delta(self.colors[index], method)))
+            .collect()
+    }
+
+    // The largest ratio of DE1976 to the given method between the query and any color in the
+    // palette
+    fn bound(&self, query: &LabValue, method: DEMethod) -> f32 {
+        let bound = match method {
+            DEMethod::DE1976 => 1.0,
+            DEMethod::DE1994G => (1.0 + 0.045 * chroma(query)).max(1.0),
+            DEMethod::DE1994T => (1.0 + 0.048 * chroma(query)).max(2.0),
+            DEMethod::DECMC(l, c) => {
+                let (s_l, s_c, s_h) = delta::cmc_weights(query);
+                (l * s_l).max(c * s_c).max(s_h)
+            }
+            DEMethod::DE2000 => {
+                // S_L is largest at the mean lightness furthest from 50
+                let s_l = |l_bar: f32| {
+                    1.0 + 0.015 * (l_bar - 50.0).powi(2) / (20.0 + (l_bar - 50.0).powi(2)).sqrt()
+                };
+                let s_l = s_l((query.l + self.min_l) / 2.0).max(s_l((query.l + self.max_l) / 2.0));
+
+                // a' is at most 1.5 times a, and S_C is at least S_H since T is less than 1.93
+                let c_bar_prime = 1.5 * (chroma(query) + self.max_chroma) / 2.0;
+                let s_c = 1.0 + 0.045 * c_bar_prime;
+
+                // R_T is at most 2 * sin(60°) in magnitude
+                s_l.max(s_c * (1.0 + 3_f32.sqrt()))
+            }
+        };
+
+        // Allow for rounding in the DeltaE calculations
+        bound * 1.001
+    }
+}
+
+impl<C: Into<LabValue>> FromIterator<C> for Palette {
+    fn from_iter<I: IntoIterator<Item = C>>(iter: I) -> Palette {
+        Palette::new(iter)
+    }
+}
+
+impl Index<usize> for Palette {
+    type Output = LabValue;
+
+    fn index(&self, index: usize) -> &LabValue {
+        &self.colors[index]
+    }
+}
+
+struct Search<'a> {
+    colors: &'a [LabValue],
+    query: LabValue,
+    method: DEMethod,
+    k: usize,
+    bound: f32,
+    best: Vec<(usize, f32)>,
+}
+
+impl Search<'_> {
+    fn visit(&mut self, tree: &[usize], depth: usize) {
+        if tree.is_empty() {
+            return;
+        }
+
+        let mid = tree.len() / 2;
+        let index = tree[mid];
+        self.insert(index);
+
+        let axis = depth % 3;
+        let diff = component(&self.query, axis) - component(&self.colors[index], axis);
+        let (near, far) = if diff < 0.0 {
+            (&tree[..mid], &tree[mid + 1..])
+        } else {
+            (&tree[mid + 1..], &tree[..mid])
+        };
+
+        self.visit(near, depth + 1);
+        let worst = self.worst();
+        if worst.is_nan() || diff.abs() <= self.bound * worst {
+            self.visit(far, depth + 1);
+        }
+    }
+
+    fn insert(&mut self, index: usize) {
+        let value = *self.query.delta(self.colors[index], self.method).value();
+        let entry = (index, value);
+        let position = self.best
+            .binary_search_by(|&(i, v)| v.total_cmp(&value).then(i.cmp(&index)))
+            .unwrap_or_else(|position| position);
+
+        if position < self.k {
+            self.best.insert(position, entry);
+            self.best.truncate(self.k);
+        }
+    }
+
+    fn worst(&self) -> f32 {
+        match self.best.last() {
+            Some(&(_, value)) if self.best.len() == self.k => value,
+            _ => f32::INFINITY,
+        }
+    }
+}
+
+// Sort the indices into an implicit k-d tree, where the median along the axis of each depth is
+// the root of its subtree
+fn build(colors: &[LabValue], tree: &mut [usize], depth: usize) {
+    if tree.len() <= 1 {
+        return;
+    }
+
+    let axis = depth % 3;
+    let mid = tree.len() / 2;
+    tree.select_nth_unstable_by(mid, |&i, &j| {
+        component(&colors[i], axis).total_cmp(&component(&colors[j], axis))
+    });
+
+    let (left, right) = tree.split_at_mut(mid);
+    build(colors, left, depth + 1);
+    build(colors, &mut right[1..], depth + 1);
+}
+
+fn component(lab: &LabValue, axis: usize) -> f32 {
+    match axis {
+        0 => lab.l,
+        1 => lab.a,
+        _ => lab.b,
+    }
+}
+
+fn chroma(lab: &LabValue) -> f32 {
+    (lab.a.powi(2) + lab.b.powi(2)).sqrt()
+}
@@ -0,0 +1,468 @@
+use super::*;
+use std::str::FromStr;
+
+// FromStr ////////////////////////////////////////////////////////////////////
+impl FromStr for DEMethod {
+    type Err = ParseMethodError;
+    fn from_str(token: &str) -> Result<DEMethod, Self::Err> {
+        let s = token.to_lowercase();
+        let s = s.trim();
+
+        // DECMC with explicit ratios: "cmc(2:1)", "decmc(1.5:1)"
+        if let Some(ratio) = s
+            .strip_prefix("decmc(")
+            .or_else(|| s.strip_prefix("cmc("))
+            .and_then(|r| r.strip_suffix(')'))
+        {
+            return parse_cmc_ratio(ratio).ok_or_else(|| ParseMethodError::new(token));
+        }
+
+        DEMethod::NAMES.iter()
+            .find(|(_, _, aliases)| aliases.contains(&s))
+            .map(|&(method, _, _)| method)
+            .ok_or_else(|| ParseMethodError::new(token))
+    }
+}
+
+// The error of a string that is not one of the accepted names
+#[cfg(feature = "illuminants")]
+fn unknown_name(s: &str) -> Error {
+    let position = s.len() - s.trim_start().len();
+    ParseError::new(ParseErrorKind::UnknownName, None, s.trim(), position).into()
+}
+
+#[cfg(feature = "illuminants")]
+impl FromStr for Illuminant {
+    type Err = Error;
+    fn from_str(s: &str) -> Result<Illuminant, Self::Err> {
+        match s.to_lowercase().trim() {
+            "a"    => Ok(Illuminant::A),
+            "b"    => Ok(Illuminant::B),
+            "c"    => Ok(Illuminant::C),
+            "d50"  => Ok(Illuminant::D50),
+            "d55"  => Ok(Illuminant::D55),
+            "d65"  => Ok(Illuminant::D65),
+            "d75"  => Ok(Illuminant::D75),
+            "e"    => Ok(Illuminant::E),
+            "f2"   => Ok(Illuminant::F2),
+            "f7"   => Ok(Illuminant::F7),
+            "f11"  => Ok(Illuminant::F11),
+            "dci"  => Ok(Illuminant::Dci),
+            "aces" => Ok(Illuminant::Aces),
+            _ => Err(unknown_name(s)),
+        }
+    }
+}
+
+#[cfg(feature = "illuminants")]
+impl FromStr for Observer {
+    type Err = Error;
+    fn from_str(s: &str) -> Result<Observer, Self::Err> {
+        match s.to_lowercase().trim() {
+            "2"  | "2°"  | "2deg"  | "1931" | "cie1931" | "cie 1931" => Ok(Observer::Cie1931),
+            "10" | "10°" | "10deg" | "1964" | "cie1964" | "cie 1964" => Ok(Observer::Cie1964),
+            _ => Err(unknown_name(s)),
+        }
+    }
+}
+
+#[cfg(feature = "illuminants")]
+impl FromStr for ChromaticAdaptationMethod {
+    type Err = Error;
+    fn from_str(s: &str) -> Result<ChromaticAdaptationMethod, Self::Err> {
+        let name = s.to_lowercase();
+        let name = name.trim();
+
+        // CMCCAT2000 with an explicit degree of adaptation, as it is displayed: "CmcCat2000(D:0.50)"
+        if let Some(d) = name
+            .strip_prefix("cmccat2000(d:")
+            .or_else(|| name.strip_prefix("cmccat00(d:"))
+            .and_then(|d| d.strip_suffix(')'))
+        {
+            let d: f32 = d.trim().parse().map_err(|_| unknown_name(s))?;
+            if !(0.0..=1.0).contains(&d) {
+                return Err(Error::OutOfBounds);
+            }
+            return Ok(ChromaticAdaptationMethod::CmcCat2000 { d });
+        }
+
+        match name {
+            "bradford"                                       => Ok(ChromaticAdaptationMethod::Bradford),
+            "vonkries"   | "von kries"   | "von-kries"       => Ok(ChromaticAdaptationMethod::VonKries),
+            "xyzscaling" | "xyz scaling" | "xyz-scaling" |
+            "xyz"        | "scaling"                         => Ok(ChromaticAdaptationMethod::XyzScaling),
+            "cat02"      | "ciecat02"                        => Ok(ChromaticAdaptationMethod::Cat02),
+            "cat16"                                          => Ok(ChromaticAdaptationMethod::Cat16),
+            "sharp"                                          => Ok(ChromaticAdaptationMethod::Sharp),
+            "fairchild"                                      => Ok(ChromaticAdaptationMethod::Fairchild),
+            "cmccat2000" | "cmccat00"                        => Ok(ChromaticAdaptationMethod::CmcCat2000 { d: 1.0 }),
+            _ => Err(unknown_name(s)),
+        }
+    }
+}
+
+#[cfg(feature = "rgb")]
+impl FromStr for RgbSystem {
+    type Err = Error;
+    fn from_str(s: &str) -> Result<RgbSystem, Self::Err> {
+        let name: String = s
+            .trim()
+            .to_lowercase()
+            .chars()
+            .filter(|c| !matches!(c, ' ' | '-' | '_'))
+            .collect();
+
+        match name.as_str() {
+            "aces2065"   | "aces20651"  | "ap0"                                 => Ok(RgbSystem::Aces2065),
+            "acescg"     | "ap1"                                                => Ok(RgbSystem::AcesCg),
+            "adobe"      | "adobe1998"  | "adobergb" | "adobergb1998" | "a98rgb" => Ok(RgbSystem::Adobe),
+            "apple"      | "applergb"                                           => Ok(RgbSystem::Apple),
+            "best"       | "bestrgb"                                            => Ok(RgbSystem::Best),
+            "beta"       | "betargb"                                            => Ok(RgbSystem::Beta),
+            "bruce"      | "brucergb"                                           => Ok(RgbSystem::Bruce),
+            "cie"        | "ciergb"                                             => Ok(RgbSystem::Cie),
+            "colormatch" | "colormatchrgb"                                      => Ok(RgbSystem::ColorMatch),
+            "dcip3"      | "p3dci"      | "dcip3rgb"                            => Ok(RgbSystem::DciP3),
+            "displayp3"  | "p3"                                                 => Ok(RgbSystem::DisplayP3),
+            "don4"       | "donrgb4"                                            => Ok(RgbSystem::Don4),
+            "eci"        | "ecirgb"     | "ecirgbv2"                            => Ok(RgbSystem::Eci),
+            "ntsc"       | "ntscrgb"                                            => Ok(RgbSystem::Ntsc),
+            "pal"        | "secam"      | "palsecam" | "palsecamrgb"            => Ok(RgbSystem::PalSecam),
+            "prophoto"   | "prophotorgb"| "romm"     | "rommrgb"                => Ok(RgbSystem::ProPhoto),
+            "rec709"     | "bt709"      | "rec.709"  | "bt.709"                  => Ok(RgbSystem::Rec709),
+            "rec2020"    | "bt2020"     | "rec.2020" | "bt.2020"                 => Ok(RgbSystem::Rec2020),
+            "smptec"     | "smptecrgb"                                          => Ok(RgbSystem::SmpteC),
+            "srgb"                                                              => Ok(RgbSystem::Srgb),
+            "widegamut"  | "widegamutrgb"                                       => Ok(RgbSystem::WideGamut),
+            _ => Err(unknown_name(s)),
+        }
+    }
+}
+
+impl FromStr for Color {
+    type Err = ValueError;
+    fn from_str(s: &str) -> ValueResult<Color> {
+        let start = s.len() - s.trim_start().len();
+        let (tag, values) = s.trim_start().split_once(':').unwrap_or((s.trim_start(), ""));
+        let offset = start + tag.len() + 1;
+
+        // Errors in the values are positioned relative to the whole string
+        let at_offset = |e: ValueError| match e {
+            ValueError::Parse(e) => ValueError::Parse(e.offset(offset)),
+            e => e,
+        };
+
+        match tag.trim().to_lowercase().as_str() {
+            "lab" => LabValue::from_str(values).map(Color::Lab).map_err(at_offset),
+            "lch" => LchValue::from_str(values).map(Color::Lch).map_err(at_offset),
+            "xyz" => XyzValue::from_str(values).map(Color::Xyz).map_err(at_offset),
+            #[cfg(feature = "rgb")]
+            "rgb" => RgbValue::from_str(values).map(Color::Rgb).map_err(at_offset),
+            _ => Err(ParseError::new(ParseErrorKind::UnknownLabel, None, tag.trim(), start).into()),
+        }
+    }
+}
+
+impl FromStr for LabValue {
+    type Err = ValueError;
+    fn from_str(s: &str) -> ValueResult<LabValue> {
+        let [l, a, b] = parse_components(s, ["L", "a", "b"])?;
+
+        Ok(LabValue {
+            l: l.within(RANGE_PCT)?,
+            a: a.within(RANGE_I8)?,
+            b: b.within(RANGE_I8)?,
+        })
+    }
+}
+
+impl FromStr for LchValue {
+    type Err = ValueError;
+    fn from_str(s: &str) -> ValueResult<LchValue> {
+        let [l, c, h] = parse_components(s, ["L", "c", "h"])?;
+
+        Ok(LchValue {
+            l: l.within(RANGE_PCT)?,
+            c: c.within(RANGE_CHROMA)?,
+            h: h.within(RANGE_360)?,
+        })
+    }
+}
+
+impl FromStr for XyzValue {
+    type Err = ValueError;
+    fn from_str(s: &str) -> ValueResult<XyzValue> {
+        let [x, y, z] = parse_components(s, ["X", "Y", "Z"])?;
+
+        Ok(XyzValue {
+            x: x.within(RANGE_01)?,
+            y: y.within(RANGE_01)?,
+            z: z.within(RANGE_01)?,
+        })
+    }
+
+}
+
+#[cfg(feature = "rgb")]
+impl FromStr for RgbValue {
+    type Err = ValueError;
+    fn from_str(s: &str) -> ValueResult<RgbValue> {
+        if !s.trim().contains(is_delimiter) {
+            #[cfg(feature = "named-colors")]
+            if let Some(rgb) = RgbValue::from_name(s) {
+                return Ok(rgb);
+            }
+
+            return parse_hex_rgb(s);
+        }
+
+        let [r, g, b] = parse_components(s, ["R", "G", "B"])?;
+        let channel = |c: Component| -> ValueResult<u8> {
+            if c.value.fract() != 0.0 {
+                Err(c.error(ParseErrorKind::Malformed).into())
+            } else {
+                c.within(0.0..=255.0).map(|v| v as u8)
+            }
+        };
+
+        Ok(RgbValue {
+            r: channel(r)?,
+            g: channel(g)?,
+            b: channel(b)?,
+        })
+    }
+}
+
+#[cfg(feature = "rgb")]
+impl FromStr for RgbNominalValue {
+    type Err = ValueError;
+    fn from_str(s: &str) -> ValueResult<RgbNominalValue> {
+        let [r, g, b] = parse_components(s, ["R", "G", "B"])?;
+
+        Ok(RgbNominalValue {
+            r: r.within(RANGE_01)?,
+            g: g.within(RANGE_01)?,
+            b: b.within(RANGE_01)?,
+        })
+    }
+}
+
+// Helper Functions ////////////////////////////////////////////////////////////
+// Parse the lightness and chroma ratio of DECMC ("2:1", "1.5 : 1")
+fn parse_cmc_ratio(ratio: &str) -> Option<DEMethod> {
+    let mut split = ratio.split(':').map(|v| v.trim().parse::<f32>().ok());
+    match (split.next()??, split.next()??, split.next()) {
+        (l, c, None) if l.is_finite() && c.is_finite() && l > 0.0 && c > 0.0 => {
+            Some(DEMethod::DECMC(l, c))
+        }
+        _ => None,
+    }
+}
+
+// A number parsed from a string, and where it came from
+#[derive(Clone, Copy)]
+struct Component<'a> {
+    label: &'static str,
+    token: &'a str,
+    position: usize,
+    value: f32,
+}
+
+impl<'a> Component<'a> {
+    fn error(&self, kind: ParseErrorKind) -> ParseError {
+        ParseError::new(kind, Some(self.label), self.token, self.position)
+    }
+
+    fn within(&self, range: std::ops::RangeInclusive<f32>) -> ValueResult<f32> {
+        if !self.value.is_finite() {
+            Err(self.error(ParseErrorKind::Malformed).into())
+        } else if range.contains(&self.value) {
+            Ok(self.value)
+        } else {
+            Err(self.error(ParseErrorKind::OutOfRange).into())
+        }
+    }
+}
+
+// Parse the three components of a color, either as a bare list of values or labeled in any order
+// ("L:50 a:2.3 b:-4", "L*=50, a*=2.3, b*=-4"). Labels are case insensitive and may end in `*`
+// or `°`. The components are returned in the order of `labels`.
+//
+// The components may be wrapped in square brackets, as in the `Display` output of the color types
+// ("[L:89.73, a:1.88, b:-6.96]").
+fn parse_components<'a>(s: &'a str, labels: [&'static str; 3]) -> ValueResult<[Component<'a>; 3]> {
+    let trimmed = s.trim();
+    let (inner, offset) = match trimmed.strip_prefix('[').and_then(|t| t.strip_suffix(']')) {
+        Some(inner) => (inner, s.len() - s.trim_start().len() + 1),
+        None => (s, 0),
+    };
+
+    let components = if inner.contains(&[':', '='][..]) {
+        parse_labeled(inner, labels)
+    } else {
+        parse_list(inner, labels)
+    };
+
+    match components {
+        Ok(mut components) => {
+            components.iter_mut().for_each(|c| c.position += offset);
+            Ok(components)
+        }
+        Err(e) => Err(e.offset(offset).into()),
+    }
+}
+
+fn parse_labeled<'a>(s: &'a str, labels: [&'static str; 3]) -> Result<[Component<'a>; 3], ParseError> {
+    // Each separator is preceded by a label: (start of label, label, separator)
+    let mut fields = Vec::new();
+    for (i, separator) in s.match_indices(&[':', '='][..]) {
+        let before = s[..i].trim_end();
+        let start = before
+            .char_indices()
+            .rev()
+            .take_while(|(_, c)| c.is_alphabetic() || *c == '*' || *c == '°')
+            .last()
+            .map(|(j, _)| j)
+            .ok_or_else(|| ParseError::new(ParseErrorKind::UnknownLabel, None, separator, i))?;
+        fields.push((start, &before[start..], i));
+    }
+
+    let leading = s[..fields[0].0].trim_start();
+    if !leading.is_empty() {
+        let position = s.len() - s.trim_start().len();
+        return Err(ParseError::new(ParseErrorKind::Unexpected, None, leading.trim_end(), position));
+    }
+
+    let mut components = [None; 3];
+    for (k, &(start, label, separator)) in fields.iter().enumerate() {
+        let index = labels
+            .iter()
+            .position(|l| l.eq_ignore_ascii_case(label.trim_end_matches(&['*', '°'][..])))
+            .ok_or_else(|| ParseError::new(ParseErrorKind::UnknownLabel, None, label, start))?;
+        if components[index].is_some() {
+            let kind = ParseErrorKind::DuplicateLabel;
+            return Err(ParseError::new(kind, Some(labels[index]), label, start));
+        }
+
+        let end = fields.get(k + 1).map_or(s.len(), |next| next.0);
+        let value = &s[separator + 1..end];
+        let token = value.trim_start().trim_end_matches(is_delimiter);
+        let position = end - value.trim_start().len();
+
+        let component = Component { label: labels[index], token, position, value: f32::NAN };
+        if token.is_empty() {
+            return Err(component.error(ParseErrorKind::Missing));
+        }
+
+        let value = token.replace(',', ".").parse::<f32>();
+        components[index] = Some(Component {
+            value: value.map_err(|_| component.error(ParseErrorKind::Malformed))?,
+            ..component
+        });
+    }
+
+    let missing = |i: usize| ParseError::new(ParseErrorKind::Missing, Some(labels[i]), "", s.len());
+    Ok([
+        components[0].ok_or_else(|| missing(0))?,
+        components[1].ok_or_else(|| missing(1))?,
+        components[2].ok_or_else(|| missing(2))?,
+    ])
+}
+
+// Split string by commas, semicolons, tabs, or runs of whitespace
+// ("92.5,33.5,-18.8", "92.5; 33.5; -18.8", "92.5 33.5 -18.8").
+//
+// Decimal commas are detected when there are no decimal points and the values are separated by
+// semicolons or tabs ("50,25;12,40;-3,10"), or when the string only makes sense with decimal
+// commas ("50,25 12,40 -3,10").
+fn parse_list<'a>(s: &'a str, labels: [&'static str; 3]) -> Result<[Component<'a>; 3], ParseError> {
+    if s.contains(',') && !s.contains('.') && s.contains(&[';', '\t'][..]) {
+        return split_list(s, &s.replace(',', "."), labels);
+    }
+
+    split_list(s, s, labels).or_else(|e| {
+        if s.contains(',') && !s.contains('.') {
+            split_list(s, &s.replace(',', "."), labels).map_err(|_| e)
+        } else {
+            Err(e)
+        }
+    })
+}
+
+// Split `normalized` into components, reporting tokens from the original string `s`.
+// Replacing decimal commas does not change the length of the string, so the positions agree.
+fn split_list<'a>(
+    s: &'a str,
+    normalized: &str,
+    labels: [&'static str; 3],
+) -> Result<[Component<'a>; 3], ParseError> {
+    let mut tokens = normalized
+        .split(is_delimiter)
+        .map(|item| (item.as_ptr() as usize - normalized.as_ptr() as usize, item))
+        .filter(|(_, item)| !item.is_empty());
+
+    let mut component = |label: &'static str| -> Result<Component<'a>, ParseError> {
+        let (position, item) = tokens
+            .next()
+            .ok_or_else(|| ParseError::new(ParseErrorKind::Missing, Some(label), "", s.len()))?;
+        let token = &s[position..position + item.len()];
+        let component = Component { label, token, position, value: f32::NAN };
+
+        match item.parse() {
+            Ok(value) => Ok(Component { value, ..component }),
+            Err(_) => Err(component.error(ParseErrorKind::Malformed)),
+        }
+    };
+
+    let components = [component(labels[0])?, component(labels[1])?, component(labels[2])?];
+
+    if let Some((position, item)) = tokens.next() {
+        let token = &s[position..position + item.len()];
+        return Err(ParseError::new(ParseErrorKind::Unexpected, None, token, position));
+    }
+
+    Ok(components)
+}
+
+#[inline]
+fn is_delimiter(c: char) -> bool {
+    c == ',' || c == ';' || c.is_whitespace()
+}
+
+// Parse a hex string to an `RgbValue`.
+// Accepts `#RRGGBB`, `RRGGBB`, `#RGB`, and `RGB` in either case.
+#[cfg(feature = "rgb")]
+fn parse_hex_rgb(s: &str) -> ValueResult<RgbValue> {
+    let token = s.trim();
+    let position = s.len() - s.trim_start().len();
+
+    if token.is_empty() {
+        return Err(ParseError::new(ParseErrorKind::Missing, None, token, position).into());
+    }
+
+    let malformed = || ParseError::new(ParseErrorKind::Malformed, None, token, position);
+    let hex = token.strip_prefix('#').unwrap_or(token);
+
+    if !hex.chars().all(|c| c.is_ascii_hexdigit()) {
+        return Err(malformed().into());
+    }
+
+    let channel = |i: usize, len: usize| -> ValueResult<u8> {
+        let value = u8::from_str_radix(&hex[i * len..(i + 1) * len], 16)
+            .map_err(|_| malformed())?;
+        // Shorthand digits are repeated: #F80 == #FF8800
+        Ok(if len == 1 { value * 0x11 } else { value })
+    };
+
+    let len = match hex.len() {
+        6 => 2,
+        3 => 1,
+        _ => return Err(malformed().into()),
+    };
+
+    Ok(RgbValue {
+        r: channel(0, len)?,
+        g: channel(1, len)?,
+        b: channel(2, len)?,
+    })
+}
@@ -0,0 +1,317 @@
+//! Python bindings, built with [PyO3](https://pyo3.rs). Requires the `python` feature.
+//!
+//! The module is named `deltae`, and has a class for each of [`LabValue`], [`LchValue`],
+//! [`XyzValue`], and [`RgbValue`]. Colors are validated when they are created, convert into each
+//! other, and compare by any [`DEMethod`], named as it parses. Invalid values and unknown methods
+//! raise a `ValueError` with the message of the [`Error`](crate::Error).
+//!
+//! ```python
+//! from deltae import LabValue, RgbValue, delta_e
+//!
+//! lab = RgbValue.from_hex("#663399").to_lab()
+//! de = lab.delta(LabValue(50.0, 40.0, -50.0), "de2000")
+//! l, c, h = lab.to_lch().components()
+//! assert delta_e(lab, lab.to_xyz(), "cmc(2:1)") < 1e-3
+//! ```
+
+use std::str::FromStr;
+
+use pyo3::exceptions::PyValueError;
+use pyo3::prelude::*;
+
+use crate::*;
+
+impl From<Error> for PyErr {
+    fn from(err: Error) -> PyErr {
+        PyValueError::new_err(err.to_string())
+    }
+}
+
+/// A color of any of the classes, as the argument of a comparison
+#[derive(FromPyObject)]
+enum AnyColor {
+    Lab(PyLabValue),
+    Lch(PyLchValue),
+    Xyz(PyXyzValue),
+    Rgb(PyRgbValue),
+}
+
+impl From<AnyColor> for LabValue {
+    fn from(color: AnyColor) -> LabValue {
+        match color {
+            AnyColor::Lab(lab) => lab.0,
+            AnyColor::Lch(lch) => lch.0.into(),
+            AnyColor::Xyz(xyz) => xyz.0.into(),
+            AnyColor::Rgb(rgb) => rgb.0.into(),
+        }
+    }
+}
+
+/// The DeltaE between a reference and a sample color of any class by a named method, such as
+/// `"de2000"`, `"de1976"`, or `"cmc(2:1)"`
+#[pyfunction]
+#[pyo3(signature = (reference, sample, method = "de2000"))]
+fn delta_e(reference: AnyColor, sample: AnyColor, method: &str) -> PyResult<f32> {
+    let method = DEMethod::from_str(method).map_err(Error::from)?;
+    Ok(*LabValue::from(reference).delta(LabValue::from(sample), method).value())
+}
+
+/// The names of the DeltaE methods, as they are displayed
+#[pyfunction]
+fn methods() -> Vec<&'static str> {
+    DEMethod::NAMES.iter().map(|&(_, name, _)| name).collect()
+}
+
+/// A CIELAB color, as a [`LabValue`]
+#[pyclass(name = "LabValue", module = "deltae", frozen, eq, from_py_object)]
+#[derive(Clone, PartialEq)]
+struct PyLabValue(LabValue);
+
+#[pymethods]
+impl PyLabValue {
+    #[new]
+    fn new(l: f32, a: f32, b: f32) -> PyResult<Self> {
+        Ok(PyLabValue(LabValue::new(l, a, b)?))
+    }
+
+    #[getter]
+    fn l(&self) -> f32 {
+        self.0.l
+    }
+
+    #[getter]
+    fn a(&self) -> f32 {
+        self.0.a
+    }
+
+    #[getter]
+    fn b(&self) -> f32 {
+        self.0.b
+    }
+
+    /// The components as a tuple of `(l, a, b)`
+    fn components(&self) -> (f32, f32, f32) {
+        (self.0.l, self.0.a, self.0.b)
+    }
+
+    fn to_lch(&self) -> PyLchValue {
+        PyLchValue(self.0.into())
+    }
+
+    fn to_xyz(&self) -> PyXyzValue {
+        PyXyzValue(self.0.into())
+    }
+
+    fn to_rgb(&self) -> PyRgbValue {
+        PyRgbValue(self.0.into())
+    }
+
+    /// The DeltaE to a sample color of any class, with this color as the reference
+    #[pyo3(signature = (sample, method = "de2000"))]
+    fn delta(&self, sample: AnyColor, method: &str) -> PyResult<f32> {
+        delta_e(AnyColor::Lab(self.clone()), sample, method)
+    }
+
+    fn __repr__(&self) -> String {
+        format!("LabValue({}, {}, {})", self.0.l, self.0.a, self.0.b)
+    }
+
+    fn __str__(&self) -> String {
+        self.0.to_string()
+    }
+}
+
+/// A CIE LCh color, as an [`LchValue`]
+#[pyclass(name = "LchValue", module = "deltae", frozen, eq, from_py_object)]
+#[derive(Clone, PartialEq)]
+struct PyLchValue(LchValue);
+
+#[pymethods]
+impl PyLchValue {
+    #[new]
+    fn new(l: f32, c: f32, h: f32) -> PyResult<Self> {
+        Ok(PyLchValue(LchValue::new(l, c, h)?))
+    }
+
+    #[getter]
+    fn l(&self) -> f32 {
+        self.0.l
+    }
+
+    #[getter]
+    fn c(&self) -> f32 {
+        self.0.c
+    }
+
+    #[getter]
+    fn h(&self) -> f32 {
+        self.0.h
+    }
+
+    /// The components as a tuple of `(l, c, h)`
+    fn components(&self) -> (f32, f32, f32) {
+        (self.0.l, self.0.c, self.0.h)
+    }
+
+    fn to_lab(&self) -> PyLabValue {
+        PyLabValue(self.0.into())
+    }
+
+    fn to_xyz(&self) -> PyXyzValue {
+        PyXyzValue(self.0.into())
+    }
+
+    fn to_rgb(&self) -> PyRgbValue {
+        PyRgbValue(self.0.into())
+    }
+
+    /// The DeltaE to a sample color of any class, with this color as the reference
+    #[pyo3(signature = (sample, method = "de2000"))]
+    fn delta(&self, sample: AnyColor, method: &str) -> PyResult<f32> {
+        delta_e(AnyColor::Lch(self.clone()), sample, method)
+    }
+
+    fn __repr__(&self) -> String {
+        format!("LchValue({}, {}, {})", self.0.l, self.0.c, self.0.h)
+    }
+
+    fn __str__(&self) -> String {
+        self.0.to_string()
+    }
+}
+
+/// A CIE 1931 XYZ color relative to D50, as an [`XyzValue`]
+#[pyclass(name = "XyzValue", module = "deltae", frozen, eq, from_py_object)]
+#[derive(Clone, PartialEq)]
+struct PyXyzValue(XyzValue);
+
+#[pymethods]
+impl PyXyzValue {
+    #[new]
+    fn new(x: f32, y: f32, z: f32) -> PyResult<Self> {
+        Ok(PyXyzValue(XyzValue::new(x, y, z)?))
+    }
+
+    #[getter]
+    fn x(&self) -> f32 {
+        self.0.x
+    }
+
+    #[getter]
+    fn y(&self) -> f32 {
+        self.0.y
+    }
+
+    #[getter]
+    fn z(&self) -> f32 {
+        self.0.z
+    }
+
+    /// The components as a tuple of `(x, y, z)`
+    fn components(&self) -> (f32, f32, f32) {
+        (self.0.x, self.0.y, self.0.z)
+    }
+
+    fn to_lab(&self) -> PyLabValue {
+        PyLabValue(self.0.into())
+    }
+
+    fn to_lch(&self) -> PyLchValue {
+        PyLchValue(self.0.into())
+    }
+
+    fn to_rgb(&self) -> PyRgbValue {
+        PyRgbValue(self.0.into())
+    }
+
+    /// The DeltaE to a sample color of any class, with this color as the reference
+    #[pyo3(signature = (sample, method = "de2000"))]
+    fn delta(&self, sample: AnyColor, method: &str) -> PyResult<f32> {
+        delta_e(AnyColor::Xyz(self.clone()), sample, method)
+    }
+
+    fn __repr__(&self) -> String {
+        format!("XyzValue({}, {}, {})", self.0.x, self.0.y, self.0.z)
+    }
+
+    fn __str__(&self) -> String {
+        self.0.to_string()
+    }
+}
+
+/// An 8-bit sRGB color, as an [`RgbValue`]
+#[pyclass(name = "RgbValue", module = "deltae", frozen, eq, hash, from_py_object)]
+#[derive(Clone, PartialEq, Eq, Hash)]
+struct PyRgbValue(RgbValue);
+
+#[pymethods]
+impl PyRgbValue {
+    #[new]
+    fn new(r: u8, g: u8, b: u8) -> Self {
+        PyRgbValue(RgbValue::new(r, g, b))
+    }
+
+    /// Parse a color like an [`RgbValue`], such as `"#663399"`, `"#639"`, or `"102, 51, 153"`
+    #[staticmethod]
+    fn from_hex(rgb: &str) -> PyResult<Self> {
+        Ok(PyRgbValue(RgbValue::from_str(rgb)?))
+    }
+
+    #[getter]
+    fn r(&self) -> u8 {
+        self.0.r
+    }
+
+    #[getter]
+    fn g(&self) -> u8 {
+        self.0.g
+    }
+
+    #[getter]
+    fn b(&self) -> u8 {
+        self.0.b
+    }
+
+    /// The components as a tuple of `(r, g, b)`
+    fn components(&self) -> (u8, u8, u8) {
+        (self.0.r, self.0.g, self.0.b)
+    }
+
+    /// The color formatted as `#rrggbb`
+    fn to_hex(&self) -> String {
+        format!("{:x}", self.0)
+    }
+
+    fn to_lab(&self) -> PyLabValue {
+        PyLabValue(self.0.into())
+    }
+
+    fn to_lch(&self) -> PyLchValue {
+        PyLchValue(self.0.into())
+    }
+
+    fn to_xyz(&self) -> PyXyzValue {
+        PyXyzValue(self.0.into())
+    }
+
+    /// The DeltaE to a sample color of any class, with this color as the reference
+    #[pyo3(signature = (sample, method = "de2000"))]
+    fn delta(&self, sample: AnyColor, method: &str) -> PyResult<f32> {
+        delta_e(AnyColor::Rgb(self.clone()), sample, method)
+    }
+
+    fn __repr__(&self) -> String {
+        format!("RgbValue({}, {}, {})", self.0.r, self.0.g, self.0.b)
+    }
+
+    fn __str__(&self) -> String {
+        self.0.to_string()
+    }
+}
+
+/// Calculate Delta E (color difference) between two colors in CIE Lab space
+#[pymodule(name = "deltae")]
+pub mod module {
+    #[pymodule_export]
+    use super::{delta_e, methods, PyLabValue, PyLchValue, PyRgbValue, PyXyzValue};
+}
@@ -0,0 +1,159 @@
+//! Machine-readable reports of color comparisons. With the `serde` feature, [`DeltaReport`] and
+//! [`DeltaSummary`] serialize to a stable schema for QC dashboards and other tooling.
+//!
+//! # Examples
+//!
+//! ```
+//! use deltae::*;
+//!
+//! let reference = LabValue::new(95.08, -0.17, -10.81).unwrap();
+//! let samples = [
+//!     LabValue::new(89.73, 1.88, -6.96).unwrap(),
+//!     LabValue::new(95.0, -0.2, -10.5).unwrap(),
+//! ];
+//!
+//! let reports: Vec<DeltaReport> = samples
+//!     .iter()
+//!     .map(|sample| DeltaReport::new(reference.delta(*sample, DE2000), 2.0))
+//!     .collect();
+//!
+//! assert!(!reports[0].pass);
+//! assert!(reports[1].pass);
+//!
+//! let summary = DeltaSummary::new(&reports);
+//! assert_eq!((summary.passed, summary.failed), (1, 1));
+//! ```
+
+use crate::*;
+
+/// # Delta Report
+///
+/// The result of comparing a sample color to a reference color: the [`DeltaE`], the difference in
+/// each component, and whether the sample is within a [`Tolerance`]. Component differences are
+/// the sample minus the reference.
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct DeltaReport {
+    /// The reference color
+    pub reference: LabValue,
+    /// The sample color
+    pub sample: LabValue,
+    /// The method used to calculate [`DeltaReport::delta_e`]
+    pub method: DEMethod,
+    /// The color difference
+    pub delta_e: f32,
+    /// Difference in lightness (ΔL\*)
+    pub delta_l: f32,
+    /// Difference in red/green (Δa\*)
+    pub delta_a: f32,
+    /// Difference in blue/yellow (Δb\*)
+    pub delta_b: f32,
+    /// Difference in chroma (ΔC\*)
+    pub delta_c: f32,
+    /// Metric difference in hue (ΔH\*), positive when the hue angle of the sample is
+    /// counter-clockwise of the reference
+    pub delta_h: f32,
+    /// The limit of [`DeltaReport::delta_e`] the sample was tested against, or `None` if the
+    /// [`Tolerance`] does not limit it, such as a [`ComponentTolerance`] on its own
+    pub tolerance: Option<f32>,
+    /// Whether the sample is within the [`Tolerance`]. For a plain tolerance value, whether
+    /// [`DeltaReport::delta_e`] is less than or equal to [`DeltaReport::tolerance`].
+    pub pass: bool,
+}
+
+impl DeltaReport {
+    /// New [`DeltaReport`] from a [`DeltaE`] and a [`Tolerance`]
+    /// ```
+    /// use deltae::*;
+    ///
+    /// let lab0 = LabValue::new(50.0, 10.0, 0.0).unwrap();
+    /// let lab1 = LabValue::new(52.0, 0.0, 10.0).unwrap();
+    /// let report = DeltaReport::new(lab0.delta(lab1, DE1976), 5.0);
+    ///
+    /// assert_eq!(report.delta_l, 2.0);
+    /// assert_eq!(report.delta_c, 0.0);
+    /// assert!((report.delta_h - 14.1421).abs() < 1e-4);
+    /// assert!(!report.pass);
+    /// ```
+    pub fn new<T: Tolerance + Copy>(delta: DeltaE, tolerance: T) -> DeltaReport {
+        let (reference, sample) = (*delta.reference(), *delta.sample());
+        let (lch0, lch1) = (LchValue::from(reference), LchValue::from(sample));
+
+        // ΔH = 2√(C₀C₁)·sin(Δh/2), with Δh the shorter arc
+        let delta_hue = hue_diff(lch0.h, lch1.h);
+        let delta_h = 2.0 * (lch0.c * lch1.c).sqrt() * (delta_hue.to_radians() / 2.0).sin();
+
+        let pass = tolerance.contains(&delta);
+        let tolerance = Some(tolerance.tolerance()).filter(|t| t.is_finite());
+
+        DeltaReport {
+            reference,
+            sample,
+            method: *delta.method(),
+            delta_e: *delta.value(),
+            delta_l: sample.l - reference.l,
+            delta_a: sample.a - reference.a,
+            delta_b: sample.b - reference.b,
+            delta_c: lch1.c - lch0.c,
+            delta_h,
+            tolerance,
+            pass,
+        }
+    }
+}
+
+impl DeltaE {
+    /// Return a [`DeltaReport`] of the comparison against a [`Tolerance`]
+    pub fn report<T: Tolerance + Copy>(self, tolerance: T) -> DeltaReport {
+        DeltaReport::new(self, tolerance)
+    }
+}
+
+/// # Delta Summary
+///
+/// Aggregate statistics of the [`DeltaReport`]s of a batch. The statistics of an empty batch are
+/// all zero.
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct DeltaSummary {
+    /// The number of reports
+    pub count: usize,
+    /// The number of reports within tolerance
+    pub passed: usize,
+    /// The number of reports out of tolerance
+    pub failed: usize,
+    /// The smallest color difference
+    pub min: f32,
+    /// The largest color difference
+    pub max: f32,
+    /// The mean color difference
+    pub mean: f32,
+    /// The population standard deviation of the color differences
+    pub std_dev: f32,
+}
+
+impl DeltaSummary {
+    /// New [`DeltaSummary`] from a batch of [`DeltaReport`]s
+    pub fn new(reports: &[DeltaReport]) -> DeltaSummary {
+        if reports.is_empty() {
+            return DeltaSummary::default();
+        }
+
+        let count = reports.len();
+        let passed = reports.iter().filter(|r| r.pass).count();
+        let values = || reports.iter().map(|r| r.delta_e);
+
+        let mean = values().sum::<f32>() / count as f32;
+        let variance = values().map(|v| (v - mean).powi(2)).sum::<f32>() / count as f32;
+
+        DeltaSummary {
+            count,
+            passed,
+            failed: count - passed,
+            min: values().fold(f32::INFINITY, f32::min),
+            max: values().fold(f32::NEG_INFINITY, f32::max),
+            mean,
+            std_dev: variance.sqrt(),
+        }
+    }
+}
@@ -0,0 +1,1415 @@
+//! RGB colors and the RGB working spaces used to convert them to and from CIE colors.
+//!
+//! RGB colors are device dependent. An [`RgbSystem`] defines the primaries, white point, and
+//! transfer function used to interpret them. Most RGB systems are relative to a white point other
+//! than [`Illuminant::D50`], so RGB values are chromatically adapted to the D50 reference white of
+//! [`LabValue`] and [`XyzValue`] by default.
+//!
+//! # Examples
+//!
+//! ```
+//! use deltae::*;
+//!
+//! // Conversions from RgbValue assume sRGB and Bradford adaptation to D50
+//! let white = RgbValue::new(255, 255, 255);
+//! assert_eq!(LabValue::from(white).round_to(2), LabValue { l: 100.0, a: 0.0, b: 0.0 });
+//!
+//! // Other RGB systems and adaptation methods
+//! let rgb = RgbNominalValue::from(RgbValue::new(255, 0, 0));
+//! let lab = LabValue::from_rgb(rgb, RgbSystem::Adobe, Some(ChromaticAdaptationMethod::Cat02));
+//!
+//! // Opt out of adaptation: Lab is then relative to the white point of the RGB system
+//! let lab_d65 = LabValue::from_rgb(rgb, RgbSystem::Srgb, None);
+//! ```
+
+use std::fmt;
+use std::sync::Arc;
+
+use crate::*;
+use crate::convert::{EPSILON, KAPPA};
+
+/// # RGB
+///
+/// An RGB color with channels of a [`Channel`] type: [`RgbValue`] with 8 bits per channel,
+/// [`Rgb16Value`] with 16 bits, or [`RgbNominalValue`] with [`f32`]. The values are encoded with
+/// the transfer function of an [`RgbSystem`] (i.e. they are not linear). Colors convert through
+/// their nominal values, so deeper channels keep the precision that 8 bits would lose:
+/// ```
+/// use deltae::*;
+///
+/// let rgb16 = Rgb16Value::new(0x6666, 0x3333, 0x9999);
+/// assert_eq!(rgb16.to_depth::<u8>(), RgbValue::new(0x66, 0x33, 0x99));
+/// assert_eq!(RgbValue::new(0x66, 0x33, 0x99).to_depth::<u16>(), rgb16);
+///
+/// // Dark shades that are the same in 8 bits
+/// let shades = [Rgb16Value::new(50, 50, 50), Rgb16Value::new(100, 100, 100)];
+/// assert_eq!(shades[0].to_depth::<u8>(), shades[1].to_depth::<u8>());
+/// assert_ne!(LabValue::from(shades[0]), LabValue::from(shades[1]));
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Rgb<C> {
+    /// Red
+    pub r: C,
+    /// Green
+    pub g: C,
+    /// Blue
+    pub b: C,
+}
+
+/// # RGB with 8 bits per channel
+///
+/// | `Value` | `Color` | `Range`       |
+/// |:-------:|:-------:|:-------------:|
+/// | `R`     | `Red`   | `0 <---> 255` |
+/// | `G`     | `Green` | `0 <---> 255` |
+/// | `B`     | `Blue`  | `0 <---> 255` |
+///
+/// Strings parse as comma separated values, or as hex in the form `#RRGGBB`, `RRGGBB`, or
+/// shorthand `#RGB`. Values format as hex with `{:x}` or `{:X}`:
+/// ```
+/// # #[cfg(feature = "parse")] {
+/// use deltae::*;
+///
+/// let rgb: RgbValue = "#663399".parse().unwrap();
+/// assert_eq!(rgb, RgbValue::new(102, 51, 153));
+/// assert_eq!("#F80".parse::<RgbValue>().unwrap(), RgbValue::new(255, 136, 0));
+/// assert_eq!("102, 51, 153".parse::<RgbValue>().unwrap(), rgb);
+/// assert_eq!(format!("{:X}", rgb), "#663399");
+/// # }
+/// ```
+pub type RgbValue = Rgb<u8>;
+
+/// # RGB with 16 bits per channel
+///
+/// | `Value` | `Color` | `Range`         |
+/// |:-------:|:-------:|:---------------:|
+/// | `R`     | `Red`   | `0 <---> 65535` |
+/// | `G`     | `Green` | `0 <---> 65535` |
+/// | `B`     | `Blue`  | `0 <---> 65535` |
+///
+/// As in 16 bit TIFF and PNG files. Wide gamut systems such as [`RgbSystem::ProPhoto`] are
+/// usually exported with 16 bits per channel, as 8 bits would band visibly.
+pub type Rgb16Value = Rgb<u16>;
+
+/// The type of the channels of an [`Rgb`] color, which converts to and from nominal values
+/// between `0.0` and `1.0`
+pub trait Channel: Copy {
+    /// The value of full intensity
+    const MAX: Self;
+
+    /// The nominal value of the channel, where [`Channel::MAX`] is `1.0`
+    fn to_nominal(self) -> f32;
+
+    /// The nearest channel value to a nominal value. Integer channels are clamped to their range.
+    fn from_nominal(nominal: f32) -> Self;
+}
+
+impl Channel for u8 {
+    const MAX: u8 = u8::MAX;
+
+    fn to_nominal(self) -> f32 {
+        self as f32 / 255.0
+    }
+
+    fn from_nominal(nominal: f32) -> u8 {
+        (nominal.clamp(0.0, 1.0) * 255.0).round() as u8
+    }
+}
+
+impl Channel for u16 {
+    const MAX: u16 = u16::MAX;
+
+    fn to_nominal(self) -> f32 {
+        self as f32 / 65535.0
+    }
+
+    fn from_nominal(nominal: f32) -> u16 {
+        (nominal.clamp(0.0, 1.0) * 65535.0).round() as u16
+    }
+}
+
+impl Channel for f32 {
+    const MAX: f32 = 1.0;
+
+    fn to_nominal(self) -> f32 {
+        self
+    }
+
+    fn from_nominal(nominal: f32) -> f32 {
+        nominal
+    }
+}
+
+impl<C: Channel> Rgb<C> {
+    /// Returns the nominal values of the channels
+    pub fn to_nominal(self) -> RgbNominalValue {
+        Rgb {
+            r: self.r.to_nominal(),
+            g: self.g.to_nominal(),
+            b: self.b.to_nominal(),
+        }
+    }
+
+    /// Returns the nearest color to nominal values. Integer channels are clamped to their range.
+    pub fn from_nominal(rgb: RgbNominalValue) -> Rgb<C> {
+        Rgb {
+            r: C::from_nominal(rgb.r),
+            g: C::from_nominal(rgb.g),
+            b: C::from_nominal(rgb.b),
+        }
+    }
+
+    /// Returns the nearest color with channels of another type
+    pub fn to_depth<D: Channel>(self) -> Rgb<D> {
+        Rgb::from_nominal(self.to_nominal())
+    }
+}
+
+impl RgbValue {
+    /// Returns an RgbValue from 3 `u8`s
+    pub const fn new(r: u8, g: u8, b: u8) -> RgbValue {
+        RgbValue { r, g, b }
+    }
+}
+
+impl Rgb16Value {
+    /// Returns an Rgb16Value from 3 `u16`s
+    pub const fn new(r: u16, g: u16, b: u16) -> Rgb16Value {
+        Rgb16Value { r, g, b }
+    }
+}
+
+impl fmt::Display for Rgb16Value {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        fmt_components(f, ["R", "G", "B"], [self.r, self.g, self.b])
+    }
+}
+
+impl fmt::Display for RgbValue {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        fmt_components(f, ["R", "G", "B"], [self.r, self.g, self.b])
+    }
+}
+
+/// Formats as a web color, `#rrggbb`, padded to the width of the formatter
+/// ```
+/// # #[cfg(feature = "parse")] {
+/// use deltae::*;
+///
+/// let rgb = RgbValue::new(102, 51, 153);
+/// assert_eq!(format!("{:x}", rgb), "#663399");
+/// assert_eq!(format!("{:x}", RgbValue::new(10, 0, 255)), "#0a00ff");
+/// assert_eq!(format!("{:x}", rgb).parse::<RgbValue>().unwrap(), rgb);
+/// # }
+/// ```
+impl fmt::LowerHex for RgbValue {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        pad(f, &format!("#{:02x}{:02x}{:02x}", self.r, self.g, self.b))
+    }
+}
+
+/// Formats as a web color in upper case, `#RRGGBB`, padded to the width of the formatter
+/// ```
+/// use deltae::*;
+///
+/// assert_eq!(format!("{:X}", RgbValue::new(255, 128, 0)), "#FF8000");
+/// ```
+impl fmt::UpperHex for RgbValue {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        pad(f, &format!("#{:02X}{:02X}{:02X}", self.r, self.g, self.b))
+    }
+}
+
+/// # Nominal RGB
+///
+/// RGB values normalized between `0.0` and `1.0`. The values are encoded with the transfer
+/// function of an [`RgbSystem`] (i.e. they are not linear).
+///
+/// | `Value` | `Color` | `Range`         |
+/// |:-------:|:-------:|:---------------:|
+/// | `R`     | `Red`   | `0.0 <---> 1.0` |
+/// | `G`     | `Green` | `0.0 <---> 1.0` |
+/// | `B`     | `Blue`  | `0.0 <---> 1.0` |
+pub type RgbNominalValue = Rgb<f32>;
+
+impl RgbNominalValue {
+    /// Returns a result of an RgbNominalValue from 3 `f32`s.
+    /// Will return `Err()` if the values are out of range as determined by the [`Validate`] trait.
+    pub fn new(r: f32, g: f32, b: f32) -> ValueResult<RgbNominalValue> {
+        RgbNominalValue { r, g, b }.validate()
+    }
+
+    /// Convert an [`XyzValue`] relative to [`Illuminant::D50`] to an [`RgbNominalValue`] in an
+    /// [`RgbSystem`]. The [`XyzValue`] is adapted to the white point of the [`RgbSystem`] with
+    /// the [`ChromaticAdaptationMethod`]. If the method is `None`, the [`XyzValue`] is assumed to
+    /// already be relative to the white point of the [`RgbSystem`]. Out of gamut values are
+    /// clipped.
+    pub fn from_xyz(
+        xyz: XyzValue,
+        system: RgbSystem,
+        adaptation: Option<ChromaticAdaptationMethod>,
+    ) -> RgbNominalValue {
+        let xyz = match adaptation {
+            Some(method) => xyz.chrom_adapt(method, Illuminant::D50, system.white_point()),
+            None => xyz,
+        };
+
+        let linear = RgbNominalValue::from(system.xyz_to_rgb() * Matrix3x1::from(xyz));
+
+        system.compand_rgb(linear)
+    }
+
+    /// Convert a [`LabValue`] relative to [`Illuminant::D50`] to an [`RgbNominalValue`] in an
+    /// [`RgbSystem`]. If the [`ChromaticAdaptationMethod`] is `None`, the [`LabValue`] is
+    /// assumed to be relative to the white point of the [`RgbSystem`]. Out of gamut values are
+    /// clipped.
+    /// ```
+    /// use deltae::*;
+    ///
+    /// let lab = LabValue::new(100.0, 0.0, 0.0).unwrap();
+    /// let rgb = RgbNominalValue::from_lab(lab, RgbSystem::ProPhoto, Some(ChromaticAdaptationMethod::Bradford));
+    /// assert_eq!(RgbValue::from(rgb), RgbValue::new(255, 255, 255));
+    /// ```
+    pub fn from_lab(
+        lab: LabValue,
+        system: RgbSystem,
+        adaptation: Option<ChromaticAdaptationMethod>,
+    ) -> RgbNominalValue {
+        let xyz = match adaptation {
+            Some(_) => XyzValue::from(lab),
+            None => XyzValue::from_lab_with_white(lab, system.white_point()),
+        };
+
+        RgbNominalValue::from_xyz(xyz, system, adaptation)
+    }
+
+    /// Like [`RgbNominalValue::from_xyz`], but out of gamut values are not clipped. Channels may
+    /// be negative or greater than `1.0`, as in scRGB and extended sRGB, and are encoded with the
+    /// transfer function mirrored around zero. Out of gamut colors also return the
+    /// [`OutOfGamut`] channels, so a gamut mapping strategy can be chosen before the values are
+    /// clipped or quantized.
+    /// ```
+    /// use deltae::*;
+    ///
+    /// let bradford = Some(ChromaticAdaptationMethod::Bradford);
+    /// let green = RgbNominalValue::new(0.0, 1.0, 0.0).unwrap();
+    /// let p3_green = XyzValue::from_rgb(green, RgbSystem::DisplayP3, bradford);
+    ///
+    /// let (rgb, out_of_gamut) =
+    ///     RgbNominalValue::from_xyz_extended(p3_green, RgbSystem::Srgb, bradford);
+    /// assert_eq!(out_of_gamut, Some(OutOfGamut { r: true, g: true, b: true }));
+    /// assert!(rgb.r < 0.0 && rgb.g > 1.0);
+    ///
+    /// // The extended values convert back without loss
+    /// let xyz = XyzValue::from_rgb_extended(rgb, RgbSystem::Srgb, bradford);
+    /// assert_eq!(xyz.round_to(4), p3_green.round_to(4));
+    /// ```
+    pub fn from_xyz_extended(
+        xyz: XyzValue,
+        system: RgbSystem,
+        adaptation: Option<ChromaticAdaptationMethod>,
+    ) -> (RgbNominalValue, Option<OutOfGamut>) {
+        let xyz = match adaptation {
+            Some(method) => xyz.chrom_adapt(method, Illuminant::D50, system.white_point()),
+            None => xyz,
+        };
+
+        let linear = system.xyz_to_rgb() * Matrix3x1::from(xyz);
+        let out = |v: f32| !(-GAMUT_EPSILON..=1.0 + GAMUT_EPSILON).contains(&v);
+        let out_of_gamut = OutOfGamut { r: out(linear[0]), g: out(linear[1]), b: out(linear[2]) };
+
+        let linear = RgbNominalValue { r: linear[0], g: linear[1], b: linear[2] };
+        let rgb = mirrored(linear, |abs| system.compand_rgb(abs));
+
+        (rgb, Some(out_of_gamut).filter(|o| o.r || o.g || o.b))
+    }
+}
+
+/// The channels of a color that are outside the gamut of an [`RgbSystem`], as returned by
+/// [`RgbNominalValue::from_xyz_extended`]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct OutOfGamut {
+    /// The red channel is out of range
+    pub r: bool,
+    /// The green channel is out of range
+    pub g: bool,
+    /// The blue channel is out of range
+    pub b: bool,
+}
+
+// Apply a transfer function to the absolute values of the channels, keeping their signs
+fn mirrored<F>(rgb: RgbNominalValue, f: F) -> RgbNominalValue
+where F: Fn(RgbNominalValue) -> RgbNominalValue {
+    let abs = f(RgbNominalValue { r: rgb.r.abs(), g: rgb.g.abs(), b: rgb.b.abs() });
+    RgbNominalValue {
+        r: abs.r.copysign(rgb.r),
+        g: abs.g.copysign(rgb.g),
+        b: abs.b.copysign(rgb.b),
+    }
+}
+
+impl fmt::Display for RgbNominalValue {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        fmt_components(f, ["R", "G", "B"], [self.r, self.g, self.b])
+    }
+}
+
+impl XyzValue {
+    /// Convert an [`RgbNominalValue`] in an [`RgbSystem`] to an [`XyzValue`]. The result is
+    /// adapted from the white point of the [`RgbSystem`] to [`Illuminant::D50`] with the
+    /// [`ChromaticAdaptationMethod`]. If the method is `None`, the result is relative to the
+    /// white point of the [`RgbSystem`].
+    /// ```
+    /// use deltae::*;
+    ///
+    /// let white = RgbNominalValue::new(1.0, 1.0, 1.0).unwrap();
+    ///
+    /// let adapted = XyzValue::from_rgb(white, RgbSystem::Srgb, Some(ChromaticAdaptationMethod::Bradford));
+    /// assert_eq!(adapted.round_to(4), Illuminant::D50.white_point());
+    ///
+    /// let unadapted = XyzValue::from_rgb(white, RgbSystem::Srgb, None);
+    /// assert_eq!(unadapted.round_to(4), Illuminant::D65.white_point().round_to(4));
+    /// ```
+    pub fn from_rgb(
+        rgb: RgbNominalValue,
+        system: RgbSystem,
+        adaptation: Option<ChromaticAdaptationMethod>,
+    ) -> XyzValue {
+        XyzValue::from_linear_rgb(system.linearize_rgb(rgb), system, adaptation)
+    }
+
+    /// Like [`XyzValue::from_rgb`], but for extended RGB values that may be negative or greater
+    /// than `1.0`, such as those of [`RgbNominalValue::from_xyz_extended`]. The channels are
+    /// decoded with the transfer function mirrored around zero.
+    pub fn from_rgb_extended(
+        rgb: RgbNominalValue,
+        system: RgbSystem,
+        adaptation: Option<ChromaticAdaptationMethod>,
+    ) -> XyzValue {
+        let linear = mirrored(rgb, |abs| system.linearize_rgb(abs));
+        XyzValue::from_linear_rgb(linear, system, adaptation)
+    }
+
+    fn from_linear_rgb(
+        linear: RgbNominalValue,
+        system: RgbSystem,
+        adaptation: Option<ChromaticAdaptationMethod>,
+    ) -> XyzValue {
+        let xyz = XyzValue::from(system.rgb_to_xyz() * matrix![linear.r; linear.g; linear.b]);
+
+        match adaptation {
+            Some(method) => xyz.chrom_adapt(method, system.white_point(), Illuminant::D50),
+            None => xyz,
+        }
+    }
+
+    /// Returns true if the color, relative to [`Illuminant::D50`], can be reproduced in an
+    /// [`RgbSystem`]: when adapted to the white point of the system with
+    /// [`ChromaticAdaptationMethod::Bradford`], each of its linear RGB channels is within
+    /// `0.0..=1.0`. The check is done before quantization, and allows an error of `0.0001` so that
+    /// the white point and primaries of the system are in gamut.
+    /// ```
+    /// use deltae::*;
+    ///
+    /// let green = XyzValue::from(RgbValue::new(0, 255, 0));
+    /// assert!(green.is_in_gamut(RgbSystem::Srgb));
+    ///
+    /// // Adobe RGB (1998) green is more saturated than sRGB green
+    /// let adobe_green = XyzValue::from_rgb(
+    ///     RgbNominalValue::new(0.0, 1.0, 0.0).unwrap(),
+    ///     RgbSystem::Adobe,
+    ///     Some(ChromaticAdaptationMethod::Bradford),
+    /// );
+    /// assert!(adobe_green.is_in_gamut(RgbSystem::Adobe));
+    /// assert!(!adobe_green.is_in_gamut(RgbSystem::Srgb));
+    /// ```
+    pub fn is_in_gamut(&self, system: RgbSystem) -> bool {
+        let method = ChromaticAdaptationMethod::Bradford;
+        let xyz = self.chrom_adapt(method, Illuminant::D50, system.white_point());
+        let linear = system.xyz_to_rgb() * Matrix3x1::from(xyz);
+
+        linear.iter().all(|v| (-GAMUT_EPSILON..=1.0 + GAMUT_EPSILON).contains(v))
+    }
+}
+
+impl LabValue {
+    /// Convert an [`RgbNominalValue`] in an [`RgbSystem`] to a [`LabValue`]. The color is adapted
+    /// from the white point of the [`RgbSystem`] to [`Illuminant::D50`] with the
+    /// [`ChromaticAdaptationMethod`]. If the method is `None`, no adaptation is done and the
+    /// result is relative to the white point of the [`RgbSystem`].
+    /// ```
+    /// use deltae::*;
+    ///
+    /// let red = RgbNominalValue::new(1.0, 0.0, 0.0).unwrap();
+    /// let lab = LabValue::from_rgb(red, RgbSystem::Srgb, Some(ChromaticAdaptationMethod::Bradford));
+    /// assert_eq!(lab.round_to(1), LabValue { l: 54.3, a: 80.8, b: 69.9 });
+    /// ```
+    pub fn from_rgb(
+        rgb: RgbNominalValue,
+        system: RgbSystem,
+        adaptation: Option<ChromaticAdaptationMethod>,
+    ) -> LabValue {
+        let white = system.white_point();
+        let xyz = XyzValue::from_rgb(rgb, system, adaptation);
+
+        match adaptation {
+            Some(_) => LabValue::from(xyz),
+            None => LabValue::from_xyz_with_white(xyz, white),
+        }
+    }
+
+    /// Returns true if the color can be reproduced in an [`RgbSystem`]. See
+    /// [`XyzValue::is_in_gamut`].
+    /// ```
+    /// use deltae::*;
+    ///
+    /// let gray = LabValue::new(50.0, 0.0, 0.0).unwrap();
+    /// let cyan = LabValue::new(60.0, -60.0, -40.0).unwrap();
+    ///
+    /// assert!(gray.is_in_gamut(RgbSystem::Srgb));
+    /// assert!(!cyan.is_in_gamut(RgbSystem::Srgb));
+    /// assert!(cyan.is_in_gamut(RgbSystem::WideGamut));
+    /// ```
+    pub fn is_in_gamut(&self, system: RgbSystem) -> bool {
+        XyzValue::from(*self).is_in_gamut(system)
+    }
+}
+
+impl LchValue {
+    /// Returns true if the color can be reproduced in an [`RgbSystem`]. See
+    /// [`XyzValue::is_in_gamut`].
+    pub fn is_in_gamut(&self, system: RgbSystem) -> bool {
+        XyzValue::from(*self).is_in_gamut(system)
+    }
+}
+
+// The error allowed in the linear channels of colors in gamut
+const GAMUT_EPSILON: f32 = 0.0001;
+
+/// # RGB Working Spaces
+///
+/// The primaries, white points, and transfer functions of common RGB systems.
+///
+/// Further Reading: [http://www.brucelindbloom.com/index.html?WorkingSpaceInfo.html](http://www.brucelindbloom.com/index.html?WorkingSpaceInfo.html)
+#[derive(Debug, Clone, PartialEq, Default)]
+pub enum RgbSystem {
+    /// ACES2065-1, the ACES interchange space with the AP0 primaries, which enclose the spectral
+    /// locus. ACES white, linear
+    Aces2065,
+    /// ACEScg, the ACES working space for rendering and compositing with the AP1 primaries. ACES
+    /// white, linear
+    AcesCg,
+    /// Adobe RGB (1998). D65, gamma 2.2
+    Adobe,
+    /// Apple RGB. D65, gamma 1.8
+    Apple,
+    /// Best RGB. D50, gamma 2.2
+    Best,
+    /// Beta RGB. D50, gamma 2.2
+    Beta,
+    /// Bruce RGB. D65, gamma 2.2
+    Bruce,
+    /// CIE RGB. E, gamma 2.2
+    Cie,
+    /// ColorMatch RGB. D50, gamma 1.8
+    ColorMatch,
+    /// DCI-P3, for digital cinema projection. DCI theater white, gamma 2.6. The primaries are
+    /// those of Display P3.
+    DciP3,
+    /// Display P3. D65, sRGB transfer function
+    DisplayP3,
+    /// Don RGB 4. D50, gamma 2.2
+    Don4,
+    /// ECI RGB v2. D50, L\*
+    Eci,
+    /// NTSC RGB. C, gamma 2.2
+    Ntsc,
+    /// PAL/SECAM RGB. D65, gamma 2.2
+    PalSecam,
+    /// ProPhoto RGB, also known as ROMM RGB. D50, ProPhoto transfer function
+    ProPhoto,
+    /// ITU-R BT.709, the HDTV standard. D65, BT.709 transfer function. The primaries are those
+    /// of sRGB.
+    Rec709,
+    /// ITU-R BT.2020, the UHDTV standard. D65, BT.709 transfer function
+    Rec2020,
+    /// SMPTE-C RGB. D65, gamma 2.2
+    SmpteC,
+    /// sRGB. D65, sRGB transfer function
+    #[default]
+    Srgb,
+    /// Wide Gamut RGB. D50, gamma 2.2
+    WideGamut,
+    /// A user supplied RGB system, such as one read from an ICC profile. The inverse matrix is
+    /// calculated at runtime. A singular matrix has no inverse and will fail [`Validate`].
+    Custom {
+        /// The matrix to convert linear RGB to XYZ relative to `white`
+        rgb_to_xyz: Matrix3x3,
+        /// The white point of the system
+        white: Illuminant,
+        /// The tone curves of the red, green, and blue channels
+        curves: [ToneCurve; 3],
+    },
+    /// A user supplied RGB system defined by the chromaticities of its primaries, such as a
+    /// monitor or camera space. The matrices are derived at runtime, so that full intensity of
+    /// every channel is the white point. Primaries with no area between them will fail
+    /// [`Validate`].
+    /// ```
+    /// use deltae::*;
+    ///
+    /// let srgb = RgbSystem::Primaries {
+    ///     primaries: [[0.64, 0.33], [0.30, 0.60], [0.15, 0.06]],
+    ///     white: Illuminant::D65,
+    ///     transfer: TransferCurve::Srgb,
+    /// };
+    /// let red = RgbNominalValue::new(1.0, 0.0, 0.0).unwrap();
+    /// let bradford = Some(ChromaticAdaptationMethod::Bradford);
+    /// assert_eq!(
+    ///     LabValue::from_rgb(red, srgb, bradford).round_to(1),
+    ///     LabValue::from_rgb(red, RgbSystem::Srgb, bradford).round_to(1),
+    /// );
+    /// ```
+    Primaries {
+        /// The CIE 1931 xy chromaticities of the red, green, and blue primaries
+        primaries: [[f32; 2]; 3],
+        /// The white point of the system
+        white: Illuminant,
+        /// The transfer function of all three channels
+        transfer: TransferCurve,
+    },
+}
+
+impl Eq for RgbSystem {}
+
+impl RgbSystem {
+    /// Returns the white point of the [`RgbSystem`]
+    pub const fn white_point(&self) -> Illuminant {
+        match self {
+            RgbSystem::Aces2065   => Illuminant::Aces,
+            RgbSystem::AcesCg     => Illuminant::Aces,
+            RgbSystem::Adobe      => Illuminant::D65,
+            RgbSystem::Apple      => Illuminant::D65,
+            RgbSystem::Best       => Illuminant::D50,
+            RgbSystem::Beta       => Illuminant::D50,
+            RgbSystem::Bruce      => Illuminant::D65,
+            RgbSystem::Cie        => Illuminant::E,
+            RgbSystem::ColorMatch => Illuminant::D50,
+            RgbSystem::DciP3      => Illuminant::Dci,
+            RgbSystem::DisplayP3  => Illuminant::D65,
+            RgbSystem::Don4       => Illuminant::D50,
+            RgbSystem::Eci        => Illuminant::D50,
+            RgbSystem::Ntsc       => Illuminant::C,
+            RgbSystem::PalSecam   => Illuminant::D65,
+            RgbSystem::ProPhoto   => Illuminant::D50,
+            RgbSystem::Rec709     => Illuminant::D65,
+            RgbSystem::Rec2020    => Illuminant::D65,
+            RgbSystem::SmpteC     => Illuminant::D65,
+            RgbSystem::Srgb       => Illuminant::D65,
+            RgbSystem::WideGamut  => Illuminant::D50,
+            RgbSystem::Custom { white, .. } => *white,
+            RgbSystem::Primaries { white, .. } => *white,
+        }
+    }
+
+    /// Returns the matrix to convert linear RGB to XYZ relative to the white point of the
+    /// [`RgbSystem`]
+    pub const fn rgb_to_xyz(&self) -> Matrix3x3 {
+        match self {
+            RgbSystem::Aces2065   => ACES2065_RGB2XYZ,
+            RgbSystem::AcesCg     => ACESCG_RGB2XYZ,
+            RgbSystem::Adobe      => ADOBE_RGB2XYZ,
+            RgbSystem::Apple      => APPLE_RGB2XYZ,
+            RgbSystem::Best       => BEST_RGB2XYZ,
+            RgbSystem::Beta       => BETA_RGB2XYZ,
+            RgbSystem::Bruce      => BRUCE_RGB2XYZ,
+            RgbSystem::Cie        => CIE_RGB2XYZ,
+            RgbSystem::ColorMatch => COLORMATCH_RGB2XYZ,
+            RgbSystem::DciP3      => DCI_P3_RGB2XYZ,
+            RgbSystem::DisplayP3  => DISPLAY_P3_RGB2XYZ,
+            RgbSystem::Don4       => DON4_RGB2XYZ,
+            RgbSystem::Eci        => ECI_RGB2XYZ,
+            RgbSystem::Ntsc       => NTSC_RGB2XYZ,
+            RgbSystem::PalSecam   => PAL_SECAM_RGB2XYZ,
+            RgbSystem::ProPhoto   => PROPHOTO_RGB2XYZ,
+            RgbSystem::Rec709     => SRGB_RGB2XYZ,
+            RgbSystem::Rec2020    => REC2020_RGB2XYZ,
+            RgbSystem::SmpteC     => SMPTE_C_RGB2XYZ,
+            RgbSystem::Srgb       => SRGB_RGB2XYZ,
+            RgbSystem::WideGamut  => WIDE_GAMUT_RGB2XYZ,
+            RgbSystem::Custom { rgb_to_xyz, .. } => *rgb_to_xyz,
+            RgbSystem::Primaries { primaries, white, .. } => {
+                primaries_to_xyz(*primaries, white.white_point())
+            }
+        }
+    }
+
+    /// Returns the matrix to convert XYZ relative to the white point of the [`RgbSystem`] to
+    /// linear RGB
+    pub const fn xyz_to_rgb(&self) -> Matrix3x3 {
+        match self {
+            RgbSystem::Aces2065   => ACES2065_XYZ2RGB,
+            RgbSystem::AcesCg     => ACESCG_XYZ2RGB,
+            RgbSystem::Adobe      => ADOBE_XYZ2RGB,
+            RgbSystem::Apple      => APPLE_XYZ2RGB,
+            RgbSystem::Best       => BEST_XYZ2RGB,
+            RgbSystem::Beta       => BETA_XYZ2RGB,
+            RgbSystem::Bruce      => BRUCE_XYZ2RGB,
+            RgbSystem::Cie        => CIE_XYZ2RGB,
+            RgbSystem::ColorMatch => COLORMATCH_XYZ2RGB,
+            RgbSystem::DciP3      => DCI_P3_XYZ2RGB,
+            RgbSystem::DisplayP3  => DISPLAY_P3_XYZ2RGB,
+            RgbSystem::Don4       => DON4_XYZ2RGB,
+            RgbSystem::Eci        => ECI_XYZ2RGB,
+            RgbSystem::Ntsc       => NTSC_XYZ2RGB,
+            RgbSystem::PalSecam   => PAL_SECAM_XYZ2RGB,
+            RgbSystem::ProPhoto   => PROPHOTO_XYZ2RGB,
+            RgbSystem::Rec709     => SRGB_XYZ2RGB,
+            RgbSystem::Rec2020    => REC2020_XYZ2RGB,
+            RgbSystem::SmpteC     => SMPTE_C_XYZ2RGB,
+            RgbSystem::Srgb       => SRGB_XYZ2RGB,
+            RgbSystem::WideGamut  => WIDE_GAMUT_XYZ2RGB,
+            RgbSystem::Custom { .. } | RgbSystem::Primaries { .. } => {
+                match self.rgb_to_xyz().inverse() {
+                    Some(inverse) => inverse,
+                    None => Matrix3x3::new([f32::NAN; 9]),
+                }
+            }
+        }
+    }
+
+    /// Returns the matrix to convert linear RGB to XYZ relative to [`Illuminant::D50`], adapted
+    /// from the white point of the [`RgbSystem`] with a [`ChromaticAdaptationMethod`]. This is a
+    /// `const fn`, so the combined matrix may be calculated at compile time.
+    /// ```
+    /// use deltae::*;
+    ///
+    /// const SRGB_TO_XYZ_D50: Matrix3x3 =
+    ///     RgbSystem::Srgb.rgb_to_xyz_adapted(ChromaticAdaptationMethod::Bradford);
+    ///
+    /// let red = SRGB_TO_XYZ_D50 * matrix![1.0; 0.0; 0.0];
+    /// let expected = XyzValue::from_rgb(
+    ///     RgbNominalValue::new(1.0, 0.0, 0.0).unwrap(),
+    ///     RgbSystem::Srgb,
+    ///     Some(ChromaticAdaptationMethod::Bradford),
+    /// );
+    /// assert_eq!(XyzValue::from(red).round_to(5), expected.round_to(5));
+    /// ```
+    pub const fn rgb_to_xyz_adapted(&self, method: ChromaticAdaptationMethod) -> Matrix3x3 {
+        let white = self.white_point().white_point();
+        let adaptation = adaptation_matrix(method, white, Illuminant::D50.white_point());
+        adaptation.mul_matrix(&self.rgb_to_xyz())
+    }
+
+    /// Returns the matrix to convert XYZ relative to [`Illuminant::D50`] to linear RGB, adapting
+    /// to the white point of the [`RgbSystem`] with a [`ChromaticAdaptationMethod`]. The inverse
+    /// of [`RgbSystem::rgb_to_xyz_adapted`].
+    pub const fn xyz_to_rgb_adapted(&self, method: ChromaticAdaptationMethod) -> Matrix3x3 {
+        let white = self.white_point().white_point();
+        let adaptation = adaptation_matrix(method, Illuminant::D50.white_point(), white);
+        self.xyz_to_rgb().mul_matrix(&adaptation)
+    }
+
+    /// Returns the [`TransferCurve`] that encodes linear values of the [`RgbSystem`].
+    /// [`RgbSystem::Custom`] may have a different curve for each channel, in which case the green
+    /// curve is returned.
+    /// ```
+    /// use deltae::*;
+    ///
+    /// assert_eq!(RgbSystem::Adobe.transfer_function(), TransferCurve::Gamma(2.2));
+    /// assert_eq!(RgbSystem::ColorMatch.transfer_function(), TransferCurve::Gamma(1.8));
+    /// assert_eq!(RgbSystem::Eci.transfer_function(), TransferCurve::LStar);
+    /// assert_eq!(RgbSystem::Srgb.transfer_function(), TransferCurve::Srgb);
+    /// ```
+    pub fn transfer_function(&self) -> TransferCurve {
+        match self {
+            RgbSystem::Aces2065   => TransferCurve::Linear,
+            RgbSystem::AcesCg     => TransferCurve::Linear,
+            RgbSystem::Adobe      => TransferCurve::Gamma(2.2),
+            RgbSystem::Apple      => TransferCurve::Gamma(1.8),
+            RgbSystem::Best       => TransferCurve::Gamma(2.2),
+            RgbSystem::Beta       => TransferCurve::Gamma(2.2),
+            RgbSystem::Bruce      => TransferCurve::Gamma(2.2),
+            RgbSystem::Cie        => TransferCurve::Gamma(2.2),
+            RgbSystem::ColorMatch => TransferCurve::Gamma(1.8),
+            RgbSystem::DciP3      => TransferCurve::Gamma(2.6),
+            RgbSystem::DisplayP3  => TransferCurve::Srgb,
+            RgbSystem::Don4       => TransferCurve::Gamma(2.2),
+            RgbSystem::Eci        => TransferCurve::LStar,
+            RgbSystem::Ntsc       => TransferCurve::Gamma(2.2),
+            RgbSystem::PalSecam   => TransferCurve::Gamma(2.2),
+            RgbSystem::ProPhoto   => TransferCurve::ProPhoto,
+            RgbSystem::Rec709     => TransferCurve::Rec709,
+            RgbSystem::Rec2020    => TransferCurve::Rec709,
+            RgbSystem::SmpteC     => TransferCurve::Gamma(2.2),
+            RgbSystem::Srgb       => TransferCurve::Srgb,
+            RgbSystem::WideGamut  => TransferCurve::Gamma(2.2),
+            RgbSystem::Custom { curves, .. } => TransferCurve::Curve(curves[1].clone()),
+            RgbSystem::Primaries { transfer, .. } => transfer.clone(),
+        }
+    }
+
+    /// Decode a nominal channel value to a linear value with the transfer function of the
+    /// [`RgbSystem`]. [`RgbSystem::Custom`] may have a different curve for each channel, in which
+    /// case the green curve is used. See [`RgbSystem::linearize_rgb`].
+    pub fn linearize(&self, v: f32) -> f32 {
+        self.transfer_function().decode(v)
+    }
+
+    /// Encode a linear channel value to a nominal value with the transfer function of the
+    /// [`RgbSystem`]. [`RgbSystem::Custom`] may have a different curve for each channel, in which
+    /// case the green curve is used. See [`RgbSystem::compand_rgb`].
+    pub fn compand(&self, v: f32) -> f32 {
+        self.transfer_function().encode(v)
+    }
+
+    /// Decode each channel of an [`RgbNominalValue`] to linear values with the transfer functions
+    /// of the [`RgbSystem`]
+    pub fn linearize_rgb(&self, rgb: RgbNominalValue) -> RgbNominalValue {
+        match self {
+            RgbSystem::Custom { curves: [r, g, b], .. } => RgbNominalValue {
+                r: r.linearize(rgb.r),
+                g: g.linearize(rgb.g),
+                b: b.linearize(rgb.b),
+            },
+            _ => {
+                let curve = self.transfer_function();
+                RgbNominalValue {
+                    r: curve.decode(rgb.r),
+                    g: curve.decode(rgb.g),
+                    b: curve.decode(rgb.b),
+                }
+            }
+        }
+    }
+
+    /// Encode each channel of linear values to an [`RgbNominalValue`] with the transfer functions
+    /// of the [`RgbSystem`]
+    pub fn compand_rgb(&self, linear: RgbNominalValue) -> RgbNominalValue {
+        match self {
+            RgbSystem::Custom { curves: [r, g, b], .. } => RgbNominalValue {
+                r: r.compand(linear.r),
+                g: g.compand(linear.g),
+                b: b.compand(linear.b),
+            },
+            _ => {
+                let curve = self.transfer_function();
+                RgbNominalValue {
+                    r: curve.encode(linear.r),
+                    g: curve.encode(linear.g),
+                    b: curve.encode(linear.b),
+                }
+            }
+        }
+    }
+}
+
+impl fmt::Display for RgbSystem {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            RgbSystem::Custom { .. } | RgbSystem::Primaries { .. } => write!(f, "Custom"),
+            _ => write!(f, "{:?}", self),
+        }
+    }
+}
+
+// The matrix to convert linear RGB to XYZ from the xy chromaticities of the primaries, with each
+// column scaled so that the sum of the primaries is the white point
+const fn primaries_to_xyz(primaries: [[f32; 2]; 3], white: XyzValue) -> Matrix3x3 {
+    let [[xr, yr], [xg, yg], [xb, yb]] = primaries;
+    let unscaled = Matrix3x3::from_columns([
+        [xr / yr, 1.0, (1.0 - xr - yr) / yr],
+        [xg / yg, 1.0, (1.0 - xg - yg) / yg],
+        [xb / yb, 1.0, (1.0 - xb - yb) / yb],
+    ]);
+
+    let scale = match unscaled.inverse() {
+        Some(inverse) => inverse.mul_column(&matrix![white.x; white.y; white.z]),
+        None => return Matrix3x3::new([f32::NAN; 9]),
+    };
+
+    unscaled.mul_matrix(&Matrix3x3::diagonal(scale.to_array()))
+}
+
+/// # Transfer Function
+///
+/// The function that encodes the linear values of an RGB system as nominal values, and decodes
+/// them again. Nominal values are companded, and linear values are proportional to light.
+/// [`TransferCurve`] implements the common functions, and [`ToneCurve`] the curves of ICC
+/// profiles. Other functions, such as the log curve of a camera, can be used with the matrix of
+/// an [`RgbSystem`]:
+/// ```
+/// use deltae::*;
+///
+/// struct Log2;
+///
+/// impl TransferFunction for Log2 {
+///     fn encode(&self, linear: f32) -> f32 {
+///         (linear * 255.0 + 1.0).log2() / 8.0
+///     }
+///
+///     fn decode(&self, nominal: f32) -> f32 {
+///         ((nominal * 8.0).exp2() - 1.0) / 255.0
+///     }
+/// }
+///
+/// let rgb = RgbNominalValue::new(1.0, 0.75, 0.5).unwrap();
+/// let linear = Log2.decode_rgb(rgb);
+/// let xyz = XyzValue::from(RgbSystem::Srgb.rgb_to_xyz() * matrix![linear.r; linear.g; linear.b]);
+/// assert_eq!(Log2.encode_rgb(linear).round_to(5), rgb);
+/// ```
+pub trait TransferFunction {
+    /// Encode a linear value to a nominal value
+    fn encode(&self, linear: f32) -> f32;
+
+    /// Decode a nominal value to a linear value. The inverse of [`TransferFunction::encode`].
+    fn decode(&self, nominal: f32) -> f32;
+
+    /// Encode each channel of linear values to an [`RgbNominalValue`]
+    fn encode_rgb(&self, linear: RgbNominalValue) -> RgbNominalValue {
+        RgbNominalValue {
+            r: self.encode(linear.r),
+            g: self.encode(linear.g),
+            b: self.encode(linear.b),
+        }
+    }
+
+    /// Decode each channel of an [`RgbNominalValue`] to linear values
+    fn decode_rgb(&self, rgb: RgbNominalValue) -> RgbNominalValue {
+        RgbNominalValue {
+            r: self.decode(rgb.r),
+            g: self.decode(rgb.g),
+            b: self.decode(rgb.b),
+        }
+    }
+}
+
+/// # Transfer Curve
+///
+/// The common [`TransferFunction`]s, as used by the [`RgbSystem`]s
+/// ```
+/// use deltae::*;
+///
+/// assert_eq!(TransferCurve::Gamma(2.0).decode(0.5), 0.25);
+/// assert!((TransferCurve::LStar.decode(0.5) - 0.18419).abs() < 1e-5);
+/// assert!((TransferCurve::Srgb.decode(0.5) - 0.21404).abs() < 1e-5);
+///
+/// // 100 cd/m² in PQ
+/// assert!((TransferCurve::Pq.encode(0.01) - 0.50808).abs() < 1e-4);
+/// ```
+#[derive(Debug, Clone, PartialEq)]
+pub enum TransferCurve {
+    /// No encoding: nominal values are linear
+    Linear,
+    /// The piecewise sRGB curve: a linear segment near black, then a power law of 2.4
+    Srgb,
+    /// A simple power law: `linear = nominal^γ`
+    Gamma(f32),
+    /// The CIE L\* lightness curve of ECI RGB v2: nominal values are `L* / 100`
+    LStar,
+    /// The ProPhoto (ROMM RGB) curve: a power law of 1.8 with a linear segment near black, where
+    /// a pure power law would have an infinite slope
+    ProPhoto,
+    /// The ITU-R BT.709 camera curve, also used by BT.2020: a linear segment near black, then a
+    /// power law of 0.45
+    Rec709,
+    /// The perceptual quantizer of SMPTE ST 2084, used by HDR10 and BT.2100 PQ. Linear values are
+    /// absolute, where `1.0` is 10000 cd/m².
+    Pq,
+    /// The hybrid log-gamma curve of BT.2100 HLG. Linear values are scene light, where `1.0` is
+    /// the nominal peak.
+    Hlg,
+    /// An ICC tone curve
+    Curve(ToneCurve),
+}
+
+// SMPTE ST 2084
+const PQ_M1: f32 = 2610.0 / 16384.0;
+const PQ_M2: f32 = 2523.0 / 4096.0 * 128.0;
+const PQ_C1: f32 = 3424.0 / 4096.0;
+const PQ_C2: f32 = 2413.0 / 4096.0 * 32.0;
+const PQ_C3: f32 = 2392.0 / 4096.0 * 32.0;
+
+// ITU-R BT.2100
+const HLG_A: f32 = 0.17883277;
+const HLG_B: f32 = 0.28466892;
+const HLG_C: f32 = 0.5599107;
+
+impl TransferFunction for TransferCurve {
+    fn encode(&self, v: f32) -> f32 {
+        match *self {
+            TransferCurve::Linear => v,
+            TransferCurve::Srgb => {
+                if v <= 0.0031308 {
+                    v * 12.92
+                } else {
+                    1.055 * v.powf(1.0 / 2.4) - 0.055
+                }
+            }
+            TransferCurve::Gamma(g) => v.powf(1.0 / g),
+            TransferCurve::LStar => {
+                if v <= EPSILON {
+                    v * KAPPA / 100.0
+                } else {
+                    1.16 * v.cbrt() - 0.16
+                }
+            }
+            TransferCurve::ProPhoto => {
+                if v < 1.0 / 512.0 {
+                    v * 16.0
+                } else {
+                    v.powf(1.0 / 1.8)
+                }
+            }
+            TransferCurve::Rec709 => {
+                if v < 0.018 {
+                    v * 4.5
+                } else {
+                    1.099 * v.powf(0.45) - 0.099
+                }
+            }
+            TransferCurve::Pq => {
+                let y = v.max(0.0).powf(PQ_M1);
+                ((PQ_C1 + PQ_C2 * y) / (1.0 + PQ_C3 * y)).powf(PQ_M2)
+            }
+            TransferCurve::Hlg => {
+                if v <= 1.0 / 12.0 {
+                    (3.0 * v.max(0.0)).sqrt()
+                } else {
+                    HLG_A * (12.0 * v - HLG_B).ln() + HLG_C
+                }
+            }
+            TransferCurve::Curve(ref curve) => curve.compand(v),
+        }
+    }
+
+    fn decode(&self, v: f32) -> f32 {
+        match *self {
+            TransferCurve::Linear => v,
+            TransferCurve::Srgb => {
+                if v <= 0.04045 {
+                    v / 12.92
+                } else {
+                    ((v + 0.055) / 1.055).powf(2.4)
+                }
+            }
+            TransferCurve::Gamma(g) => v.powf(g),
+            TransferCurve::LStar => {
+                if v <= 0.08 {
+                    100.0 * v / KAPPA
+                } else {
+                    ((v + 0.16) / 1.16).powi(3)
+                }
+            }
+            TransferCurve::ProPhoto => {
+                if v < 16.0 / 512.0 {
+                    v / 16.0
+                } else {
+                    v.powf(1.8)
+                }
+            }
+            TransferCurve::Rec709 => {
+                if v < 0.081 {
+                    v / 4.5
+                } else {
+                    ((v + 0.099) / 1.099).powf(1.0 / 0.45)
+                }
+            }
+            TransferCurve::Pq => {
+                let p = v.max(0.0).powf(1.0 / PQ_M2);
+                ((p - PQ_C1).max(0.0) / (PQ_C2 - PQ_C3 * p)).powf(1.0 / PQ_M1)
+            }
+            TransferCurve::Hlg => {
+                if v <= 0.5 {
+                    v * v / 3.0
+                } else {
+                    (((v - HLG_C) / HLG_A).exp() + HLG_B) / 12.0
+                }
+            }
+            TransferCurve::Curve(ref curve) => curve.linearize(v),
+        }
+    }
+}
+
+impl From<ToneCurve> for TransferCurve {
+    fn from(curve: ToneCurve) -> TransferCurve {
+        TransferCurve::Curve(curve)
+    }
+}
+
+/// # Tone Reproduction Curve
+///
+/// The transfer function of one channel of an [`RgbSystem::Custom`], in the form of the
+/// parametric and sampled curves of the ICC specification.
+#[derive(Debug, Clone, PartialEq)]
+pub enum ToneCurve {
+    /// A simple power law: `Y = X^γ`
+    Gamma(f32),
+    /// The ICC parametric curve with a linear segment. All other ICC parametric curve types can
+    /// be expressed with this one:
+    ///
+    /// `Y = (aX + b)^γ + e` for `X >= d`, and `Y = cX + f` for `X < d`
+    Parametric {
+        /// Gamma
+        g: f32,
+        /// Scale of the power segment
+        a: f32,
+        /// Offset of the power segment
+        b: f32,
+        /// Slope of the linear segment
+        c: f32,
+        /// The breakpoint between the linear and power segments
+        d: f32,
+        /// Offset after the power segment
+        e: f32,
+        /// Offset of the linear segment
+        f: f32,
+    },
+    /// A table of linear values sampled at evenly spaced nominal values from 0 to 1, such as the
+    /// `curv` table of an ICC profile. Values between the samples are interpolated linearly. The
+    /// samples must not decrease, and there must be at least two of them to pass [`Validate`].
+    /// ```
+    /// use deltae::*;
+    ///
+    /// let curve = ToneCurve::Sampled(vec![0.0, 0.25, 1.0].into());
+    /// assert_eq!(curve.linearize(0.25), 0.125);
+    /// assert_eq!(curve.compand(0.125), 0.25);
+    /// ```
+    Sampled(Arc<[f32]>),
+}
+
+impl ToneCurve {
+    /// Decode a nominal value to a linear value
+    /// ```
+    /// use deltae::*;
+    ///
+    /// // The sRGB transfer function
+    /// let srgb = ToneCurve::Parametric {
+    ///     g: 2.4, a: 1.0 / 1.055, b: 0.055 / 1.055, c: 1.0 / 12.92, d: 0.04045, e: 0.0, f: 0.0,
+    /// };
+    /// assert_eq!(srgb.linearize(0.5), RgbSystem::Srgb.linearize(0.5));
+    /// assert_eq!(ToneCurve::Gamma(2.0).linearize(0.5), 0.25);
+    /// ```
+    pub fn linearize(&self, v: f32) -> f32 {
+        match *self {
+            ToneCurve::Gamma(g) => v.powf(g),
+            ToneCurve::Parametric { g, a, b, c, d, e, f } => {
+                if v >= d {
+                    (a * v + b).max(0.0).powf(g) + e
+                } else {
+                    c * v + f
+                }
+            }
+            ToneCurve::Sampled(ref table) => {
+                let (first, last) = match (table.first(), table.last()) {
+                    (Some(first), Some(last)) if table.len() > 1 => (*first, *last),
+                    _ => return v,
+                };
+
+                let x = v * (table.len() - 1) as f32;
+                if x.is_nan() || x <= 0.0 {
+                    first
+                } else if x >= (table.len() - 1) as f32 {
+                    last
+                } else {
+                    let i = x as usize;
+                    table[i] + (table[i + 1] - table[i]) * (x - i as f32)
+                }
+            }
+        }
+    }
+
+    /// Encode a linear value to a nominal value
+    pub fn compand(&self, v: f32) -> f32 {
+        match *self {
+            ToneCurve::Gamma(g) => v.powf(1.0 / g),
+            ToneCurve::Parametric { g, a, b, c, d, e, f } => {
+                if v >= (a * d + b).max(0.0).powf(g) + e {
+                    ((v - e).max(0.0).powf(1.0 / g) - b) / a
+                } else if c != 0.0 {
+                    (v - f) / c
+                } else {
+                    0.0
+                }
+            }
+            ToneCurve::Sampled(ref table) => {
+                let last = match table.len() {
+                    0 | 1 => return v,
+                    len => len - 1,
+                };
+
+                // The first sample at or above the value, so flat runs invert to their start
+                let i = table.partition_point(|&s| s < v);
+                if i == 0 {
+                    0.0
+                } else if i > last {
+                    1.0
+                } else {
+                    let (low, high) = (table[i - 1], table[i]);
+                    (i - 1) as f32 / last as f32 + (v - low) / (high - low) / last as f32
+                }
+            }
+        }
+    }
+}
+
+impl TransferFunction for ToneCurve {
+    fn encode(&self, linear: f32) -> f32 {
+        self.compand(linear)
+    }
+
+    fn decode(&self, nominal: f32) -> f32 {
+        self.linearize(nominal)
+    }
+}
+
+impl From<Matrix3x1> for RgbNominalValue {
+    fn from(m: Matrix3x1) -> RgbNominalValue {
+        RgbNominalValue {
+            r: m[0].clamp(0.0, 1.0),
+            g: m[1].clamp(0.0, 1.0),
+            b: m[2].clamp(0.0, 1.0),
+        }
+    }
+}
+
+const ACES2065_RGB2XYZ: Matrix3x3 = matrix![
+     0.9525524,  0.0,        0.0000937;
+     0.3439664,  0.7281661, -0.0721325;
+     0.0,        0.0,        1.0088252
+];
+
+const ACES2065_XYZ2RGB: Matrix3x3 = matrix![
+     1.049811,   0.0,       -0.0000975;
+    -0.495903,   1.373313,   0.09824;
+     0.0,        0.0,        0.991252
+];
+
+const ACESCG_RGB2XYZ: Matrix3x3 = matrix![
+     0.6624542,  0.1340042,  0.1561877;
+     0.2722287,  0.6740818,  0.0536895;
+    -0.0055746,  0.0040607,  1.0103391
+];
+
+const ACESCG_XYZ2RGB: Matrix3x3 = matrix![
+     1.6410234, -0.3248033, -0.2364247;
+    -0.6636629,  1.6153316,  0.0167563;
+     0.0117219, -0.0082844,  0.9883949
+];
+
+const ADOBE_RGB2XYZ: Matrix3x3 = matrix![
+     0.5767309,  0.185554,   0.1881852;
+     0.2973769,  0.6273491,  0.0752741;
+     0.0270343,  0.0706872,  0.9911085
+];
+
+const ADOBE_XYZ2RGB: Matrix3x3 = matrix![
+     2.041369,  -0.5649464, -0.3446944;
+    -0.969266,   1.8760108,  0.041556;
+     0.0134474, -0.1183897,  1.0154096
+];
+
+const APPLE_RGB2XYZ: Matrix3x3 = matrix![
+     0.4497288,  0.3162486,  0.1844926;
+     0.2446525,  0.6720283,  0.0833192;
+     0.0251848,  0.1411824,  0.9224628
+];
+
+const APPLE_XYZ2RGB: Matrix3x3 = matrix![
+     2.951537,  -1.289412,  -0.4738445;
+    -1.085109,   1.9908566,  0.0372026;
+     0.0854934, -0.2694964,  1.0912975
+];
+
+const BEST_RGB2XYZ: Matrix3x3 = matrix![
+     0.632665,   0.2045575,  0.1269774;
+     0.2284552,  0.7373586,  0.0341862;
+     0.0,        0.0095143,  0.8155857
+];
+
+const BEST_XYZ2RGB: Matrix3x3 = matrix![
+     1.7552727, -0.4836821, -0.2530019;
+    -0.544129,   1.5068661,  0.0215526;
+     0.0063476, -0.0175785,  1.2258613
+];
+
+const BETA_RGB2XYZ: Matrix3x3 = matrix![
+     0.6712485,  0.1745852,  0.1183662;
+     0.3032702,  0.6637931,  0.0329367;
+     0.0,        0.0407014,  0.7843986
+];
+
+const BETA_XYZ2RGB: Matrix3x3 = matrix![
+     1.6832399, -0.4282396, -0.2360203;
+    -0.7710148,  1.7065392,  0.0446895;
+     0.0400069, -0.08855,    1.2725431
+];
+
+const BRUCE_RGB2XYZ: Matrix3x3 = matrix![
+     0.4674162,  0.2944512,  0.1886026;
+     0.2410115,  0.6835475,  0.075441;
+     0.0219101,  0.0736128,  0.9933071
+];
+
+const BRUCE_XYZ2RGB: Matrix3x3 = matrix![
+     2.745467,  -1.1358136, -0.4350269;
+    -0.969266,   1.8760108,  0.041556;
+     0.0112723, -0.1139754,  1.013254
+];
+
+const CIE_RGB2XYZ: Matrix3x3 = matrix![
+     0.488718,   0.3106803,  0.2006017;
+     0.1762044,  0.8129847,  0.0108109;
+     0.0,        0.0102048,  0.9897952
+];
+
+const CIE_XYZ2RGB: Matrix3x3 = matrix![
+     2.370674,  -0.9000405, -0.4706338;
+    -0.513885,   1.4253036,  0.0885814;
+     0.0052982, -0.0146949,  1.0093968
+];
+
+const COLORMATCH_RGB2XYZ: Matrix3x3 = matrix![
+     0.509339,   0.3209137,  0.1339474;
+     0.2748813,  0.658145,   0.0669737;
+     0.0242542,  0.1087843,  0.6920615
+];
+
+const COLORMATCH_XYZ2RGB: Matrix3x3 = matrix![
+     2.6423128, -1.223439,  -0.3930181;
+    -1.1119535,  2.058976,   0.0159611;
+     0.0821832, -0.2807708,  1.456223
+];
+
+const DCI_P3_RGB2XYZ: Matrix3x3 = matrix![
+     0.4451728,  0.2771338,  0.1722835;
+     0.2094931,  0.7215935,  0.0689134;
+     0.0,        0.0470604,  0.9073596
+];
+
+const DCI_P3_XYZ2RGB: Matrix3x3 = matrix![
+     2.725376,  -1.0179962, -0.4401603;
+    -0.7951699,  1.6897361,  0.0226472;
+     0.0412417, -0.0876386,  1.1009243
+];
+
+const DISPLAY_P3_RGB2XYZ: Matrix3x3 = matrix![
+     0.4866326,  0.2656632,  0.1981742;
+     0.2290036,  0.6917267,  0.0792697;
+     0.0,        0.0451126,  1.0437174
+];
+
+const DISPLAY_P3_XYZ2RGB: Matrix3x3 = matrix![
+     2.4931808, -0.9312655, -0.4026597;
+    -0.8295031,  1.7626941,  0.0236251;
+     0.0358536, -0.076189,   0.9570926
+];
+
+const DON4_RGB2XYZ: Matrix3x3 = matrix![
+     0.6457664,  0.193353,   0.1250806;
+     0.2783476,  0.6879769,  0.0336755;
+     0.0037113,  0.0179863,  0.8034024
+];
+
+const DON4_XYZ2RGB: Matrix3x3 = matrix![
+     1.760403,  -0.4881234, -0.2536145;
+    -0.7126219,  1.6527272,  0.0416711;
+     0.0078218, -0.0347459,  1.2449449
+];
+
+const ECI_RGB2XYZ: Matrix3x3 = matrix![
+     0.6502003,  0.1780813,  0.1359184;
+     0.3202479,  0.6020844,  0.0776676;
+     0.0,        0.0678405,  0.7572595
+];
+
+const ECI_XYZ2RGB: Matrix3x3 = matrix![
+     1.782773,  -0.4969878, -0.2690117;
+    -0.959341,   1.947753,  -0.0275801;
+     0.0859443, -0.1744931,  1.3230221
+];
+
+const NTSC_RGB2XYZ: Matrix3x3 = matrix![
+     0.6068909,  0.1735011,  0.200348;
+     0.2989164,  0.586599,   0.1144845;
+     0.0,        0.0660957,  1.1162243
+];
+
+const NTSC_XYZ2RGB: Matrix3x3 = matrix![
+     1.909996,  -0.5324542, -0.2882091;
+    -0.9846663,  1.999171,  -0.0283082;
+     0.0583056, -0.1183781,  0.8975535
+];
+
+const PAL_SECAM_RGB2XYZ: Matrix3x3 = matrix![
+     0.430619,   0.3415419,  0.1783091;
+     0.2220379,  0.7066384,  0.0713236;
+     0.0201853,  0.1295504,  0.9390944
+];
+
+const PAL_SECAM_XYZ2RGB: Matrix3x3 = matrix![
+     3.062897,  -1.393179,  -0.4757517;
+    -0.969266,   1.8760108,  0.041556;
+     0.0678775, -0.2288548,  1.069349
+];
+
+const PROPHOTO_RGB2XYZ: Matrix3x3 = matrix![
+     0.797658,   0.1351929,  0.0313492;
+     0.2880341,  0.7118802,  0.0000857;
+     0.0,        0.0,        0.8251
+];
+
+const PROPHOTO_XYZ2RGB: Matrix3x3 = matrix![
+     1.345972,  -0.255613,  -0.0511129;
+    -0.5445942,  1.5081543,  0.020535;
+     0.0,        0.0,        1.2119743
+];
+
+const REC2020_RGB2XYZ: Matrix3x3 = matrix![
+     0.6370102,  0.144615,   0.1688448;
+     0.2627217,  0.6779893,  0.059289;
+     0.0,        0.0280723,  1.0607577
+];
+
+const REC2020_XYZ2RGB: Matrix3x3 = matrix![
+     1.7165107, -0.3556417, -0.2533455;
+    -0.666693,   1.6165022,  0.0157688;
+     0.0176436, -0.0427798,  0.9423051
+];
+
+const SMPTE_C_RGB2XYZ: Matrix3x3 = matrix![
+     0.3935891,  0.3652497,  0.1916313;
+     0.2124132,  0.7010437,  0.0865432;
+     0.0187423,  0.1119313,  0.9581563
+];
+
+const SMPTE_C_XYZ2RGB: Matrix3x3 = matrix![
+     3.505396,  -1.7394894, -0.543964;
+    -1.0690722,  1.977824,   0.0351722;
+     0.05632,   -0.1970226,  1.0502026
+];
+
+const SRGB_RGB2XYZ: Matrix3x3 = matrix![
+     0.4124564,  0.3575761,  0.1804375;
+     0.2126729,  0.7151522,  0.072175;
+     0.0193339,  0.119192,   0.9503041
+];
+
+const SRGB_XYZ2RGB: Matrix3x3 = matrix![
+     3.2404542, -1.5371385, -0.4985314;
+    -0.969266,   1.8760108,  0.041556;
+     0.0556434, -0.2040259,  1.0572252
+];
+
+const WIDE_GAMUT_RGB2XYZ: Matrix3x3 = matrix![
+     0.7161052,  0.1009299,  0.1471649;
+     0.2581876,  0.72494,    0.0168724;
+     0.0,        0.0517814,  0.7733186
+];
+
+const WIDE_GAMUT_XYZ2RGB: Matrix3x3 = matrix![
+     1.4628054, -0.1840622, -0.2743604;
+    -0.5217918,  1.4472337,  0.0677225;
+     0.0349392, -0.0969068,  1.2885934
+];
@@ -1,59 +1,220 @@
 use super::*;
 
-/// Trait for rounding values to a number of decimal places
-pub trait Round {
-    /// Rounds the value to a number of decimal places
-    fn round_to(self, places: i32) -> Self;
+/// Trait for rounding values to a number of decimal places or significant figures
+pub trait Round where Self: Sized {
+    /// Rounds each value to a [`Precision`] by a [`RoundingMode`]
+    /// ```
+    /// use deltae::*;
+    ///
+    /// let lab = LabValue { l: 50.125, a: -2.5, b: 0.35 };
+    /// assert_eq!(
+    ///     lab.round_by(Precision::DecimalPlaces(2), RoundingMode::HalfEven),
+    ///     LabValue { l: 50.12, a: -2.5, b: 0.35 },
+    /// );
+    /// assert_eq!(
+    ///     lab.round_by(Precision::DecimalPlaces(0), RoundingMode::HalfUp),
+    ///     LabValue { l: 50.0, a: -2.0, b: 0.0 },
+    /// );
+    /// assert_eq!(
+    ///     lab.round_by(Precision::SignificantFigures(1), RoundingMode::HalfAwayFromZero),
+    ///     LabValue { l: 50.0, a: -3.0, b: 0.4 },
+    /// );
+    /// ```
+    fn round_by(self, precision: Precision, mode: RoundingMode) -> Self;
+
+    /// Rounds the value to a number of decimal places, with halves rounded away from zero
+    fn round_to(self, places: i32) -> Self {
+        self.round_by(Precision::DecimalPlaces(places), RoundingMode::default())
+    }
+
+    /// Rounds each value to a number of significant figures, with halves rounded away from zero
+    /// ```
+    /// use deltae::*;
+    ///
+    /// let xyz = XyzValue { x: 0.950456, y: 1.0, z: 0.0108883 };
+    /// assert_eq!(xyz.round_to_sig_figs(3), XyzValue { x: 0.950, y: 1.0, z: 0.0109 });
+    /// ```
+    fn round_to_sig_figs(self, figures: u32) -> Self {
+        self.round_by(Precision::SignificantFigures(figures), RoundingMode::default())
+    }
+}
+
+/// The precision that values are rounded to
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum Precision {
+    /// A number of digits after the decimal point, or before it if negative
+    DecimalPlaces(i32),
+    /// A number of significant figures, at least one. Zero is always rounded to zero.
+    SignificantFigures(u32),
+}
+
+/// How values exactly halfway between two rounded values are rounded. Values are rounded as the
+/// decimal numbers they display as, so `0.125_f32` is a half even though it is stored as a
+/// binary fraction.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum RoundingMode {
+    /// Halves are rounded away from zero, like [`f32::round`]: `2.5` to `3` and `-2.5` to `-3`
+    #[default]
+    HalfAwayFromZero,
+    /// Halves are rounded towards positive infinity: `2.5` to `3` and `-2.5` to `-2`
+    HalfUp,
+    /// Halves are rounded to the even neighbor, also called banker's rounding: `2.5` to `2` and
+    /// `3.5` to `4`
+    HalfEven,
 }
 
 // Round an f32 to a number of decimal places
-fn round_to(val: f32, places: i32) -> f32 {
-    let mult = 10_f32.powi(places);
-    (val * mult).round() / mult
+#[cfg(test)]
+pub(crate) fn round_to(val: f32, places: i32) -> f32 {
+    round_f32(val, Precision::DecimalPlaces(places), RoundingMode::default())
+}
+
+fn round_f32(val: f32, precision: Precision, mode: RoundingMode) -> f32 {
+    round_value(val as f64, precision, mode, |half| half as f32 == val) as f32
+}
+
+fn round_f64(val: f64, precision: Precision, mode: RoundingMode) -> f64 {
+    round_value(val, precision, mode, |half| half == val)
+}
+
+// Round a value, treating it as a half if the half is the nearest value in its original precision,
+// since the decimal it was written as is usually not exactly representable
+fn round_value<F>(val: f64, precision: Precision, mode: RoundingMode, is_val: F) -> f64
+where F: Fn(f64) -> bool {
+    if !val.is_finite() || val == 0.0 {
+        return val;
+    }
+
+    let places = match precision {
+        Precision::DecimalPlaces(places) => places,
+        Precision::SignificantFigures(figures) => {
+            figures.max(1) as i32 - 1 - val.abs().log10().floor() as i32
+        }
+    };
+
+    let mult = 10_f64.powi(places);
+    let scaled = val * mult;
+    let floor = scaled.floor();
+
+    let rounded = if is_val((floor + 0.5) / mult) {
+        match mode {
+            RoundingMode::HalfAwayFromZero if scaled < 0.0 => floor,
+            RoundingMode::HalfEven if floor % 2.0 == 0.0 => floor,
+            _ => floor + 1.0,
+        }
+    } else {
+        scaled.round()
+    };
+
+    rounded / mult
 }
 
 impl Round for DeltaE {
-    fn round_to(self, places: i32) -> Self {
+    fn round_by(self, precision: Precision, mode: RoundingMode) -> Self {
         Self {
-            value: round_to(self.value, places),
+            value: round_f32(self.value, precision, mode),
             ..self
         }
     }
 }
 
 impl Round for LabValue {
-    fn round_to(self, places: i32) -> LabValue {
+    fn round_by(self, precision: Precision, mode: RoundingMode) -> LabValue {
         Self {
-            l: round_to(self.l, places),
-            a: round_to(self.a, places),
-            b: round_to(self.b, places),
+            l: round_f32(self.l, precision, mode),
+            a: round_f32(self.a, precision, mode),
+            b: round_f32(self.b, precision, mode),
         }
     }
 }
 
 impl Round for LchValue {
-    fn round_to(self, places: i32) -> LchValue {
+    fn round_by(self, precision: Precision, mode: RoundingMode) -> LchValue {
         Self {
-            l: round_to(self.l, places),
-            c: round_to(self.c, places),
-            h: round_to(self.h, places),
+            l: round_f32(self.l, precision, mode),
+            c: round_f32(self.c, precision, mode),
+            h: round_f32(self.h, precision, mode),
         }
     }
 }
 
 impl Round for XyzValue {
-    fn round_to(self, places: i32) -> XyzValue {
+    fn round_by(self, precision: Precision, mode: RoundingMode) -> XyzValue {
         Self {
-            x: round_to(self.x, places),
-            y: round_to(self.y, places),
-            z: round_to(self.z, places),
+            x: round_f32(self.x, precision, mode),
+            y: round_f32(self.y, precision, mode),
+            z: round_f32(self.z, precision, mode),
         }
     }
 }
 
+#[cfg(feature = "rgb")]
+impl Round for RgbNominalValue {
+    fn round_by(self, precision: Precision, mode: RoundingMode) -> RgbNominalValue {
+        Self {
+            r: round_f32(self.r, precision, mode),
+            g: round_f32(self.g, precision, mode),
+            b: round_f32(self.b, precision, mode),
+        }
+    }
+}
+
+#[cfg(feature = "rgb")]
+impl Round for HslValue {
+    fn round_by(self, precision: Precision, mode: RoundingMode) -> HslValue {
+        Self {
+            h: round_f32(self.h, precision, mode),
+            s: round_f32(self.s, precision, mode),
+            l: round_f32(self.l, precision, mode),
+        }
+    }
+}
+
+#[cfg(feature = "rgb")]
+impl Round for HsvValue {
+    fn round_by(self, precision: Precision, mode: RoundingMode) -> HsvValue {
+        Self {
+            h: round_f32(self.h, precision, mode),
+            s: round_f32(self.s, precision, mode),
+            v: round_f32(self.v, precision, mode),
+        }
+    }
+}
+
+impl Round for Color {
+    fn round_by(self, precision: Precision, mode: RoundingMode) -> Color {
+        match self {
+            Color::Lab(lab) => Color::Lab(lab.round_by(precision, mode)),
+            Color::Lch(lch) => Color::Lch(lch.round_by(precision, mode)),
+            Color::Xyz(xyz) => Color::Xyz(xyz.round_by(precision, mode)),
+            #[cfg(feature = "rgb")]
+            Color::Rgb(_) => self,
+        }
+    }
+}
+
+// Round each value of a matrix
+macro_rules! impl_round_matrix {
+    ($t:ty, $round:ident) => {
+        impl Round for $t {
+            fn round_by(mut self, precision: Precision, mode: RoundingMode) -> $t {
+                self.iter_mut().for_each(|v| *v = $round(*v, precision, mode));
+                self
+            }
+        }
+    }
+}
+
+impl_round_matrix!(Matrix3x3, round_f32);
+impl_round_matrix!(Matrix3x1, round_f32);
+impl_round_matrix!(Matrix3x3d, round_f64);
+impl_round_matrix!(Matrix3x1d, round_f64);
+
 #[test]
 fn round() {
-    let val = 1.234567890;
+    let val = 1.2345679;
     let rnd = round::round_to(val, 4);
     assert_eq!(rnd, 1.2346);
     assert_ne!(rnd, val);
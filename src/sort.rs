@@ -0,0 +1,91 @@
+//! Ordering colors, such as arranging the swatches of a palette or swatch book. Colors are compared
+//! by a [`SortKey`] with [`f32::total_cmp`], so sorting never panics on unusual values.
+//!
+//! # Examples
+//!
+//! ```
+//! use deltae::*;
+//!
+//! let mut swatches = [
+//!     LchValue::new(70.0, 40.0, 200.0).unwrap(),
+//!     LchValue::new(30.0, 60.0, 10.0).unwrap(),
+//!     LchValue::new(50.0, 20.0, 100.0).unwrap(),
+//! ];
+//!
+//! sort_colors(&mut swatches, SortKey::Lightness);
+//! assert_eq!(swatches.iter().map(|lch| lch.l).collect::<Vec<_>>(), vec![30.0, 50.0, 70.0]);
+//!
+//! // Hue starting from yellow-green instead of 0°
+//! sort_colors(&mut swatches, SortKey::Hue(90.0));
+//! assert_eq!(swatches.iter().map(|lch| lch.h).collect::<Vec<_>>(), vec![100.0, 200.0, 10.0]);
+//! ```
+
+use crate::*;
+use std::cmp::Ordering;
+
+/// The property colors are ordered by
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum SortKey {
+    /// Lightness (L\*), darkest first
+    Lightness,
+    /// Chroma (C\*), least saturated first
+    Chroma,
+    /// Hue angle (h), counter-clockwise from an origin in degrees. Achromatic colors have a hue
+    /// of 0°.
+    Hue(f32),
+}
+
+impl SortKey {
+    /// The value a color is ordered by
+    /// ```
+    /// use deltae::*;
+    ///
+    /// let lch = LchValue::new(50.0, 20.0, 30.0).unwrap();
+    /// assert_eq!(SortKey::Chroma.key(lch), 20.0);
+    /// assert_eq!(SortKey::Hue(0.0).key(lch), 30.0);
+    /// assert_eq!(SortKey::Hue(60.0).key(lch), 330.0);
+    /// ```
+    pub fn key<C: Into<LchValue>>(self, color: C) -> f32 {
+        let lch = color.into();
+        match self {
+            SortKey::Lightness => lch.l,
+            SortKey::Chroma => lch.c,
+            SortKey::Hue(origin) => (lch.h - origin).rem_euclid(360.0),
+        }
+    }
+
+    /// Compare two colors by their keys
+    pub fn compare<C: Into<LchValue> + Copy>(self, a: &C, b: &C) -> Ordering {
+        self.key(*a).total_cmp(&self.key(*b))
+    }
+}
+
+/// Sort colors in ascending order of a [`SortKey`]. The sort is stable, so sorting by one key and
+/// then another orders colors by the second key, and then the first.
+pub fn sort_colors<C: Into<LchValue> + Copy>(colors: &mut [C], key: SortKey) {
+    colors.sort_by_cached_key(|&color| TotalOrder(key.key(color)));
+}
+
+// An f32 ordered by total_cmp, as a key of sort_by_cached_key
+struct TotalOrder(f32);
+
+impl PartialEq for TotalOrder {
+    fn eq(&self, other: &TotalOrder) -> bool {
+        self.cmp(other) == Ordering::Equal
+    }
+}
+
+impl Eq for TotalOrder {}
+
+impl PartialOrd for TotalOrder {
+    fn partial_cmp(&self, other: &TotalOrder) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for TotalOrder {
+    fn cmp(&self, other: &TotalOrder) -> Ordering {
+        self.0.total_cmp(&other.0)
+    }
+}
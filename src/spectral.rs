@@ -0,0 +1,753 @@
+//! Spectral data and the integration of spectra to [`XyzValue`]s.
+//!
+//! # Examples
+//!
+//! ```
+//! use deltae::*;
+//!
+//! // A perfect reflector is the reference white
+//! let observer = ColorMatchingFunctions::cie1931();
+//! let white = observer.integrate(380.0, 10.0, &[1.0; 41]);
+//!
+//! assert_eq!(white.y, 1.0);
+//!
+//! // A filter over a gray patch transmits half of the light
+//! let gray = SpectralCurve::constant(380.0, 10.0, 41, 0.5).unwrap();
+//! let filter = SpectralCurve::constant(380.0, 10.0, 41, 0.5).unwrap();
+//! let filtered = gray.multiply(&filter).unwrap();
+//! let xyz = observer.integrate(filtered.start(), filtered.interval(), filtered.samples());
+//! assert_eq!(xyz.round_to(4).y, 0.25);
+//! ```
+
+use crate::*;
+
+/// # Spectral Curve
+///
+/// Reflectance or transmittance factors of a sample, as measured by a spectrophotometer, at a
+/// regular interval of wavelengths in nanometers. A factor of `1.0` reflects (or transmits) all of
+/// the light at its wavelength.
+#[derive(Debug, Clone, PartialEq)]
+pub struct SpectralCurve {
+    start: f32,
+    interval: f32,
+    samples: Vec<f32>,
+}
+
+impl SpectralCurve {
+    /// Returns a result of a [`SpectralCurve`] from samples starting at the wavelength `start` and
+    /// taken every `interval` nanometers.
+    /// Will return `Err()` if there are no samples, if a sample is not finite, or if the interval
+    /// is not positive.
+    /// ```
+    /// use deltae::*;
+    ///
+    /// let curve = SpectralCurve::new(400.0, 20.0, vec![0.1, 0.2, 0.4, 0.8]).unwrap();
+    /// assert_eq!(curve.len(), 4);
+    /// assert_eq!(curve.end(), 460.0);
+    ///
+    /// assert!(SpectralCurve::new(400.0, 20.0, vec![]).is_err());
+    /// assert!(SpectralCurve::new(400.0, 20.0, vec![f32::NAN]).is_err());
+    /// ```
+    pub fn new(start: f32, interval: f32, samples: Vec<f32>) -> ValueResult<SpectralCurve> {
+        if samples.is_empty() {
+            return Err(ValueError::BadFormat);
+        }
+
+        if !(interval > 0.0 && start.is_finite()) {
+            return Err(ValueError::OutOfBounds);
+        }
+
+        if !samples.iter().all(|sample| sample.is_finite()) {
+            return Err(ValueError::NotFinite);
+        }
+
+        Ok(SpectralCurve { start, interval, samples })
+    }
+
+    /// Returns a result of a [`SpectralCurve`] from a slice of samples. See [`SpectralCurve::new`].
+    pub fn from_slice(start: f32, interval: f32, samples: &[f32]) -> ValueResult<SpectralCurve> {
+        SpectralCurve::new(start, interval, samples.to_vec())
+    }
+
+    /// Returns a result of a [`SpectralCurve`] of `count` samples of the same `value`, such as a
+    /// perfect reflector with a value of `1.0`. See [`SpectralCurve::new`].
+    pub fn constant(
+        start: f32,
+        interval: f32,
+        count: usize,
+        value: f32,
+    ) -> ValueResult<SpectralCurve> {
+        SpectralCurve::new(start, interval, vec![value; count])
+    }
+
+    /// The first wavelength of the samples in nanometers
+    pub fn start(&self) -> f32 {
+        self.start
+    }
+
+    /// The last wavelength of the samples in nanometers
+    pub fn end(&self) -> f32 {
+        self.start + self.interval * (self.len() - 1) as f32
+    }
+
+    /// The interval between samples in nanometers
+    pub fn interval(&self) -> f32 {
+        self.interval
+    }
+
+    /// The number of samples
+    pub fn len(&self) -> usize {
+        self.samples.len()
+    }
+
+    /// Always returns `false`. A [`SpectralCurve`] cannot be constructed without samples.
+    pub fn is_empty(&self) -> bool {
+        self.samples.is_empty()
+    }
+
+    /// The samples, in order of wavelength
+    pub fn samples(&self) -> &[f32] {
+        &self.samples
+    }
+
+    /// Iterate over the wavelengths of the samples paired with their values
+    pub fn iter(&self) -> impl Iterator<Item = (f32, f32)> + '_ {
+        self.samples.iter()
+            .enumerate()
+            .map(move |(i, &sample)| (self.start + self.interval * i as f32, sample))
+    }
+
+    /// Returns the value at a wavelength, linearly interpolating between samples.
+    /// Returns `None` if the wavelength is outside of the range of the samples.
+    /// ```
+    /// use deltae::*;
+    ///
+    /// let curve = SpectralCurve::new(500.0, 10.0, vec![0.25, 0.75]).unwrap();
+    /// assert_eq!(curve.at(505.0), Some(0.5));
+    /// assert_eq!(curve.at(520.0), None);
+    /// ```
+    pub fn at(&self, wavelength: f32) -> Option<f32> {
+        let position = (wavelength - self.start) / self.interval;
+        if !(0.0..=(self.len() - 1) as f32).contains(&position) {
+            return None;
+        }
+
+        let i = position.floor() as usize;
+        let t = position - i as f32;
+        let j = (i + 1).min(self.len() - 1);
+
+        Some(self.samples[i] + (self.samples[j] - self.samples[i]) * t)
+    }
+
+    /// Integrate the curve as the reflectance (or transmittance) of a sample lit by the spectral
+    /// power distribution of an illuminant, to an [`XyzValue`] seen by an observer. The result is
+    /// normalized so that a perfect reflector has a `Y` of `1.0`.
+    ///
+    /// The illuminant and the observer are interpolated at the wavelengths of the samples, and
+    /// samples outside of their ranges are ignored. There is no bandpass correction: at a 5nm
+    /// interval, the white points of the CIE illuminants are within 0.0005 of those published in
+    /// ASTM E308, and coarser intervals drift further from them.
+    /// ```
+    /// use deltae::*;
+    ///
+    /// let observer = ColorMatchingFunctions::cie1931();
+    /// // 380nm to 780nm every 40nm
+    /// let tungsten = SpectralCurve::new(
+    ///     380.0, 40.0,
+    ///     vec![0.1, 0.3, 0.6, 0.9, 1.3, 1.7, 2.2, 2.6, 3.1, 3.5, 4.0],
+    /// ).unwrap();
+    /// let red = SpectralCurve::new(
+    ///     380.0, 40.0,
+    ///     vec![0.05, 0.05, 0.05, 0.05, 0.1, 0.4, 0.7, 0.8, 0.8, 0.8, 0.8],
+    /// ).unwrap();
+    ///
+    /// // A red sample reflects more under reddish tungsten light than under equal energy
+    /// let flat = SpectralCurve::constant(380.0, 40.0, 11, 1.0).unwrap();
+    /// assert!(red.to_xyz(&tungsten, &observer).y > red.to_xyz(&flat, &observer).y);
+    ///
+    /// // A perfect reflector is the white point of the illuminant
+    /// assert_eq!(flat.to_xyz(&tungsten, &observer).y, 1.0);
+    /// ```
+    pub fn to_xyz(
+        &self,
+        illuminant: &SpectralCurve,
+        observer: &ColorMatchingFunctions,
+    ) -> XyzValue {
+        let (mut x, mut y, mut z, mut k) = (0.0, 0.0, 0.0, 0.0);
+
+        for (wavelength, sample) in self.iter() {
+            if let (Some(power), Some([x_bar, y_bar, z_bar])) =
+                (illuminant.at(wavelength), observer.at(wavelength))
+            {
+                x += sample * power * x_bar;
+                y += sample * power * y_bar;
+                z += sample * power * z_bar;
+                k += power * y_bar;
+            }
+        }
+
+        if k == 0.0 {
+            return XyzValue::default();
+        }
+
+        XyzValue { x: x / k, y: y / k, z: z / k }
+    }
+
+    /// Integrate the curve to a [`LabValue`] relative to the white point of the illuminant, as
+    /// with [`SpectralCurve::to_xyz`]. The white point is a perfect reflector sampled at the same
+    /// wavelengths as the curve, so that no chromatic adaptation is involved.
+    pub fn to_lab(
+        &self,
+        illuminant: &SpectralCurve,
+        observer: &ColorMatchingFunctions,
+    ) -> LabValue {
+        let white = self.map(|_| 1.0).to_xyz(illuminant, observer);
+        LabValue::from_xyz_with_white_point(self.to_xyz(illuminant, observer), white)
+    }
+
+    /// Returns a result of the relative spectral power of a blackbody (Planckian) radiator at a
+    /// temperature in kelvin, normalized to `1.0` at 560nm, sampled like [`SpectralCurve::new`].
+    /// Will return `Err()` if the temperature is not positive and finite, or as
+    /// [`SpectralCurve::new`].
+    /// ```
+    /// use deltae::*;
+    ///
+    /// // Illuminant A is a blackbody at 2856K
+    /// let a = SpectralCurve::blackbody(2856.0, 360.0, 5.0, 95).unwrap();
+    /// assert_eq!(a.at(560.0), Some(1.0));
+    ///
+    /// # #[cfg(feature = "illuminants")] {
+    /// let perfect_reflector = SpectralCurve::constant(360.0, 5.0, 95, 1.0).unwrap();
+    /// let white = perfect_reflector.to_xyz(&a, &ColorMatchingFunctions::cie1931());
+    /// assert!((white.x - Illuminant::A.white_point().x).abs() < 0.001);
+    /// # }
+    /// ```
+    pub fn blackbody(
+        temperature: f32,
+        start: f32,
+        interval: f32,
+        count: usize,
+    ) -> ValueResult<SpectralCurve> {
+        if !(temperature > 0.0 && temperature.is_finite()) {
+            return Err(ValueError::OutOfBounds);
+        }
+
+        // Planck's law relative to 560nm
+        let exp_m1 = |wavelength: f32| (PLANCK_C2 / (wavelength * temperature)).exp_m1();
+        let samples = (0..count).map(|i| {
+            let wavelength = start + interval * i as f32;
+            (560.0 / wavelength).powi(5) * exp_m1(560.0) / exp_m1(wavelength)
+        });
+
+        SpectralCurve::new(start, interval, samples.collect())
+    }
+
+    /// Returns a result of the relative spectral power of CIE daylight at a correlated color
+    /// temperature in kelvin, normalized to `1.0` at 560nm, from 380nm to 780nm at 10nm intervals.
+    /// The D series illuminants are daylight at their nominal temperatures scaled by 1.4388/1.438,
+    /// such as 6504K for [`Illuminant::D65`].
+    /// Will return `Err()` if the temperature is outside of the defined range of 4000K to 25000K.
+    /// ```
+    /// use deltae::*;
+    ///
+    /// let d65 = SpectralCurve::daylight(6504.0).unwrap();
+    /// # #[cfg(feature = "illuminants")] {
+    /// let perfect_reflector = SpectralCurve::constant(380.0, 10.0, 41, 1.0).unwrap();
+    /// let white = perfect_reflector.to_xyz(&d65, &ColorMatchingFunctions::cie1931());
+    /// assert_eq!(white.round_to(2), Illuminant::D65.white_point().round_to(2));
+    /// # }
+    ///
+    /// assert!(SpectralCurve::daylight(3000.0).is_err());
+    /// ```
+    pub fn daylight(temperature: f32) -> ValueResult<SpectralCurve> {
+        let t = temperature;
+        let x = if (4000.0..=7000.0).contains(&t) {
+            -4.6070e9 / t.powi(3) + 2.9678e6 / t.powi(2) + 0.09911e3 / t + 0.244063
+        } else if (7000.0..=25000.0).contains(&t) {
+            -2.0064e9 / t.powi(3) + 1.9018e6 / t.powi(2) + 0.24748e3 / t + 0.237040
+        } else {
+            return Err(ValueError::OutOfBounds);
+        };
+        let y = -3.0 * x * x + 2.870 * x - 0.275;
+
+        let m = 0.0241 + 0.2562 * x - 0.7341 * y;
+        let m1 = (-1.3515 - 1.7703 * x + 5.9114 * y) / m;
+        let m2 = (0.0300 - 31.4424 * x + 30.0717 * y) / m;
+        let samples = (0..DAYLIGHT_S0.len())
+            .map(|i| (DAYLIGHT_S0[i] + m1 * DAYLIGHT_S1[i] + m2 * DAYLIGHT_S2[i]) / 100.0)
+            .collect();
+
+        SpectralCurve::new(380.0, 10.0, samples)
+    }
+
+    /// Returns the curve with every sample multiplied by a factor
+    pub fn scale(&self, factor: f32) -> SpectralCurve {
+        self.map(|sample| sample * factor)
+    }
+
+    /// Returns the sum of two curves, sample by sample.
+    /// Will return `Err()` if the curves are not sampled at the same wavelengths.
+    pub fn add(&self, other: &SpectralCurve) -> ValueResult<SpectralCurve> {
+        self.zip(other, |a, b| a + b)
+    }
+
+    /// Returns the product of two curves, sample by sample, such as the transmittance of a filter
+    /// applied to the reflectance of a sample.
+    /// Will return `Err()` if the curves are not sampled at the same wavelengths.
+    /// ```
+    /// use deltae::*;
+    ///
+    /// let sample = SpectralCurve::new(400.0, 10.0, vec![0.5, 0.8]).unwrap();
+    /// let filter = SpectralCurve::new(400.0, 10.0, vec![1.0, 0.5]).unwrap();
+    /// assert_eq!(sample.multiply(&filter).unwrap().samples(), &[0.5, 0.4]);
+    ///
+    /// let offset = SpectralCurve::new(410.0, 10.0, vec![1.0, 0.5]).unwrap();
+    /// assert!(sample.multiply(&offset).is_err());
+    /// ```
+    pub fn multiply(&self, other: &SpectralCurve) -> ValueResult<SpectralCurve> {
+        self.zip(other, |a, b| a * b)
+    }
+
+    // Apply a function to every sample
+    pub(crate) fn map<F: Fn(f32) -> f32>(&self, f: F) -> SpectralCurve {
+        SpectralCurve {
+            samples: self.samples.iter().map(|&sample| f(sample)).collect(),
+            ..*self
+        }
+    }
+
+    // Combine the samples of two curves taken at the same wavelengths
+    fn zip<F>(&self, other: &SpectralCurve, f: F) -> ValueResult<SpectralCurve>
+    where F: Fn(f32, f32) -> f32 {
+        let sampling = |curve: &SpectralCurve| (curve.start, curve.interval, curve.len());
+        if sampling(self) != sampling(other) {
+            return Err(ValueError::BadFormat);
+        }
+
+        Ok(SpectralCurve {
+            samples: self.samples.iter().zip(&other.samples).map(|(&a, &b)| f(a, b)).collect(),
+            ..*self
+        })
+    }
+}
+
+/// # Color Matching Functions
+///
+/// Tables of the `x̄`, `ȳ`, and `z̄` functions of a standard observer, sampled at a regular
+/// interval of wavelengths in nanometers. The CIE 1931 and 1964 standard observers are built in,
+/// and any other observer may be supplied, such as the CIE 2006 cone-fundamental-based observers.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ColorMatchingFunctions {
+    start: f32,
+    interval: f32,
+    x_bar: Vec<f32>,
+    y_bar: Vec<f32>,
+    z_bar: Vec<f32>,
+}
+
+impl ColorMatchingFunctions {
+    /// Returns a result of [`ColorMatchingFunctions`] from tables starting at the wavelength
+    /// `start` and sampled every `interval` nanometers.
+    /// Will return `Err()` if the tables are empty or of unequal length, or if the interval is not
+    /// positive.
+    /// ```
+    /// use deltae::*;
+    ///
+    /// let cmf = ColorMatchingFunctions::new(
+    ///     400.0, 100.0,
+    ///     vec![0.0143, 0.0049, 0.0114],
+    ///     vec![0.0004, 0.3230, 0.0041],
+    ///     vec![0.0679, 0.2720, 0.0000],
+    /// ).unwrap();
+    ///
+    /// assert_eq!(cmf.len(), 3);
+    /// assert_eq!(cmf.end(), 600.0);
+    /// ```
+    pub fn new(
+        start: f32,
+        interval: f32,
+        x_bar: Vec<f32>,
+        y_bar: Vec<f32>,
+        z_bar: Vec<f32>,
+    ) -> ValueResult<ColorMatchingFunctions> {
+        if x_bar.is_empty() || x_bar.len() != y_bar.len() || x_bar.len() != z_bar.len() {
+            return Err(ValueError::BadFormat);
+        }
+
+        if !(interval > 0.0 && start.is_finite()) {
+            return Err(ValueError::OutOfBounds);
+        }
+
+        Ok(ColorMatchingFunctions { start, interval, x_bar, y_bar, z_bar })
+    }
+
+    /// The CIE 1931 2° standard observer from 360nm to 830nm at 5nm intervals, as tabulated in
+    /// CIE 015. This is the default observer.
+    /// ```
+    /// use deltae::*;
+    ///
+    /// let cie1931 = ColorMatchingFunctions::cie1931();
+    /// assert_eq!(cie1931.at(555.0), Some([0.5120501, 1.0, 0.005749999]));
+    /// assert_eq!(cie1931, ColorMatchingFunctions::default());
+    /// ```
+    pub fn cie1931() -> ColorMatchingFunctions {
+        ColorMatchingFunctions::from_tables(&CIE1931_X, &CIE1931_Y, &CIE1931_Z)
+    }
+
+    /// The CIE 1964 10° standard observer from 360nm to 830nm at 5nm intervals, as tabulated in
+    /// CIE 015. The supplementary observer for fields of view larger than 4°.
+    pub fn cie1964() -> ColorMatchingFunctions {
+        ColorMatchingFunctions::from_tables(&CIE1964_X, &CIE1964_Y, &CIE1964_Z)
+    }
+
+    /// An approximation of the CIE 1931 2° standard observer from 360nm to 830nm at 5nm
+    /// intervals, generated from the multi-lobe Gaussian fit published by Wyman, Sloan, and
+    /// Shirley in "Simple Analytic Approximations to the CIE XYZ Color Matching Functions"
+    /// (Journal of Computer Graphics Techniques, 2013). Prefer the tabulated
+    /// [`ColorMatchingFunctions::cie1931`] unless the results must match other software that
+    /// uses the fit.
+    pub fn wyman_fit() -> ColorMatchingFunctions {
+        let wavelengths = (0..95).map(|i| 360.0 + 5.0 * i as f32);
+
+        let x_bar = wavelengths.clone().map(|w| {
+            1.056 * lobe(w, 599.8, 37.9, 31.0)
+          + 0.362 * lobe(w, 442.0, 16.0, 26.7)
+          - 0.065 * lobe(w, 501.1, 20.4, 26.2)
+        }).collect();
+
+        let y_bar = wavelengths.clone().map(|w| {
+            0.821 * lobe(w, 568.8, 46.9, 40.5)
+          + 0.286 * lobe(w, 530.9, 16.3, 31.1)
+        }).collect();
+
+        let z_bar = wavelengths.map(|w| {
+            1.217 * lobe(w, 437.0, 11.8, 36.0)
+          + 0.681 * lobe(w, 459.0, 26.0, 13.8)
+        }).collect();
+
+        ColorMatchingFunctions { start: 360.0, interval: 5.0, x_bar, y_bar, z_bar }
+    }
+
+    // The 360nm to 830nm tables of a CIE standard observer
+    fn from_tables(
+        x_bar: &[f32; 95],
+        y_bar: &[f32; 95],
+        z_bar: &[f32; 95],
+    ) -> ColorMatchingFunctions {
+        ColorMatchingFunctions {
+            start: 360.0,
+            interval: 5.0,
+            x_bar: x_bar.to_vec(),
+            y_bar: y_bar.to_vec(),
+            z_bar: z_bar.to_vec(),
+        }
+    }
+
+    /// The first wavelength of the tables in nanometers
+    pub fn start(&self) -> f32 {
+        self.start
+    }
+
+    /// The last wavelength of the tables in nanometers
+    pub fn end(&self) -> f32 {
+        self.start + self.interval * (self.len() - 1) as f32
+    }
+
+    /// The interval between samples in nanometers
+    pub fn interval(&self) -> f32 {
+        self.interval
+    }
+
+    /// The number of samples in each table
+    pub fn len(&self) -> usize {
+        self.x_bar.len()
+    }
+
+    /// Always returns `false`. [`ColorMatchingFunctions`] cannot be constructed without samples.
+    pub fn is_empty(&self) -> bool {
+        self.x_bar.is_empty()
+    }
+
+    /// Returns the `[x̄, ȳ, z̄]` values at a wavelength, linearly interpolating between samples.
+    /// Returns `None` if the wavelength is outside of the range of the tables.
+    /// ```
+    /// use deltae::*;
+    ///
+    /// let cmf = ColorMatchingFunctions::new(
+    ///     500.0, 10.0,
+    ///     vec![0.0, 1.0],
+    ///     vec![1.0, 1.0],
+    ///     vec![1.0, 0.0],
+    /// ).unwrap();
+    ///
+    /// assert_eq!(cmf.at(505.0), Some([0.5, 1.0, 0.5]));
+    /// assert_eq!(cmf.at(520.0), None);
+    /// ```
+    pub fn at(&self, wavelength: f32) -> Option<[f32; 3]> {
+        let position = (wavelength - self.start) / self.interval;
+        if !(0.0..=(self.len() - 1) as f32).contains(&position) {
+            return None;
+        }
+
+        let i = position.floor() as usize;
+        let t = position - i as f32;
+        let j = (i + 1).min(self.len() - 1);
+        let lerp = |table: &[f32]| table[i] + (table[j] - table[i]) * t;
+
+        Some([lerp(&self.x_bar), lerp(&self.y_bar), lerp(&self.z_bar)])
+    }
+
+    /// Integrate spectral reflectance (or transmittance) samples, starting at the wavelength
+    /// `start` and sampled every `interval` nanometers, to an [`XyzValue`] under an equal energy
+    /// illuminant. The result is normalized so that a perfect reflector has a `Y` of `1.0`.
+    /// Samples outside of the range of the tables are ignored.
+    pub fn integrate(&self, start: f32, interval: f32, samples: &[f32]) -> XyzValue {
+        let (mut x, mut y, mut z, mut k) = (0.0, 0.0, 0.0, 0.0);
+
+        for (i, sample) in samples.iter().enumerate() {
+            if let Some([x_bar, y_bar, z_bar]) = self.at(start + interval * i as f32) {
+                x += sample * x_bar;
+                y += sample * y_bar;
+                z += sample * z_bar;
+                k += y_bar;
+            }
+        }
+
+        if k == 0.0 {
+            return XyzValue::default();
+        }
+
+        XyzValue { x: x / k, y: y / k, z: z / k }
+    }
+}
+
+/// The CIE special metamerism index for a change in illuminant: the [`DeltaE`] under a test
+/// illuminant between two samples that match under a reference illuminant, given their spectral
+/// reflectance. This is whether two samples that match in a daylight booth still match under
+/// store lighting.
+///
+/// The pair needn't match exactly under the reference illuminant: the tristimulus values of the
+/// second sample under the test illuminant are scaled by the ratio of the first to the second under
+/// the reference illuminant (the multiplicative correction of CIE 15). Lab values are relative to
+/// the white point of the test illuminant. See [`metamerism_index_lab`] for samples measured as
+/// Lab values under both illuminants.
+/// ```
+/// use deltae::*;
+///
+/// let observer = ColorMatchingFunctions::cie1931();
+/// let daylight = SpectralCurve::constant(400.0, 50.0, 7, 1.0).unwrap();
+/// let store = SpectralCurve::new(400.0, 50.0, vec![0.2, 0.4, 0.7, 1.0, 1.4, 1.9, 2.5]).unwrap();
+///
+/// // A flat gray, and a metameric gray with peaks in the blue and the red
+/// let gray = SpectralCurve::constant(400.0, 50.0, 7, 0.5).unwrap();
+/// let peaks = SpectralCurve::new(400.0, 50.0, vec![0.5, 0.6, 0.4, 0.5, 0.6, 0.5, 0.8]).unwrap();
+///
+/// let mi = metamerism_index(&gray, &peaks, &daylight, &store, &observer, DE2000);
+/// assert!(*mi.value() > 1.0);
+///
+/// // Under the same illuminant, the corrected pair matches
+/// let mi = metamerism_index(&gray, &peaks, &daylight, &daylight, &observer, DE2000);
+/// assert!(*mi.value() < 1e-3);
+/// ```
+pub fn metamerism_index(
+    sample_a: &SpectralCurve,
+    sample_b: &SpectralCurve,
+    reference_illuminant: &SpectralCurve,
+    test_illuminant: &SpectralCurve,
+    observer: &ColorMatchingFunctions,
+    method: DEMethod,
+) -> DeltaE {
+    let a_reference = sample_a.to_xyz(reference_illuminant, observer);
+    let b_reference = sample_b.to_xyz(reference_illuminant, observer);
+    let a_test = sample_a.to_xyz(test_illuminant, observer);
+    let b_test = sample_b.to_xyz(test_illuminant, observer);
+
+    // A component of zero under the reference illuminant can't be corrected
+    let ratio = |a: f32, b: f32| if b > 0.0 { a / b } else { 1.0 };
+    let corrected = XyzValue {
+        x: b_test.x * ratio(a_reference.x, b_reference.x),
+        y: b_test.y * ratio(a_reference.y, b_reference.y),
+        z: b_test.z * ratio(a_reference.z, b_reference.z),
+    };
+
+    let white = sample_a.map(|_| 1.0).to_xyz(test_illuminant, observer);
+    let lab = |xyz| LabValue::from_xyz_with_white_point(xyz, white);
+    lab(a_test).delta(lab(corrected), method)
+}
+
+impl Default for ColorMatchingFunctions {
+    fn default() -> ColorMatchingFunctions {
+        ColorMatchingFunctions::cie1931()
+    }
+}
+
+// The second radiation constant of Planck's law in nm·K, as used by CIE 15
+const PLANCK_C2: f32 = 1.4388e7;
+
+// The mean and characteristic vectors of CIE daylight from 380nm to 780nm at 10nm intervals
+const DAYLIGHT_S0: [f32; 41] = [
+     63.4,  65.8,  94.8, 104.8, 105.9,  96.8, 113.9, 125.6, 125.5, 121.3,
+    121.3, 113.5, 113.1, 110.8, 106.5, 108.8, 105.3, 104.4, 100.0,  96.0,
+     95.1,  89.1,  90.5,  90.3,  88.4,  84.0,  85.1,  81.9,  82.6,  84.9,
+     81.3,  71.9,  74.3,  76.4,  63.3,  71.7,  77.0,  65.2,  47.7,  68.6,
+     65.0,
+];
+
+const DAYLIGHT_S1: [f32; 41] = [
+     38.5,  35.0,  43.4,  46.3,  43.9,  37.1,  36.7,  35.9,  32.6,  27.9,
+     24.3,  20.1,  16.2,  13.2,   8.6,   6.1,   4.2,   1.9,   0.0,  -1.6,
+     -3.5,  -3.5,  -5.8,  -7.2,  -8.6,  -9.5, -10.9, -10.7, -12.0, -14.0,
+    -13.6, -12.0, -13.3, -12.9, -10.6, -11.6, -12.2, -10.2,  -7.8, -11.2,
+    -10.4,
+];
+
+const DAYLIGHT_S2: [f32; 41] = [
+      3.0,   1.2,  -1.1,  -0.5,  -0.7,  -1.2,  -2.6,  -2.9,  -2.8,  -2.6,
+     -2.6,  -1.8,  -1.5,  -1.3,  -1.2,  -1.0,  -0.5,  -0.3,   0.0,   0.2,
+      0.5,   2.1,   3.2,   4.1,   4.7,   5.1,   6.7,   7.3,   8.6,   9.8,
+     10.2,   8.3,   9.6,   8.5,   7.0,   7.6,   8.0,   6.7,   5.2,   7.4,
+      6.8,
+];
+
+// The CIE 1931 2° standard observer from 360nm to 830nm at 5nm intervals, as tabulated
+// in CIE 015
+const CIE1931_X: [f32; 95] = [
+    0.0001299,      0.0002321,      0.0004149,      0.0007416,      0.001368,
+    0.002236,       0.004243,       0.00765,        0.01431,        0.02319,
+    0.04351,        0.07763,        0.13438,        0.21477,        0.2839,
+    0.3285,         0.34828,        0.34806,        0.3362,         0.3187,
+    0.2908,         0.2511,         0.19536,        0.1421,         0.09564,
+    0.05795001,     0.03201,        0.0147,         0.0049,         0.0024,
+    0.0093,         0.0291,         0.06327,        0.1096,         0.1655,
+    0.2257499,      0.2904,         0.3597,         0.4334499,      0.5120501,
+    0.5945,         0.6784,         0.7621,         0.8425,         0.9163,
+    0.9786,         1.0263,         1.0567,         1.0622,         1.0456,
+    1.0026,         0.9384,         0.8544499,      0.7514,         0.6424,
+    0.5419,         0.4479,         0.3608,         0.2835,         0.2187,
+    0.1649,         0.1212,         0.0874,         0.0636,         0.04677,
+    0.0329,         0.0227,         0.01584,        0.01135916,     0.008110916,
+    0.005790346,    0.004109457,    0.002899327,    0.00204919,     0.001439971,
+    0.0009999493,   0.0006900786,   0.0004760213,   0.0003323011,   0.0002348261,
+    0.0001661505,   0.000117413,    0.00008307527,  0.00005870652,  0.00004150994,
+    0.00002935326,  0.00002067383,  0.00001455977,  0.00001025398,  0.000007221456,
+    0.000005085868, 0.000003581652, 0.000002522525, 0.000001776509, 0.000001251141,
+];
+
+const CIE1931_Y: [f32; 95] = [
+    0.000003917,   0.000006965,   0.00001239,    0.00002202,    0.000039,
+    0.000064,      0.00012,       0.000217,      0.000396,      0.00064,
+    0.00121,       0.00218,       0.004,         0.0073,        0.0116,
+    0.01684,       0.023,         0.0298,        0.038,         0.048,
+    0.06,          0.0739,        0.09098,       0.1126,        0.13902,
+    0.1693,        0.20802,       0.2586,        0.323,         0.4073,
+    0.503,         0.6082,        0.71,          0.7932,        0.862,
+    0.9148501,     0.954,         0.9803,        0.9949501,     1.0,
+    0.995,         0.9786,        0.952,         0.9154,        0.87,
+    0.8163,        0.757,         0.6949,        0.631,         0.5668,
+    0.503,         0.4412,        0.381,         0.321,         0.265,
+    0.217,         0.175,         0.1382,        0.107,         0.0816,
+    0.061,         0.04458,       0.032,         0.0232,        0.017,
+    0.01192,       0.00821,       0.005723,      0.004102,      0.002929,
+    0.002091,      0.001484,      0.001047,      0.00074,       0.00052,
+    0.0003611,     0.0002492,     0.0001719,     0.00012,       0.0000848,
+    0.00006,       0.0000424,     0.00003,       0.0000212,     0.00001499,
+    0.0000106,     0.0000074657,  0.0000052578,  0.0000037029,  0.0000026078,
+    0.0000018366,  0.0000012934,  0.00000091093, 0.00000064153, 0.00000045181,
+];
+
+const CIE1931_Z: [f32; 95] = [
+    0.0006061,   0.001086,    0.001946,    0.003486,    0.00645,
+    0.01055,     0.02005,     0.03621,     0.06785,     0.1102,
+    0.2074,      0.3713,      0.6456,      1.0390501,   1.3856,
+    1.62296,     1.74706,     1.7826,      1.77211,     1.7441,
+    1.6692,      1.5281,      1.28764,     1.0419,      0.81295,
+    0.6162,      0.46518,     0.3533,      0.272,       0.2123,
+    0.1582,      0.1117,      0.07825,     0.05725001,  0.04216,
+    0.02984,     0.0203,      0.0134,      0.008749999, 0.005749999,
+    0.0039,      0.002749999, 0.0021,      0.0018,      0.001650001,
+    0.0014,      0.0011,      0.001,       0.0008,      0.0006,
+    0.00034,     0.00024,     0.00019,     0.0001,      0.00005,
+    0.00003,     0.00002,     0.00001,     0.0,         0.0,
+    0.0,         0.0,         0.0,         0.0,         0.0,
+    0.0,         0.0,         0.0,         0.0,         0.0,
+    0.0,         0.0,         0.0,         0.0,         0.0,
+    0.0,         0.0,         0.0,         0.0,         0.0,
+    0.0,         0.0,         0.0,         0.0,         0.0,
+    0.0,         0.0,         0.0,         0.0,         0.0,
+    0.0,         0.0,         0.0,         0.0,         0.0,
+];
+
+// The CIE 1964 10° standard observer from 360nm to 830nm at 5nm intervals, as tabulated
+// in CIE 015
+const CIE1964_X: [f32; 95] = [
+    0.0000001222,  0.00000091927, 0.0000059586,  0.000033266,   0.000159952,
+    0.00066244,    0.0023616,     0.0072423,     0.0191097,     0.0434,
+    0.084736,      0.140638,      0.204492,      0.264737,      0.314679,
+    0.357719,      0.383734,      0.386726,      0.370702,      0.342957,
+    0.302273,      0.254085,      0.195618,      0.132349,      0.080507,
+    0.041072,      0.016172,      0.005132,      0.003816,      0.015444,
+    0.037465,      0.071358,      0.117749,      0.172953,      0.236491,
+    0.304213,      0.376772,      0.451584,      0.529826,      0.616053,
+    0.705224,      0.793832,      0.878655,      0.951162,      1.01416,
+    1.0743,        1.11852,       1.1343,        1.12399,       1.0891,
+    1.03048,       0.95074,       0.856297,      0.75493,       0.647467,
+    0.53511,       0.431567,      0.34369,       0.268329,      0.2043,
+    0.152568,      0.11221,       0.0812606,     0.05793,       0.0408508,
+    0.028623,      0.0199413,     0.013842,      0.00957688,    0.0066052,
+    0.00455263,    0.0031447,     0.00217496,    0.0015057,     0.00104476,
+    0.00072745,    0.000508258,   0.00035638,    0.000250969,   0.00017773,
+    0.00012639,    0.000090151,   0.0000645258,  0.000046339,   0.0000333117,
+    0.0000239883,  0.0000173265,  0.0000125461,  0.00000909949, 0.00000661312,
+    0.00000481502, 0.00000351337, 0.00000257053, 0.00000188326, 0.00000138238,
+];
+
+const CIE1964_Y: [f32; 95] = [
+    0.000000013398, 0.00000010065,  0.00000065111,  0.000003625,    0.0000173716,
+    0.00007156,     0.0002534,      0.0007685,      0.0020044,      0.004509,
+    0.008756,       0.014456,       0.021391,       0.029497,       0.038676,
+    0.049602,       0.062077,       0.074704,       0.089456,       0.106256,
+    0.128201,       0.152761,       0.18519,        0.21994,        0.253589,
+    0.297665,       0.339133,       0.395379,       0.460777,       0.53136,
+    0.606741,       0.68566,        0.761757,       0.82333,        0.875211,
+    0.92381,        0.961988,       0.9822,         0.991761,       0.99911,
+    0.99734,        0.98238,        0.955552,       0.915175,       0.868934,
+    0.825623,       0.777405,       0.720353,       0.658341,       0.593878,
+    0.527963,       0.461834,       0.398057,       0.339554,       0.283493,
+    0.228254,       0.179828,       0.140211,       0.107633,       0.081187,
+    0.060281,       0.044096,       0.0318004,      0.0226017,      0.0159051,
+    0.0111303,      0.0077488,      0.0053751,      0.00371774,     0.00256456,
+    0.00176847,     0.00122239,     0.00084619,     0.00058644,     0.00040741,
+    0.000284041,    0.00019873,     0.00013955,     0.000098428,    0.000069819,
+    0.000049737,    0.00003554,     0.0000254736,   0.000018321,    0.0000131761,
+    0.00000949782,  0.00000686588,  0.00000497397,  0.00000361044,  0.00000262616,
+    0.00000191303,  0.000001397,    0.00000102226,  0.000000749453, 0.000000550331,
+];
+
+const CIE1964_Z: [f32; 95] = [
+    0.000000535027, 0.0000040283,   0.000026143,    0.00014622,     0.000704776,
+    0.0029278,      0.0104822,      0.032344,       0.0860109,      0.19712,
+    0.389366,       0.65676,        0.972542,       1.2825,         1.55348,
+    1.7985,         1.96728,        2.0273,         1.9948,         1.9007,
+    1.74537,        1.5549,         1.31756,        1.0302,         0.772125,
+    0.57006,        0.415254,       0.302356,       0.218502,       0.159249,
+    0.112044,       0.082248,       0.060709,       0.04305,        0.030451,
+    0.020584,       0.013676,       0.007918,       0.003988,       0.001091,
+    0.0,            0.0,            0.0,            0.0,            0.0,
+    0.0,            0.0,            0.0,            0.0,            0.0,
+    0.0,            0.0,            0.0,            0.0,            0.0,
+    0.0,            0.0,            0.0,            0.0,            0.0,
+    0.0,            0.0,            0.0,            0.0,            0.0,
+    0.0,            0.0,            0.0,            0.0,            0.0,
+    0.0,            0.0,            0.0,            0.0,            0.0,
+    0.0,            0.0,            0.0,            0.0,            0.0,
+    0.0,            0.0,            0.0,            0.0,            0.0,
+    0.0,            0.0,            0.0,            0.0,            0.0,
+    0.0,            0.0,            0.0,            0.0,            0.0,
+];
+
+// Piecewise Gaussian with separate widths on either side of the peak
+#[inline]
+fn lobe(wavelength: f32, peak: f32, below: f32, above: f32) -> f32 {
+    let width = if wavelength < peak { below } else { above };
+    (-0.5 * ((wavelength - peak) / width).powi(2)).exp()
+}
@@ -0,0 +1,582 @@
+//! Summary statistics of many color differences, such as the patches of a proof compared to
+//! their references. [`DeltaEStats`] accumulates the mean, spread, and percentiles, and a
+//! [`DeltaHistogram`] counts the differences in bins. [`LabValue::mean`] and [`LchValue::mean`]
+//! average repeated readings of the same color, and [`ColorStats`] measures how repeatable they
+//! are.
+//!
+//! # Examples
+//!
+//! ```
+//! use deltae::*;
+//!
+//! let reference = LabValue::new(50.0, 0.0, 0.0).unwrap();
+//! let samples = [49.0, 50.5, 52.0, 48.0, 50.0];
+//!
+//! let stats: DeltaEStats = samples.iter()
+//!     .map(|&l| reference.delta(LabValue::new(l, 0.0, 0.0).unwrap(), DE1976))
+//!     .collect();
+//!
+//! assert_eq!(stats.count(), 5);
+//! assert_eq!(stats.mean(), Some(1.1));
+//! assert_eq!(stats.min(), Some(0.0));
+//! assert_eq!(stats.max(), Some(2.0));
+//! assert_eq!(stats.median(), Some(1.0));
+//! ```
+
+use crate::*;
+use std::iter::FromIterator;
+
+/// # DeltaE Statistics
+///
+/// The count, mean, minimum, maximum, standard deviation, and percentiles of a set of [`DeltaE`]
+/// values, accumulated one at a time with [`DeltaEStats::push`].
+///
+/// By default every value is kept, so percentiles are exact. [`DeltaEStats::streaming`] keeps a
+/// constant amount of memory instead, and estimates a fixed set of percentiles with the
+/// [P² algorithm](https://www.cse.wustl.edu/~jain/papers/ftp/psqr.pdf).
+#[derive(Debug, Clone, PartialEq)]
+pub struct DeltaEStats {
+    count: usize,
+    mean: f64,
+    m2: f64,
+    min: f32,
+    max: f32,
+    values: Option<Vec<f32>>,
+    estimators: Vec<P2Quantile>,
+}
+
+impl Default for DeltaEStats {
+    fn default() -> DeltaEStats {
+        DeltaEStats::new()
+    }
+}
+
+impl DeltaEStats {
+    /// New empty [`DeltaEStats`] that keeps every value for exact percentiles
+    pub fn new() -> DeltaEStats {
+        DeltaEStats {
+            count: 0,
+            mean: 0.0,
+            m2: 0.0,
+            min: f32::INFINITY,
+            max: f32::NEG_INFINITY,
+            values: Some(Vec::new()),
+            estimators: Vec::new(),
+        }
+    }
+
+    /// New empty [`DeltaEStats`] that does not keep the values, and only estimates the given
+    /// percentiles (from `0.0` to `100.0`)
+    /// ```
+    /// use deltae::*;
+    ///
+    /// let reference = LabValue::new(50.0, 0.0, 0.0).unwrap();
+    /// let mut stats = DeltaEStats::streaming(&[50.0, 95.0]);
+    /// for i in 0..=1000 {
+    ///     let sample = LabValue::new(50.0, i as f32 / 100.0, 0.0).unwrap();
+    ///     stats.push(reference.delta(sample, DE1976));
+    /// }
+    ///
+    /// assert!((stats.percentile(50.0).unwrap() - 5.0).abs() < 0.05);
+    /// assert!((stats.percentile(95.0).unwrap() - 9.5).abs() < 0.05);
+    /// assert_eq!(stats.percentile(99.0), None);
+    /// ```
+    pub fn streaming(percentiles: &[f32]) -> DeltaEStats {
+        DeltaEStats {
+            values: None,
+            estimators: percentiles.iter().map(|&p| P2Quantile::new(p)).collect(),
+            ..DeltaEStats::new()
+        }
+    }
+
+    /// Add a [`DeltaE`] to the statistics
+    pub fn push(&mut self, delta: DeltaE) {
+        let value = *delta.value();
+
+        // Welford's online algorithm
+        self.count += 1;
+        let diff = value as f64 - self.mean;
+        self.mean += diff / self.count as f64;
+        self.m2 += diff * (value as f64 - self.mean);
+
+        self.min = self.min.min(value);
+        self.max = self.max.max(value);
+
+        if let Some(values) = self.values.as_mut() {
+            values.push(value);
+        }
+        self.estimators.iter_mut().for_each(|estimator| estimator.push(value as f64));
+    }
+
+    /// The number of values
+    pub fn count(&self) -> usize {
+        self.count
+    }
+
+    /// The mean, or `None` if there are no values
+    pub fn mean(&self) -> Option<f32> {
+        self.any().then_some(self.mean as f32)
+    }
+
+    /// The smallest value, or `None` if there are no values
+    pub fn min(&self) -> Option<f32> {
+        self.any().then_some(self.min)
+    }
+
+    /// The largest value, or `None` if there are no values
+    pub fn max(&self) -> Option<f32> {
+        self.any().then_some(self.max)
+    }
+
+    /// The population standard deviation, as in [`DeltaSummary`], or `None` if there are no
+    /// values
+    /// ```
+    /// use deltae::*;
+    ///
+    /// let reference = LabValue::new(50.0, 0.0, 0.0).unwrap();
+    /// let stats: DeltaEStats = [2.0, 4.0, 4.0, 4.0, 5.0, 5.0, 7.0, 9.0].iter()
+    ///     .map(|&l| reference.delta(LabValue::new(50.0 + l, 0.0, 0.0).unwrap(), DE1976))
+    ///     .collect();
+    ///
+    /// assert_eq!(stats.std_dev(), Some(2.0));
+    /// ```
+    pub fn std_dev(&self) -> Option<f32> {
+        self.any().then(|| (self.m2 / self.count as f64).sqrt() as f32)
+    }
+
+    /// The value below which `p` percent of the values fall, from `0.0` to `100.0`. Exact
+    /// percentiles interpolate linearly between the nearest values. Returns `None` if there are no
+    /// values, or if the statistics are [streaming](DeltaEStats::streaming) and do not estimate
+    /// the percentile.
+    pub fn percentile(&self, p: f32) -> Option<f32> {
+        if !self.any() {
+            return None;
+        }
+
+        match &self.values {
+            Some(values) => {
+                let mut sorted = values.clone();
+                sorted.sort_by(|a, b| a.total_cmp(b));
+
+                let rank = (p.clamp(0.0, 100.0) / 100.0) * (sorted.len() - 1) as f32;
+                let (lower, upper) = (rank.floor() as usize, rank.ceil() as usize);
+                let fraction = rank - lower as f32;
+                Some(sorted[lower] + (sorted[upper] - sorted[lower]) * fraction)
+            }
+            None => self.estimators.iter()
+                .find(|estimator| estimator.percentile == p)
+                .map(|estimator| estimator.estimate() as f32),
+        }
+    }
+
+    /// The 50th percentile
+    pub fn median(&self) -> Option<f32> {
+        self.percentile(50.0)
+    }
+
+    fn any(&self) -> bool {
+        self.count > 0
+    }
+}
+
+impl Extend<DeltaE> for DeltaEStats {
+    fn extend<I: IntoIterator<Item = DeltaE>>(&mut self, iter: I) {
+        iter.into_iter().for_each(|delta| self.push(delta));
+    }
+}
+
+impl FromIterator<DeltaE> for DeltaEStats {
+    fn from_iter<I: IntoIterator<Item = DeltaE>>(iter: I) -> DeltaEStats {
+        let mut stats = DeltaEStats::new();
+        stats.extend(iter);
+        stats
+    }
+}
+
+/// # Color Statistics
+///
+/// The spread of repeated readings of the same color, as in instrument repeatability and
+/// inter-instrument agreement studies: the mean and population standard deviation of each Lab
+/// component, and the mean color difference from the mean (MCDM) by a [`DEMethod`], with the mean
+/// as the reference color.
+///
+/// To compare instruments, compare the [`ColorStats::mean`] of each with [`Delta::delta`].
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct ColorStats {
+    /// The number of readings
+    pub count: usize,
+    /// The mean of the readings
+    pub mean: LabValue,
+    /// The standard deviation of L\*, a\*, and b\*
+    pub std_dev: [f32; 3],
+    /// The method of [`ColorStats::mean_delta`] and [`ColorStats::max_delta`]
+    pub method: DEMethod,
+    /// The mean color difference of the readings from the mean (MCDM)
+    pub mean_delta: f32,
+    /// The largest color difference of a reading from the mean
+    pub max_delta: f32,
+}
+
+impl ColorStats {
+    /// New [`ColorStats`] from readings, or `None` if there are none
+    /// ```
+    /// use deltae::*;
+    ///
+    /// let readings = [
+    ///     LabValue::new(50.0, 10.0, 0.0).unwrap(),
+    ///     LabValue::new(52.0, 10.0, 0.0).unwrap(),
+    ///     LabValue::new(51.0, 11.0, 0.0).unwrap(),
+    ///     LabValue::new(51.0, 9.0, 0.0).unwrap(),
+    /// ];
+    /// let stats = ColorStats::new(readings.iter(), DE1976).unwrap();
+    ///
+    /// assert_eq!(stats.mean, LabValue::new(51.0, 10.0, 0.0).unwrap());
+    /// assert_eq!(stats.std_dev, [0.70710677, 0.70710677, 0.0]);
+    /// assert_eq!((stats.mean_delta, stats.max_delta), (1.0, 1.0));
+    /// ```
+    pub fn new<C, I>(readings: I, method: DEMethod) -> Option<ColorStats>
+    where C: Into<LabValue>, I: IntoIterator<Item = C> {
+        let readings: Vec<LabValue> = readings.into_iter().map(Into::into).collect();
+        let mean = LabValue::mean(readings.iter())?;
+        let count = readings.len();
+
+        let std_dev = |component: fn(&LabValue) -> f32, mean: f32| {
+            let variance = readings.iter()
+                .map(|lab| (component(lab) as f64 - mean as f64).powi(2))
+                .sum::<f64>() / count as f64;
+            variance.sqrt() as f32
+        };
+
+        let deltas: DeltaEStats = readings.iter().deltas_against(mean, method).collect();
+
+        Some(ColorStats {
+            count,
+            mean,
+            std_dev: [std_dev(|lab| lab.l, mean.l), std_dev(|lab| lab.a, mean.a), std_dev(|lab| lab.b, mean.b)],
+            method,
+            mean_delta: deltas.mean()?,
+            max_delta: deltas.max()?,
+        })
+    }
+}
+
+impl LabValue {
+    /// The mean of colors, or `None` if there are none
+    /// ```
+    /// use deltae::*;
+    ///
+    /// let readings = [
+    ///     LabValue::new(50.1, 20.0, -10.2).unwrap(),
+    ///     LabValue::new(49.9, 20.4, -9.8).unwrap(),
+    /// ];
+    /// assert_eq!(LabValue::mean(readings.iter()).unwrap().round_to(2), LabValue::new(50.0, 20.2, -10.0).unwrap());
+    /// assert_eq!(LabValue::mean(Vec::<LabValue>::new()), None);
+    /// ```
+    pub fn mean<C: Into<LabValue>, I: IntoIterator<Item = C>>(colors: I) -> Option<LabValue> {
+        LabValue::weighted_mean(colors.into_iter().map(|color| (color, 1.0)))
+    }
+
+    /// The mean of colors, each with a weight. Returns `None` if there are no colors or the
+    /// weights add up to zero.
+    pub fn weighted_mean<C, I>(colors: I) -> Option<LabValue>
+    where C: Into<LabValue>, I: IntoIterator<Item = (C, f32)> {
+        let (mut l, mut a, mut b, mut total) = (0.0, 0.0, 0.0, 0.0);
+        for (color, weight) in colors {
+            let lab = color.into();
+            let weight = weight as f64;
+            l += lab.l as f64 * weight;
+            a += lab.a as f64 * weight;
+            b += lab.b as f64 * weight;
+            total += weight;
+        }
+
+        (total != 0.0).then(|| LabValue {
+            l: (l / total) as f32,
+            a: (a / total) as f32,
+            b: (b / total) as f32,
+        })
+    }
+}
+
+impl LchValue {
+    /// The mean of colors, or `None` if there are none. Lightness and chroma are averaged
+    /// arithmetically, and hue as an angle, so that the mean of 350° and 10° is 0° rather than 180°.
+    /// ```
+    /// use deltae::*;
+    ///
+    /// let readings = [
+    ///     LchValue::new(50.0, 30.0, 350.0).unwrap(),
+    ///     LchValue::new(52.0, 34.0, 10.0).unwrap(),
+    /// ];
+    /// let mean = LchValue::mean(readings.iter().copied()).unwrap();
+    ///
+    /// assert_eq!((mean.l, mean.c), (51.0, 32.0));
+    /// assert!(mean.h.abs() < 1e-3 || (mean.h - 360.0).abs() < 1e-3);
+    /// ```
+    pub fn mean<C: Into<LchValue>, I: IntoIterator<Item = C>>(colors: I) -> Option<LchValue> {
+        LchValue::weighted_mean(colors.into_iter().map(|color| (color, 1.0)))
+    }
+
+    /// The mean of colors, each with a weight, averaging hue as an angle. Returns `None` if there
+    /// are no colors or the weights add up to zero. When the hues cancel each other out, such as
+    /// two colors with opposite hues, the mean hue is `0.0`.
+    pub fn weighted_mean<C, I>(colors: I) -> Option<LchValue>
+    where C: Into<LchValue>, I: IntoIterator<Item = (C, f32)> {
+        let (mut l, mut c, mut sin, mut cos, mut total) = (0.0, 0.0, 0.0, 0.0, 0.0);
+        for (color, weight) in colors {
+            let lch = color.into();
+            let weight = weight as f64;
+            let (h_sin, h_cos) = (lch.h as f64).to_radians().sin_cos();
+            l += lch.l as f64 * weight;
+            c += lch.c as f64 * weight;
+            sin += h_sin * weight;
+            cos += h_cos * weight;
+            total += weight;
+        }
+
+        if total == 0.0 {
+            return None;
+        }
+
+        // The resultant of opposite hues is only rounding error
+        let h = if (sin.powi(2) + cos.powi(2)).sqrt() < 1e-9 * total.abs() {
+            0.0
+        } else {
+            sin.atan2(cos).to_degrees().rem_euclid(360.0)
+        };
+
+        Some(LchValue { l: (l / total) as f32, c: (c / total) as f32, h: h as f32 })
+    }
+}
+
+// An estimate of one percentile that moves five markers toward the minimum, the percentile, the
+// points halfway to it from each end, and the maximum
+#[derive(Debug, Clone, PartialEq)]
+struct P2Quantile {
+    percentile: f32,
+    count: usize,
+    heights: [f64; 5],
+    positions: [f64; 5],
+    desired: [f64; 5],
+    increments: [f64; 5],
+}
+
+impl P2Quantile {
+    fn new(percentile: f32) -> P2Quantile {
+        let p = percentile.clamp(0.0, 100.0) as f64 / 100.0;
+        P2Quantile {
+            percentile,
+            count: 0,
+            heights: [0.0; 5],
+            positions: [1.0, 2.0, 3.0, 4.0, 5.0],
+            desired: [1.0, 1.0 + 2.0 * p, 1.0 + 4.0 * p, 3.0 + 2.0 * p, 5.0],
+            increments: [0.0, p / 2.0, p, (1.0 + p) / 2.0, 1.0],
+        }
+    }
+
+    fn push(&mut self, value: f64) {
+        if self.count < 5 {
+            self.heights[self.count] = value;
+            self.count += 1;
+            if self.count == 5 {
+                self.heights.sort_by(|a, b| a.total_cmp(b));
+            }
+            return;
+        }
+        self.count += 1;
+
+        let h = &mut self.heights;
+        let cell = if value < h[0] {
+            h[0] = value;
+            0
+        } else if value >= h[4] {
+            h[4] = value;
+            3
+        } else {
+            (0..4).rev().find(|&i| h[i] <= value).unwrap_or(0)
+        };
+
+        self.positions.iter_mut().skip(cell + 1).for_each(|n| *n += 1.0);
+        self.desired.iter_mut().zip(self.increments.iter()).for_each(|(d, i)| *d += i);
+
+        for i in 1..4 {
+            let diff = self.desired[i] - self.positions[i];
+            let n = &self.positions;
+            if (diff >= 1.0 && n[i + 1] - n[i] > 1.0) || (diff <= -1.0 && n[i - 1] - n[i] < -1.0) {
+                let d = diff.signum();
+                let parabolic = self.parabolic(i, d);
+                self.heights[i] = if self.heights[i - 1] < parabolic && parabolic < self.heights[i + 1] {
+                    parabolic
+                } else {
+                    self.linear(i, d)
+                };
+                self.positions[i] += d;
+            }
+        }
+    }
+
+    fn parabolic(&self, i: usize, d: f64) -> f64 {
+        let (q, n) = (&self.heights, &self.positions);
+        q[i] + d / (n[i + 1] - n[i - 1]) * (
+            (n[i] - n[i - 1] + d) * (q[i + 1] - q[i]) / (n[i + 1] - n[i])
+          + (n[i + 1] - n[i] - d) * (q[i] - q[i - 1]) / (n[i] - n[i - 1])
+        )
+    }
+
+    fn linear(&self, i: usize, d: f64) -> f64 {
+        let (q, n) = (&self.heights, &self.positions);
+        let j = if d > 0.0 { i + 1 } else { i - 1 };
+        q[i] + d * (q[j] - q[i]) / (n[j] - n[i])
+    }
+
+    // Until there are five values, the exact percentile of the values so far
+    fn estimate(&self) -> f64 {
+        if self.count >= 5 {
+            return self.heights[2];
+        }
+
+        let mut sorted = self.heights[..self.count].to_vec();
+        sorted.sort_by(|a, b| a.total_cmp(b));
+        let rank = (self.percentile.clamp(0.0, 100.0) as f64 / 100.0) * (self.count - 1) as f64;
+        sorted[rank.round() as usize]
+    }
+}
+
+/// # DeltaE Histogram
+///
+/// The number of [`DeltaE`] values between each pair of bin edges. Below the first edge and at or
+/// above the last edge are open-ended bins, so there is one more bin than there are edges. Each
+/// bin includes its lower edge.
+///
+/// With the `serde` feature, the histogram serializes as its edges and counts. [`fmt::Display`]
+/// renders it as a text bar chart.
+/// ```
+/// use deltae::*;
+///
+/// let reference = LabValue::new(50.0, 0.0, 0.0).unwrap();
+/// let histogram: DeltaHistogram = [0.2, 0.7, 0.9, 1.5, 3.0, 6.0].iter()
+///     .map(|&l| reference.delta(LabValue::new(50.0 + l, 0.0, 0.0).unwrap(), DE1976))
+///     .collect();
+///
+/// assert_eq!(histogram.edges(), &[0.5, 1.0, 2.0, 4.0]);
+/// assert_eq!(histogram.counts(), &[1, 2, 1, 1, 1]);
+///
+/// print!("{}", histogram);
+/// //   < 0.5 | ##########           1  16.7%
+/// // 0.5 - 1 | #################### 2  33.3%
+/// //   1 - 2 | ##########           1  16.7%
+/// //   2 - 4 | ##########           1  16.7%
+/// //    >= 4 | ##########           1  16.7%
+/// ```
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct DeltaHistogram {
+    edges: Vec<f32>,
+    counts: Vec<usize>,
+}
+
+impl Default for DeltaHistogram {
+    /// Bins below 0.5, from 0.5 to 1, 1 to 2, 2 to 4, and at or above 4
+    fn default() -> DeltaHistogram {
+        DeltaHistogram { edges: vec![0.5, 1.0, 2.0, 4.0], counts: vec![0; 5] }
+    }
+}
+
+impl DeltaHistogram {
+    /// New empty [`DeltaHistogram`] from its bin edges. Returns an error if the edges are not
+    /// finite and strictly increasing.
+    /// ```
+    /// use deltae::*;
+    ///
+    /// assert!(DeltaHistogram::new(&[1.0, 2.0, 3.0]).is_ok());
+    /// assert!(DeltaHistogram::new(&[2.0, 1.0]).is_err());
+    /// assert!(DeltaHistogram::new(&[1.0, f32::INFINITY]).is_err());
+    /// ```
+    pub fn new(edges: &[f32]) -> ValueResult<DeltaHistogram> {
+        let increasing = edges.windows(2).all(|pair| pair[0] < pair[1]);
+        if !increasing || edges.iter().any(|edge| !edge.is_finite()) {
+            return Err(ValueError::OutOfBounds);
+        }
+
+        Ok(DeltaHistogram { edges: edges.to_vec(), counts: vec![0; edges.len() + 1] })
+    }
+
+    /// Add a [`DeltaE`] to its bin
+    pub fn push(&mut self, delta: DeltaE) {
+        let value = *delta.value();
+        let bin = self.edges.iter().take_while(|&&edge| value >= edge).count();
+        self.counts[bin] += 1;
+    }
+
+    /// The bin edges
+    pub fn edges(&self) -> &[f32] {
+        &self.edges
+    }
+
+    /// The number of values in each bin
+    pub fn counts(&self) -> &[usize] {
+        &self.counts
+    }
+
+    /// The total number of values
+    pub fn total(&self) -> usize {
+        self.counts.iter().sum()
+    }
+
+    /// The lower and upper edges of each bin, with `None` for the open ends, and its count
+    pub fn bins(&self) -> impl Iterator<Item = (Option<f32>, Option<f32>, usize)> + '_ {
+        self.counts.iter().enumerate().map(move |(i, &count)| {
+            let lower = i.checked_sub(1).map(|j| self.edges[j]);
+            let upper = self.edges.get(i).copied();
+            (lower, upper, count)
+        })
+    }
+}
+
+impl Extend<DeltaE> for DeltaHistogram {
+    fn extend<I: IntoIterator<Item = DeltaE>>(&mut self, iter: I) {
+        iter.into_iter().for_each(|delta| self.push(delta));
+    }
+}
+
+impl FromIterator<DeltaE> for DeltaHistogram {
+    /// Collect into the [`DeltaHistogram::default`] bins
+    fn from_iter<I: IntoIterator<Item = DeltaE>>(iter: I) -> DeltaHistogram {
+        let mut histogram = DeltaHistogram::default();
+        histogram.extend(iter);
+        histogram
+    }
+}
+
+impl fmt::Display for DeltaHistogram {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        const BAR_WIDTH: usize = 20;
+
+        let labels: Vec<String> = self.bins()
+            .map(|bin| match bin {
+                (None, Some(upper), _) => format!("< {}", upper),
+                (Some(lower), None, _) => format!(">= {}", lower),
+                (Some(lower), Some(upper), _) => format!("{} - {}", lower, upper),
+                (None, None, _) => "all".to_string(),
+            })
+            .collect();
+        let label_width = labels.iter().map(String::len).max().unwrap_or(0);
+
+        let total = self.total();
+        let most = self.counts.iter().copied().max().unwrap_or(0);
+        let count_width = most.to_string().len();
+
+        for (label, &count) in labels.iter().zip(self.counts.iter()) {
+            let bar = (count * BAR_WIDTH).checked_div(most).unwrap_or(0);
+            let percent = if total == 0 { 0.0 } else { count as f32 * 100.0 / total as f32 };
+            writeln!(
+                f, "{:>lw$} | {:<bw$} {:>cw$} {:>5.1}%",
+                label, "#".repeat(bar), count, percent,
+                lw = label_width, bw = BAR_WIDTH, cw = count_width,
+            )?;
+        }
+
+        Ok(())
+    }
+}
@@ -0,0 +1,292 @@
+//! Reading named colors from Adobe Swatch Exchange (`.ase`) and Photoshop (`.aco`) palettes, and
+//! reading and writing GIMP (`.gpl`) palettes. Requires the `swatches` feature.
+//!
+//! Swatches are read in the color model they were saved in. RGB and Lab swatches convert
+//! directly to the crate types. Gray swatches are treated as neutral RGB, and CMYK swatches can
+//! not be compared without a printing profile, so [`SwatchColor::to_lab`] returns `None` for
+//! them.
+//!
+//! # Examples
+//!
+//! ```no_run
+//! use deltae::*;
+//!
+//! let bytes = std::fs::read("brand.ase").unwrap();
+//! let reference = LabValue::new(54.29, 80.8, 69.89).unwrap();
+//!
+//! for swatch in swatch::read_ase(&bytes).unwrap() {
+//!     if let Some(lab) = swatch.color.to_lab(RgbSystem::Srgb) {
+//!         println!("{}: {}", swatch.name, reference.delta(lab, DE2000));
+//!     }
+//! }
+//! ```
+
+use std::convert::TryInto;
+use std::io;
+use std::str::FromStr;
+
+use crate::*;
+
+/// A named color from a palette
+#[derive(Debug, Clone, PartialEq)]
+pub struct Swatch {
+    /// The name of the color. Empty if the palette does not name its colors.
+    pub name: String,
+    /// The color
+    pub color: SwatchColor,
+}
+
+/// The color of a [`Swatch`], in the color model it was saved in
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum SwatchColor {
+    /// An RGB color
+    Rgb(RgbNominalValue),
+    /// A CIE L\*a\*b\* color
+    Lab(LabValue),
+    /// A gray value from `0.0` (black) to `1.0` (white)
+    Gray(f32),
+    /// Ink coverage of cyan, magenta, yellow, and black, from `0.0` to `1.0`
+    Cmyk([f32; 4]),
+}
+
+impl SwatchColor {
+    /// Returns the [`LabValue`] of the color, interpreting RGB and gray values in an
+    /// [`RgbSystem`]. Returns `None` for CMYK colors.
+    pub fn to_lab(&self, system: RgbSystem) -> Option<LabValue> {
+        let rgb = match *self {
+            SwatchColor::Lab(lab) => return Some(lab),
+            SwatchColor::Cmyk(_) => return None,
+            SwatchColor::Rgb(rgb) => rgb,
+            SwatchColor::Gray(v) => RgbNominalValue { r: v, g: v, b: v },
+        };
+
+        Some(LabValue::from_rgb(rgb, system, Some(ChromaticAdaptationMethod::Bradford)))
+    }
+
+    /// Returns the [`RgbNominalValue`] of the color in an [`RgbSystem`]. Lab colors that are out of
+    /// the gamut of the system are clipped. Returns `None` for CMYK colors.
+    pub fn to_rgb(&self, system: RgbSystem) -> Option<RgbNominalValue> {
+        let bradford = Some(ChromaticAdaptationMethod::Bradford);
+        match *self {
+            SwatchColor::Rgb(rgb) => Some(rgb),
+            SwatchColor::Lab(lab) => Some(RgbNominalValue::from_lab(lab, system, bradford)),
+            SwatchColor::Gray(v) => Some(RgbNominalValue { r: v, g: v, b: v }),
+            SwatchColor::Cmyk(_) => None,
+        }
+    }
+}
+
+/// Read the swatches of an Adobe Swatch Exchange (`.ase`) file. Groups are flattened.
+/// Will return `Err()` if the file is malformed or a swatch is out of range.
+pub fn read_ase(bytes: &[u8]) -> ValueResult<Vec<Swatch>> {
+    if bytes.get(0..4) != Some(b"ASEF") {
+        return Err(ValueError::BadFormat);
+    }
+
+    let count = u32_at(bytes, 8)?;
+    let mut offset = 12;
+    let mut swatches = Vec::new();
+
+    for _ in 0..count {
+        let kind = u16_at(bytes, offset)?;
+        let length = u32_at(bytes, offset + 2)? as usize;
+        let block = offset
+            .checked_add(6 + length)
+            .and_then(|end| bytes.get(offset + 6..end))
+            .ok_or(ValueError::BadFormat)?;
+        offset += 6 + length;
+
+        // 0xC001 and 0xC002 begin and end a group
+        if kind != 0x0001 {
+            continue;
+        }
+
+        let name_length = u16_at(block, 0)? as usize;
+        let name = utf16_at(block, 2, name_length)?;
+
+        let model_offset = 2 + name_length * 2;
+        let model = block.get(model_offset..model_offset + 4).ok_or(ValueError::BadFormat)?;
+        let v = |i: usize| f32_at(block, model_offset + 4 + i * 4);
+
+        let color = match model {
+            b"RGB " => SwatchColor::Rgb(RgbNominalValue { r: v(0)?, g: v(1)?, b: v(2)? }.validate()?),
+            b"LAB " => SwatchColor::Lab(LabValue { l: v(0)? * 100.0, a: v(1)?, b: v(2)? }.validate()?),
+            b"Gray" => SwatchColor::Gray(unit(v(0)?)?),
+            b"CMYK" => SwatchColor::Cmyk([unit(v(0)?)?, unit(v(1)?)?, unit(v(2)?)?, unit(v(3)?)?]),
+            _ => return Err(ValueError::BadFormat),
+        };
+
+        swatches.push(Swatch { name, color });
+    }
+
+    Ok(swatches)
+}
+
+/// Read the swatches of a Photoshop color swatch (`.aco`) file. Names are read from the version 2
+/// section when it is present. Colors from color books (Pantone, Trumatch, etc.) are skipped,
+/// since only their book and index are stored.
+/// Will return `Err()` if the file is malformed or a swatch is out of range.
+pub fn read_aco(bytes: &[u8]) -> ValueResult<Vec<Swatch>> {
+    if u16_at(bytes, 0)? != 1 {
+        return Err(ValueError::BadFormat);
+    }
+
+    let count = u16_at(bytes, 2)? as usize;
+    let mut offset = 4;
+    let mut colors = Vec::with_capacity(count);
+    for _ in 0..count {
+        colors.push(aco_color(bytes, offset)?);
+        offset += 10;
+    }
+
+    // The version 2 section repeats the colors with their names
+    let mut names = vec![String::new(); count];
+    if offset < bytes.len() {
+        if u16_at(bytes, offset)? != 2 || u16_at(bytes, offset + 2)? as usize != count {
+            return Err(ValueError::BadFormat);
+        }
+        offset += 4;
+
+        for name in names.iter_mut() {
+            let length = u32_at(bytes, offset + 10)? as usize;
+            *name = utf16_at(bytes, offset + 14, length)?;
+            offset += 14 + length * 2;
+        }
+    }
+
+    Ok(colors
+        .into_iter()
+        .zip(names)
+        .filter_map(|(color, name)| color.map(|color| Swatch { name, color }))
+        .collect())
+}
+
+/// Read the swatches of a GIMP palette (`.gpl`) file.
+/// Will return `Err()` if the header is missing or a color is malformed.
+pub fn read_gpl(s: &str) -> ValueResult<Vec<Swatch>> {
+    let mut lines = s.lines();
+    if lines.next().map(str::trim) != Some("GIMP Palette") {
+        return Err(ValueError::BadFormat);
+    }
+
+    let mut swatches = Vec::new();
+    for line in lines.map(str::trim) {
+        if line.is_empty() || line.starts_with('#') || line.starts_with("Name:")
+            || line.starts_with("Columns:")
+        {
+            continue;
+        }
+
+        // Three channels, then the rest of the line is the name
+        let mut rest = line;
+        let mut channels = Vec::with_capacity(3);
+        for _ in 0..3 {
+            let end = rest.find(char::is_whitespace).unwrap_or(rest.len());
+            channels.push(&rest[..end]);
+            rest = rest[end..].trim_start();
+        }
+        let name = rest;
+
+        let rgb = RgbValue::from_str(&channels.join(" "))?;
+        swatches.push(Swatch { name: name.to_string(), color: SwatchColor::Rgb(rgb.into()) });
+    }
+
+    Ok(swatches)
+}
+
+/// Write swatches to a GIMP palette (`.gpl`) file with a palette name. Lab and gray colors are
+/// converted to 8-bit RGB in an [`RgbSystem`].
+/// Will return [`Error::UnsupportedConversion`] if a swatch is a CMYK color, or [`Error::Io`] if
+/// the writer fails.
+pub fn write_gpl<W: io::Write>(
+    mut writer: W,
+    name: &str,
+    swatches: &[Swatch],
+    system: RgbSystem,
+) -> ValueResult<()> {
+    writeln!(writer, "GIMP Palette")?;
+    writeln!(writer, "Name: {}", name)?;
+    writeln!(writer, "#")?;
+
+    for swatch in swatches {
+        let rgb = swatch.color.to_rgb(system.clone()).map(RgbValue::from).ok_or(Error::UnsupportedConversion)?;
+        writeln!(writer, "{:3} {:3} {:3}\t{}", rgb.r, rgb.g, rgb.b, swatch.name)?;
+    }
+
+    Ok(())
+}
+
+// Read an ACO color record: a color space and four 16-bit values
+fn aco_color(bytes: &[u8], offset: usize) -> ValueResult<Option<SwatchColor>> {
+    let v = |i: usize| u16_at(bytes, offset + 2 + i * 2);
+    let nominal = |i: usize| -> ValueResult<f32> { Ok(v(i)? as f32 / 65535.0) };
+
+    let color = match u16_at(bytes, offset)? {
+        0 => SwatchColor::Rgb(RgbNominalValue { r: nominal(0)?, g: nominal(1)?, b: nominal(2)? }),
+        1 => SwatchColor::Rgb(hsb_to_rgb(nominal(0)? * 360.0, nominal(1)?, nominal(2)?)),
+        // Ink coverage is inverted: 0 is 100%
+        2 => SwatchColor::Cmyk([
+            1.0 - nominal(0)?, 1.0 - nominal(1)?, 1.0 - nominal(2)?, 1.0 - nominal(3)?,
+        ]),
+        7 => {
+            let lab = LabValue {
+                l: v(0)? as f32 / 100.0,
+                a: v(1)? as i16 as f32 / 100.0,
+                b: v(2)? as i16 as f32 / 100.0,
+            };
+            SwatchColor::Lab(lab.validate()?)
+        }
+        // Grayscale is stored as ink coverage from 0 to 10000
+        8 => SwatchColor::Gray(1.0 - unit(v(0)? as f32 / 10000.0)?),
+        9 => SwatchColor::Cmyk([
+            unit(v(0)? as f32 / 10000.0)?,
+            unit(v(1)? as f32 / 10000.0)?,
+            unit(v(2)? as f32 / 10000.0)?,
+            unit(v(3)? as f32 / 10000.0)?,
+        ]),
+        _ => return Ok(None),
+    };
+
+    Ok(Some(color))
+}
+
+fn hsb_to_rgb(h: f32, s: f32, b: f32) -> RgbNominalValue {
+    let f = |n: f32| {
+        let k = (n + h / 60.0) % 6.0;
+        b - b * s * k.min(4.0 - k).clamp(0.0, 1.0)
+    };
+
+    RgbNominalValue { r: f(5.0), g: f(3.0), b: f(1.0) }
+}
+
+fn unit(v: f32) -> ValueResult<f32> {
+    if (0.0..=1.0).contains(&v) {
+        Ok(v)
+    } else {
+        Err(ValueError::OutOfBounds)
+    }
+}
+
+// Read a null terminated UTF-16BE string of `length` code units, including the terminator
+fn utf16_at(bytes: &[u8], i: usize, length: usize) -> ValueResult<String> {
+    let units = (0..length)
+        .map(|n| u16_at(bytes, i + n * 2))
+        .collect::<ValueResult<Vec<u16>>>()?;
+
+    let units = units.strip_suffix(&[0]).unwrap_or(&units);
+    String::from_utf16(units).map_err(|_| ValueError::BadFormat)
+}
+
+fn u16_at(bytes: &[u8], i: usize) -> ValueResult<u16> {
+    let b = bytes.get(i..i + 2).ok_or(ValueError::BadFormat)?;
+    Ok(u16::from_be_bytes(b.try_into().unwrap()))
+}
+
+fn u32_at(bytes: &[u8], i: usize) -> ValueResult<u32> {
+    let b = bytes.get(i..i + 4).ok_or(ValueError::BadFormat)?;
+    Ok(u32::from_be_bytes(b.try_into().unwrap()))
+}
+
+fn f32_at(bytes: &[u8], i: usize) -> ValueResult<f32> {
+    Ok(f32::from_bits(u32_at(bytes, i)?))
+}
@@ -1,6 +1,9 @@
 use super::*;
 use color::{LabValue, LchValue, XyzValue};
+use proptest::num;
+use proptest::prelude::*;
 use std::convert::TryFrom;
+#[cfg(feature = "parse")]
 use std::str::FromStr;
 
 #[test]
@@ -42,6 +45,7 @@ fn lab_to_xyz() {
     assert_eq!(lab.round_to(4), lab2.round_to(4));
 }
 
+#[cfg(feature = "parse")]
 #[test]
 fn lab_string() {
     let good = &[
@@ -52,10 +56,15 @@ fn lab_string() {
         "0,1,-1",
         "50,-1,-1",
         "99.9999,127.9999,-127.9999",
+        "95.08 -0.17 -10.81",
+        "95.08\t-0.17\t-10.81",
+        "95.08;-0.17;-10.81",
+        "95.08; -0.17; -10.81",
+        "  95.08   -0.17 \t -10.81  ",
     ];
 
     for i in good {
-        assert!(LabValue::from_str(i).is_ok());
+        assert!(LabValue::from_str(i).is_ok(), "{}", i);
     }
 
     let bad = &[
@@ -68,160 +77,3748 @@ fn lab_string() {
         "1,2,3,4",
         "1,2",
         "1",
+        "1,2,3,derp",
+        "1 2 3 4",
+        "1;2",
+        "1.2.3",
+    ];
+
+    for i in bad {
+        assert!(LabValue::from_str(i).is_err(), "{}", i);
+    }
+}
+
+#[cfg(all(feature = "rgb", feature = "parse"))]
+#[test]
+fn labeled_string() {
+    let lab = LabValue { l: 50.0, a: 2.3, b: -4.0 };
+    for s in &[
+        "L:50 a:2.3 b:-4",
+        "L*=50, a*=2.3, b*=-4",
+        "b: -4; a: 2.3; L: 50",
+        "l=50,a=2.3,b=-4",
+        "  a*: 2,3  b*: -4  L*: 50 ",
+        "L:50\ta:2.3\tb:-4",
+    ] {
+        assert_eq!(LabValue::from_str(s).unwrap(), lab, "{}", s);
+    }
+
+    assert_eq!(
+        LchValue::from_str("L*:50 C*:10 h°:270").unwrap(),
+        LchValue { l: 50.0, c: 10.0, h: 270.0 }
+    );
+    assert_eq!(
+        XyzValue::from_str("Z=0.3 Y=0.2 X=0.1").unwrap(),
+        XyzValue { x: 0.1, y: 0.2, z: 0.3 }
+    );
+    assert_eq!(RgbValue::from_str("R:128 G:64 B:255").unwrap(), RgbValue::new(128, 64, 255));
+    assert_eq!(
+        RgbNominalValue::from_str("r=1, g=0.5, b=0").unwrap(),
+        RgbNominalValue { r: 1.0, g: 0.5, b: 0.0 }
+    );
+
+    let bad = &[
+        "L:50 a:2.3",
+        "L:50 a:2.3 a:2.3",
+        "L:50 a:2.3 b:-4 c:1",
+        "L:50 a:2.3 x:-4",
+        "50 a:2.3 b:-4",
+        "L:50 a:2.3 b:",
+        "L:50 a:2.3 b:derp",
+        ":50 a:2.3 b:-4",
+        "L:50 a:2.3 b:-4 5",
+        "L:101 a:2.3 b:-4",
+    ];
+
+    for s in bad {
+        assert!(LabValue::from_str(s).is_err(), "{}", s);
+    }
+}
+
+#[cfg(all(feature = "rgb", feature = "parse"))]
+#[test]
+fn parse_error() {
+    use ParseErrorKind::*;
+
+    let cases: &[(&str, ParseErrorKind, Option<&str>, &str, usize)] = &[
+        ("89.73, 1.88, derp",     Malformed,      Some("b"), "derp",  13),
+        ("89; 1,8x; -6",          Malformed,      Some("a"), "1,8x",  4),
+        ("189.73, 1.88, -6.96",   OutOfRange,     Some("L"), "189.73", 0),
+        ("89.73 1.88",            Missing,        Some("b"), "",      10),
+        ("89.73 1.88 -6.96 4",    Unexpected,     None,      "4",     17),
+        ("L:50 a:2.3 x:-4",       UnknownLabel,   None,      "x",     11),
+        ("L:50 a:2.3 A*:-4",      DuplicateLabel, Some("a"), "A*",    11),
+        ("L:50 b:-4",             Missing,        Some("a"), "",      9),
+        ("L:50 a: b:-4",          Missing,        Some("a"), "",      8),
+        ("L:50 a:2.3 b:-400",     OutOfRange,     Some("b"), "-400",  13),
+        ("oops L:50 a:2.3 b:-4",  Unexpected,     None,      "oops",  0),
+    ];
+
+    for &(s, kind, component, token, position) in cases {
+        match LabValue::from_str(s) {
+            Err(ValueError::Parse(e)) => {
+                assert_eq!(e.kind(), kind, "{}", s);
+                assert_eq!(e.component(), component, "{}", s);
+                assert_eq!(e.token(), token, "{}", s);
+                assert_eq!(e.position(), position, "{}", s);
+            }
+            other => panic!("{}: {:?}", s, other),
+        }
+    }
+
+    match RgbValue::from_str("255, 127.5, 0") {
+        Err(ValueError::Parse(e)) => {
+            assert_eq!((e.kind(), e.component(), e.token()), (Malformed, Some("G"), "127.5"));
+        }
+        other => panic!("{:?}", other),
+    }
+
+    let err = XyzValue::from_str("0.5 1.5 0.5").unwrap_err();
+    assert_eq!(err.to_string(), r#"value "1.5" out of range for Y at position 4"#);
+    assert!(std::error::Error::source(&err).is_none());
+}
+
+#[cfg(all(feature = "serde", feature = "rgb", feature = "parse"))]
+#[test]
+fn serde_round_trip() {
+    let lab = LabValue::new(89.73, 1.88, -6.96).unwrap();
+    let json = serde_json::to_string(&lab).unwrap();
+    assert_eq!(json, r#"{"l":89.73,"a":1.88,"b":-6.96}"#);
+    assert_eq!(serde_json::from_str::<LabValue>(&json).unwrap(), lab);
+
+    let lch = LchValue::from(lab);
+    assert_eq!(serde_json::from_str::<LchValue>(&serde_json::to_string(&lch).unwrap()).unwrap(), lch);
+    let xyz = XyzValue::from(lab);
+    assert_eq!(serde_json::from_str::<XyzValue>(&serde_json::to_string(&xyz).unwrap()).unwrap(), xyz);
+    let rgb = RgbValue::new(102, 51, 153);
+    assert_eq!(serde_json::from_str::<RgbValue>(r#"{"r":102,"g":51,"b":153}"#).unwrap(), rgb);
+
+    for method in &[DE2000, DE1976, DE1994G, DE1994T, DECMC1, DECMC2] {
+        let json = serde_json::to_string(method).unwrap();
+        assert_eq!(serde_json::from_str::<DEMethod>(&json).unwrap(), *method);
+    }
+    assert_eq!(serde_json::to_string(&DECMC2).unwrap(), r#"{"DECMC":[2.0,1.0]}"#);
+    assert_eq!(serde_json::from_str::<Illuminant>(r#""D65""#).unwrap(), Illuminant::D65);
+
+    let de = DeltaE::new(lab, LabValue::new(95.08, -0.17, -10.81).unwrap(), DE2000);
+    let de2: DeltaE = serde_json::from_str(&serde_json::to_string(&de).unwrap()).unwrap();
+    assert_eq!(de2, de);
+    assert_eq!(de2.method(), de.method());
+
+    let mut histogram = DeltaHistogram::new(&[1.0, 2.0]).unwrap();
+    histogram.push(de);
+    assert_eq!(serde_json::to_string(&histogram).unwrap(), r#"{"edges":[1.0,2.0],"counts":[0,0,1]}"#);
+}
+
+#[cfg(feature = "csv")]
+#[test]
+fn csv_batch() {
+    use batch::{Pairing, Record};
+
+    let references = "id, L, a, b\nwhite, 95.08, -0.17, -10.81\ngray, 50, 0, 0\nblack, 0, 0, 0\n";
+    let samples = "black,1,0,0\nwhite,89.73,1.88,-6.96\ngray,50,0,0\n";
+    let references = batch::read::<LabValue, _>(references.as_bytes()).unwrap();
+    let samples = batch::read::<LabValue, _>(samples.as_bytes()).unwrap();
+    assert_eq!(references.len(), 3);
+    assert_eq!((references[1].id.as_deref(), references[1].line), (Some("gray"), 3));
+    assert_eq!((samples[0].id.as_deref(), samples[0].line), (Some("black"), 1));
+
+    let by_id = batch::compare(&references, &samples, DE2000, Pairing::Id).unwrap();
+    let ids: Vec<_> = by_id.iter().map(|c| c.id.as_deref().unwrap()).collect();
+    assert_eq!(ids, ["white", "gray", "black"]);
+    assert_eq!(by_id[0].delta, DeltaE::new(references[0].color, samples[1].color, DE2000));
+    assert_eq!(by_id[1].delta, 0.0);
+
+    let by_position = batch::compare(&references, &samples, DE1976, Pairing::Position).unwrap();
+    assert_eq!(by_position[0].delta, DeltaE::new(references[0].color, samples[0].color, DE1976));
+
+    // Colors without IDs, in another color type
+    let xyz = batch::read::<XyzValue, _>("0.5,0.5,0.5\n0.1,0.2,0.3\n".as_bytes()).unwrap();
+    assert_eq!(xyz[1], Record { id: None, color: XyzValue { x: 0.1, y: 0.2, z: 0.3 }, line: 2 });
+    assert!(matches!(
+        batch::compare(&xyz, &samples, DE2000, Pairing::Id),
+        Err(Error::Unpaired { id: None, line: 1 })
+    ));
+
+    let mut out = Vec::new();
+    batch::write(&mut out, &xyz).unwrap();
+    assert_eq!(String::from_utf8(out).unwrap(), "X,Y,Z\n0.5,0.5,0.5\n0.1,0.2,0.3\n");
+
+    let mut out = Vec::new();
+    batch::write(&mut out, &samples[..1]).unwrap();
+    assert_eq!(String::from_utf8(out).unwrap(), "id,L,a,b\nblack,1,0,0\n");
+
+    let mut out = Vec::new();
+    batch::write_comparisons(&mut out, &by_id[1..2]).unwrap();
+    assert_eq!(
+        String::from_utf8(out).unwrap(),
+        "id,reference_L,reference_a,reference_b,sample_L,sample_a,sample_b,method,delta_e\n\
+         gray,50,0,0,50,0,0,DE2000,0\n"
+    );
+
+    // Errors
+    match batch::read::<LabValue, _>("50,0,0\n50,derp,0\n".as_bytes()) {
+        Err(Error::Row { line: 2, error }) => match *error {
+            Error::Parse(e) => assert_eq!((e.component(), e.token()), (Some("a"), "derp")),
+            other => panic!("{:?}", other),
+        },
+        other => panic!("{:?}", other),
+    }
+    assert_eq!(
+        batch::read::<LabValue, _>("50,0,0\n50,derp,0\n".as_bytes()).unwrap_err().to_string(),
+        "line 2: invalid number \"derp\" for a at position 3",
+    );
+    assert!(matches!(
+        batch::read::<LabValue, _>("50,0\n".as_bytes()),
+        Err(Error::Row { line: 1, error }) if matches!(*error, Error::BadFormat)
+    ));
+    assert!(matches!(
+        batch::compare(&references, &samples[..2], DE2000, Pairing::Position),
+        Err(Error::Unpaired { line: 4, .. })
+    ));
+    assert!(matches!(
+        batch::compare(&references[..2], &samples, DE2000, Pairing::Id),
+        Err(Error::Unpaired { line: 1, .. })
+    ));
+    let duplicate = [samples[0].clone(), Record { line: 9, ..samples[0].clone() }];
+    assert!(matches!(
+        batch::compare(&references, &duplicate, DE2000, Pairing::Id),
+        Err(Error::Duplicate { line: 9, .. })
+    ));
+}
+
+#[cfg(feature = "parse")]
+#[test]
+fn delta_report() {
+    let reference = LabValue::new(50.0, 20.0, 0.0).unwrap();
+    let samples = [
+        LabValue::new(50.0, 20.0, 0.0).unwrap(),
+        LabValue::new(51.0, 0.0, 20.0).unwrap(),
+        LabValue::new(50.0, 0.0, -10.0).unwrap(),
+    ];
+
+    let reports: Vec<DeltaReport> = samples
+        .iter()
+        .map(|sample| reference.delta(*sample, DE1976).report(1.0))
+        .collect();
+
+    assert_eq!(reports[0].delta_e, 0.0);
+    assert!(reports[0].pass);
+
+    let report = reports[1];
+    assert_eq!((report.delta_l, report.delta_a, report.delta_b), (1.0, -20.0, 20.0));
+    assert_eq!(round::round_to(report.delta_c, 4), 0.0);
+    // Hue rotates counter-clockwise from 0° to 90°
+    assert_eq!(round::round_to(report.delta_h, 4), 28.2843);
+    assert_eq!(report.method, DE1976);
+    assert!(!report.pass);
+
+    // ΔE76² = ΔL² + ΔC² + ΔH²
+    for report in &reports {
+        let sum = report.delta_l.powi(2) + report.delta_c.powi(2) + report.delta_h.powi(2);
+        assert_eq!(round::round_to(sum.sqrt(), 3), round::round_to(report.delta_e, 3));
+    }
+    // Hue rotates clockwise from 0° to 270°
+    assert!(reports[2].delta_h < 0.0);
+
+    let summary = DeltaSummary::new(&reports);
+    assert_eq!((summary.count, summary.passed, summary.failed), (3, 1, 2));
+    assert_eq!(summary.min, 0.0);
+    assert_eq!(summary.max, reports[1].delta_e);
+    assert_eq!(summary.mean, (reports[1].delta_e + reports[2].delta_e) / 3.0);
+    assert!(summary.std_dev > 0.0);
+    assert_eq!(DeltaSummary::new(&[]), DeltaSummary::default());
+
+    #[cfg(feature = "serde")]
+    {
+        let json = serde_json::to_value(reports[0]).unwrap();
+        let keys: Vec<&str> = json.as_object().unwrap().keys().map(String::as_str).collect();
+        assert_eq!(keys.len(), 11);
+        for key in &["reference", "sample", "method", "delta_e", "delta_l", "delta_a", "delta_b",
+            "delta_c", "delta_h", "tolerance", "pass"] {
+            assert!(keys.contains(key), "{}", key);
+        }
+        assert_eq!(json["method"], "DE1976");
+        assert_eq!(json["pass"], true);
+        assert_eq!(json["tolerance"], 1.0);
+
+        // Tolerances that do not limit ΔE are written as null and read back
+        let unbounded = [
+            reference.delta(samples[1], DE1976).report(ComponentTolerance::lab(2.0, 20.0, 20.0)),
+            reference.delta(samples[1], DE1976).report(AnyOf::<[f32; 0]>([])),
+        ];
+        for report in &unbounded {
+            assert_eq!(report.tolerance, None);
+            let json = serde_json::to_string(report).unwrap();
+            assert!(json.contains(r#""tolerance":null"#));
+            assert_eq!(serde_json::from_str::<DeltaReport>(&json).unwrap(), *report);
+        }
+        assert!(unbounded[0].pass);
+
+        let json = serde_json::to_string(&summary).unwrap();
+        assert_eq!(serde_json::from_str::<DeltaSummary>(&json).unwrap(), summary);
+    }
+}
+
+#[cfg(all(feature = "rgb", feature = "parse"))]
+#[test]
+fn display_alternate() {
+    let lab = LabValue::new(95.08, -0.17, -10.81).unwrap();
+    assert_eq!(format!("{:#}", lab), "95.08,-0.17,-10.81");
+    assert_eq!(LabValue::from_str(&format!("{:#}", lab)).unwrap(), lab);
+
+    let lch = LchValue::from(lab);
+    assert_eq!(LchValue::from_str(&format!("{:#}", lch)).unwrap(), lch);
+
+    let xyz = XyzValue::from(lab);
+    assert_eq!(XyzValue::from_str(&format!("{:#}", xyz)).unwrap(), xyz);
+
+    let rgb = RgbValue::new(102, 51, 153);
+    assert_eq!(format!("{:#}", rgb), "102,51,153");
+    assert_eq!(RgbValue::from_str(&format!("{:#}", rgb)).unwrap(), rgb);
+
+    let nominal = RgbNominalValue::from(rgb);
+    assert_eq!(RgbNominalValue::from_str(&format!("{:#}", nominal)).unwrap(), nominal);
+}
+
+#[cfg(all(feature = "rgb", feature = "parse"))]
+#[test]
+fn display_string() {
+    assert_eq!(
+        LabValue::from_str("[L:89.73, a:1.88, b:-6.96]").unwrap(),
+        LabValue { l: 89.73, a: 1.88, b: -6.96 }
+    );
+
+    let lab = LabValue::new(95.08, -0.17, -10.81).unwrap();
+    assert_eq!(LabValue::from_str(&lab.to_string()).unwrap(), lab);
+    let lch = LchValue::from(lab);
+    assert_eq!(LchValue::from_str(&lch.to_string()).unwrap(), lch);
+    let xyz = XyzValue::from(lab);
+    assert_eq!(XyzValue::from_str(&xyz.to_string()).unwrap(), xyz);
+    let rgb = RgbValue::new(102, 51, 153);
+    assert_eq!(RgbValue::from_str(&rgb.to_string()).unwrap(), rgb);
+    let nominal = RgbNominalValue::from(rgb);
+    assert_eq!(RgbNominalValue::from_str(&nominal.to_string()).unwrap(), nominal);
+
+    assert_eq!(LabValue::from_str(" [95.08, -0.17, -10.81] ").unwrap(), lab);
+
+    // Positions are relative to the whole input
+    match LabValue::from_str(" [L:89.73, a:derp, b:-6.96]") {
+        Err(ValueError::Parse(e)) => assert_eq!((e.token(), e.position()), ("derp", 13)),
+        other => panic!("{:?}", other),
+    }
+
+    for bad in &["[L:89.73, a:1.88, b:-6.96", "L:89.73, a:1.88, b:-6.96]", "[[89.73, 1.88, -6.96]]"] {
+        assert!(LabValue::from_str(bad).is_err(), "{}", bad);
+    }
+}
+
+#[cfg(feature = "parse")]
+#[test]
+fn demethod_string() {
+    let good = &[
+        ("de2000", DE2000),
+        ("94T", DE1994T),
+        ("cmc", DECMC1),
+        ("CMC2", DECMC2),
+        ("cmc(2:1)", DECMC(2.0, 1.0)),
+        ("decmc(1.5:1)", DECMC(1.5, 1.0)),
+        (" DECMC( 1.5 : 0.8 ) ", DECMC(1.5, 0.8)),
+    ];
+
+    for (s, method) in good {
+        assert_eq!(DEMethod::from_str(s).unwrap(), *method, "{}", s);
+    }
+
+    // The Display output can be parsed back
+    let custom = DECMC(1.5, 1.0);
+    assert_eq!(DEMethod::from_str(&custom.to_string()).unwrap(), custom);
+
+    let bad = &["cmc(2)", "cmc(2:1:1)", "cmc(0:1)", "cmc(-1:1)", "cmc(a:b)", "cmc(2:1", "cmc[2:1]", "de3000"];
+    for s in bad {
+        assert_eq!(DEMethod::from_str(s).unwrap_err().token(), *s);
+    }
+
+    let err = DEMethod::from_str("de3000").unwrap_err().to_string();
+    for name in ParseMethodError::ACCEPTED {
+        assert!(err.contains(name), "{}", name);
+    }
+
+    // Names, aliases, and the accepted names agree
+    for &(method, name, aliases) in DEMethod::NAMES {
+        assert_eq!(method.as_str(), name);
+        assert_eq!(method.to_string(), name);
+        assert_eq!(aliases[0], name.to_lowercase());
+        assert!(ParseMethodError::ACCEPTED.contains(&aliases[0]), "{}", name);
+        for alias in aliases.iter().copied().chain(std::iter::once(name)) {
+            assert_eq!(DEMethod::from_str(alias).unwrap(), method, "{}", alias);
+            let listed = format!(" {},", alias.to_lowercase());
+            assert!(err.contains(&listed) || err.ends_with(&format!(" {})", alias)), "{}", alias);
+        }
+    }
+    assert_eq!(ParseMethodError::ACCEPTED.len(), DEMethod::NAMES.len() + 1);
+    assert_eq!(ParseMethodError::ACCEPTED.last(), Some(&"decmc(l:c)"));
+    assert_eq!(DEMethod::from_str("decmc(2:1)").unwrap(), DECMC2);
+}
+
+#[cfg(all(feature = "illuminants", feature = "parse"))]
+#[test]
+fn observer_white_point() {
+    let good = &[("2", Observer::Cie1931), ("10°", Observer::Cie1964), ("CIE 1964", Observer::Cie1964)];
+    for (s, observer) in good {
+        assert_eq!(Observer::from_str(s).unwrap(), *observer);
+    }
+    assert!(Observer::from_str("5").is_err());
+
+    // The 2° white points are the default
+    assert_eq!(Illuminant::D50.white_point_for(Observer::default()), Illuminant::D50.white_point());
+
+    let white = Illuminant::D65.white_point_for(Observer::Cie1964);
+    let lab = LabValue::new(50.0, 20.0, -30.0).unwrap();
+    let xyz = XyzValue::from_lab_with_white_point(lab, white);
+    assert_eq!(LabValue::from_xyz_with_white_point(xyz, white).round_to(4), lab);
+
+    // Adapting between white points matches adapting between illuminants
+    let d65 = Illuminant::D65.white_point();
+    let d50 = Illuminant::D50.white_point();
+    assert_eq!(
+        xyz.chrom_adapt_white_point(ChromaticAdaptationMethod::Cat02, d65, d50),
+        xyz.chrom_adapt(ChromaticAdaptationMethod::Cat02, Illuminant::D65, Illuminant::D50),
+    );
+}
+
+#[cfg(all(feature = "rgb", feature = "parse"))]
+#[test]
+fn color_enum() {
+    let colors = [
+        Color::Lab(LabValue { l: 50.0, a: 2.0, b: -3.0 }),
+        Color::Lch(LchValue { l: 50.0, c: 3.6, h: 303.7 }),
+        Color::Xyz(XyzValue { x: 0.2, y: 0.18, z: 0.15 }),
+        Color::Rgb(RgbValue::new(170, 187, 204)),
+    ];
+
+    // Display is tagged and parses back
+    for color in &colors {
+        assert_eq!(Color::from_str(&color.to_string()).unwrap(), *color);
+    }
+    assert_eq!(colors[3].to_string(), "rgb:170,187,204");
+    assert_eq!(Color::from_str(" RGB: #aabbcc").unwrap(), colors[3]);
+    assert_eq!(Color::from_str("lab:L:50, a:2, b:-3").unwrap(), colors[0]);
+
+    // Conversions and DeltaE work across variants
+    let lab = LabValue::from(colors[1]);
+    assert_eq!(lab.round_to(1), LabValue::from(LchValue { l: 50.0, c: 3.6, h: 303.7 }).round_to(1));
+    assert_eq!(Color::from(lab), Color::Lab(lab));
+    assert_eq!(colors[0].delta(colors[0], DE2000), 0.0);
+    assert!(colors[0].delta_eq(colors[1], DE2000, 0.1));
+
+    let err = |s: &str| match Color::from_str(s).unwrap_err() {
+        ValueError::Parse(e) => (e.kind(), e.token().to_string(), e.position()),
+        e => panic!("{:?}", e),
+    };
+    assert_eq!(err("cmyk:0,0,0,0"), (ParseErrorKind::UnknownLabel, "cmyk".into(), 0));
+    assert_eq!(err("50,2,-3"), (ParseErrorKind::UnknownLabel, "50,2,-3".into(), 0));
+    assert_eq!(err(" lab:50,2,derp"), (ParseErrorKind::Malformed, "derp".into(), 10));
+
+    assert!(Color::Lab(LabValue { l: 101.0, a: 0.0, b: 0.0 }).validate().is_err());
+}
+
+#[test]
+fn component_tolerance() {
+    let reference = LabValue::new(50.0, 20.0, 0.0).unwrap();
+    let lighter = LabValue::new(52.0, 20.0, 0.0).unwrap();
+    let redder = LabValue::new(50.0, 21.0, 0.0).unwrap();
+    let yellower = LabValue::new(50.0, 20.0, 1.5).unwrap();
+
+    let lch = ComponentTolerance::lch(1.5, 1.5, 1.0);
+    assert!(!reference.delta_eq(lighter, DE1976, lch));
+    assert!(reference.delta_eq(redder, DE1976, lch));
+    assert!(!reference.delta_eq(yellower, DE1976, lch));
+
+    let lab = ComponentTolerance::lab(2.0, 0.5, 2.0);
+    assert!(reference.delta_eq(lighter, DE1976, lab));
+    assert!(!reference.delta_eq(redder, DE1976, lab));
+    assert!(reference.delta_eq(yellower, DE1976, lab));
+
+    // Both a total limit and the components
+    assert!(reference.delta_eq(lighter, DE1976, (2.0, lab)));
+    assert!(!reference.delta_eq(lighter, DE1976, (1.9, lab)));
+    assert_eq!((1.9, lab).tolerance(), 1.9);
+    assert_eq!(lab.tolerance(), f32::INFINITY);
+
+    let report = reference.delta(yellower, DE1976).report((2.0, lch));
+    assert_eq!((report.tolerance, report.pass), (Some(2.0), false));
+    let comparator = DeltaE::builder().method(DE1976).tolerance(2.0);
+    assert!(comparator.delta_eq(reference, yellower));
+    assert!(!comparator.components(lch).delta_eq(reference, yellower));
+}
+
+#[test]
+fn tolerance_combinators() {
+    let reference = LabValue::new(50.0, 20.0, 0.0).unwrap();
+    let sample = LabValue::new(51.0, 20.0, 1.0).unwrap();
+    let de = reference.delta(sample, DE1976);
+    let lightness = ComponentTolerance::lab(0.5, f32::INFINITY, f32::INFINITY);
+    let b = ComponentTolerance::lab(f32::INFINITY, f32::INFINITY, 1.0);
+
+    assert!(AllOf([2.0, 1.5]).contains(&de));
+    assert!(!AllOf(vec![2.0, 1.0]).contains(&de));
+    assert!(AnyOf(vec![2.0, 1.0]).contains(&de));
+    assert!(!AnyOf([1.0, 0.5]).contains(&de));
+    assert!(!AllOf([lightness, b]).contains(&de));
+    assert!(AnyOf([lightness, b]).contains(&de));
+    assert!(AllOf([AnyOf([lightness, b]), AnyOf([b, b])]).contains(&de));
+
+    // Empty combinations
+    assert!(AllOf(Vec::<f32>::new()).contains(&de));
+    assert!(!AnyOf(Vec::<f32>::new()).contains(&de));
+
+    assert_eq!(AllOf([2.0, 1.5, 3.0]).tolerance(), 1.5);
+    assert_eq!(AnyOf([2.0, 1.5, 3.0]).tolerance(), 3.0);
+    assert!(reference.delta_eq(sample, DE1976, (AllOf([2.0, 3.0]), AnyOf([lightness, b]))));
+    let report = de.report(AllOf([2.0, 1.0]));
+    assert_eq!((report.tolerance, report.pass), (Some(1.0), false));
+}
+
+#[test]
+fn cmc_ellipse() {
+    let reference = LabValue::new(62.0, -18.0, 34.0).unwrap();
+    let samples = [
+        LabValue::new(62.0, -18.0, 34.0).unwrap(),
+        LabValue::new(63.5, -17.0, 35.0).unwrap(),
+        LabValue::new(60.0, -19.0, 31.0).unwrap(),
+        LabValue::new(62.0, -14.0, 34.0).unwrap(),
+    ];
+
+    // The ellipsoid agrees with DE CMC
+    for sample in samples.iter() {
+        for &(l, c) in [(1.0, 1.0), (2.0, 1.0)].iter() {
+            let de = reference.delta(*sample, DECMC(l, c));
+            assert!(is_within_cmc_ellipse(reference, *sample, l, c, *de.value() + 1e-4));
+            if *de.value() > 0.0 {
+                assert!(!is_within_cmc_ellipse(reference, *sample, l, c, *de.value() - 1e-4));
+            }
+        }
+    }
+
+    assert!(is_within_cmc_ellipse(reference, reference, 2.0, 1.0, 0.0));
+
+    // A sample displaced along the lightness axis by the semi-axis is on the surface
+    let [axis_l, _, _] = cmc_ellipse_axes(reference, 2.0, 1.0, 1.5);
+    let lighter = LabValue { l: reference.l + axis_l * 0.999, ..reference };
+    let too_light = LabValue { l: reference.l + axis_l * 1.001, ..reference };
+    assert!(is_within_cmc_ellipse(reference, lighter, 2.0, 1.0, 1.5));
+    assert!(!is_within_cmc_ellipse(reference, too_light, 2.0, 1.0, 1.5));
+}
+
+#[cfg(feature = "illuminants")]
+#[test]
+fn tolerance_ellipsoid() {
+    let center = LabValue::new(60.0, 10.0, 10.0).unwrap();
+    let ellipsoid = ToleranceEllipsoid::new(center, [2.0, 1.5, 0.5], 45.0);
+
+    // Along the rotated axes
+    let (d, s) = (1.5 * 0.99 / 2_f32.sqrt(), 0.5 * 1.01 / 2_f32.sqrt());
+    assert!(ellipsoid.contains(LabValue { a: 10.0 + d, b: 10.0 + d, ..center }));
+    assert!(!ellipsoid.contains(LabValue { a: 10.0 - s, b: 10.0 + s, ..center }));
+    assert!(ellipsoid.contains(LabValue { l: 58.0, ..center }));
+    assert!(!ellipsoid.contains(LabValue { l: 57.9, ..center }));
+    assert!(ellipsoid.scaled(1.1).contains(LabValue { l: 57.9, ..center }));
+    assert_eq!(ellipsoid.distance(center), 0.0);
+    assert_eq!(round::round_to(ellipsoid.distance(LchValue::from(center)), 4), 0.0);
+
+    // Chromaticity
+    let white = Illuminant::D50.white_point();
+    let [x, y] = white.chromaticity();
+    assert_eq!(XyzValue::from_chromaticity([x, y], 1.0).unwrap().round_to(4), white.round_to(4));
+    assert_eq!(XyzValue { x: 0.0, y: 0.0, z: 0.0 }.chromaticity(), [1.0 / 3.0, 1.0 / 3.0]);
+
+    let ellipse = MacAdamEllipse::new([x, y], 0.002, 0.001, 90.0);
+    assert_eq!(ellipse.steps(white), 0.0);
+    // The chromaticity is independent of luminance
+    let steps = ellipse.steps(XyzValue::from_chromaticity([x, y + 0.001], 0.2).unwrap());
+    assert_eq!(round::round_to(steps, 3), 0.5);
+    let steps = ellipse.steps(XyzValue::from_chromaticity([x + 0.001, y], 1.0).unwrap());
+    assert_eq!(round::round_to(steps, 3), 1.0);
+    assert!(!ellipse.contains(XyzValue::from_chromaticity([x + 0.0011, y], 1.0).unwrap()));
+    assert!(ellipse.scaled(3.0).contains(XyzValue::from_chromaticity([x + 0.0011, y + 0.005], 1.0).unwrap()));
+    assert!(matches!(XyzValue::from_chromaticity([x, 0.0], 1.0), Err(Error::OutOfBounds)));
+
+    // As tolerances, centered on the reference
+    let reference = LabValue::new(50.0, 0.0, 0.0).unwrap();
+    assert!(reference.delta_eq(LabValue { l: 51.9, ..reference }, DE2000, ellipsoid));
+    assert!(!reference.delta_eq(LabValue { l: 52.1, ..reference }, DE2000, ellipsoid));
+    assert!(!reference.delta_eq(LabValue { a: -0.4, b: 0.4, ..reference }, DE2000, ellipsoid));
+    assert!(center.delta_eq(center, DE2000, ellipsoid));
+    assert_eq!(ellipsoid.tolerance(), f32::INFINITY);
+
+    let neutral = XyzValue::from(reference);
+    let shifted = |dx: f32, dy: f32| {
+        let [x, y] = neutral.chromaticity();
+        XyzValue::from_chromaticity([x + dx, y + dy], neutral.y).unwrap()
+    };
+    assert!(neutral.delta_eq(shifted(0.0009, 0.0), DE2000, ellipse));
+    assert!(neutral.delta_eq(shifted(0.0, 0.0019), DE2000, ellipse));
+    assert!(!neutral.delta_eq(shifted(0.0011, 0.0), DE2000, ellipse));
+    assert!(reference.delta_eq(LabValue { l: 90.0, ..reference }, DE2000, ellipse));
+}
+
+#[cfg(feature = "illuminants")]
+#[test]
+fn mix() {
+    let lab0 = LabValue::new(89.73, 1.88, -6.96).unwrap();
+    let lab1 = LabValue::new(30.0, -40.0, 20.0).unwrap();
+
+    assert_eq!(lab0.mix(lab1, 0.0), lab0);
+    assert_eq!(lab0.mix(lab1, 1.0), lab1);
+    assert_eq!(lab0.mix(lab1, 0.25).round_to(4), LabValue::new(74.7975, -8.59, -0.22).unwrap());
+
+    // Oklab round trips and ends at the endpoints
+    assert_eq!(lab0.mix_oklab(lab1, 0.0).round_to(2), lab0.round_to(2));
+    assert_eq!(lab0.mix_oklab(lab1, 1.0).round_to(2), lab1.round_to(2));
+    let middle = lab0.mix_oklab(lab1, 0.5);
+    assert!(middle.l > 30.0 && middle.l < 89.73);
+    assert_ne!(middle.round_to(2), lab0.mix(lab1, 0.5).round_to(2));
+
+    // Hue takes the shorter arc in both directions
+    let lch0 = LchValue::new(50.0, 40.0, 20.0).unwrap();
+    let lch1 = LchValue::new(50.0, 40.0, 300.0).unwrap();
+    assert_eq!(lch0.mix(lch1, 0.5).round_to(4).h, 340.0);
+    assert_eq!(lch1.mix(lch0, 0.5).round_to(4).h, 340.0);
+    assert_eq!(lch0.mix(lch1, 1.0).round_to(4).h, 300.0);
+
+    // The hue of an achromatic color is ignored
+    let gray = LchValue::new(50.0, 0.0, 180.0).unwrap();
+    assert_eq!(gray.mix(lch0, 0.5).h, 20.0);
+    assert_eq!(lch0.mix(gray, 0.5).round_to(4), LchValue::new(50.0, 20.0, 20.0).unwrap());
+
+    let xyz0 = XyzValue::new(0.2, 0.4, 0.6).unwrap();
+    let xyz1 = XyzValue::new(0.4, 0.4, 0.2).unwrap();
+    assert_eq!(xyz0.mix(xyz1, 0.5).round_to(4), XyzValue::new(0.3, 0.4, 0.4).unwrap());
+}
+
+#[cfg(feature = "illuminants")]
+#[test]
+fn gradient() {
+    let start = LabValue::new(30.0, -40.0, 20.0).unwrap();
+    let end = LchValue::new(80.0, 40.0, 300.0).unwrap();
+
+    for &space in [
+        InterpolationSpace::Lab,
+        InterpolationSpace::Lch(HueInterpolation::Shorter),
+        InterpolationSpace::Lch(HueInterpolation::Longer),
+        InterpolationSpace::Oklab,
+        InterpolationSpace::Xyz,
+    ].iter() {
+        let gradient = Gradient::new(start, end, 7, space);
+        assert_eq!(gradient.len(), 7);
+
+        let steps: Vec<LabValue> = gradient.clone().collect();
+        assert_eq!(steps[0].round_to(2), start.round_to(2));
+        assert_eq!(steps[6].round_to(2), LabValue::from(end).round_to(2));
+        assert_eq!(steps[3].round_to(4), space.mix(start, end.into(), 0.5).round_to(4));
+
+        let reversed: Vec<LabValue> = gradient.rev().collect();
+        assert_eq!(reversed.into_iter().rev().collect::<Vec<_>>(), steps);
+    }
+
+    let mut gradient = Gradient::new(start, end, 3, InterpolationSpace::Lab);
+    assert_eq!(gradient.next_back(), Some(end.into()));
+    assert_eq!(gradient.next(), Some(start));
+    assert_eq!(gradient.len(), 1);
+    assert_eq!(gradient.next(), Some(start.mix(end.into(), 0.5)));
+    assert_eq!(gradient.next(), None);
+    assert_eq!(gradient.next_back(), None);
+
+    assert_eq!(Gradient::new(start, end, 1, InterpolationSpace::Lab).collect::<Vec<_>>(), [start]);
+    assert_eq!(Gradient::new(start, end, 0, InterpolationSpace::Lab).count(), 0);
+}
+
+#[cfg(feature = "illuminants")]
+#[test]
+fn uniform_gradient() {
+    let spread = |ramp: &[LabValue], method: DEMethod| {
+        let deltas: Vec<f32> = ramp.windows(2).map(|w| *w[0].delta(w[1], method).value()).collect();
+        let min = deltas.iter().cloned().fold(f32::INFINITY, f32::min);
+        let max = deltas.iter().cloned().fold(0.0, f32::max);
+        max / min
+    };
+
+    let yellow = LabValue::new(97.14, -21.56, 94.48).unwrap();
+    let blue = LabValue::new(32.3, 79.19, -107.86).unwrap();
+
+    for &space in [
+        InterpolationSpace::Lab,
+        InterpolationSpace::Lch(HueInterpolation::Shorter),
+        InterpolationSpace::Oklab,
+    ].iter() {
+        for &method in [DE2000, DE1994G, DECMC1].iter() {
+            let even: Vec<LabValue> = Gradient::new(yellow, blue, 9, space).collect();
+            let uniform: Vec<LabValue> = Gradient::uniform(yellow, blue, 9, space, method).collect();
+
+            assert_eq!(uniform.len(), 9);
+            assert_eq!(uniform[0], even[0]);
+            assert_eq!(uniform[8].round_to(3), even[8].round_to(3));
+            assert!(spread(&uniform, method) < 1.01, "{:?} {:?}", space, method);
+            assert!(spread(&uniform, method) <= spread(&even, method));
+        }
+    }
+
+    // DE1976 is linear in Lab, so even steps are already uniform
+    let even: Vec<LabValue> = Gradient::new(yellow, blue, 5, InterpolationSpace::Lab).collect();
+    let uniform = Gradient::uniform(yellow, blue, 5, InterpolationSpace::Lab, DE1976);
+    assert_eq!(uniform.collect::<Vec<_>>(), even);
+
+    // Identical ends and short gradients
+    assert_eq!(Gradient::uniform(blue, blue, 4, InterpolationSpace::Lab, DE2000).count(), 4);
+    assert_eq!(Gradient::uniform(yellow, blue, 2, InterpolationSpace::Lab, DE2000).count(), 2);
+}
+
+#[test]
+fn respace_duplicate_stops() {
+    let positions = [0.0, 0.25, 0.5, 0.75, 1.0];
+
+    // The duplicate colors between 0.25 and 0.75 take none of the length of the path
+    assert_eq!(interpolate::respace(&positions, &[1.0, 0.0, 0.0, 1.0]), [0.0, 0.125, 0.25, 0.875, 1.0]);
+    assert_eq!(interpolate::respace(&positions, &[0.0, 0.0, 1.0, 1.0]), [0.0, 0.625, 0.75, 0.875, 1.0]);
+
+    // A target on a zero length segment, where the length of the path underflows
+    let respaced = interpolate::respace(&positions[..3], &[0.0, f32::from_bits(1)]);
+    assert_eq!(respaced, [0.0, 0.0, 0.5]);
+}
+
+#[test]
+fn hue_interpolation() {
+    use HueInterpolation::*;
+
+    // (start, end, halfway) for each strategy, from the examples of CSS Color 4
+    let cases = [
+        (Shorter, 50.0, 330.0, 10.0),
+        (Shorter, 330.0, 50.0, 10.0),
+        (Shorter, 20.0, 100.0, 60.0),
+        (Longer, 50.0, 330.0, 190.0),
+        (Longer, 20.0, 100.0, 240.0),
+        (Longer, 100.0, 20.0, 240.0),
+        (Increasing, 50.0, 330.0, 190.0),
+        (Increasing, 330.0, 50.0, 10.0),
+        (Decreasing, 50.0, 330.0, 10.0),
+        (Decreasing, 330.0, 50.0, 190.0),
+    ];
+
+    for &(hue, start, end, halfway) in cases.iter() {
+        assert_eq!(round::round_to(hue.mix(start, end, 0.5), 4), halfway, "{:?}", hue);
+        assert_eq!(round::round_to(hue.mix(start, end, 0.0), 4), start, "{:?}", hue);
+        assert_eq!(round::round_to(hue.mix(start, end, 1.0), 4), end, "{:?}", hue);
+    }
+
+    // Hues outside 0..360 are normalized first
+    assert_eq!(Shorter.mix(-10.0, 370.0, 0.5), 0.0);
+    // Equal hues go all the way around only with Longer
+    assert_eq!(Shorter.mix(90.0, 90.0, 0.5), 90.0);
+    assert_eq!(Longer.mix(90.0, 90.0, 0.5), 270.0);
+
+    let lch0 = LchValue::new(50.0, 40.0, 20.0).unwrap();
+    let lch1 = LchValue::new(50.0, 40.0, 300.0).unwrap();
+    assert_eq!(lch0.mix_hue(lch1, 0.5, Shorter), lch0.mix(lch1, 0.5));
+    assert_eq!(lch0.mix_hue(lch1, 0.5, Increasing).round_to(4).h, 160.0);
+    assert_eq!(HueInterpolation::default(), Shorter);
+}
+
+#[cfg(feature = "rgb")]
+#[test]
+fn gamut() {
+    let systems = [RgbSystem::Srgb, RgbSystem::Adobe, RgbSystem::ProPhoto, RgbSystem::DisplayP3];
+    let bradford = Some(ChromaticAdaptationMethod::Bradford);
+
+    // The white, black, and primaries of each system are in its own gamut
+    for system in systems.iter() {
+        let colors = [(1.0, 1.0, 1.0), (0.0, 0.0, 0.0), (1.0, 0.0, 0.0), (0.0, 1.0, 0.0), (0.0, 0.0, 1.0)];
+        for &(r, g, b) in colors.iter() {
+            let xyz = XyzValue::from_rgb(RgbNominalValue { r, g, b }, system.clone(), bradford);
+            assert!(xyz.is_in_gamut(system.clone()), "{} {} {} {}", system, r, g, b);
+            assert!(LabValue::from(xyz).is_in_gamut(system.clone()));
+            assert!(LchValue::from(xyz).is_in_gamut(system.clone()));
+        }
+    }
+
+    // sRGB is inside Display P3. ProPhoto holds P3 green, but P3 red is just outside it.
+    let p3 = |r, g, b| XyzValue::from_rgb(RgbNominalValue { r, g, b }, RgbSystem::DisplayP3, bradford);
+    assert!(!p3(1.0, 0.0, 0.0).is_in_gamut(RgbSystem::Srgb));
+    assert!(!p3(1.0, 0.0, 0.0).is_in_gamut(RgbSystem::ProPhoto));
+    assert!(p3(0.0, 1.0, 0.0).is_in_gamut(RgbSystem::ProPhoto));
+    assert!(XyzValue::from(RgbValue::new(255, 0, 0)).is_in_gamut(RgbSystem::DisplayP3));
+
+    // Brighter than white, and too saturated for any system
+    assert!(!LabValue { l: 105.0, a: 0.0, b: 0.0 }.is_in_gamut(RgbSystem::Srgb));
+    let lch = LchValue::new(50.0, 150.0, 200.0).unwrap();
+    assert!(systems.iter().all(|system| !lch.is_in_gamut(system.clone())));
+}
+
+#[test]
+fn palette() {
+    // A deterministic scatter of colors across Lab space
+    let mut seed = 12345_u32;
+    let mut random = |min: f32, max: f32| {
+        seed = seed.wrapping_mul(1_103_515_245).wrapping_add(12345);
+        min + (max - min) * (seed >> 8) as f32 / (1 << 24) as f32
+    };
+    let colors: Vec<LabValue> = (0..500)
+        .map(|_| LabValue { l: random(0.0, 100.0), a: random(-100.0, 100.0), b: random(-100.0, 100.0) })
+        .collect();
+    let queries: Vec<LabValue> = (0..50)
+        .map(|_| LabValue { l: random(0.0, 100.0), a: random(-80.0, 80.0), b: random(-80.0, 80.0) })
+        .collect();
+    let palette = Palette::new(colors.iter().copied());
+    assert_eq!(palette.len(), 500);
+    assert_eq!(palette.colors(), &colors[..]);
+
+    // Every method finds the same colors as comparing against the whole palette
+    for &method in [DE1976, DE1994G, DE1994T, DE2000, DECMC1, DECMC2].iter() {
+        for &query in queries.iter() {
+            let mut expected: Vec<(usize, f32)> = colors.iter()
+                .enumerate()
+                .map(|(i, &lab)| (i, *query.delta(lab, method).value()))
+                .collect();
+            expected.sort_by(|a, b| a.1.total_cmp(&b.1).then(a.0.cmp(&b.0)));
+            expected.truncate(5);
+
+            let found: Vec<(usize, f32)> = palette.k_nearest(query, 5, method)
+                .into_iter()
+                .map(|(i, de)| (i, *de.value()))
+                .collect();
+            assert_eq!(found, expected, "{:?} {:?}", method, query);
+            assert_eq!(palette.nearest(query, method).unwrap().0, expected[0].0);
+        }
+    }
+
+    // Edge cases
+    let query = queries[0];
+    assert_eq!(palette.k_nearest(query, 0, DE2000).len(), 0);
+    assert_eq!(palette.k_nearest(query, 1000, DE2000).len(), 500);
+    assert!(Palette::default().nearest(query, DE2000).is_none());
+    assert_eq!(palette.nearest(colors[42], DE2000).unwrap().0, 42);
+}
+
+#[test]
+fn cluster_colors() {
+    // Three tight groups of colors, interleaved
+    let centers = [(30.0, 40.0, -20.0), (60.0, -30.0, 10.0), (85.0, 5.0, 60.0)];
+    let colors: Vec<LabValue> = (0..30)
+        .map(|i| {
+            let (l, a, b) = centers[i % 3];
+            let offset = (i / 3) as f32 * 0.05;
+            LabValue { l: l + offset, a: a - offset, b: b + offset }
+        })
+        .collect();
+
+    for &method in [DE1976, DE1994G, DE2000, DECMC1].iter() {
+        let clusters = cluster(colors.iter().copied(), 2.0, method);
+        assert_eq!(clusters.len(), 3, "{:?}", method);
+        for (i, cluster) in clusters.iter().enumerate() {
+            assert_eq!(cluster.len(), 10);
+            assert!(cluster.members.iter().all(|&m| m % 3 == i));
+            assert!(cluster.members.contains(&cluster.representative));
+            assert!(cluster.members.iter().all(|&m| cluster.centroid.delta(colors[m], method).value() <= &2.0));
+        }
+    }
+
+    // Members are always within the threshold of their centroid, even along a chain of colors
+    let chain: Vec<LabValue> = (0..50).map(|i| LabValue { l: 50.0 + i as f32 * 0.5, a: 0.0, b: 0.0 }).collect();
+    let clusters = cluster(chain.iter().copied(), 2.0, DE1976);
+    assert!(clusters.len() > 1);
+    assert_eq!(clusters.iter().map(Cluster::len).sum::<usize>(), 50);
+    for cluster in clusters.iter() {
+        assert!(cluster.members.iter().all(|&m| cluster.centroid.delta(chain[m], DE1976).value() <= &2.0));
+    }
+
+    // A threshold of zero only merges identical colors
+    let unique = dedupe(vec![colors[0], colors[1], colors[0], colors[3]], 0.0, DE2000);
+    assert_eq!(unique, vec![colors[0], colors[1], colors[3]]);
+    assert!(cluster(Vec::<LabValue>::new(), 1.0, DE2000).is_empty());
+}
+
+#[cfg(feature = "illuminants")]
+#[test]
+fn kmeans_palette() {
+    // Three groups of colors of different sizes
+    let centers = [(30.0, 40.0, -20.0), (60.0, -30.0, 10.0), (85.0, 5.0, 60.0)];
+    let sizes = [12, 8, 4];
+    let mut colors = Vec::new();
+    for (&(l, a, b), &size) in centers.iter().zip(sizes.iter()) {
+        for i in 0..size {
+            let offset = (i as f32 - (size - 1) as f32 / 2.0) * 0.2;
+            colors.push(LabValue { l: l + offset, a: a - offset, b: b + offset });
+        }
+    }
+
+    for &space in [CentroidSpace::Lab, CentroidSpace::Oklab].iter() {
+        for &method in [DE1976, DE2000, DECMC1].iter() {
+            let clusters = kmeans(colors.iter().copied(), 3, method, space);
+            assert_eq!(clusters.iter().map(Cluster::len).collect::<Vec<_>>(), vec![12, 8, 4]);
+            for (cluster, &(l, a, b)) in clusters.iter().zip(centers.iter()) {
+                let center = LabValue { l, a, b };
+                assert!(cluster.centroid.delta(center, DE1976).value() < &0.1, "{:?}", space);
+                assert!(cluster.members.contains(&cluster.representative));
+            }
+        }
+    }
+
+    // Fewer distinct colors than clusters
+    let twice = [colors[0], colors[20], colors[0], colors[20]];
+    let clusters = kmeans(twice.iter().copied(), 5, DE2000, CentroidSpace::Lab);
+    assert_eq!(clusters.len(), 2);
+    assert_eq!(clusters.iter().map(Cluster::len).sum::<usize>(), 4);
+    assert!(kmeans(colors.iter().copied(), 0, DE2000, CentroidSpace::Lab).is_empty());
+    assert!(kmeans(Vec::<LabValue>::new(), 3, DE2000, CentroidSpace::Lab).is_empty());
+
+    // One cluster is the mean of everything
+    let all = kmeans(colors.iter().copied(), 1, DE1976, CentroidSpace::Lab);
+    assert_eq!(all.len(), 1);
+    assert_eq!(all[0].len(), colors.len());
+}
+
+#[test]
+fn delta_e_stats() {
+    let reference = LabValue::new(50.0, 0.0, 0.0).unwrap();
+    let delta = |value: f32| reference.delta(LabValue { l: 50.0 + value, a: 0.0, b: 0.0 }, DE1976);
+
+    let empty = DeltaEStats::new();
+    assert_eq!(empty, DeltaEStats::default());
+    assert_eq!((empty.count(), empty.mean(), empty.min(), empty.max()), (0, None, None, None));
+    assert_eq!((empty.std_dev(), empty.median()), (None, None));
+
+    let mut stats = DeltaEStats::new();
+    stats.push(delta(3.0));
+    assert_eq!((stats.mean(), stats.std_dev(), stats.median()), (Some(3.0), Some(0.0), Some(3.0)));
+    stats.extend(vec![delta(1.0), delta(2.0), delta(4.0)]);
+    assert_eq!(stats.count(), 4);
+    assert_eq!((stats.min(), stats.max(), stats.mean()), (Some(1.0), Some(4.0), Some(2.5)));
+    assert_eq!(round::round_to(stats.std_dev().unwrap(), 4), 1.118);
+    assert_eq!(stats.median(), Some(2.5));
+    assert_eq!(stats.percentile(0.0), Some(1.0));
+    assert_eq!(stats.percentile(100.0), Some(4.0));
+    assert_eq!(stats.percentile(75.0), Some(3.25));
+
+    // Streaming estimates are close to the exact percentiles of a skewed distribution
+    let values: Vec<f32> = (0..5000).map(|i| ((i * 7919) % 5000) as f32 / 1000.0).map(|x| x * x / 5.0).collect();
+    let exact: DeltaEStats = values.iter().map(|&v| delta(v)).collect();
+    let mut streaming = DeltaEStats::streaming(&[50.0, 90.0, 99.0]);
+    streaming.extend(values.iter().map(|&v| delta(v)));
+    assert_eq!(streaming.count(), exact.count());
+    assert_eq!((streaming.min(), streaming.max()), (exact.min(), exact.max()));
+    assert_eq!(round::round_to(streaming.mean().unwrap(), 3), round::round_to(exact.mean().unwrap(), 3));
+    for &p in [50.0, 90.0, 99.0].iter() {
+        let (estimate, expected) = (streaming.percentile(p).unwrap(), exact.percentile(p).unwrap());
+        assert!((estimate - expected).abs() < 0.02 * expected, "{} {} {}", p, estimate, expected);
+    }
+    assert_eq!(streaming.percentile(75.0), None);
+}
+
+#[test]
+fn delta_histogram() {
+    let reference = LabValue::new(50.0, 0.0, 0.0).unwrap();
+    let delta = |value: f32| reference.delta(LabValue { l: 50.0 + value, a: 0.0, b: 0.0 }, DE1976);
+
+    // Values on an edge fall in the bin above it
+    let mut histogram = DeltaHistogram::new(&[1.0, 2.5]).unwrap();
+    histogram.extend(vec![delta(0.0), delta(1.0), delta(2.0), delta(2.5), delta(10.0), delta(0.5)]);
+    assert_eq!(histogram.counts(), &[2, 2, 2]);
+    assert_eq!(histogram.total(), 6);
+    let bins: Vec<_> = histogram.bins().collect();
+    assert_eq!(bins, vec![(None, Some(1.0), 2), (Some(1.0), Some(2.5), 2), (Some(2.5), None, 2)]);
+
+    let histogram: DeltaHistogram = vec![delta(0.2), delta(0.7), delta(0.9), delta(1.5), delta(3.0), delta(6.0)]
+        .into_iter()
+        .collect();
+    assert_eq!(histogram.to_string(), [
+        "  < 0.5 | ##########           1  16.7%",
+        "0.5 - 1 | #################### 2  33.3%",
+        "  1 - 2 | ##########           1  16.7%",
+        "  2 - 4 | ##########           1  16.7%",
+        "   >= 4 | ##########           1  16.7%",
+        "",
+    ].join("\n"));
+
+    // Without edges there is one bin
+    let mut all = DeltaHistogram::new(&[]).unwrap();
+    all.push(delta(1.0));
+    assert_eq!(all.counts(), &[1]);
+    assert_eq!(DeltaHistogram::default().to_string().lines().count(), 5);
+    assert!(DeltaHistogram::new(&[1.0, 1.0]).is_err());
+    assert!(DeltaHistogram::new(&[f32::NAN]).is_err());
+}
+
+#[test]
+fn delta_iterators() {
+    let reference = LabValue::new(95.08, -0.17, -10.81).unwrap();
+    let samples = [
+        LabValue::new(89.73, 1.88, -6.96).unwrap(),
+        LabValue::new(95.0, -0.2, -10.5).unwrap(),
+        LabValue::new(50.0, 0.0, 0.0).unwrap(),
+    ];
+
+    // The same deltas as calculating each one
+    let expected: Vec<DeltaE> = samples.iter().map(|s| reference.delta(*s, DE2000)).collect();
+    let lazy = samples.iter().deltas_against(reference, DE2000);
+    assert_eq!(lazy.len(), 3);
+    assert_eq!(lazy.collect::<Vec<_>>(), expected);
+    let reversed: Vec<DeltaE> = samples.iter().deltas_against(&reference, DE2000).rev().collect();
+    assert_eq!(reversed, expected.iter().rev().copied().collect::<Vec<_>>());
+
+    // Pairs of any color types, collected straight into statistics
+    let lch = LchValue::from(reference);
+    let pairs = samples.iter().map(|s| (lch, XyzValue::from(*s)));
+    assert_eq!(pairs.clone().deltas(DE1976).len(), 3);
+    let stats: DeltaEStats = pairs.deltas(DE1976).collect();
+    let max = samples.iter().map(|s| *reference.delta(*s, DE1976).value()).fold(0.0, f32::max);
+    assert_eq!(round::round_to(stats.max().unwrap(), 3), round::round_to(max, 3));
+}
+
+#[test]
+fn color_mean() {
+    let readings = [
+        LabValue::new(50.0, 10.0, -20.0).unwrap(),
+        LabValue::new(52.0, 14.0, -18.0).unwrap(),
+        LabValue::new(51.0, 12.0, -22.0).unwrap(),
+    ];
+    assert_eq!(LabValue::mean(readings.iter()), Some(LabValue { l: 51.0, a: 12.0, b: -20.0 }));
+    assert_eq!(LabValue::mean(Vec::<LabValue>::new()), None);
+
+    // Weights
+    let weighted = LabValue::weighted_mean(vec![(readings[0], 3.0), (readings[1], 1.0)]).unwrap();
+    assert_eq!(weighted, LabValue { l: 50.5, a: 11.0, b: -19.5 });
+    assert_eq!(LabValue::weighted_mean(vec![(readings[0], 0.0)]), None);
+    assert_eq!(LabValue::weighted_mean(vec![(readings[0], 1.0), (readings[1], 0.0)]), Some(readings[0]));
+
+    // Hue wraps around the circle
+    let lch = |l, c, h| LchValue { l, c, h };
+    let mean = LchValue::mean(vec![lch(50.0, 20.0, 340.0), lch(50.0, 20.0, 30.0)]).unwrap();
+    assert_eq!(round::round_to(mean.h, 3), 5.0);
+    let mean = LchValue::mean(vec![lch(50.0, 20.0, 80.0), lch(50.0, 20.0, 100.0), lch(50.0, 20.0, 120.0)]).unwrap();
+    assert_eq!(round::round_to(mean.h, 3), 100.0);
+    let mean = LchValue::weighted_mean(vec![(lch(40.0, 10.0, 350.0), 1.0), (lch(60.0, 30.0, 20.0), 2.0)]).unwrap();
+    assert_eq!((round::round_to(mean.l, 3), round::round_to(mean.c, 3)), (53.333, 23.333));
+    assert!(mean.h > 0.0 && mean.h < 20.0);
+    assert_eq!(LchValue::mean(vec![lch(50.0, 20.0, 90.0), lch(50.0, 20.0, 270.0)]).unwrap().h, 0.0);
+
+    // LCh values can be averaged from Lab readings
+    let mean = LchValue::mean(readings.iter()).unwrap();
+    assert_eq!(round::round_to(mean.l, 3), 51.0);
+    assert!(mean.h > 290.0 && mean.h < 310.0);
+    assert_eq!(LchValue::mean(Vec::<LchValue>::new()), None);
+}
+
+#[test]
+fn color_stats() {
+    let readings = [
+        LabValue::new(60.2, -20.1, 35.4).unwrap(),
+        LabValue::new(59.8, -19.7, 35.0).unwrap(),
+        LabValue::new(60.0, -20.0, 35.2).unwrap(),
+        LabValue::new(60.4, -20.2, 34.8).unwrap(),
+        LabValue::new(59.6, -20.0, 35.6).unwrap(),
+    ];
+    let stats = ColorStats::new(readings.iter(), DE2000).unwrap();
+    assert_eq!(stats.count, 5);
+    assert_eq!(stats.mean.round_to(3), LabValue::new(60.0, -20.0, 35.2).unwrap());
+    assert_eq!(round::round_to(stats.std_dev[0], 4), 0.2828);
+    assert_eq!(round::round_to(stats.std_dev[1], 4), 0.1673);
+    assert_eq!(round::round_to(stats.std_dev[2], 4), 0.2828);
+    assert_eq!(stats.method, DE2000);
+
+    // MCDM is the mean DeltaE from the mean
+    let deltas: Vec<f32> = readings.iter().map(|r| *stats.mean.delta(*r, DE2000).value()).collect();
+    let mcdm = deltas.iter().sum::<f32>() / 5.0;
+    assert_eq!(round::round_to(stats.mean_delta, 4), round::round_to(mcdm, 4));
+    assert_eq!(stats.max_delta, deltas.iter().copied().fold(0.0, f32::max));
+    assert!(stats.mean_delta <= stats.max_delta);
+
+    // A single reading has no spread
+    let single = ColorStats::new(vec![readings[0]], DE1976).unwrap();
+    assert_eq!((single.std_dev, single.mean_delta, single.max_delta), ([0.0; 3], 0.0, 0.0));
+    assert!(ColorStats::new(Vec::<LabValue>::new(), DE1976).is_none());
+}
+
+#[cfg(feature = "rgb")]
+#[test]
+fn sort_colors_by_key() {
+    let lab = |l, a, b| LabValue { l, a, b };
+    let mut colors = vec![
+        lab(80.0, 0.0, 0.0),
+        lab(30.0, -40.0, 10.0),
+        lab(55.0, 20.0, 20.0),
+        lab(55.0, 0.0, -60.0),
+        lab(10.0, 5.0, -5.0),
+    ];
+
+    sort_colors(&mut colors, SortKey::Lightness);
+    assert_eq!(colors.iter().map(|c| c.l).collect::<Vec<_>>(), vec![10.0, 30.0, 55.0, 55.0, 80.0]);
+    // Stable: the two colors with L* 55 keep their order
+    assert_eq!(colors[2], lab(55.0, 20.0, 20.0));
+
+    sort_colors(&mut colors, SortKey::Chroma);
+    assert_eq!(colors[0], lab(80.0, 0.0, 0.0));
+    assert_eq!(colors[4], lab(55.0, 0.0, -60.0));
+
+    // Hues 0 (achromatic), 45, 166, 270, and 315
+    sort_colors(&mut colors, SortKey::Hue(0.0));
+    assert_eq!(colors, vec![
+        lab(80.0, 0.0, 0.0),
+        lab(55.0, 20.0, 20.0),
+        lab(30.0, -40.0, 10.0),
+        lab(55.0, 0.0, -60.0),
+        lab(10.0, 5.0, -5.0),
+    ]);
+    sort_colors(&mut colors, SortKey::Hue(300.0));
+    assert_eq!(colors[0], lab(10.0, 5.0, -5.0));
+    assert_eq!(colors[4], lab(55.0, 0.0, -60.0));
+    sort_colors(&mut colors, SortKey::Hue(-60.0));
+    assert_eq!(colors[0], lab(10.0, 5.0, -5.0));
+
+    // Comparators for sort_by and friends
+    let darkest = colors.iter().copied().min_by(|a, b| SortKey::Lightness.compare(a, b));
+    assert_eq!(darkest, Some(lab(10.0, 5.0, -5.0)));
+    let mut rgbs = [RgbValue::new(255, 255, 255), RgbValue::new(0, 0, 0), RgbValue::new(128, 128, 128)];
+    rgbs.sort_by(|a, b| SortKey::Lightness.compare(a, b).reverse());
+    assert_eq!(rgbs[0], RgbValue::new(255, 255, 255));
+    assert_eq!(rgbs[2], RgbValue::new(0, 0, 0));
+}
+
+#[test]
+fn lab_delta() {
+    let reference = LabValue::new(50.0, 20.0, -10.0).unwrap();
+    let sample = LabValue::new(52.0, 17.0, -6.0).unwrap();
+
+    let delta = reference.difference(sample);
+    assert_eq!(delta, LabDelta { dl: 2.0, da: -3.0, db: 4.0 });
+    assert_eq!(sample.difference(reference), -delta);
+    assert_eq!(reference.difference(reference), LabDelta::default());
+    assert_eq!((delta.chromatic_magnitude(), round::round_to(delta.magnitude(), 4)), (5.0, 5.3852));
+    assert_eq!(round::round_to(delta.direction(), 3), 126.87);
+    assert_eq!(LabDelta { dl: 0.0, da: 0.0, db: -1.0 }.direction(), 270.0);
+
+    // The components agree with a DeltaReport
+    let report = reference.delta(sample, DE2000).report(1.0);
+    assert_eq!((report.delta_l, report.delta_a, report.delta_b), (delta.dl, delta.da, delta.db));
+
+    // Arithmetic
+    assert_eq!(reference + delta, sample);
+    assert_eq!(sample - delta, reference);
+    assert_eq!(delta + delta, delta * 2.0);
+    assert_eq!(delta - delta, LabDelta::default());
+    let drifts = [delta, delta * 3.0, -delta];
+    assert_eq!(drifts.iter().copied().sum::<LabDelta>(), delta * 3.0);
+    assert_eq!(delta.to_string(), "[ΔL:2, Δa:-3, Δb:4]");
+}
+
+#[test]
+fn angles() {
+    use std::f32::consts::{FRAC_PI_2, PI, TAU};
+
+    assert_eq!(Radians::from(Degrees(180.0)), Radians(PI));
+    assert_eq!(Degrees::from(Radians(FRAC_PI_2)), Degrees(90.0));
+    assert_eq!(Degrees(725.0).normalized(), Degrees(5.0));
+    assert_eq!(Degrees(-1e-7).normalized(), Degrees(0.0));
+    assert_eq!(Radians(-FRAC_PI_2).normalized(), Radians(1.5 * PI));
+    assert_eq!(Radians(TAU).normalized(), Radians(0.0));
+    let (sin, cos) = Degrees(30.0).sin_cos();
+    assert_eq!((round::round_to(sin, 4), round::round_to(cos, 4)), (0.5, 0.866));
+    assert_eq!(Degrees(90.0).to_string(), "90°");
+    assert_eq!(Radians(1.5).to_string(), "1.5 rad");
+    assert!(Degrees(10.0) < Degrees(20.0));
+
+    let mut lch = LchValue::new(50.0, 20.0, 300.0).unwrap();
+    assert_eq!(lch.hue(), Degrees(300.0));
+    assert_eq!(Radians::from(lch.hue()).0, lch.hue_radians());
+    lch.set_hue(Degrees(420.0));
+    assert_eq!(lch.h, 60.0);
+    lch.set_hue(Radians(-FRAC_PI_2));
+    assert_eq!(lch.h, 270.0);
+}
+
+#[test]
+fn hue_difference() {
+    for &(h1, h2) in [(0.0, 90.0), (350.0, 20.0), (200.0, 10.0), (45.0, 44.0), (720.0, 1.0)].iter() {
+        let diff = hue_diff(h1, h2);
+        assert!((-180.0..=180.0).contains(&diff));
+        assert_eq!(hue_diff(h2, h1), -diff);
+        assert_eq!(round::round_to((h1 + diff - h2).rem_euclid(360.0) % 360.0, 3), 0.0);
+    }
+    assert_eq!(hue_diff(200.0, 10.0), 170.0);
+    assert_eq!(hue_diff(123.0, 123.0), 0.0);
+
+    // DE2000 and DeltaReport agree on both sides of the 0°/360° hue boundary
+    let lch = |h: f32| LabValue::from(LchValue { l: 50.0, c: 30.0, h });
+    for &(h1, h2) in [(350.0, 10.0), (300.0, 100.0), (260.0, 290.0), (5.0, 355.0)].iter() {
+        let (a, b) = (lch(h1), lch(h2));
+        assert_eq!(round::round_to(*a.delta(b, DE2000).value(), 4), round::round_to(*b.delta(a, DE2000).value(), 4));
+        let (forward, back) = (a.delta(b, DE1976).report(1.0), b.delta(a, DE1976).report(1.0));
+        assert_eq!(round::round_to(forward.delta_h, 4), -round::round_to(back.delta_h, 4));
+    }
+}
+
+#[cfg(feature = "rgb")]
+#[test]
+fn hash_key() {
+    use std::collections::HashSet;
+
+    // RgbValue is hashable on its own
+    let rgbs: HashSet<RgbValue> = [RgbValue::new(1, 2, 3), RgbValue::new(1, 2, 3), RgbValue::new(3, 2, 1)]
+        .iter().copied().collect();
+    assert_eq!(rgbs.len(), 2);
+
+    // Signed zeros and NaNs are canonical
+    let lab = |l, a, b| HashKey(LabValue { l, a, b });
+    let set: HashSet<HashKey<LabValue>> = vec![
+        lab(50.0, 0.0, -0.0),
+        lab(50.0, -0.0, 0.0),
+        lab(f32::NAN, 1.0, 1.0),
+        lab(-f32::NAN, 1.0, 1.0),
+        lab(50.0, 0.0, 1e-30),
+    ].into_iter().collect();
+    assert_eq!(set.len(), 3);
+    assert_eq!(lab(f32::NAN, 0.0, 0.0), lab(f32::NAN, 0.0, 0.0));
+    assert_ne!(lab(1.0, 2.0, 3.0), lab(1.0, 2.0, 3.0001));
+
+    // Colors of different types with the same components are different keys
+    let colors: HashSet<HashKey<Color>> = vec![
+        HashKey(Color::Lab(LabValue { l: 1.0, a: 2.0, b: 3.0 })),
+        HashKey(Color::Lch(LchValue { l: 1.0, c: 2.0, h: 3.0 })),
+        HashKey::from(Color::Lab(LabValue { l: 1.0, a: 2.0, b: 3.0 })),
+    ].into_iter().collect();
+    assert_eq!(colors.len(), 2);
+    assert!(colors.contains(&HashKey(Color::Lch(LchValue { l: 1.0, c: 2.0, h: 3.0 }))));
+}
+
+#[cfg(feature = "rgb")]
+#[test]
+fn validate_clamped() {
+    let lch = LchValue { l: -0.00001, c: 181.01934, h: 360.00003 };
+    assert!(lch.validate().is_err());
+    assert_eq!(lch.validate_clamped(1e-4).unwrap(), LchValue { l: 0.0, c: 181.01933, h: 360.0 });
+    assert!(lch.validate_clamped(0.0).is_err());
+
+    let xyz = XyzValue { x: 0.5, y: 1.000001, z: -0.000001 };
+    assert_eq!(xyz.validate_clamped(1e-5).unwrap(), XyzValue { x: 0.5, y: 1.0, z: 0.0 });
+    assert!(XyzValue { x: 0.5, y: 1.1, z: 0.0 }.validate_clamped(1e-5).is_err());
+    let rgb = RgbNominalValue { r: 1.0000001, g: 0.5, b: 0.0 };
+    assert_eq!(rgb.validate_clamped(1e-6).unwrap(), RgbNominalValue { r: 1.0, g: 0.5, b: 0.0 });
+
+    // Values in range are unchanged, and values that are not numbers are never in range
+    let lab = LabValue::new(50.0, -20.0, 30.0).unwrap();
+    assert_eq!(lab.validate_clamped(1.0).unwrap(), lab);
+    assert!(LabValue { l: f32::NAN, a: 0.0, b: 0.0 }.validate_clamped(1.0).is_err());
+    let color = Color::Lab(LabValue { l: 100.00001, a: 0.0, b: 0.0 });
+    assert_eq!(color.validate_clamped(1e-4).unwrap(), Color::Lab(LabValue { l: 100.0, a: 0.0, b: 0.0 }));
+    assert_eq!(RgbSystem::Srgb.validate_clamped(1.0).unwrap(), RgbSystem::Srgb);
+
+    // Round trips through XYZ stay in range once clamped
+    for l in (0..=100).step_by(5) {
+        for &(a, b) in [(0.0, 0.0), (-128.0, 128.0), (128.0, -128.0)].iter() {
+            let round_trip = LabValue::from(XyzValue::from(LabValue { l: l as f32, a, b }));
+            assert!(round_trip.validate_clamped(1e-3).is_ok(), "{}", round_trip);
+        }
+    }
+}
+
+#[cfg(all(feature = "rgb", feature = "parse"))]
+#[test]
+fn validate_not_finite() {
+    for &bad in [f32::NAN, f32::INFINITY, f32::NEG_INFINITY].iter() {
+        assert!(matches!(LabValue { l: 50.0, a: bad, b: 0.0 }.validate(), Err(ValueError::NotFinite)));
+        assert!(matches!(LchValue { l: 50.0, c: 10.0, h: bad }.validate(), Err(ValueError::NotFinite)));
+        assert!(matches!(XyzValue { x: bad, y: 0.5, z: 0.5 }.validate(), Err(ValueError::NotFinite)));
+        assert!(matches!(RgbNominalValue { r: 0.5, g: 0.5, b: bad }.validate(), Err(ValueError::NotFinite)));
+        assert!(matches!(LabValue::new(bad, 0.0, 0.0), Err(ValueError::NotFinite)));
+        assert!(matches!(LabValue { l: bad, a: 0.0, b: 0.0 }.validate_clamped(1.0), Err(ValueError::NotFinite)));
+    }
+    assert!(matches!(LabValue { l: 101.0, a: 0.0, b: 0.0 }.validate(), Err(ValueError::OutOfBounds)));
+    assert!("inf, 0, 0".parse::<LabValue>().is_err());
+    assert!("50, NaN, 0".parse::<LabValue>().is_err());
+
+    // Every delta between validated colors is finite, including at the corners of the ranges
+    let methods = [DE1976, DE1994G, DE1994T, DECMC1, DECMC2, DE2000];
+    let mut labs = Vec::new();
+    for &l in [0.0, 0.001, 50.0, 99.999, 100.0].iter() {
+        for &a in [-128.0, -1e-6, 0.0, 1e-6, 128.0].iter() {
+            for &b in [-128.0, -1e-6, 0.0, 1e-6, 128.0].iter() {
+                labs.push(LabValue::new(l, a, b).unwrap());
+            }
+        }
+    }
+    for &h in [0.0, 90.0, 180.0, 359.999, 360.0].iter() {
+        for &c in [0.0, 1e-6, 181.01933].iter() {
+            labs.push(LabValue::from(LchValue::new(50.0, c, h).unwrap()));
+        }
+    }
+    for &x in [0.0, 0.5, 1.0].iter() {
+        labs.push(LabValue::from(XyzValue::new(x, x, x).unwrap()));
+        labs.push(LabValue::from(XyzValue::new(x, 0.0, 1.0 - x).unwrap()));
+    }
+    for reference in labs.iter() {
+        for sample in labs.iter() {
+            for &method in methods.iter() {
+                let de = reference.delta(*sample, method);
+                assert!(de.value().is_finite(), "{} {} {}", reference, sample, method);
+            }
+        }
+    }
+}
+
+// Colors of any components, from the whole range of `f32` including NaN and the infinities
+fn any_lab() -> impl Strategy<Value = LabValue> {
+    (num::f32::ANY, num::f32::ANY, num::f32::ANY).prop_map(|(l, a, b)| LabValue { l, a, b })
+}
+
+// Lab colors in the valid range, from Lab, LCh and XYZ
+fn valid_lab() -> impl Strategy<Value = LabValue> {
+    prop_oneof![
+        (0.0f32..=100.0, -128.0f32..=128.0, -128.0f32..=128.0)
+            .prop_map(|(l, a, b)| LabValue::new(l, a, b).unwrap()),
+        (0.0f32..=100.0, 0.0f32..=181.01933, 0.0f32..=360.0)
+            .prop_map(|(l, c, h)| LabValue::from(LchValue::new(l, c, h).unwrap())),
+        (0.0f32..=1.0, 0.0f32..=1.0, 0.0f32..=1.0)
+            .prop_map(|(x, y, z)| LabValue::from(XyzValue::new(x, y, z).unwrap())),
+    ]
+}
+
+proptest! {
+    #[test]
+    fn validate_any_components(lab in any_lab()) {
+        let LabValue { l, a, b } = lab;
+        let lch = LchValue { l, c: a, h: b };
+        let xyz = XyzValue { x: l, y: a, z: b };
+
+        if [l, a, b].iter().all(|v| v.is_finite()) {
+            prop_assert!(!matches!(lab.validate(), Err(ValueError::NotFinite)));
+            prop_assert!(!matches!(lch.validate(), Err(ValueError::NotFinite)));
+            prop_assert!(!matches!(xyz.validate(), Err(ValueError::NotFinite)));
+        } else {
+            prop_assert!(matches!(lab.validate(), Err(ValueError::NotFinite)));
+            prop_assert!(matches!(lch.validate(), Err(ValueError::NotFinite)));
+            prop_assert!(matches!(xyz.validate(), Err(ValueError::NotFinite)));
+            prop_assert!(matches!(LabValue::new(l, a, b), Err(ValueError::NotFinite)));
+            prop_assert!(matches!(lab.validate_clamped(1.0), Err(ValueError::NotFinite)));
+        }
+
+        // Whatever passes validation has a finite delta to a valid color
+        if let Ok(lab) = lab.validate() {
+            prop_assert!(lab.delta(LabValue::default(), DE2000).value().is_finite());
+        }
+    }
+
+    #[test]
+    fn delta_of_valid_colors_is_finite(reference in valid_lab(), sample in valid_lab()) {
+        for &method in [DE1976, DE1994G, DE1994T, DECMC1, DECMC2, DE2000].iter() {
+            let de = reference.delta(sample, method);
+            prop_assert!(de.value().is_finite(), "{} {} {}", reference, sample, method);
+            prop_assert!(*de.value() >= 0.0);
+        }
+    }
+}
+
+#[cfg(feature = "rgb")]
+proptest! {
+    #[test]
+    fn validate_any_rgb(r in num::f32::ANY, g in num::f32::ANY, b in num::f32::ANY) {
+        let rgb = RgbNominalValue { r, g, b };
+        let not_finite = matches!(rgb.validate(), Err(ValueError::NotFinite));
+        prop_assert_eq!(not_finite, ![r, g, b].iter().all(|v| v.is_finite()));
+
+        if let Ok(rgb) = rgb.validate() {
+            let lab = LabValue::from_rgb(rgb, RgbSystem::Srgb, None);
+            prop_assert!(lab.delta(LabValue::default(), DE2000).value().is_finite());
+        }
+    }
+}
+
+#[test]
+fn validate_with_profile() {
+    let green = LabValue { l: 85.0, a: -140.0, b: 120.0 };
+    assert!(matches!(green.validate(), Err(ValueError::OutOfBounds)));
+    assert_eq!(green.validate_with(&RangeProfile::EXTENDED).unwrap(), green);
+    assert!(green.validate_with(&RangeProfile::default()).is_err());
+    assert!(LabValue { l: 85.0, a: -201.0, b: 0.0 }.validate_with(&RangeProfile::EXTENDED).is_err());
+
+    let lch = LchValue::from(green);
+    assert!(lch.validate().is_err());
+    assert!(lch.validate_with(&RangeProfile::EXTENDED).is_ok());
+    assert!(LchValue { l: 50.0, c: 10.0, h: 361.0 }.validate_with(&RangeProfile::EXTENDED).is_err());
+
+    let white = XyzValue { x: 95.047, y: 100.0, z: 108.883 };
+    assert!(white.validate().is_err());
+    assert!(white.validate_with(&RangeProfile::EXTENDED).is_ok());
+    let profile = RangeProfile { xyz: 0.0..=100.0, ..RangeProfile::STRICT };
+    assert!(Color::Xyz(white).validate_with(&profile).is_err());
+    let profile = RangeProfile { xyz: 0.0..=120.0, ..RangeProfile::STRICT };
+    assert!(Color::Xyz(white).validate_with(&profile).is_ok());
+    assert!(Color::Lab(green).validate_with(&profile).is_err());
+
+    // Non-finite values are rejected by every profile
+    let nan = LabValue { l: 50.0, a: f32::NAN, b: 0.0 };
+    assert!(matches!(nan.validate_with(&RangeProfile::EXTENDED), Err(ValueError::NotFinite)));
+}
+
+#[test]
+fn rounding_modes() {
+    let round = |val: f32, precision, mode| {
+        LabValue { l: val, a: 0.0, b: 0.0 }.round_by(precision, mode).l
+    };
+    let places = Precision::DecimalPlaces;
+
+    assert_eq!(round(2.5, places(0), RoundingMode::HalfAwayFromZero), 3.0);
+    assert_eq!(round(2.5, places(0), RoundingMode::HalfEven), 2.0);
+    assert_eq!(round(3.5, places(0), RoundingMode::HalfEven), 4.0);
+    assert_eq!(round(1.2345, places(3), RoundingMode::HalfEven), 1.234);
+    assert_eq!(round(1.2355, places(3), RoundingMode::HalfEven), 1.236);
+    assert_eq!(round(1.2345, places(3), RoundingMode::HalfUp), 1.235);
+    assert_eq!(round(0.35, places(1), RoundingMode::HalfEven), 0.4);
+    assert_eq!(round(0.45, places(1), RoundingMode::HalfEven), 0.4);
+    assert_eq!(round(1.23449, places(3), RoundingMode::HalfUp), 1.234);
+    assert_eq!(round(55.0, places(-1), RoundingMode::HalfEven), 60.0);
+
+    // Negative halves
+    let lab = LabValue { l: 0.0, a: -2.5, b: -3.5 };
+    assert_eq!(lab.round_by(places(0), RoundingMode::HalfAwayFromZero), LabValue { l: 0.0, a: -3.0, b: -4.0 });
+    assert_eq!(lab.round_by(places(0), RoundingMode::HalfUp), LabValue { l: 0.0, a: -2.0, b: -3.0 });
+    assert_eq!(lab.round_by(places(0), RoundingMode::HalfEven), LabValue { l: 0.0, a: -2.0, b: -4.0 });
+
+    // Significant figures
+    let sig = Precision::SignificantFigures;
+    assert_eq!(round(12.345, sig(3), RoundingMode::HalfAwayFromZero), 12.3);
+    assert_eq!(round(99.96, sig(3), RoundingMode::HalfAwayFromZero), 100.0);
+    assert_eq!(round(0.0, sig(3), RoundingMode::HalfAwayFromZero), 0.0);
+    assert_eq!(round(0.000123456, sig(2), RoundingMode::HalfAwayFromZero), 0.00012);
+    assert_eq!(round(0.125, sig(2), RoundingMode::HalfEven), 0.12);
+    assert_eq!(round(7.0, sig(0), RoundingMode::HalfEven), 7.0);
+    let lch = LchValue { l: 54.29054, c: 106.83897, h: 40.85261 };
+    assert_eq!(lch.round_to_sig_figs(4), LchValue { l: 54.29, c: 106.8, h: 40.85 });
+    assert_eq!(lch.round_to_sig_figs(4), lch.round_by(sig(4), RoundingMode::default()));
+
+    // Values that are not halves are rounded to the nearest either way
+    for &mode in [RoundingMode::HalfAwayFromZero, RoundingMode::HalfUp, RoundingMode::HalfEven].iter() {
+        assert_eq!(round(181.01933, places(4), mode), 181.0193);
+        assert_eq!(round(1.2346, places(3), mode), 1.235);
+    }
+
+    let matrix = matrix![0.125, 0.375, 2.5; 1.0, 2.0, 3.0; -0.125, 0.0, 0.5];
+    assert_eq!(matrix.round_by(places(2), RoundingMode::HalfEven)[0], 0.12);
+    assert_eq!(matrix.round_by(places(2), RoundingMode::HalfEven)[1], 0.38);
+}
+
+#[cfg(feature = "rgb")]
+#[test]
+fn display_width() {
+    let lab = LabValue { l: 50.0, a: -2.5, b: 10.125 };
+    assert_eq!(format!("{:.2}", lab), "[L:50.00, a:-2.50, b:10.12]");
+    assert_eq!(format!("{:#.1}", lab), "50.0,-2.5,10.1");
+    assert_eq!(format!("{:>18.1}", lab), "[L:50.0, a:-2.5, b:10.1]");
+    assert_eq!(format!("{:>16.1}|", LchValue { l: 50.0, c: 0.0, h: 0.0 }), "[L:50.0, c:0.0, h:0.0]|");
+    assert_eq!(format!("{:>#16}|", lab), "  50,-2.5,10.125|");
+    assert_eq!(format!("{:<#16}|", lab), "50,-2.5,10.125  |");
+    assert_eq!(format!("{:*^#18.1}", lab), "**50.0,-2.5,10.1**");
+    assert_eq!(format!("{:#16}|", XyzValue { x: 0.5, y: 0.25, z: 0.0 }), "0.5,0.25,0      |");
+    assert_eq!(format!("{:>#14}", RgbValue::new(255, 0, 128)), "     255,0,128");
+    assert_eq!(format!("{:#.3}", RgbNominalValue { r: 1.0, g: 0.5, b: 0.0 }), "1.000,0.500,0.000");
+    assert_eq!(format!("{:>20.1}", Color::Lab(lab)), "  lab:50.0,-2.5,10.1");
+    assert_eq!(format!("{:.1}", LabDelta { dl: 1.0, da: -0.25, db: 0.0 }), "[ΔL:1.0, Δa:-0.2, Δb:0.0]");
+    assert_eq!(format!("{:>28.1}", LabDelta { dl: 1.0, da: -0.25, db: 0.0 }), "   [ΔL:1.0, Δa:-0.2, Δb:0.0]");
+
+    let de = LabValue::new(50.0, 0.0, 0.0).unwrap().delta(LabValue::new(53.0, 4.0, 0.0).unwrap(), DE1976);
+    assert_eq!(format!("{:>8.2}|", de), "    5.00|");
+    assert_eq!(format!("{:<8.1}|", de), "5.0     |");
+    assert_eq!(format!("{:08.3}", de), "0005.000");
+    assert_eq!(format!("{:>8}|{:<8}|", DE2000, DECMC1), "  DE2000|DECMC1  |");
+
+    // Tables of colors line up
+    let rows: Vec<String> = [lab, LabValue::default()].iter()
+        .map(|lab| format!("{:>#18.2}|", lab))
+        .collect();
+    assert_eq!(rows, vec![" 50.00,-2.50,10.12|", "    0.00,0.00,0.00|"]);
+}
+
+#[test]
+fn delta_e_inputs() {
+    let reference = LabValue::new(50.0, 10.0, -10.0).unwrap();
+    let sample = XyzValue::from(LabValue::new(52.0, 8.0, -11.0).unwrap());
+    let de = reference.delta(sample, DE1994T);
+
+    assert_eq!(de.reference(), &reference);
+    assert_eq!(de.sample().round_to(3), LabValue::new(52.0, 8.0, -11.0).unwrap());
+    assert_eq!(de.method(), &DE1994T);
+
+    // The inputs are kept through rounding and recalculation
+    let rounded = de.round_to(2).with_method(DE2000);
+    assert_eq!(rounded.reference(), &reference);
+    assert_eq!(rounded.sample(), de.sample());
+    assert_eq!(rounded, reference.delta(sample, DE2000));
+}
+
+#[cfg(all(feature = "rgb", feature = "parse"))]
+#[test]
+fn unified_error() {
+    use std::error::Error as _;
+
+    let err = Illuminant::from_str(" d99").unwrap_err();
+    match &err {
+        Error::Parse(e) => {
+            assert_eq!(e.kind(), ParseErrorKind::UnknownName);
+            assert_eq!(e.token(), "d99");
+            assert_eq!(e.position(), 1);
+        }
+        other => panic!("{:?}", other),
+    }
+    assert_eq!(err.to_string(), r#"unknown name "d99" at position 1"#);
+    // Display already prints the wrapped error, so the chain does not repeat it
+    assert!(err.source().is_none());
+    assert!(matches!(Observer::from_str("5"), Err(Error::Parse(_))));
+    assert!(matches!(ChromaticAdaptationMethod::from_str("nope"), Err(Error::Parse(_))));
+    assert!(matches!(RgbSystem::from_str("nope"), Err(Error::Parse(_))));
+
+    let err = Error::from(DEMethod::from_str("de3000").unwrap_err());
+    assert!(matches!(err, Error::Method(_)));
+    assert!(err.to_string().starts_with("unknown DeltaE method"));
+
+    let err = Error::from(std::io::Error::from(std::io::ErrorKind::UnexpectedEof));
+    assert!(matches!(&err, Error::Io(e) if e.kind() == std::io::ErrorKind::UnexpectedEof));
+    assert!(err.source().is_none());
+
+    // An error wrapped by an io::Error is reached once, not twice
+    let inner = std::io::Error::new(std::io::ErrorKind::InvalidData, Error::SingularMatrix);
+    let outer = std::io::Error::other(Error::Io(inner));
+    let mut chain = vec![outer.to_string()];
+    let mut source = outer.source();
+    while let Some(e) = source {
+        chain.push(e.to_string());
+        source = e.source();
+    }
+    assert_eq!(chain, ["Matrix is singular!"; 1]);
+
+    let singular = ChromaticAdaptationMethod::Custom(Matrix3x3::new([1.0; 9]));
+    assert!(matches!(singular.validate(), Err(Error::SingularMatrix)));
+    assert_eq!(Error::SingularMatrix.to_string(), "Matrix is singular!");
+    assert!(Error::SingularMatrix.source().is_none());
+
+    // ValueError is the same type
+    let err: ValueError = LabValue::new(101.0, 0.0, 0.0).unwrap_err();
+    assert!(matches!(err, Error::OutOfBounds));
+}
+
+#[cfg(feature = "rgb")]
+#[test]
+fn const_conversions() {
+    const CAT: ChromaticAdaptationTransform = ChromaticAdaptationTransform::new(
+        ChromaticAdaptationMethod::Cat02,
+        Illuminant::A,
+        Illuminant::D65,
+    );
+    const TO_XYZ: Matrix3x3 = RgbSystem::Adobe.rgb_to_xyz_adapted(ChromaticAdaptationMethod::Bradford);
+    const FROM_XYZ: Matrix3x3 = RgbSystem::Adobe.xyz_to_rgb_adapted(ChromaticAdaptationMethod::Bradford);
+    const WHITE: XyzValue = Illuminant::D55.white_point_for(Observer::Cie1964);
+    const ORANGE: RgbValue = RgbValue::new(255, 128, 0);
+    const PRODUCT: Matrix3x3 = TO_XYZ.mul_matrix(&FROM_XYZ);
+
+    // The same as calculating at runtime
+    let runtime = ChromaticAdaptationTransform::new(
+        ChromaticAdaptationMethod::Cat02,
+        Illuminant::A,
+        Illuminant::D65,
+    );
+    assert_eq!(CAT.matrix().round_to(5), runtime.matrix().round_to(5));
+    let xyz = XyzValue::new(0.3, 0.4, 0.2).unwrap();
+    assert_eq!(
+        CAT.apply(xyz).round_to(5),
+        xyz.chrom_adapt(ChromaticAdaptationMethod::Cat02, Illuminant::A, Illuminant::D65).round_to(5)
+    );
+    assert_eq!(WHITE, XyzValue { x: 0.95799, y: 1.0, z: 0.90926 });
+    assert_eq!(ORANGE, RgbValue { r: 255, g: 128, b: 0 });
+    assert_eq!(PRODUCT.round_to(5), Matrix3x3::IDENTITY);
+
+    for &(r, g, b) in [(1.0, 1.0, 1.0), (0.2, 0.5, 0.9), (0.0, 0.0, 1.0)].iter() {
+        let rgb = RgbNominalValue::new(r, g, b).unwrap();
+        let linear = RgbSystem::Adobe.linearize_rgb(rgb);
+        let xyz = XyzValue::from(TO_XYZ * matrix![linear.r; linear.g; linear.b]);
+        let expected = XyzValue::from_rgb(rgb, RgbSystem::Adobe, Some(ChromaticAdaptationMethod::Bradford));
+        assert_eq!(xyz.round_to(5), expected.round_to(5));
+    }
+
+    // Matrix products agree with the operators
+    let a = matrix![1.0, 2.0, 3.0; 4.0, 5.0, 6.0; 7.0, 8.0, 10.0];
+    let b = matrix![0.5, -1.0, 0.0; 2.0, 0.25, 1.0; -3.0, 0.0, 4.0];
+    assert_eq!(a.mul_matrix(&b), a * b);
+    assert_eq!(a.mul_column(&matrix![1.0; -2.0; 0.5]), a * matrix![1.0; -2.0; 0.5]);
+    assert_eq!(a.to_array()[8], 10.0);
+    assert_eq!(matrix![1.0; 2.0; 3.0].to_array(), [1.0, 2.0, 3.0]);
+}
+
+#[cfg(feature = "wasm")]
+#[test]
+fn wasm_bindings() {
+    // Only the successful results, since JavaScript errors can not be created outside of wasm
+    let (lab0, lab1) = (LabValue::new(89.73, 1.88, -6.96).unwrap(), LabValue::new(95.08, -0.17, -10.81).unwrap());
+    let de = wasm::delta_e(89.73, 1.88, -6.96, 95.08, -0.17, -10.81, "de2000").unwrap();
+    assert_eq!(de, *lab0.delta(lab1, DE2000).value());
+    let de = wasm::delta_e(89.73, 1.88, -6.96, 95.08, -0.17, -10.81, "cmc(2:1)").unwrap();
+    assert_eq!(de, *lab0.delta(lab1, DECMC2).value());
+
+    let lch = LchValue::from(lab0);
+    assert_eq!(wasm::lab_to_lch(89.73, 1.88, -6.96).unwrap(), vec![lch.l, lch.c, lch.h]);
+    let lab = wasm::lch_to_lab(lch.l, lch.c, lch.h).unwrap();
+    assert_eq!(LabValue { l: lab[0], a: lab[1], b: lab[2] }.round_to(4), lab0);
+
+    let xyz = wasm::lab_to_xyz(89.73, 1.88, -6.96).unwrap();
+    let lab = wasm::xyz_to_lab(xyz[0], xyz[1], xyz[2]).unwrap();
+    assert_eq!(LabValue { l: lab[0], a: lab[1], b: lab[2] }.round_to(3), lab0.round_to(3));
+
+    let lab = wasm::hex_to_lab("#663399").unwrap();
+    assert_eq!(lab, {
+        let lab = LabValue::from(RgbValue::new(102, 51, 153));
+        vec![lab.l, lab.a, lab.b]
+    });
+    assert_eq!(wasm::lab_to_hex(lab[0], lab[1], lab[2]).unwrap(), "#663399");
+}
+
+#[cfg(feature = "python")]
+#[test]
+fn python_bindings() {
+    use pyo3::prelude::*;
+    use pyo3::types::PyDict;
+
+    let lab0 = LabValue::new(89.73, 1.88, -6.96).unwrap();
+    let lab1 = LabValue::new(95.08, -0.17, -10.81).unwrap();
+
+    Python::initialize();
+    Python::attach(|py| {
+        let module = pyo3::wrap_pymodule!(python::module)(py);
+        py.import("sys").unwrap().getattr("modules").unwrap().set_item("deltae", module).unwrap();
+        let globals = PyDict::new(py);
+        globals.set_item("de2000", *lab0.delta(lab1, DE2000).value()).unwrap();
+        globals.set_item("decmc2", *lab0.delta(lab1, DECMC2).value()).unwrap();
+        let code = std::ffi::CString::new("
+from deltae import LabValue, LchValue, XyzValue, RgbValue, delta_e, methods
+
+lab0 = LabValue(89.73, 1.88, -6.96)
+lab1 = LabValue(95.08, -0.17, -10.81)
+assert lab0.delta(lab1) == de2000
+assert delta_e(lab0, lab1, 'cmc(2:1)') == decmc2
+assert abs(delta_e(lab0.to_lch(), lab1.to_xyz(), 'de2000') - de2000) < 1e-4
+assert methods() == ['DE2000', 'DE1994G', 'DE1994T', 'DECMC1', 'DECMC2', 'DE1976']
+for method in methods():
+    lab0.delta(RgbValue(102, 51, 153), method)
+
+assert RgbValue.from_hex('#639') == RgbValue(102, 51, 153)
+assert RgbValue.from_hex('#663399').to_lab().to_rgb().to_hex() == '#663399'
+assert lab0.to_lch().components()[0] == lab0.l
+assert repr(lab0) == 'LabValue(89.73, 1.88, -6.96)'
+assert str(lab0) == '[L:89.73, a:1.88, b:-6.96]'
+
+for invalid in [lambda: LabValue(101.0, 0.0, 0.0), lambda: lab0.delta(lab1, 'de1999')]:
+    try:
+        invalid()
+        assert False
+    except ValueError:
+        pass
+").unwrap();
+        py.run(&code, Some(&globals), None).unwrap();
+    });
+}
+
+#[test]
+fn convert_buffers() {
+    let labs = [
+        LabValue::new(89.73, 1.88, -6.96).unwrap(),
+        LabValue::new(95.08, -0.17, -10.81).unwrap(),
+        LabValue::new(30.0, 40.0, 50.0).unwrap(),
+    ];
+
+    let mut lchs = [LchValue::default(); 3];
+    convert_into(&labs, &mut lchs).unwrap();
+    let mut xyzs = [XyzValue::default(); 3];
+    convert_into(&lchs, &mut xyzs).unwrap();
+    for i in 0..3 {
+        assert_eq!(lchs[i], LchValue::from(labs[i]));
+        assert_eq!(xyzs[i], XyzValue::from(lchs[i]));
+    }
+    assert!(matches!(convert_into(&labs[..2], &mut lchs), Err(ValueError::BadFormat)));
+
+    // Packed buffers match the typed conversions, and round trip
+    let packed: Vec<f32> = labs.iter().flat_map(|lab| lab.components()).collect();
+    let mut lch_plane = vec![0.0; packed.len()];
+    convert_packed_into::<LabValue, LchValue>(&packed, &mut lch_plane).unwrap();
+    let expected: Vec<f32> = lchs.iter().flat_map(|lch| lch.components()).collect();
+    assert_eq!(lch_plane, expected);
+
+    let mut lab_plane = vec![0.0; packed.len()];
+    convert_packed_into::<LchValue, LabValue>(&lch_plane, &mut lab_plane).unwrap();
+    for (a, b) in lab_plane.iter().zip(packed.iter()) {
+        assert!((a - b).abs() < 1e-4);
+    }
+
+    assert!(convert_packed_into::<LabValue, XyzValue>(&packed[..8], &mut lab_plane[..8]).is_err());
+    assert!(convert_packed_into::<LabValue, XyzValue>(&packed, &mut lab_plane[..6]).is_err());
+
+    #[cfg(feature = "rgb")]
+    {
+        let mut rgbs = [RgbValue::default(); 3];
+        convert_into(&labs, &mut rgbs).unwrap();
+        assert_eq!(rgbs[2], RgbValue::from(labs[2]));
+
+        let mut rgb_plane = vec![0.0; packed.len()];
+        convert_packed_into::<LabValue, RgbNominalValue>(&packed, &mut rgb_plane).unwrap();
+        assert_eq!(rgb_plane[6..], RgbNominalValue::from(labs[2]).components());
+    }
+}
+
+#[cfg(feature = "rgb")]
+#[test]
+fn rgb_transfer_functions() {
+    let grey = RgbNominalValue { r: 0.5, g: 0.5, b: 0.5 };
+    let bradford = Some(ChromaticAdaptationMethod::Bradford);
+
+    // Mid grey decodes by the curve of each system, so its luminance follows the curve
+    for (system, y) in &[
+        (RgbSystem::Srgb, 0.21404),
+        (RgbSystem::DisplayP3, 0.21404),
+        (RgbSystem::Adobe, 0.5_f32.powf(2.2)),
+        (RgbSystem::AcesCg, 0.5),
+        (RgbSystem::DciP3, 0.5_f32.powf(2.6)),
+        (RgbSystem::ColorMatch, 0.5_f32.powf(1.8)),
+        (RgbSystem::ProPhoto, 0.5_f32.powf(1.8)),
+        (RgbSystem::Eci, 0.18419),
+        (RgbSystem::Rec709, 0.25959),
+        (RgbSystem::Rec2020, 0.25959),
+    ] {
+        let xyz = XyzValue::from_rgb(grey, system.clone(), bradford);
+        assert!((xyz.y - y).abs() < 1e-4, "{}: {} != {}", system, xyz.y, y);
+        assert_eq!(RgbNominalValue::from_xyz(xyz, system.clone(), bradford).round_to(4), grey);
+    }
+
+    for system in &[RgbSystem::Srgb, RgbSystem::Apple, RgbSystem::Eci, RgbSystem::Rec709, RgbSystem::WideGamut] {
+        let function = system.transfer_function();
+        for v in &[0.0, 0.001, 0.01, 0.05, 0.08, 0.2, 0.5, 1.0] {
+            assert!((function.encode(function.decode(*v)) - v).abs() < 1e-5);
+            assert_eq!(system.linearize(*v), function.decode(*v));
+        }
+    }
+
+    let gamma = TransferCurve::from(ToneCurve::Gamma(2.2));
+    assert_eq!(gamma.decode(0.5), TransferCurve::Gamma(2.2).decode(0.5));
+}
+
+#[cfg(feature = "rgb")]
+#[test]
+fn rgb_system_primaries() {
+    let adobe = RgbSystem::Primaries {
+        primaries: [[0.64, 0.33], [0.21, 0.71], [0.15, 0.06]],
+        white: Illuminant::D65,
+        transfer: TransferCurve::Gamma(2.2),
+    };
+    assert!(adobe.clone().validate().is_ok());
+    assert_eq!(adobe.to_string(), "Custom");
+    assert_eq!(adobe.white_point(), Illuminant::D65);
+    for (derived, canned) in adobe.rgb_to_xyz().iter().zip(RgbSystem::Adobe.rgb_to_xyz().iter()) {
+        assert!((derived - canned).abs() < 1e-4, "{} != {}", derived, canned);
+    }
+    assert!((adobe.xyz_to_rgb() * adobe.rgb_to_xyz()).round_to(5).is_identity());
+
+    // Full intensity of every channel is the white point
+    let white = XyzValue::from_rgb(RgbNominalValue { r: 1.0, g: 1.0, b: 1.0 }, adobe.clone(), None);
+    assert_eq!(white.round_to(4), Illuminant::D65.white_point().round_to(4));
+
+    let bradford = Some(ChromaticAdaptationMethod::Bradford);
+    let rgb = RgbNominalValue { r: 0.2, g: 0.7, b: 0.4 };
+    assert_eq!(
+        LabValue::from_rgb(rgb, adobe, bradford).round_to(2),
+        LabValue::from_rgb(rgb, RgbSystem::Adobe, bradford).round_to(2)
+    );
+
+    // Coincident primaries span no gamut
+    let coincident = RgbSystem::Primaries {
+        primaries: [[0.64, 0.33], [0.3, 0.6], [0.3, 0.6]],
+        white: Illuminant::D50,
+        transfer: TransferCurve::Srgb,
+    };
+    assert!(coincident.validate().is_err());
+    let degenerate = RgbSystem::Primaries {
+        primaries: [[0.64, 0.0], [0.21, 0.71], [0.15, 0.06]],
+        white: Illuminant::D50,
+        transfer: TransferCurve::Srgb,
+    };
+    assert!(matches!(degenerate.validate(), Err(ValueError::NotFinite)));
+}
+
+#[cfg(feature = "rgb")]
+#[test]
+fn rgb_system_aces() {
+    let bradford = Some(ChromaticAdaptationMethod::Bradford);
+    let white = RgbNominalValue { r: 1.0, g: 1.0, b: 1.0 };
+    for system in &[RgbSystem::Aces2065, RgbSystem::AcesCg] {
+        assert_eq!(system.white_point(), Illuminant::Aces);
+        assert_eq!(system.transfer_function(), TransferCurve::Linear);
+        let lab = LabValue::from_rgb(white, system.clone(), bradford);
+        assert_eq!(lab.round_to(2), LabValue { l: 100.0, a: 0.0, b: 0.0 });
+    }
+
+    // ACEScg to ACES2065-1 without adaptation, as the AP1 to AP0 matrix
+    let cg = RgbNominalValue { r: 0.2, g: 0.4, b: 0.6 };
+    let xyz = XyzValue::from_rgb(cg, RgbSystem::AcesCg, None);
+    let ap0 = RgbNominalValue::from_xyz(xyz, RgbSystem::Aces2065, None);
+    assert_eq!(ap0.round_to(4), RgbNominalValue { r: 0.2937, g: 0.4101, b: 0.6014 });
+
+    // Adapted to D50, the round trip through Lab is lossless
+    let lab = LabValue::from_rgb(cg, RgbSystem::AcesCg, bradford);
+    assert_eq!(RgbNominalValue::from_lab(lab, RgbSystem::AcesCg, bradford).round_to(4), cg);
+    assert!(LabValue::from_rgb(cg, RgbSystem::Aces2065, bradford).delta(lab, DE2000).value() > &1.0);
+}
+
+#[cfg(feature = "rgb")]
+#[test]
+fn prophoto_16_bit() {
+    let prophoto = RgbSystem::ProPhoto.transfer_function();
+    assert_eq!(prophoto, TransferCurve::ProPhoto);
+
+    // Linear near black, where a 1.8 power law would amplify noise
+    assert_eq!(prophoto.encode(0.001), 0.016);
+    assert_eq!(prophoto.decode(0.016), 0.001);
+    assert!((prophoto.encode(1.0 / 512.0) - 16.0 / 512.0).abs() < 1e-3);
+    for v in &[0.0, 0.0005, 0.002, 0.03, 0.2, 0.5, 1.0] {
+        assert!((prophoto.decode(prophoto.encode(*v)) - v).abs() < 1e-6);
+    }
+
+    // Every 16 bit value round trips
+    for v in (0..=u16::MAX).step_by(257) {
+        let rgb = Rgb16Value::new(v, v, v);
+        assert_eq!(Rgb16Value::from(RgbNominalValue::from(rgb)), rgb);
+    }
+    assert_eq!(Rgb16Value::new(65535, 0, 0).to_nominal(), RgbNominalValue { r: 1.0, g: 0.0, b: 0.0 });
+    assert_eq!(Rgb16Value::from(RgbNominalValue { r: 1.5, g: -0.5, b: 0.5 }), Rgb16Value::new(65535, 0, 32768));
+
+    // A dark 16 bit ProPhoto value survives the round trip through Lab, which 8 bits cannot hold
+    let bradford = Some(ChromaticAdaptationMethod::Bradford);
+    let dark = Rgb16Value::new(300, 200, 100);
+    let lab = LabValue::from_rgb(dark.to_nominal(), RgbSystem::ProPhoto, bradford);
+    assert_eq!(Rgb16Value::from(RgbNominalValue::from_lab(lab, RgbSystem::ProPhoto, bradford)), dark);
+    assert_eq!(RgbValue::from(dark), RgbValue::new(1, 1, 0));
+}
+
+#[cfg(feature = "rgb")]
+#[test]
+fn hsl_hsv() {
+    let colors = &[
+        (RgbValue::new(255, 0, 0), HslValue { h: 0.0, s: 1.0, l: 0.5 }, HsvValue { h: 0.0, s: 1.0, v: 1.0 }),
+        (RgbValue::new(0, 128, 0), HslValue { h: 120.0, s: 1.0, l: 0.251 }, HsvValue { h: 120.0, s: 1.0, v: 0.502 }),
+        (RgbValue::new(255, 0, 255), HslValue { h: 300.0, s: 1.0, l: 0.5 }, HsvValue { h: 300.0, s: 1.0, v: 1.0 }),
+        (RgbValue::new(102, 51, 153), HslValue { h: 270.0, s: 0.5, l: 0.4 }, HsvValue { h: 270.0, s: 0.667, v: 0.6 }),
+        (RgbValue::new(255, 128, 0), HslValue { h: 30.118, s: 1.0, l: 0.5 }, HsvValue { h: 30.118, s: 1.0, v: 1.0 }),
+        (RgbValue::new(128, 128, 128), HslValue { h: 0.0, s: 0.0, l: 0.502 }, HsvValue { h: 0.0, s: 0.0, v: 0.502 }),
+        (RgbValue::new(255, 255, 255), HslValue { h: 0.0, s: 0.0, l: 1.0 }, HsvValue { h: 0.0, s: 0.0, v: 1.0 }),
+        (RgbValue::new(0, 0, 0), HslValue { h: 0.0, s: 0.0, l: 0.0 }, HsvValue { h: 0.0, s: 0.0, v: 0.0 }),
+    ];
+
+    for (rgb, hsl, hsv) in colors {
+        assert_eq!(rgb.to_hsl().round_to(3), *hsl, "{}", rgb);
+        assert_eq!(rgb.to_hsv().round_to(3), *hsv, "{}", rgb);
+        assert!(rgb.to_hsl().validate().is_ok());
+        assert!(rgb.to_hsv().validate().is_ok());
+        assert_eq!(RgbValue::from(rgb.to_hsl().to_rgb()), *rgb);
+        assert_eq!(RgbValue::from(RgbNominalValue::from(rgb.to_hsv())), *rgb);
+        assert_eq!(HsvValue::from(rgb.to_hsl()).round_to(3), rgb.to_hsv().round_to(3));
+    }
+
+    // Every 8 bit color round trips
+    for v in (0..=0xFF_FF_FF).step_by(997) {
+        let rgb = RgbValue::new((v >> 16) as u8, (v >> 8) as u8, v as u8);
+        assert_eq!(RgbValue::from(HslValue::from(RgbNominalValue::from(rgb)).to_rgb()), rgb);
+        assert_eq!(RgbValue::from(HsvValue::from(RgbNominalValue::from(rgb)).to_rgb()), rgb);
+    }
+
+    // Hues wrap around
+    assert_eq!(HslValue { h: 360.0, s: 1.0, l: 0.5 }.to_rgb(), RgbNominalValue { r: 1.0, g: 0.0, b: 0.0 });
+    assert_eq!(HsvValue { h: -120.0, s: 1.0, v: 1.0 }.to_rgb(), RgbNominalValue { r: 0.0, g: 0.0, b: 1.0 });
+
+    assert!(HslValue::new(0.0, 1.5, 0.5).is_err());
+    assert!(HsvValue::new(400.0, 0.5, 0.5).is_err());
+    assert!(HsvValue::new(f32::NAN, 0.5, 0.5).is_err());
+    assert_eq!(HslValue { h: 270.0, s: 0.5, l: 0.4 }.to_string(), "[H:270, S:0.5, L:0.4]");
+}
+
+#[cfg(feature = "rgb")]
+#[test]
+fn hdr_transfer_functions() {
+    let pq = TransferCurve::Pq;
+    assert!(pq.encode(0.0) < 1e-6);
+    assert!((pq.encode(1.0) - 1.0).abs() < 1e-6);
+    // 100 and 203 cd/m², SDR and HDR reference white
+    assert!((pq.encode(0.01) - 0.5081).abs() < 1e-4);
+    assert!((pq.encode(0.0203) - 0.5806).abs() < 1e-4);
+
+    let hlg = TransferCurve::Hlg;
+    assert_eq!(hlg.encode(0.0), 0.0);
+    assert!((hlg.encode(1.0 / 12.0) - 0.5).abs() < 1e-6);
+    assert!((hlg.encode(1.0) - 1.0).abs() < 1e-6);
+    assert!((hlg.decode(0.75) - 0.26496).abs() < 1e-4);
+
+    let functions: [&dyn TransferFunction; 4] = [&pq, &hlg, &TransferCurve::Srgb, &ToneCurve::Gamma(2.4)];
+    for function in &functions {
+        for v in &[0.0001, 0.001, 0.01, 0.05, 1.0 / 12.0, 0.2, 0.5, 0.9, 1.0] {
+            assert!((function.decode(function.encode(*v)) - v).abs() < 1e-4 * v.max(0.01), "{}", v);
+        }
+    }
+
+    let rgb = RgbNominalValue { r: 0.75, g: 0.5, b: 0.25 };
+    let linear = hlg.decode_rgb(rgb);
+    assert_eq!(linear.g, hlg.decode(0.5));
+    assert_eq!(hlg.encode_rgb(linear).round_to(5), rgb);
+
+    // An HDR system from primaries
+    let rec2100 = RgbSystem::Primaries {
+        primaries: [[0.708, 0.292], [0.170, 0.797], [0.131, 0.046]],
+        white: Illuminant::D65,
+        transfer: TransferCurve::Pq,
+    };
+    let white = XyzValue::from_rgb(RgbNominalValue { r: 0.5806, g: 0.5806, b: 0.5806 }, rec2100, None);
+    assert!((white.y - 0.0203).abs() < 1e-4);
+}
+
+#[cfg(feature = "rgb")]
+#[test]
+fn rgb_extended_range() {
+    let bradford = Some(ChromaticAdaptationMethod::Bradford);
+
+    // In gamut colors are the same as when clipped
+    for system in RGB_SYSTEMS {
+        let xyz = XyzValue::from_rgb(RgbNominalValue { r: 0.2, g: 0.4, b: 0.6 }, system.clone(), bradford);
+        let (rgb, out_of_gamut) = RgbNominalValue::from_xyz_extended(xyz, system.clone(), bradford);
+        assert_eq!(out_of_gamut, None, "{}", system);
+        assert_eq!(rgb.round_to(4), RgbNominalValue::from_xyz(xyz, system.clone(), bradford).round_to(4));
+    }
+
+    // Out of gamut colors round trip through every system, where clipping distorts them
+    let p3_red = XyzValue::from_rgb(RgbNominalValue { r: 1.0, g: 0.0, b: 0.0 }, RgbSystem::DisplayP3, bradford);
+    for system in &[RgbSystem::Srgb, RgbSystem::Adobe, RgbSystem::Eci, RgbSystem::Rec709] {
+        let (rgb, out_of_gamut) = RgbNominalValue::from_xyz_extended(p3_red, system.clone(), bradford);
+        assert!(out_of_gamut.is_some(), "{}", system);
+        assert!(rgb.validate().is_err());
+        assert_eq!(XyzValue::from_rgb_extended(rgb, system.clone(), bradford).round_to(4), p3_red.round_to(4));
+    }
+    let (srgb, out_of_gamut) = RgbNominalValue::from_xyz_extended(p3_red, RgbSystem::Srgb, bradford);
+    assert_eq!(out_of_gamut, Some(OutOfGamut { r: true, g: true, b: true }));
+    assert!(srgb.r > 1.0 && srgb.g < 0.0 && srgb.b < 0.0);
+    let clipped = RgbNominalValue::from_xyz(p3_red, RgbSystem::Srgb, bradford);
+    let clipped = XyzValue::from_rgb(clipped, RgbSystem::Srgb, bradford);
+    assert!(LabValue::from(clipped).delta(LabValue::from(p3_red), DE2000).value() > &1.0);
+
+    // Highlights brighter than white in a linear system
+    let white = Illuminant::D50.white_point();
+    let highlight = XyzValue { x: white.x * 4.0, y: white.y * 4.0, z: white.z * 4.0 };
+    let (rgb, out_of_gamut) = RgbNominalValue::from_xyz_extended(highlight, RgbSystem::AcesCg, bradford);
+    assert_eq!(out_of_gamut, Some(OutOfGamut { r: true, g: true, b: true }));
+    assert_eq!(rgb.round_to(3), RgbNominalValue { r: 4.0, g: 4.0, b: 4.0 });
+
+    // A negative channel is decoded as the negative of its magnitude
+    let rgb = RgbNominalValue { r: -0.5, g: 0.5, b: 0.0 };
+    let xyz = XyzValue::from_rgb_extended(rgb, RgbSystem::Srgb, None);
+    let linear = RgbSystem::Srgb.xyz_to_rgb() * Matrix3x1::from(xyz);
+    assert!((linear[0] + RgbSystem::Srgb.linearize(0.5)).abs() < 1e-5);
+}
+
+#[cfg(feature = "rgb")]
+#[test]
+fn rgb_channel_depth() {
+    // Integer channels are clamped and rounded, floats pass through
+    assert_eq!(u8::from_nominal(0.5), 128);
+    assert_eq!(u16::from_nominal(-0.1), 0);
+    assert_eq!(u16::from_nominal(1.1), u16::MAX);
+    assert_eq!(f32::from_nominal(1.1), 1.1);
+    assert_eq!(<u8 as Channel>::MAX.to_nominal(), 1.0);
+
+    // Every 8 bit value round trips through 16 bits and nominal values
+    for v in 0..=u8::MAX {
+        let rgb = RgbValue::new(v, v, v);
+        assert_eq!(rgb.to_depth::<u16>().to_depth::<u8>(), rgb);
+        assert_eq!(RgbValue::from(RgbNominalValue::from(rgb)), rgb);
+        assert_eq!(rgb.to_depth::<u16>().r, v as u16 * 257);
+    }
+
+    // Conversions to the CIE types don't depend on the depth of the channels
+    let rgb = RgbValue::new(102, 51, 153);
+    assert_eq!(LabValue::from(rgb.to_depth::<u16>()), LabValue::from(rgb));
+    assert_eq!(XyzValue::from(rgb.to_depth::<f32>()), XyzValue::from(rgb));
+    assert_eq!(Rgb16Value::from(LabValue::from(rgb)).to_depth::<u8>(), rgb);
+    assert_eq!(rgb.to_depth::<u16>().to_hsl(), rgb.to_hsl());
+
+    // 16 bits tell apart colors that are the same in 8 bits
+    let lab = LabValue::new(0.9, 0.0, 0.0).unwrap();
+    let darker = LabValue::new(0.8, 0.0, 0.0).unwrap();
+    assert_eq!(RgbValue::from(lab), RgbValue::from(darker));
+    assert_ne!(Rgb16Value::from(lab), Rgb16Value::from(darker));
+    assert!(*LabValue::from(Rgb16Value::from(lab)).delta(lab, DE2000).value() < 0.01);
+}
+
+#[cfg(feature = "spectral")]
+#[test]
+fn spectral_curve() {
+    assert!(SpectralCurve::new(380.0, 10.0, vec![]).is_err());
+    assert!(SpectralCurve::new(380.0, 0.0, vec![1.0]).is_err());
+    assert!(SpectralCurve::new(f32::NAN, 10.0, vec![1.0]).is_err());
+    assert!(matches!(SpectralCurve::new(380.0, 10.0, vec![1.0, f32::INFINITY]), Err(ValueError::NotFinite)));
+    assert!(SpectralCurve::constant(380.0, 10.0, 0, 1.0).is_err());
+
+    let curve = SpectralCurve::from_slice(400.0, 10.0, &[0.1, 0.3, 0.5]).unwrap();
+    assert_eq!((curve.start(), curve.end(), curve.interval(), curve.len()), (400.0, 420.0, 10.0, 3));
+    assert_eq!(curve.iter().last(), Some((420.0, 0.5)));
+    assert_eq!(curve.at(400.0), Some(0.1));
+    assert_eq!(curve.at(420.0), Some(0.5));
+    assert_eq!(curve.at(415.0).map(|v| round::round_to(v, 4)), Some(0.4));
+    assert_eq!(curve.at(399.0), None);
+
+    // Arithmetic is sample by sample
+    let half = SpectralCurve::constant(400.0, 10.0, 3, 0.5).unwrap();
+    assert_eq!(curve.scale(2.0).samples(), &[0.2, 0.6, 1.0]);
+    assert_eq!(curve.add(&half).unwrap().samples(), &[0.6, 0.8, 1.0]);
+    assert_eq!(curve.multiply(&half).unwrap().samples(), &[0.05, 0.15, 0.25]);
+    assert_eq!(curve.multiply(&half).unwrap().start(), 400.0);
+
+    // Curves must be sampled at the same wavelengths
+    let coarse = SpectralCurve::constant(400.0, 20.0, 3, 0.5).unwrap();
+    let short = SpectralCurve::constant(400.0, 10.0, 2, 0.5).unwrap();
+    assert!(curve.add(&coarse).is_err());
+    assert!(curve.multiply(&short).is_err());
+}
+
+#[cfg(all(feature = "illuminants", feature = "spectral"))]
+#[test]
+fn spectral_to_xyz() {
+    let observer = ColorMatchingFunctions::cie1931();
+    let equal_energy = SpectralCurve::constant(360.0, 5.0, 95, 1.0).unwrap();
+
+    // Under an equal energy illuminant, matches the integration of the observer alone
+    let samples: Vec<f32> = (0..95).map(|i| i as f32 / 94.0).collect();
+    let ramp = SpectralCurve::new(360.0, 5.0, samples.clone()).unwrap();
+    assert_eq!(ramp.to_xyz(&equal_energy, &observer).round_to(5), observer.integrate(360.0, 5.0, &samples).round_to(5));
+
+    // The scale of the illuminant doesn't matter, only its shape
+    let bright = equal_energy.scale(100.0);
+    assert_eq!(ramp.to_xyz(&bright, &observer).round_to(5), ramp.to_xyz(&equal_energy, &observer).round_to(5));
+
+    // A perfect reflector is white, and a gray is neutral
+    let white = SpectralCurve::constant(400.0, 10.0, 31, 1.0).unwrap();
+    assert_eq!(white.to_xyz(&equal_energy, &observer).round_to(2), Illuminant::E.white_point());
+    let gray = white.scale(0.18);
+    let lab = gray.to_lab(&bright, &observer).round_to(2);
+    assert_eq!((lab.a, lab.b), (0.0, 0.0));
+    assert_eq!(round::round_to(gray.to_xyz(&bright, &observer).y, 4), 0.18);
+
+    // The illuminant changes the color of a sample, but not of a neutral
+    let blue_sky: Vec<f32> = (0..95).map(|i| 2.0 - i as f32 / 94.0).collect();
+    let blue_sky = SpectralCurve::new(360.0, 5.0, blue_sky).unwrap();
+    assert!(ramp.to_xyz(&blue_sky, &observer).y < ramp.to_xyz(&equal_energy, &observer).y);
+    let lab = gray.to_lab(&blue_sky, &observer).round_to(2);
+    assert_eq!((lab.a, lab.b), (0.0, 0.0));
+
+    // No overlap with the illuminant
+    let infrared = SpectralCurve::constant(900.0, 10.0, 5, 1.0).unwrap();
+    assert_eq!(infrared.to_xyz(&equal_energy, &observer), XyzValue::default());
+}
+
+#[test]
+fn metamerism_index_from_lab() {
+    let lab = |l, a, b| LabValue::new(l, a, b).unwrap();
+
+    // A pair that matches under the reference illuminant is the plain DeltaE under the test one
+    let a = [lab(60.0, 5.0, 20.0), lab(62.0, 8.0, 25.0)];
+    let b = [lab(60.0, 5.0, 20.0), lab(61.0, 2.0, 22.0)];
+    for &method in &[DE1976, DE1994G, DE2000, DEMethod::DECMC(2.0, 1.0)] {
+        assert_eq!(metamerism_index_lab(a, b, method), a[1].delta(b[1], method));
+    }
+
+    // The mismatch under the reference illuminant is corrected
+    let offset = [lab(58.0, 6.0, 18.0), lab(59.0, 3.0, 20.0)];
+    assert_eq!(metamerism_index_lab(a, offset, DE2000).round_to(4), a[1].delta(b[1], DE2000).round_to(4));
+    assert_eq!(metamerism_index_lab(a, a, DE2000), 0.0);
+}
+
+#[cfg(all(feature = "illuminants", feature = "spectral"))]
+#[test]
+fn metamerism_index_from_spectra() {
+    let observer = ColorMatchingFunctions::cie1931();
+    let equal_energy = SpectralCurve::constant(380.0, 10.0, 41, 1.0).unwrap();
+    let warm: Vec<f32> = (0..41).map(|i| 0.2 + i as f32 / 20.0).collect();
+    let warm = SpectralCurve::new(380.0, 10.0, warm).unwrap();
+
+    // Grays of different lightness are corrected to a match under any illuminant
+    let gray = SpectralCurve::constant(380.0, 10.0, 41, 0.5).unwrap();
+    let darker = gray.scale(0.8);
+    assert!(*metamerism_index(&gray, &darker, &equal_energy, &warm, &observer, DE2000).value() < 1e-3);
+    assert!(*metamerism_index(&gray, &gray, &equal_energy, &warm, &observer, DE1976).value() < 1e-3);
+
+    // A pair reflecting different wavelengths changes under a different illuminant
+    let notched: Vec<f32> = (0..41).map(|i| if (20..30).contains(&i) { 0.2 } else { 0.6 }).collect();
+    let notched = SpectralCurve::new(380.0, 10.0, notched).unwrap();
+    let mi = metamerism_index(&gray, &notched, &equal_energy, &warm, &observer, DE2000);
+    assert!(*mi.value() > 1.0);
+    assert!(*metamerism_index(&gray, &notched, &warm, &warm, &observer, DE2000).value() < 1e-3);
+
+    // The index is the DeltaE under the test illuminant, relative to its white point
+    assert_eq!(*mi.method(), DE2000);
+    assert_eq!(mi.reference().round_to(3), gray.to_lab(&warm, &observer).round_to(3));
+}
+
+#[cfg(all(feature = "illuminants", feature = "spectral"))]
+#[test]
+fn spectral_illuminants() {
+    let observer = ColorMatchingFunctions::cie1931();
+    let perfect_reflector = SpectralCurve::constant(380.0, 10.0, 41, 1.0).unwrap();
+    let white = |spd: &SpectralCurve| perfect_reflector.to_xyz(spd, &observer);
+
+    // The D series are daylight at their nominal temperatures
+    for &(illuminant, cct) in &[(Illuminant::D50, 5003.0), (Illuminant::D55, 5503.0), (Illuminant::D75, 7504.0)] {
+        let xyz = white(&SpectralCurve::daylight(cct).unwrap());
+        let expected = illuminant.white_point();
+        assert!((xyz.x - expected.x).abs() < 0.01 && (xyz.z - expected.z).abs() < 0.01);
+    }
+    assert_eq!(SpectralCurve::daylight(6504.0).unwrap().at(560.0), Some(1.0));
+    assert!(SpectralCurve::daylight(3999.0).is_err());
+    assert!(SpectralCurve::daylight(25001.0).is_err());
+
+    // Hotter blackbodies are bluer
+    let warm = SpectralCurve::blackbody(2000.0, 380.0, 10.0, 41).unwrap();
+    let cool = SpectralCurve::blackbody(10000.0, 380.0, 10.0, 41).unwrap();
+    assert!(warm.at(400.0) < cool.at(400.0));
+    assert!(white(&warm).z < white(&cool).z);
+    assert!(SpectralCurve::blackbody(0.0, 380.0, 10.0, 41).is_err());
+    assert!(SpectralCurve::blackbody(f32::NAN, 380.0, 10.0, 41).is_err());
+}
+
+#[cfg(all(feature = "illuminants", feature = "spectral"))]
+#[test]
+fn spectral_white_points_e308() {
+    let (cie1931, cie1964) = (ColorMatchingFunctions::cie1931(), ColorMatchingFunctions::cie1964());
+    let a = SpectralCurve::blackbody(2856.0, 360.0, 5.0, 95).unwrap();
+    let d50 = SpectralCurve::daylight(5003.0).unwrap();
+    let d65 = SpectralCurve::daylight(6504.0).unwrap();
+
+    // The white points of ASTM E308 Table 5, for a perfect reflector sampled every 5nm over the
+    // range of the illuminant
+    let cases = [
+        (&a, &cie1931, [109.850, 35.585]),
+        (&a, &cie1964, [111.144, 35.200]),
+        (&d50, &cie1931, [96.422, 82.521]),
+        (&d50, &cie1964, [96.720, 81.427]),
+        (&d65, &cie1931, [95.047, 108.883]),
+        (&d65, &cie1964, [94.811, 107.304]),
+    ];
+    for (illuminant, observer, [x, z]) in &cases {
+        let count = 1 + ((illuminant.end() - illuminant.start()) / 5.0) as usize;
+        let perfect_reflector = SpectralCurve::constant(illuminant.start(), 5.0, count, 1.0).unwrap();
+        let white = perfect_reflector.to_xyz(illuminant, observer);
+        assert_eq!(white.y, 1.0);
+        assert!((white.x - x / 100.0).abs() < 5e-4, "{} != {}", white.x, x);
+        assert!((white.z - z / 100.0).abs() < 5e-4, "{} != {}", white.z, z);
+    }
+}
+
+#[cfg(feature = "spectral")]
+#[test]
+fn color_rendering() {
+    let observer = ColorMatchingFunctions::cie1931();
+    let curve = |f: &dyn Fn(f32) -> f32| {
+        SpectralCurve::new(380.0, 5.0, (0..81).map(|i| f(380.0 + 5.0 * i as f32)).collect()).unwrap()
+    };
+    let samples: Vec<SpectralCurve> = (0..14)
+        .map(|i| {
+            let peak = 400.0 + 25.0 * i as f32;
+            curve(&|w| 0.1 + 0.6 * (-((w - peak) / 60.0).powi(2)).exp())
+        })
+        .collect();
+
+    assert!(color_rendering_index_with(&SpectralCurve::daylight(6504.0).unwrap(), &samples[..7], &observer).is_err());
+
+    // Blackbodies and daylight render colors like their reference
+    for &cct in &[2000.0, 2856.0, 4500.0] {
+        let source = SpectralCurve::blackbody(cct, 380.0, 5.0, 81).unwrap();
+        assert!((correlated_color_temperature(&source, &observer).0 / cct - 1.0).abs() < 1e-3);
+        let cri = color_rendering_index_with(&source, &samples, &observer).unwrap();
+        assert!(cri.duv().abs() < 1e-4);
+        assert_eq!(cri.special().len(), 14);
+        assert!(cri.special().iter().all(|&r| r > 99.9));
+    }
+    let d65 = color_rendering_index_with(&SpectralCurve::daylight(6504.0).unwrap(), &samples, &observer).unwrap();
+    assert!((d65.cct() - 6504.0).abs() < 50.0);
+    assert!(d65.ra() > 99.5);
+
+    // A source off the blackbody locus
+    let (_, duv) = correlated_color_temperature(&curve(&|w| if w < 500.0 { 0.5 } else { 1.0 }), &observer);
+    let (_, green_duv) = correlated_color_temperature(&curve(&|w| 1.0 + (-((w - 530.0) / 30.0).powi(2)).exp()), &observer);
+    assert!(duv.abs() > 1e-3);
+    assert!(green_duv > 1e-3);
+
+    // Narrow bands render worse than broad bands
+    let bands = |width: f32| curve(&|w| [450.0, 545.0, 610.0].iter().map(|peak| (-((w - peak) / width).powi(2)).exp()).sum());
+    let narrow = color_rendering_index_with(&bands(10.0), &samples, &observer).unwrap();
+    let broad = color_rendering_index_with(&bands(40.0), &samples, &observer).unwrap();
+    assert!(narrow.ra() < broad.ra());
+    assert!(broad.ra() < 100.0);
+    assert_eq!(narrow.ra(), narrow.special()[..8].iter().sum::<f32>() / 8.0);
+}
+
+#[cfg(feature = "spectral")]
+#[test]
+fn color_rendering_fluorescent() {
+    // The CIE F2 cool white, F7 broadband daylight and F11 narrow band fluorescent illuminants of
+    // CIE 15, from 380nm to 780nm at 5nm intervals
+    let f2 = [
+        1.18, 1.48, 1.84, 2.15, 3.44, 15.69, 3.85, 3.74, 4.19, 4.62,
+        5.06, 34.98, 11.81, 6.27, 6.63, 6.93, 7.19, 7.40, 7.54, 7.62,
+        7.65, 7.62, 7.62, 7.45, 7.28, 7.15, 7.05, 7.04, 7.16, 7.47,
+        8.04, 8.88, 10.01, 24.88, 16.64, 14.59, 16.16, 17.56, 18.62, 21.47,
+        22.79, 19.29, 18.66, 17.73, 16.54, 15.21, 13.80, 12.36, 10.95, 9.65,
+        8.40, 7.32, 6.31, 5.43, 4.68, 4.02, 3.45, 2.96, 2.55, 2.19,
+        1.89, 1.64, 1.53, 1.27, 1.10, 0.99, 0.88, 0.76, 0.68, 0.61,
+        0.56, 0.54, 0.51, 0.47, 0.47, 0.43, 0.46, 0.47, 0.40, 0.33,
+        0.27,
+    ];
+    let f7 = [
+        2.56, 3.18, 3.84, 4.53, 6.15, 19.37, 7.37, 7.05, 7.71, 8.41,
+        9.15, 44.14, 17.52, 11.35, 12.00, 12.58, 13.08, 13.45, 13.71, 13.88,
+        13.95, 13.93, 13.82, 13.64, 13.43, 13.25, 13.08, 12.93, 12.78, 12.60,
+        12.44, 12.33, 12.26, 29.52, 17.05, 12.44, 12.58, 12.72, 12.83, 15.46,
+        16.75, 12.83, 12.67, 12.45, 12.19, 11.89, 11.60, 11.35, 11.12, 10.95,
+        10.76, 10.42, 10.11, 10.04, 10.02, 10.11, 9.87, 8.65, 7.27, 6.44,
+        5.83, 5.41, 5.04, 4.57, 4.12, 3.77, 3.46, 3.08, 2.73, 2.47,
+        2.25, 2.06, 1.90, 1.75, 1.62, 1.54, 1.45, 1.32, 1.17, 0.99,
+        0.81,
+    ];
+    let f11 = [
+        0.91, 0.63, 0.46, 0.37, 1.29, 12.68, 1.59, 1.79, 2.46, 3.33,
+        4.49, 33.94, 12.13, 6.95, 7.19, 7.12, 6.72, 6.13, 5.46, 4.79,
+        5.66, 14.29, 14.96, 8.97, 4.72, 2.33, 1.47, 1.10, 0.89, 0.83,
+        1.18, 4.90, 39.59, 72.84, 32.61, 7.52, 2.83, 1.96, 1.67, 4.43,
+        11.28, 14.76, 12.73, 9.74, 7.33, 9.72, 55.27, 42.58, 13.18, 13.16,
+        12.26, 5.11, 2.07, 2.34, 3.58, 3.01, 2.48, 2.14, 1.54, 1.33,
+        1.46, 1.94, 2.00, 1.20, 1.35, 4.10, 5.58, 2.51, 0.57, 0.27,
+        0.23, 0.21, 0.24, 0.24, 0.20, 0.24, 0.32, 0.26, 0.16, 0.12,
+        0.09,
+    ];
+
+    let lamp = |spd: &[f32]| color_rendering_index(&SpectralCurve::new(380.0, 5.0, spd.to_vec()).unwrap()).unwrap();
+
+    // The CCT and Ra published with the illuminants in CIE 15
+    let f2 = lamp(&f2);
+    assert!((f2.cct() - 4230.0).abs() < 10.0);
+    assert_eq!(f2.ra().round(), 64.0);
+    let f7 = lamp(&f7);
+    assert!((f7.cct() - 6500.0).abs() < 10.0);
+    assert_eq!(f7.ra().round(), 90.0);
+    let f11 = lamp(&f11);
+    assert!((f11.cct() - 4000.0).abs() < 10.0);
+    assert_eq!(f11.ra().round(), 83.0);
+    assert!(f11.duv().abs() < 1e-3);
+
+    // Cool white fluorescent renders saturated red poorly
+    assert_eq!(f2.special().len(), 14);
+    assert_eq!(f2.special()[8].round(), -46.0);
+    assert_eq!(f11.special()[8].round(), 18.0);
+
+    assert!(color_rendering_index(&SpectralCurve::constant(380.0, 5.0, 81, 0.0).unwrap()).is_err());
+}
+
+#[cfg(feature = "rgb")]
+#[test]
+fn comparator() {
+    let lab0 = LabValue::new(89.73, 1.88, -6.96).unwrap();
+    let lab1 = LabValue::new(95.08, -0.17, -10.81).unwrap();
+
+    // The defaults match the free functions
+    let comparator = DeltaE::builder();
+    assert_eq!(comparator, Comparator::default());
+    assert_eq!(comparator.delta(lab0, lab1), lab0.delta(lab1, DE2000));
+    assert_eq!(comparator.clone().method(DE1976).delta(lab0, lab1), lab0.delta(lab1, DE1976));
+    let rgb = RgbValue::new(170, 187, 204);
+    assert_eq!(comparator.to_lab(rgb).round_to(3), LabValue::from(rgb).round_to(3));
+
+    // XYZ and RGB values are converted relative to the white point of the conditions
+    let d65 = comparator.clone().illuminant(Illuminant::D65).observer(Observer::Cie1964);
+    assert_eq!(d65.white_point(), Illuminant::D65.white_point_for(Observer::Cie1964));
+    assert_eq!(d65.to_lab(d65.white_point()).round_to(4), LabValue::new(100.0, 0.0, 0.0).unwrap());
+    assert_eq!(d65.to_lab(lab0), lab0);
+    let white = RgbValue::new(255, 255, 255);
+    assert_eq!(d65.rgb_system(RgbSystem::Adobe).to_lab(white).round_to(2).l, 100.0);
+
+    // Tolerance
+    let strict = comparator.tolerance(5.0);
+    assert!(!strict.delta_eq(lab0, lab1));
+    assert!(strict.clone().tolerance(5.5).delta_eq(lab0, lab1));
+    let reports = strict.compare_all(vec![(lab0, lab1), (lab0, lab0)]);
+    assert_eq!(reports.len(), 2);
+    assert_eq!((reports[0].pass, reports[1].pass), (false, true));
+    assert_eq!(reports[0].tolerance, Some(5.0));
+}
+
+#[cfg(feature = "rgb")]
+#[test]
+fn color_value_trait() {
+    fn max_component<C: ColorValue>(color: C) -> (&'static str, f32) {
+        let (labels, components) = (color.labels(), color.components());
+        let i = (0..3).max_by(|&i, &j| components[i].total_cmp(&components[j])).unwrap();
+        (labels[i], components[i])
+    }
+
+    assert_eq!(max_component(LabValue { l: 50.0, a: 60.0, b: 0.0 }), ("a", 60.0));
+    assert_eq!(max_component(LchValue { l: 50.0, c: 3.0, h: 90.0 }), ("h", 90.0));
+    assert_eq!(max_component(RgbValue::new(0, 0, 200)), ("B", 200.0));
+    assert_eq!(max_component(Color::Xyz(XyzValue { x: 0.1, y: 0.3, z: 0.2 })), ("Y", 0.3));
+
+    assert_eq!(RgbNominalValue { r: 1.0, g: 1.0, b: 1.0 }.white_point(), Illuminant::D65);
+    assert_eq!(Color::Lab(LabValue::default()).white_point(), Illuminant::D50);
+
+    // Conversions agree with the From implementations
+    let rgb = RgbValue::new(170, 187, 204);
+    assert_eq!(rgb.to_lab(), LabValue::from(rgb));
+    assert_eq!(rgb.to_xyz().round_to(4), XyzValue::from(rgb).round_to(4));
+    let xyz = XyzValue { x: 0.1, y: 0.3, z: 0.2 };
+    assert_eq!(xyz.to_xyz(), xyz);
+    assert_eq!(Color::Xyz(xyz).to_xyz(), xyz);
+
+    // DeltaEq is implemented for every ColorValue
+    assert!(Color::Rgb(rgb).delta_eq(rgb.to_lab(), DE2000, 0.0));
+    assert!(rgb.delta_eq(Color::Rgb(rgb), DE2000, 0.0));
+}
+
+#[cfg(all(feature = "rgb", feature = "parse"))]
+#[test]
+fn rgb_system_string() {
+    let good = &[
+        ("sRGB", RgbSystem::Srgb),
+        ("adobe1998", RgbSystem::Adobe),
+        ("Adobe RGB", RgbSystem::Adobe),
+        ("a98-rgb", RgbSystem::Adobe),
+        ("display-p3", RgbSystem::DisplayP3),
+        ("DCI-P3", RgbSystem::DciP3),
+        ("ACES2065-1", RgbSystem::Aces2065),
+        ("ACEScg", RgbSystem::AcesCg),
+        ("ProPhoto", RgbSystem::ProPhoto),
+        ("SMPTE-C", RgbSystem::SmpteC),
+        ("wide_gamut", RgbSystem::WideGamut),
+        ("ECI RGB v2", RgbSystem::Eci),
+        ("pal-secam", RgbSystem::PalSecam),
+        ("Rec. 709", RgbSystem::Rec709),
+        ("BT.2020", RgbSystem::Rec2020),
+    ];
+
+    for (s, system) in good {
+        assert_eq!(RgbSystem::from_str(s).unwrap(), *system, "{}", s);
+    }
+
+    // The Display output can be parsed back
+    for system in RGB_SYSTEMS {
+        assert_eq!(RgbSystem::from_str(&system.to_string()).unwrap(), *system);
+    }
+
+    assert!(RgbSystem::from_str("custom").is_err());
+    assert!(RgbSystem::from_str("").is_err());
+}
+
+#[cfg(all(feature = "rgb", feature = "parse"))]
+#[test]
+fn decimal_comma_string() {
+    let lab = LabValue { l: 50.25, a: 12.4, b: -3.1 };
+    for s in &[
+        "50,25;12,40;-3,10",
+        "50,25; 12,40; -3,10",
+        "50,25\t12,40\t-3,10",
+        "50,25 12,40 -3,10",
+        "50.25;12.40;-3.10",
+        "50.25, 12.40, -3.10",
+        "50.25,\t12.40,\t-3.10",
+        "50.25, 12.40; -3.10",
+    ] {
+        assert_eq!(LabValue::from_str(s).unwrap(), lab, "{}", s);
+    }
+
+    // Commas are delimiters when that is a valid reading
+    assert_eq!(
+        LabValue::from_str("95.08,\t-0.17,\t-10.81").unwrap(),
+        LabValue { l: 95.08, a: -0.17, b: -10.81 },
+    );
+    assert_eq!(
+        LabValue::from_str("95.08, -0.17; -10.81").unwrap(),
+        LabValue { l: 95.08, a: -0.17, b: -10.81 },
+    );
+    assert_eq!(LabValue::from_str("50,12,3").unwrap(), LabValue { l: 50.0, a: 12.0, b: 3.0 });
+    assert_eq!(XyzValue::from_str("0,5;0,25;0").unwrap(), XyzValue { x: 0.5, y: 0.25, z: 0.0 });
+    assert_eq!(RgbNominalValue::from_str("0,5\t1\t0").unwrap(), RgbNominalValue { r: 0.5, g: 1.0, b: 0.0 });
+
+    assert!(LabValue::from_str("50,25,12,40,-3,10").is_err());
+    assert!(LabValue::from_str("50,25 12.40 -3,10").is_err());
+    assert!(LabValue::from_str("50,2,5;12;-3").is_err());
+}
+
+#[cfg(feature = "parse")]
+#[test]
+fn lch_string() {
+    let good = &[
+        "100,181.0193,360",
+        "100, 181.0193, 360",
+        "100,129,129",
+        "0,0,0",
+        "99.9999,181.0193,359.9999",
+    ];
+
+    for i in good {
+        assert!(LchValue::from_str(i).is_ok());
+    }
+
+    let bad = &[
+        "100,128,-129",
+        "100,181.0194,360",
+        "100, 181.0194, 360",
+        "0,-0.01,-0.01",
+        "derp",
+        "1,2,three,4",
+        "",
+        "1,2,3,4",
+        "1,2",
+        "1",
         "1,2,3,derp"
     ];
 
-    for i in bad {
-        assert!(LabValue::from_str(i).is_err());
+    for i in bad {
+        assert!(LchValue::from_str(i).is_err());
+    }
+}
+
+#[cfg(feature = "parse")]
+#[test]
+fn xyz_string() {
+    let good = &[
+        "0, 0, 0",
+        "1, 1, 1",
+        "0.5, 0.5, 0.5"
+    ];
+
+    for i in good {
+        assert!(XyzValue::from_str(i).is_ok());
+    }
+
+    let bad = &[
+        "-0.01, 0, 0",
+        "0, 1.01, 0",
+        "0, 0, 1.01",
+        "derp",
+        "0, 0, 0, derp",
+        "0, 0, derp"
+    ];
+
+    for i in bad {
+        assert!(XyzValue::from_str(i).is_err());
+    }
+}
+
+#[cfg(all(feature = "rgb", feature = "parse"))]
+#[test]
+fn rgb_hex_string() {
+    let good = &[
+        ("#FF8000", RgbValue::new(255, 128, 0)),
+        ("ff8000", RgbValue::new(255, 128, 0)),
+        (" #663399 ", RgbValue::new(102, 51, 153)),
+        ("#F80", RgbValue::new(255, 136, 0)),
+        ("fff", RgbValue::new(255, 255, 255)),
+        ("#000000", RgbValue::new(0, 0, 0)),
+    ];
+
+    for (s, rgb) in good {
+        assert_eq!(RgbValue::from_str(s).unwrap(), *rgb);
+    }
+
+    let bad = &[
+        "",
+        "#",
+        "#FF80",
+        "#FF80000",
+        "##FF8000",
+        "#GG8000",
+        "#+F8000",
+        "#FF 800",
+        "#ÿÿÿ",
+    ];
+
+    for s in bad {
+        assert!(RgbValue::from_str(s).is_err(), "{}", s);
+    }
+
+    // Hex errors point at the offending token like the component parsers do
+    let err = RgbValue::from_str("  #GG8000 ").unwrap_err();
+    assert_eq!(err.to_string(), r##"invalid number "#GG8000" at position 2"##);
+    match err {
+        Error::Parse(e) => {
+            assert_eq!(e.kind(), ParseErrorKind::Malformed);
+            assert_eq!(e.component(), None);
+            assert_eq!(e.token(), "#GG8000");
+            assert_eq!(e.position(), 2);
+        }
+        other => panic!("{:?}", other),
+    }
+    match RgbValue::from_str("#FF80").unwrap_err() {
+        Error::Parse(e) => assert_eq!((e.kind(), e.token()), (ParseErrorKind::Malformed, "#FF80")),
+        other => panic!("{:?}", other),
+    }
+    match RgbValue::from_str(" ").unwrap_err() {
+        Error::Parse(e) => assert_eq!((e.kind(), e.position()), (ParseErrorKind::Missing, 1)),
+        other => panic!("{:?}", other),
+    }
+
+    // Hex formatting round trips through parsing
+    for r in (0..=255).step_by(15) {
+        let rgb = RgbValue::new(r, 255 - r, r / 3);
+        assert_eq!(RgbValue::from_str(&format!("{:x}", rgb)).unwrap(), rgb);
+        assert_eq!(RgbValue::from_str(&format!("{:X}", rgb)).unwrap(), rgb);
+    }
+    assert_eq!(format!("{:X}", RgbValue::new(255, 255, 255)), "#FFFFFF");
+    assert_eq!(format!("{:x}", RgbValue::new(0, 0, 0)), "#000000");
+    assert_eq!(format!("{:>9x}|{:<9X}|", RgbValue::new(1, 2, 3), RgbValue::new(171, 205, 239)), "  #010203|#ABCDEF  |");
+}
+
+#[cfg(all(feature = "rgb", feature = "parse"))]
+#[test]
+fn rgb_string() {
+    let good = &[
+        ("128, 64, 255", RgbValue::new(128, 64, 255)),
+        ("0,0,0", RgbValue::new(0, 0, 0)),
+        ("255, 255, 255", RgbValue::new(255, 255, 255)),
+        ("128 64 255", RgbValue::new(128, 64, 255)),
+        ("128\t64\t255", RgbValue::new(128, 64, 255)),
+    ];
+
+    for (s, rgb) in good {
+        assert_eq!(RgbValue::from_str(s).unwrap(), *rgb);
+    }
+
+    let bad = &[
+        "256, 0, 0",
+        "-1, 0, 0",
+        "0.5, 0, 0",
+        "0, 0",
+        "0, 0, 0, 0",
+        "0, 0, derp",
+    ];
+
+    for s in bad {
+        assert!(RgbValue::from_str(s).is_err(), "{}", s);
+    }
+
+    assert_eq!(
+        RgbNominalValue::from_str("0.5, 0, 1").unwrap(),
+        RgbNominalValue { r: 0.5, g: 0.0, b: 1.0 }
+    );
+    assert!(RgbNominalValue::from_str("0.5, 0, 1.01").is_err());
+    assert!(RgbNominalValue::from_str("128, 64, 255").is_err());
+}
+
+fn compare_de(method: DEMethod, expected: f32, reference: &[f32; 3], sample: &[f32; 3]) -> ValueResult<()> {
+    let lab0 = LabValue::try_from(reference)?;
+    let lab1 = LabValue::try_from(sample)?;
+
+    let de = lab0.delta(lab1, method).round_to(4).value;
+
+    assert_eq!(expected, de);
+
+    Ok(())
+}
+
+// Colors of the same hue whose squared hue difference rounds to a tiny negative value in f32,
+// which made the square root of DE1994 and CMC NaN
+#[test]
+fn same_hue_not_nan() {
+    let lab0 = LabValue { l: 50.0, a: 0.5, b: 2.5 };
+    let lab1 = LabValue { l: 50.0, a: 0.6, b: 3.0 };
+    let delta_c = (0.5f32.powi(2) + 2.5f32.powi(2)).sqrt() - (0.6f32.powi(2) + 3.0f32.powi(2)).sqrt();
+
+    assert_eq!(lab0.delta(lab1, DE1994G).round_to(4).value, 0.4574);
+    assert_eq!(lab0.delta(lab1, DE1994T).round_to(4).value, 0.4543);
+    assert_eq!(lab0.delta(lab1, DE1994G).round_to(4).value, round::round_to(delta_c.abs() / (1.0 + 0.045 * 6.5f32.sqrt()), 4));
+    for method in &[DECMC1, DECMC2] {
+        assert!(lab0.delta(lab1, *method).value().is_finite());
+    }
+}
+
+#[test]
+fn decmc1() {
+    assert!(compare_de(DEMethod::DECMC(1.0, 1.0), 17.4901, &[20.0, 30.0, 40.0], &[30.0, 40.0, 50.0]).is_ok());
+}
+
+#[test]
+fn decmc2() {
+    assert!(compare_de(DEMethod::DECMC(2.0, 1.0), 10.0731, &[20.0, 30.0, 40.0], &[30.0, 40.0, 50.0]).is_ok());
+}
+
+#[test]
+fn de1976_test_set() {
+    let set = &[
+        (0.0000,   &[0.0000,  0.0000,    0.0000  ], &[0.0000,    0.0000,    0.0000  ]),
+        (5.0000,   &[0.0000,  0.0000,    0.0000  ], &[0.0000,    3.0000,    4.0000  ]),
+        (5.0000,   &[0.0000,  0.0000,    0.0000  ], &[0.0000,   -3.0000,   -4.0000  ]),
+        (50.0000,  &[0.0000,  0.0000,    0.0000  ], &[0.0000,   -30.0000,  -40.0000 ]),
+        (181.0193, &[0.0000,  0.0000,    0.0000  ], &[0.0000,    128.0000,  128.0000]),
+        (362.0387, &[0.0000, -128.0000, -128.0000], &[0.0000,    128.0000,  128.0000]),
+        (375.5955, &[0.0000, -128.0000, -128.0000], &[100.0000,  128.0000,  128.0000])
+    ];
+
+    for (expected, reference, sample) in set.iter() {
+        assert!(compare_de(DEMethod::DE1976, *expected, reference, sample).is_ok());
+    }
+}
+
+// Tests taken from Table 1: "CIEDE2000 total color difference test data" of
+// "The CIEDE2000 Color-Difference Formula: Implementation Notes,
+// Supplementary Test Data, and Mathematical Observations" by Gaurav Sharma,
+// Wencheng Wu and Edul N. Dalal.
+//
+// http://www.ece.rochester.edu/~gsharma/papers/CIEDE2000CRNAFeb05.pdf
+
+#[test]
+fn de2000_test_set() {
+    let set = &[
+        (0.0000,   &[0.0000,   0.0000,   0.0000 ], &[0.0000,   0.0000,   0.0000 ]),
+        (0.0000,   &[99.5000,  0.0050,  -0.0100 ], &[99.5000,  0.0050,  -0.0100 ]),
+        (100.0000, &[100.0000, 0.0050,  -0.0100 ], &[0.0000,   0.0000,   0.0000 ]),
+        (2.0425,   &[50.0000,  2.6772,  -79.7751], &[50.0000,  0.0000,  -82.7485]),
+        (2.8615,   &[50.0000,  3.1571,  -77.2803], &[50.0000,  0.0000,  -82.7485]),
+        (3.4412,   &[50.0000,  2.8361,  -74.0200], &[50.0000,  0.0000,  -82.7485]),
+        (1.0000,   &[50.0000, -1.3802,  -84.2814], &[50.0000,  0.0000,  -82.7485]),
+        (1.0000,   &[50.0000, -1.1848,  -84.8006], &[50.0000,  0.0000,  -82.7485]),
+        (1.0000,   &[50.0000, -0.9009,  -85.5211], &[50.0000,  0.0000,  -82.7485]),
+        (2.3669,   &[50.0000,  0.0000,   0.0000 ], &[50.0000, -1.0000,   2.0000 ]),
+        (2.3669,   &[50.0000, -1.0000,   2.0000 ], &[50.0000,  0.0000,   0.0000 ]),
+        (7.1792,   &[50.0000,  2.4900,  -0.0010 ], &[50.0000, -2.4900,   0.0009 ]),
+        (7.1792,   &[50.0000,  2.4900,  -0.0010 ], &[50.0000, -2.4900,   0.0010 ]),
+        (7.2195,   &[50.0000,  2.4900,  -0.0010 ], &[50.0000, -2.4900,   0.0011 ]),
+        (7.2195,   &[50.0000,  2.4900,  -0.0010 ], &[50.0000, -2.4900,   0.0012 ]),
+        (4.8045,   &[50.0000, -0.0010,   2.4900 ], &[50.0000,  0.0009,  -2.4900 ]),
+        (4.7461,   &[50.0000, -0.0010,   2.4900 ], &[50.0000,  0.0011,  -2.4900 ]),
+        (4.3065,   &[50.0000,  2.5000,   0.0000 ], &[50.0000,  0.0000,  -2.5000 ]),
+        (27.1492,  &[50.0000,  2.5000,   0.0000 ], &[73.0000,  25.0000, -18.0000]),
+        (22.8977,  &[50.0000,  2.5000,   0.0000 ], &[61.0000, -5.0000,   29.0000]),
+        (31.9030,  &[50.0000,  2.5000,   0.0000 ], &[56.0000, -27.0000, -3.0000 ]),
+        (19.4535,  &[50.0000,  2.5000,   0.0000 ], &[58.0000,  24.0000,  15.0000]),
+        (1.0000,   &[50.0000,  2.5000,   0.0000 ], &[50.0000,  3.1736,   0.5854 ]),
+        (1.0000,   &[50.0000,  2.5000,   0.0000 ], &[50.0000,  3.2972,   0.0000 ]),
+        (1.0000,   &[50.0000,  2.5000,   0.0000 ], &[50.0000,  1.8634,   0.5757 ]),
+        (1.0000,   &[50.0000,  2.5000,   0.0000 ], &[50.0000,  3.2592,   0.3350 ]),
+        (1.2644,   &[60.2574, -34.0099,  36.2677], &[60.4626, -34.1751,  39.4387]),
+        (1.2630,   &[63.0109, -31.0961, -5.8663 ], &[62.8187, -29.7946, -4.0864 ]),
+        (1.8731,   &[61.2901,  3.7196,  -5.3901 ], &[61.4292,  2.2480,  -4.9620 ]),
+        (1.8645,   &[35.0830, -44.1164,  3.7933 ], &[35.0232, -40.0716,  1.5901 ]),
+        (2.0373,   &[22.7233,  20.0904, -46.6940], &[23.0331, 14.9730,  -42.5619]),
+        (1.4146,   &[36.4612,  47.8580,  18.3852], &[36.2715,  50.5065,  21.2231]),
+        (1.4441,   &[90.8027, -2.0831,   1.4410 ], &[91.1528, -1.6435,   0.0447 ]),
+        (1.5381,   &[90.9257, -0.5406,  -0.9208 ], &[88.6381, -0.8985,   -0.7239]),
+        (0.6377,   &[6.7747,  -0.2908,  -2.4247 ], &[5.8714,  -0.0985,   -2.2286]),
+        (0.9082,   &[2.0776,   0.0795,  -1.1350 ], &[0.9033,  -0.0636,   -0.5514])
+    ];
+
+    for (expected, reference, sample) in set.iter() {
+        assert!(compare_de(DEMethod::DE2000, *expected, reference, sample).is_ok())
+    }
+}
+
+// The pairs of the test set whose hues are more than 180° apart, across the 0°/360° boundary.
+// DE2000 is symmetric, so each is also compared with the reference and sample swapped, which
+// used to give 21.1200 for the third pair.
+#[test]
+fn de2000_hue_wrap() {
+    let set = &[
+        (4.3065,   &[50.0000,  2.5000,   0.0000 ], &[50.0000,  0.0000,  -2.5000 ]),
+        (27.1492,  &[50.0000,  2.5000,   0.0000 ], &[73.0000,  25.0000, -18.0000]),
+        (31.9030,  &[50.0000,  2.5000,   0.0000 ], &[56.0000, -27.0000, -3.0000 ]),
+    ];
+
+    for (expected, reference, sample) in set.iter() {
+        assert!(compare_de(DEMethod::DE2000, *expected, reference, sample).is_ok());
+        assert!(compare_de(DEMethod::DE2000, *expected, sample, reference).is_ok());
+    }
+}
+
+#[cfg(feature = "illuminants")]
+#[test]
+fn lab_to_xyz_with_white() {
+    let lab = LabValue {
+        l: 30.0,
+        a: 40.0,
+        b: 50.0,
+    };
+
+    for illuminant in &[Illuminant::A, Illuminant::D50, Illuminant::D65, Illuminant::F11] {
+        let xyz  = XyzValue::from_lab_with_white(lab, *illuminant);
+        let lab2 = LabValue::from_xyz_with_white(xyz, *illuminant);
+        assert_eq!(lab.round_to(4), lab2.round_to(4));
+    }
+
+    let xyz = XyzValue::from(lab);
+    assert_eq!(xyz, XyzValue::from_lab_with_white(lab, Illuminant::D50));
+    assert_ne!(xyz, XyzValue::from_lab_with_white(lab, Illuminant::D65));
+}
+
+#[cfg(all(feature = "illuminants", feature = "parse"))]
+#[test]
+fn illuminant_string() {
+    let good = &[
+        ("d65", Illuminant::D65),
+        ("D50", Illuminant::D50),
+        (" f11 ", Illuminant::F11),
+        ("A", Illuminant::A),
+        ("e", Illuminant::E),
+        ("DCI", Illuminant::Dci),
+        ("aces", Illuminant::Aces),
+    ];
+
+    for (s, illuminant) in good {
+        assert_eq!(Illuminant::from_str(s).unwrap(), *illuminant);
+    }
+
+    for s in &["d", "d66", "f", "derp", ""] {
+        assert!(Illuminant::from_str(s).is_err());
+    }
+}
+
+#[cfg(all(feature = "illuminants", feature = "parse"))]
+#[test]
+fn chromatic_adaptation_method_string() {
+    let good = &[
+        ("bradford", ChromaticAdaptationMethod::Bradford),
+        ("Bradford", ChromaticAdaptationMethod::Bradford),
+        ("vonkries", ChromaticAdaptationMethod::VonKries),
+        ("Von Kries", ChromaticAdaptationMethod::VonKries),
+        ("xyz", ChromaticAdaptationMethod::XyzScaling),
+        ("XYZ-Scaling", ChromaticAdaptationMethod::XyzScaling),
+        ("CAT02", ChromaticAdaptationMethod::Cat02),
+        ("cat16", ChromaticAdaptationMethod::Cat16),
+        ("sharp", ChromaticAdaptationMethod::Sharp),
+        ("Fairchild", ChromaticAdaptationMethod::Fairchild),
+        ("CMCCAT2000", ChromaticAdaptationMethod::CmcCat2000 { d: 1.0 }),
+        ("cmccat2000(d:0.8)", ChromaticAdaptationMethod::CmcCat2000 { d: 0.8 }),
+        (" CmcCat00(D: 0) ", ChromaticAdaptationMethod::CmcCat2000 { d: 0.0 }),
+    ];
+
+    for (s, method) in good {
+        assert_eq!(ChromaticAdaptationMethod::from_str(s).unwrap(), *method);
+    }
+
+    for s in &["brad", "kries", "rlab", "derp", "", "CmcCat2000(D:)", "CmcCat2000(D:x)", "CmcCat2000(0.5)"] {
+        assert!(ChromaticAdaptationMethod::from_str(s).is_err());
+    }
+
+    // The degree of adaptation must be between 0 and 1
+    for s in &["CmcCat2000(D:1.50)", "CmcCat2000(D:-0.10)", "CmcCat2000(D:NaN)"] {
+        assert!(matches!(ChromaticAdaptationMethod::from_str(s), Err(Error::OutOfBounds)));
+    }
+
+    // Every method parses from its Display form
+    let methods = [
+        ChromaticAdaptationMethod::Bradford,
+        ChromaticAdaptationMethod::VonKries,
+        ChromaticAdaptationMethod::XyzScaling,
+        ChromaticAdaptationMethod::Cat02,
+        ChromaticAdaptationMethod::Cat16,
+        ChromaticAdaptationMethod::Sharp,
+        ChromaticAdaptationMethod::Fairchild,
+        ChromaticAdaptationMethod::CmcCat2000 { d: 1.0 },
+        ChromaticAdaptationMethod::CmcCat2000 { d: 0.5 },
+        ChromaticAdaptationMethod::CmcCat2000 { d: 0.0 },
+    ];
+
+    for method in &methods {
+        assert_eq!(method.to_string().parse::<ChromaticAdaptationMethod>().unwrap(), *method);
+    }
+}
+
+#[cfg(feature = "illuminants")]
+#[test]
+fn chromatic_adaptation() {
+    let methods = &[
+        ChromaticAdaptationMethod::Bradford,
+        ChromaticAdaptationMethod::VonKries,
+        ChromaticAdaptationMethod::XyzScaling,
+        ChromaticAdaptationMethod::Cat02,
+        ChromaticAdaptationMethod::Cat16,
+        ChromaticAdaptationMethod::Sharp,
+        ChromaticAdaptationMethod::Fairchild,
+        ChromaticAdaptationMethod::CmcCat2000 { d: 1.0 },
+    ];
+
+    for method in methods {
+        // The inverse matrices are correct
+        assert_almost_identity(method.matrix() * method.inverse(), method);
+
+        // The source white point always maps to the destination white point
+        let white = Illuminant::D65.white_point().chrom_adapt(*method, Illuminant::D65, Illuminant::D50);
+        assert_eq!(white.round_to(4), Illuminant::D50.white_point().round_to(4));
+    }
+
+    // Lindbloom's reference D65 -> D50 Bradford matrix
+    let xyz = XyzValue { x: 1.0, y: 0.0, z: 0.0 };
+    let adapted = xyz.chrom_adapt(ChromaticAdaptationMethod::Bradford, Illuminant::D65, Illuminant::D50);
+    assert_eq!(adapted.round_to(3), XyzValue { x: 1.048, y: 0.030, z: -0.009 });
+
+    // Round trip through the inverse matrix
+    let xyz = XyzValue { x: 0.5, y: 0.4, z: 0.3 };
+    let adapted = xyz.chrom_adapt(ChromaticAdaptationMethod::Cat02, Illuminant::A, Illuminant::D65);
+    let restored = adapted.chrom_adapt(ChromaticAdaptationMethod::Cat02, Illuminant::D65, Illuminant::A);
+    assert_eq!(xyz.round_to(4), restored.round_to(4));
+}
+
+#[cfg(feature = "illuminants")]
+#[test]
+fn chromatic_adaptation_incomplete() {
+    let xyz = XyzValue { x: 0.5, y: 0.4, z: 0.3 };
+    let adapt = |d| xyz.chrom_adapt(ChromaticAdaptationMethod::CmcCat2000 { d }, Illuminant::A, Illuminant::D65);
+
+    // No adaptation leaves the value unchanged
+    assert_eq!(adapt(0.0).round_to(4), xyz.round_to(4));
+
+    // Partial adaptation lands between no adaptation and complete adaptation
+    let (none, half, full) = (adapt(0.0), adapt(0.5), adapt(1.0));
+    assert!(half.z > none.z && half.z < full.z);
+
+    // The degree of adaptation is clamped
+    assert_eq!(adapt(1.5), full);
+    assert_eq!(ChromaticAdaptationMethod::CmcCat2000 { d: -1.0 }.degree_of_adaptation(), 0.0);
+    assert_eq!(ChromaticAdaptationMethod::Bradford.degree_of_adaptation(), 1.0);
+
+    // Equal adapting luminances in an average surround
+    let d = ChromaticAdaptationMethod::cmccat2000_degree(1.0, 100.0, 100.0);
+    assert_eq!(round::round_to(d, 4), 0.92);
+}
+
+#[cfg(all(feature = "illuminants", feature = "spectral"))]
+#[test]
+fn color_matching_functions() {
+    assert!(ColorMatchingFunctions::new(380.0, 10.0, vec![], vec![], vec![]).is_err());
+    assert!(ColorMatchingFunctions::new(380.0, 10.0, vec![1.0, 2.0], vec![1.0], vec![1.0, 2.0]).is_err());
+    assert!(ColorMatchingFunctions::new(380.0, 0.0, vec![1.0], vec![1.0], vec![1.0]).is_err());
+    assert!(ColorMatchingFunctions::new(380.0, 10.0, vec![1.0], vec![1.0], vec![1.0]).is_ok());
+
+    let cie1931 = ColorMatchingFunctions::cie1931();
+    assert_eq!((cie1931.start(), cie1931.end(), cie1931.len()), (360.0, 830.0, 95));
+
+    assert_eq!(ColorMatchingFunctions::default(), cie1931);
+
+    // A perfect reflector under an equal energy illuminant is the E white point
+    let cie1964 = ColorMatchingFunctions::cie1964();
+    assert_eq!((cie1964.start(), cie1964.end(), cie1964.len()), (360.0, 830.0, 95));
+    for observer in &[&cie1931, &cie1964] {
+        let white = observer.integrate(360.0, 5.0, &[1.0; 95]);
+        assert_eq!(white.round_to(3), Illuminant::E.white_point());
+    }
+
+    // The ȳ functions peak at 555nm and 555nm-560nm
+    assert_eq!(cie1931.at(555.0), Some([0.5120501, 1.0, 0.005749999]));
+    assert_eq!(cie1964.at(555.0), Some([0.616053, 0.99911, 0.001091]));
+    assert_eq!(cie1964.at(560.0), Some([0.705224, 0.99734, 0.0]));
+    assert_eq!(cie1931.at(460.0), Some([0.2908, 0.06, 1.6692]));
+    assert_eq!(cie1964.at(460.0), Some([0.302273, 0.128201, 1.74537]));
+
+    // The Gaussian fit is close to the tables
+    let fit = ColorMatchingFunctions::wyman_fit();
+    assert_eq!((fit.start(), fit.end(), fit.len()), (360.0, 830.0, 95));
+    for i in 0..95 {
+        let wavelength = 360.0 + 5.0 * i as f32;
+        let (fit, table) = (fit.at(wavelength).unwrap(), cie1931.at(wavelength).unwrap());
+        for (f, t) in fit.iter().zip(table.iter()) {
+            assert!((f - t).abs() < 0.025, "{}nm: {} != {}", wavelength, f, t);
+        }
+    }
+
+    // A custom observer is used for integration
+    let custom = ColorMatchingFunctions::new(
+        400.0, 100.0,
+        vec![0.0, 0.0, 1.0],
+        vec![0.0, 1.0, 0.0],
+        vec![1.0, 0.0, 0.0],
+    ).unwrap();
+    let xyz = custom.integrate(400.0, 100.0, &[0.2, 0.5, 0.8]);
+    assert_eq!(xyz.round_to(4), XyzValue { x: 0.8, y: 0.5, z: 0.2 });
+}
+
+#[cfg(feature = "illuminants")]
+#[test]
+fn chromatic_adaptation_custom() {
+    // A custom Bradford matrix matches the built-in Bradford transform
+    let bradford = ChromaticAdaptationMethod::Custom(ChromaticAdaptationMethod::Bradford.matrix())
+        .validate()
+        .unwrap();
+
+    let xyz = XyzValue { x: 0.5, y: 0.4, z: 0.3 };
+    let custom = xyz.chrom_adapt(bradford, Illuminant::D65, Illuminant::D50);
+    let builtin = xyz.chrom_adapt(ChromaticAdaptationMethod::Bradford, Illuminant::D65, Illuminant::D50);
+    assert_eq!(custom.round_to(5), builtin.round_to(5));
+
+    // Singular matrices have no inverse
+    let singular = ChromaticAdaptationMethod::Custom(Matrix3x3::diagonal([1.0, 0.0, 1.0]));
+    assert!(singular.validate().is_err());
+    assert!(singular.inverse()[0].is_nan());
+}
+
+#[cfg(feature = "illuminants")]
+#[test]
+fn chromatic_adaptation_lab_lch() {
+    let method = ChromaticAdaptationMethod::Bradford;
+    let lab = LabValue { l: 50.0, a: 20.0, b: -30.0 };
+
+    // The white point is preserved
+    let white = LabValue { l: 100.0, a: 0.0, b: 0.0 };
+    assert_eq!(white.chrom_adapt(method, Illuminant::A, Illuminant::D65).round_to(3), white);
+
+    // Same as converting through XYZ by hand
+    let xyz = XyzValue::from_lab_with_white(lab, Illuminant::D65)
+        .chrom_adapt(method, Illuminant::D65, Illuminant::D50);
+    let expected = LabValue::from_xyz_with_white(xyz, Illuminant::D50);
+    assert_eq!(lab.chrom_adapt(method, Illuminant::D65, Illuminant::D50), expected);
+
+    let lch = LchValue::from(lab).chrom_adapt(method, Illuminant::D65, Illuminant::D50);
+    assert_eq!(lch.round_to(4), LchValue::from(expected).round_to(4));
+}
+
+#[cfg(feature = "illuminants")]
+#[test]
+fn chromatic_adaptation_transform() {
+    let cat = ChromaticAdaptationTransform::new(
+        ChromaticAdaptationMethod::Cat02,
+        Illuminant::D65,
+        Illuminant::D50,
+    );
+
+    assert_eq!(cat.method(), ChromaticAdaptationMethod::Cat02);
+    assert_eq!((cat.source(), cat.destination()), (Illuminant::D65, Illuminant::D50));
+    assert_eq!(cat.apply(Illuminant::D65.white_point()).round_to(4), Illuminant::D50.white_point());
+
+    // Adapting to the same illuminant does nothing
+    let identity = ChromaticAdaptationTransform::new(
+        ChromaticAdaptationMethod::Bradford,
+        Illuminant::D50,
+        Illuminant::D50,
+    );
+    let xyz = XyzValue { x: 0.5, y: 0.4, z: 0.3 };
+    assert_eq!(identity.apply(xyz).round_to(5), xyz);
+}
+
+#[cfg(feature = "rgb")]
+const RGB_SYSTEMS: &[RgbSystem] = &[
+    RgbSystem::Aces2065,
+    RgbSystem::AcesCg,
+    RgbSystem::Adobe,
+    RgbSystem::Apple,
+    RgbSystem::Best,
+    RgbSystem::Beta,
+    RgbSystem::Bruce,
+    RgbSystem::Cie,
+    RgbSystem::ColorMatch,
+    RgbSystem::DciP3,
+    RgbSystem::DisplayP3,
+    RgbSystem::Don4,
+    RgbSystem::Eci,
+    RgbSystem::Ntsc,
+    RgbSystem::PalSecam,
+    RgbSystem::ProPhoto,
+    RgbSystem::Rec709,
+    RgbSystem::Rec2020,
+    RgbSystem::SmpteC,
+    RgbSystem::Srgb,
+    RgbSystem::WideGamut,
+];
+
+#[cfg(feature = "rgb")]
+#[test]
+fn rgb_to_lab() {
+    let white = RgbNominalValue { r: 1.0, g: 1.0, b: 1.0 };
+    let rgb = RgbNominalValue { r: 0.2, g: 0.4, b: 0.6 };
+    let bradford = Some(ChromaticAdaptationMethod::Bradford);
+
+    for system in RGB_SYSTEMS {
+        // White is always white, with or without adaptation
+        let lab = LabValue::from_rgb(white, system.clone(), bradford);
+        assert_eq!(lab.round_to(2), LabValue { l: 100.0, a: 0.0, b: 0.0 }, "{}", system);
+        let lab = LabValue::from_rgb(white, system.clone(), None);
+        assert_eq!(lab.round_to(2), LabValue { l: 100.0, a: 0.0, b: 0.0 }, "{}", system);
+
+        // Round trip
+        let lab = LabValue::from_rgb(rgb, system.clone(), bradford);
+        let rgb2 = RgbNominalValue::from_lab(lab, system.clone(), bradford);
+        assert_eq!(rgb.round_to(4), rgb2.round_to(4), "{}", system);
+
+        let lab = LabValue::from_rgb(rgb, system.clone(), None);
+        let rgb2 = RgbNominalValue::from_lab(lab, system.clone(), None);
+        assert_eq!(rgb.round_to(4), rgb2.round_to(4), "{}", system);
+    }
+
+    // Known sRGB values, adapted to D50
+    let red = LabValue::from(RgbValue::new(255, 0, 0));
+    assert_eq!(red.round_to(1), LabValue { l: 54.3, a: 80.8, b: 69.9 });
+
+    // Without adaptation, sRGB is relative to D65
+    let red = LabValue::from_rgb(RgbValue::new(255, 0, 0).into(), RgbSystem::Srgb, None);
+    assert_eq!(red.round_to(1), LabValue { l: 53.2, a: 80.1, b: 67.2 });
+
+    // u8 round trip
+    for rgb in &[RgbValue::new(0, 0, 0), RgbValue::new(12, 200, 97), RgbValue::new(255, 128, 1)] {
+        assert_eq!(RgbValue::from(LabValue::from(rgb)), *rgb);
+    }
+}
+
+#[cfg(feature = "rgb")]
+#[test]
+fn matrix_inverse() {
+    // The calculated inverse matches the published inverse
+    for system in RGB_SYSTEMS {
+        let inverse = system.rgb_to_xyz().inverse().unwrap();
+        let expected = system.xyz_to_rgb();
+        for i in 0..9 {
+            assert!((inverse[i] - expected[i]).abs() < 1e-4, "{}", system);
+        }
+    }
+
+    let singular = matrix![
+        1.0, 2.0, 3.0;
+        2.0, 4.0, 6.0;
+        0.0, 1.0, 0.0
+    ];
+    assert_eq!(singular.inverse(), None);
+    assert_eq!(Matrix3x3::zero().inverse(), None);
+    assert_eq!(Matrix3x3::new([f32::NAN; 9]).inverse(), None);
+}
+
+#[cfg(feature = "rgb")]
+#[test]
+fn matrix_determinant_transpose_trace() {
+    let m = matrix![
+        2.0, -3.0,  1.0;
+        2.0,  0.0, -1.0;
+        1.0,  4.0,  5.0
+    ];
+
+    assert_eq!(m.determinant(), 49.0);
+    assert_eq!(m.trace(), 7.0);
+    assert_eq!(m.transpose().transpose(), m);
+    assert_eq!(m.transpose().determinant(), m.determinant());
+    assert_eq!(m.transpose()[(0, 2)], m[(2, 0)]);
+    assert_eq!(Matrix3x3::diagonal([1.0, 2.0, 3.0]).trace(), 6.0);
+
+    // The shipped conversion matrices are inverses of each other
+    for system in RGB_SYSTEMS {
+        let det = system.rgb_to_xyz().determinant() * system.xyz_to_rgb().determinant();
+        assert!((det - 1.0).abs() < 1e-5, "{}", system);
     }
 }
 
 #[test]
-fn lch_string() {
-    let good = &[
-        "100,181.0193,360",
-        "100, 181.0193, 360",
-        "100,129,129",
-        "0,0,0",
-        "99.9999,181.0193,359.9999",
+fn matrix_arithmetic() {
+    let m = matrix![
+        1.0, 2.0, 3.0;
+        4.0, 5.0, 6.0;
+        7.0, 8.0, 9.0
     ];
+    let v = matrix![1.0; 2.0; 3.0];
 
-    for i in good {
-        assert!(LchValue::from_str(i).is_ok());
+    assert_eq!(m * 2.0, m + m);
+    assert_eq!(m - m, Matrix3x3::zero());
+    assert_eq!((m * 4.0) / 2.0, m * 2.0);
+    assert_eq!((m * 2.0)[(2, 1)], 16.0);
+
+    assert_eq!(v * 3.0, v + v + v);
+    assert_eq!(v - v, Matrix3x1::new([0.0; 3]));
+    assert_eq!((v / 2.0)[2], 1.5);
+
+    // Scalar multiplication distributes over matrix multiplication
+    assert_eq!((m * 2.0) * v, (m * v) * 2.0);
+}
+
+#[test]
+fn matrix_mutation() {
+    let mut m = Matrix3x3::zero();
+    for row in 0..3 {
+        for col in 0..3 {
+            m[(row, col)] = (row * 3 + col) as f32;
+        }
     }
+    assert_eq!(m, Matrix3x3::new([0.0, 1.0, 2.0, 3.0, 4.0, 5.0, 6.0, 7.0, 8.0]));
 
-    let bad = &[
-        "100,128,-129",
-        "100,181.0194,360",
-        "100, 181.0194, 360",
-        "0,-0.01,-0.01",
-        "derp",
-        "1,2,three,4",
-        "",
-        "1,2,3,4",
-        "1,2",
-        "1",
-        "1,2,3,derp"
+    m[4] = 10.0;
+    assert_eq!(m[(1, 1)], 10.0);
+
+    m.iter_mut().for_each(|v| *v *= 2.0);
+    assert_eq!(m.iter().sum::<f32>(), 2.0 * (36.0 + 6.0));
+
+    let mut v = Matrix3x1::new([1.0, 2.0, 3.0]);
+    v[0] = 4.0;
+    v.iter_mut().for_each(|x| *x -= 1.0);
+    assert_eq!(v.iter().copied().collect::<Vec<f32>>(), vec![3.0, 1.0, 2.0]);
+}
+
+#[test]
+#[should_panic]
+fn matrix_index_out_of_bounds() {
+    let mut m = Matrix3x3::zero();
+    m[(0, 3)] = 1.0;
+}
+
+#[test]
+fn matrix_from_arrays() {
+    let flat = [1.0, 2.0, 3.0, 4.0, 5.0, 6.0, 7.0, 8.0, 9.0];
+    let m = Matrix3x3::from(flat);
+    assert_eq!(m[(0, 2)], 3.0);
+    assert_eq!(m[(2, 0)], 7.0);
+
+    assert_eq!(Matrix3x3::from([[1.0, 2.0, 3.0], [4.0, 5.0, 6.0], [7.0, 8.0, 9.0]]), m);
+    assert_eq!(Matrix3x3::from_columns([[1.0, 4.0, 7.0], [2.0, 5.0, 8.0], [3.0, 6.0, 9.0]]), m);
+    assert_eq!(Matrix3x3::try_from(&flat[..]).unwrap(), m);
+
+    let v: Vec<f32> = flat.iter().copied().chain(Some(10.0)).collect();
+    assert!(Matrix3x3::try_from(&v[..]).is_err());
+    assert!(Matrix3x3::try_from(&v[..8]).is_err());
+    assert!(Matrix3x3::try_from(&v[1..]).is_ok());
+}
+
+fn assert_almost_identity<D: std::fmt::Display>(m: Matrix3x3, name: D) {
+    for i in 0..9 {
+        let expected = Matrix3x3::IDENTITY[i];
+        assert!((m[i] - expected).abs() < 1e-5, "{} is not an identity matrix: {:?}", name, m);
+    }
+}
+
+#[test]
+fn matrix_identity() {
+    assert!(Matrix3x3::IDENTITY.is_identity());
+    assert!(!Matrix3x3::zero().is_identity());
+    assert!(Matrix3x3::IDENTITY.is_invertible());
+    assert!(!Matrix3x3::zero().is_invertible());
+    assert_eq!(Matrix3x3::IDENTITY.inverse(), Some(Matrix3x3::IDENTITY));
+
+    let m = matrix![
+        2.0, -3.0,  1.0;
+        2.0,  0.0, -1.0;
+        1.0,  4.0,  5.0
     ];
+    assert_eq!(m * Matrix3x3::IDENTITY, m);
+    assert_eq!(Matrix3x3::IDENTITY * m, m);
+    assert_almost_identity(m * m.inverse().unwrap(), "m * m⁻¹");
 
-    for i in bad {
-        assert!(LchValue::from_str(i).is_err());
+    // Guard against typos in the shipped matrix pairs
+    #[cfg(feature = "rgb")]
+    for system in RGB_SYSTEMS {
+        assert_almost_identity(system.rgb_to_xyz() * system.xyz_to_rgb(), system);
+        assert_almost_identity(system.xyz_to_rgb() * system.rgb_to_xyz(), system);
     }
 }
 
+#[cfg(feature = "illuminants")]
 #[test]
-fn xyz_string() {
-    let good = &[
-        "0, 0, 0",
-        "1, 1, 1",
-        "0.5, 0.5, 0.5"
+fn matrix_double_precision() {
+    let m = ChromaticAdaptationMethod::Bradford.matrix();
+    let md = m.to_f64();
+    assert_eq!(md, Matrix3x3d::from(m));
+    assert_eq!(md.to_f32(), m);
+
+    // Chained products accumulate less error in f64
+    let (mut single, mut double) = (Matrix3x3::IDENTITY, Matrix3x3d::IDENTITY);
+    for _ in 0..100 {
+        single = single * m * m.inverse().unwrap();
+        double = double * md * md.inverse().unwrap();
+    }
+
+    let error = |a: Matrix3x3d| (a - Matrix3x3d::IDENTITY).iter().fold(0.0, |e, v| v.abs().max(e));
+    assert!(error(double) < 1e-12);
+    assert!(error(double) < error(single.to_f64()));
+
+    let xyz = XyzValue::new(0.5, 0.4, 0.3).unwrap();
+    assert_eq!(XyzValue::from(double * Matrix3x1d::from(xyz)).round_to(6), xyz.round_to(6));
+}
+
+#[test]
+fn matrix_into_iter() {
+    let mut m = matrix![
+        1.0, 2.0, 3.0;
+        4.0, 5.0, 6.0;
+        7.0, 8.0, 9.0
     ];
 
-    for i in good {
-        assert!(XyzValue::from_str(i).is_ok());
+    let owned: Vec<f32> = m.into_iter().collect();
+    assert_eq!(owned, (1..=9).map(|i| i as f32).collect::<Vec<f32>>());
+    assert_eq!((&m).into_iter().count(), 9);
+
+    for value in &mut m {
+        *value *= 2.0;
+    }
+    assert_eq!(m[(2, 2)], 18.0);
+
+    let v = Matrix3x1d::new([1.0, 2.0, 3.0]);
+    assert_eq!(v.into_iter().rev().collect::<Vec<f64>>(), vec![3.0, 2.0, 1.0]);
+    assert_eq!((&v).into_iter().len(), 3);
+}
+
+#[cfg(feature = "illuminants")]
+#[test]
+fn matrix_display_round() {
+    let m = ChromaticAdaptationMethod::Bradford.matrix();
+    assert_eq!(
+        format!("{:.4}", m),
+        "[ 0.8951,  0.2664, -0.1614]\n[-0.7502,  1.7135,  0.0367]\n[ 0.0389, -0.0685,  1.0296]"
+    );
+    assert_eq!(m.round_to(4), m);
+    assert_eq!(m.to_f64().round_to(4).to_f32(), m);
+
+    let inv = m.inverse().unwrap().round_to(4);
+    assert_eq!(inv[(0, 0)], 0.987);
+    assert_eq!(format!("{}", inv).lines().count(), 3);
+
+    let v = Matrix3x1::new([1.0, 22.5, -3.25]);
+    assert_eq!(format!("{}", v), "[    1]\n[ 22.5]\n[-3.25]");
+    assert_eq!(v.round_to(0), Matrix3x1::new([1.0, 23.0, -3.0]));
+    assert_eq!(format!("{:.0}", v.to_f64()), "[ 1]\n[22]\n[-3]");
+}
+
+#[cfg(all(feature = "rgb", feature = "parse"))]
+#[test]
+fn css_string() {
+    let css = |s: &str| CssColor::from_str(s).unwrap();
+    let red = CssColor::Rgb(RgbNominalValue { r: 1.0, g: 0.0, b: 0.0 }, RgbSystem::Srgb);
+
+    // Equivalent notations
+    for s in &[
+        "#f00",
+        "#FF0000",
+        "rgb(255 0 0)",
+        "RGB(255, 0, 0)",
+        "rgba(255, 0, 0, 0.5)",
+        "rgb(100% 0% 0% / 50%)",
+        "rgb(255 none none)",
+        "hsl(0 100% 50%)",
+        "hsl(360deg 100% 50%)",
+        "hsla(1turn, 100%, 50%, 1)",
+        "hsl(0rad 100 50)",
+        "color(srgb 1 0 0)",
+        "color(srgb 100% 0 0 / 0.25)",
+    ] {
+        assert_eq!(css(s), red, "{}", s);
     }
 
+    assert_eq!(css("hsl(120 100% 25%)"), CssColor::Rgb(RgbNominalValue { r: 0.0, g: 0.5, b: 0.0 }, RgbSystem::Srgb));
+    assert_eq!(css("hsl(240 50% 100%)"), CssColor::Rgb(RgbNominalValue { r: 1.0, g: 1.0, b: 1.0 }, RgbSystem::Srgb));
+    assert_eq!(css("lab(50% 40 -20)"), CssColor::Lab(LabValue { l: 50.0, a: 40.0, b: -20.0 }));
+    assert_eq!(css("lab(50 100% -100%)"), CssColor::Lab(LabValue { l: 50.0, a: 125.0, b: -125.0 }));
+    assert_eq!(css("lch(50 30 -90deg)"), CssColor::Lch(LchValue { l: 50.0, c: 30.0, h: 270.0 }));
+    assert_eq!(css("lch(50 20% 200grad)"), CssColor::Lch(LchValue { l: 50.0, c: 30.0, h: 180.0 }));
+    assert_eq!(
+        css("color(xyz-d50 0.9642 1 0.8251)"),
+        CssColor::Xyz(Illuminant::D50.white_point())
+    );
+
+    // D65 white is adapted to D50 white. Oklab's D65 is defined by chromaticity, so Z differs
+    // from the tabulated white point in the fourth decimal place.
+    let d50 = Illuminant::D50.white_point().round_to(3);
+    for s in &["color(xyz-d65 0.95047 1 1.08883)", "color(xyz 0.95047 1 1.08883)", "oklab(1 0 0)", "oklch(100% 0 0)"] {
+        match css(s) {
+            CssColor::Xyz(xyz) => assert_eq!(xyz.round_to(3), d50, "{}", s),
+            other => panic!("{} parsed as {:?}", s, other),
+        }
+    }
+
+    // The same color in different spaces
+    let lab_red = LabValue::from(red);
+    for s in &["oklab(0.62796 0.22486 0.12585)", "oklch(62.796% 0.25768 29.234)", "lab(54.29 80.8 69.89)"] {
+        assert!(lab_red.delta(css(s), DE2000).value() < &0.1, "{}", s);
+    }
+    assert!(lab_red.delta(css("color(display-p3 1 0 0)"), DE2000).value() > &5.0);
+    assert!(lab_red.delta(css("color(display-p3 0.9175 0.2003 0.1386)"), DE2000).value() < &0.1);
+    assert!(lab_red.delta(css("color(rec2020 0.792 0.231 0.0738)"), DE2000).value() < &0.1);
+
     let bad = &[
-        "-0.01, 0, 0",
-        "0, 1.01, 0",
-        "0, 0, 1.01",
+        "",
         "derp",
-        "0, 0, 0, derp",
-        "0, 0, derp"
+        "#ff00",
+        "rgb()",
+        "rgb(255 0)",
+        "rgb(255 0 0 0)",
+        "rgb(255 0 0",
+        "rgb(255, 0 0)",
+        "rgb(255, 0, 0 / 0.5)",
+        "rgb(255 0 0 / 0.5 / 0.5)",
+        "rgb(255 0 0 /)",
+        "rgb(256 0 0)",
+        "rgb(255 0 0 / 1.5)",
+        "rgb(derp 0 0)",
+        "hsl(0 101% 50%)",
+        "hsl(0% 100% 50%)",
+        "lab(101 0 0)",
+        "oklab(1.5 0 0)",
+        "color(rec2100-pq 1 0 0)",
+        "color(srgb 1.1 0 0)",
+        "color()",
+        "hwb(0 0% 0%)",
     ];
 
-    for i in bad {
-        assert!(XyzValue::from_str(i).is_err());
+    for s in bad {
+        assert!(CssColor::from_str(s).is_err(), "{}", s);
     }
 }
 
-fn compare_de(method: DEMethod, expected: f32, reference: &[f32; 3], sample: &[f32; 3]) -> ValueResult<()> {
-    let lab0 = LabValue::try_from(reference)?;
-    let lab1 = LabValue::try_from(sample)?;
+#[test]
+#[cfg(all(feature = "named-colors", feature = "parse"))]
+fn named_colors() {
+    assert_eq!(RgbValue::from_name("white"), Some(RgbValue::new(255, 255, 255)));
+    assert_eq!(RgbValue::from_name(" CornflowerBlue "), Some(RgbValue::new(100, 149, 237)));
+    assert_eq!(RgbValue::from_name("gray"), RgbValue::from_name("grey"));
+    assert_eq!(RgbValue::from_name("aqua"), RgbValue::from_name("cyan"));
+    assert_eq!(RgbValue::from_name("transparent"), None);
+    assert_eq!(RgbValue::from_name(""), None);
 
-    let de = lab0.delta(lab1, method).round_to(4).value;
+    assert_eq!(RgbValue::from_str("rebeccapurple").unwrap(), RgbValue::from_str("#663399").unwrap());
+    assert_eq!(CssColor::from_str("Red").unwrap(), CssColor::from_str("rgb(255 0 0)").unwrap());
+    assert!(RgbValue::from_str("derp").is_err());
+}
 
-    assert_eq!(expected, de);
+#[cfg(feature = "rgb")]
+#[test]
+fn rgb_system_custom() {
+    let srgb_curve = ToneCurve::Parametric {
+        g: 2.4, a: 1.0 / 1.055, b: 0.055 / 1.055, c: 1.0 / 12.92, d: 0.04045, e: 0.0, f: 0.0,
+    };
+    let custom = RgbSystem::Custom {
+        rgb_to_xyz: RgbSystem::Srgb.rgb_to_xyz(),
+        white: Illuminant::D65,
+        curves: [srgb_curve.clone(), srgb_curve.clone(), srgb_curve.clone()],
+    };
+    assert!(custom.clone().validate().is_ok());
+    assert_eq!(custom.to_string(), "Custom");
 
-    Ok(())
+    // Parameters that the curve would divide by zero with, or compare to an infinite breakpoint
+    let bad_curves = [
+        ToneCurve::Parametric { g: 2.4, a: 0.0, b: 0.0, c: 1.0 / 12.92, d: 0.04045, e: 0.0, f: 0.0 },
+        ToneCurve::Parametric { g: 0.0, a: 1.0 / 1.055, b: 0.055 / 1.055, c: 1.0 / 12.92, d: 0.04045, e: 0.0, f: 0.0 },
+        ToneCurve::Parametric { g: -2.4, a: 1.0 / 1.055, b: 0.055 / 1.055, c: 1.0 / 12.92, d: 0.04045, e: 0.0, f: 0.0 },
+        ToneCurve::Parametric { g: 2.4, a: 1.0 / 1.055, b: 0.055 / 1.055, c: 1.0 / 12.92, d: f32::NAN, e: 0.0, f: 0.0 },
+    ];
+    for curve in &bad_curves {
+        let bad = RgbSystem::Custom {
+            rgb_to_xyz: RgbSystem::Srgb.rgb_to_xyz(),
+            white: Illuminant::D65,
+            curves: [srgb_curve.clone(), curve.clone(), srgb_curve.clone()],
+        };
+        assert!(matches!(bad.validate(), Err(ValueError::OutOfBounds)), "{:?}", curve);
+    }
+
+    let bradford = Some(ChromaticAdaptationMethod::Bradford);
+    for v in &[0.0, 0.01, 0.04, 0.2, 0.5, 1.0] {
+        assert!((srgb_curve.compand(srgb_curve.linearize(*v)) - v).abs() < 1e-6);
+        let rgb = RgbNominalValue { r: *v, g: 0.5, b: 1.0 - v };
+        assert_eq!(
+            LabValue::from_rgb(rgb, custom.clone(), bradford).round_to(3),
+            LabValue::from_rgb(rgb, RgbSystem::Srgb, bradford).round_to(3)
+        );
+    }
+
+    // Per-channel curves
+    let curves = RgbSystem::Custom {
+        rgb_to_xyz: Matrix3x3::IDENTITY,
+        white: Illuminant::E,
+        curves: [ToneCurve::Gamma(1.0), ToneCurve::Gamma(2.0), ToneCurve::Gamma(3.0)],
+    };
+    let linear = curves.linearize_rgb(RgbNominalValue { r: 0.5, g: 0.5, b: 0.5 });
+    assert_eq!(linear, RgbNominalValue { r: 0.5, g: 0.25, b: 0.125 });
+    assert_eq!(curves.compand_rgb(linear), RgbNominalValue { r: 0.5, g: 0.5, b: 0.5 });
+
+    let singular = RgbSystem::Custom {
+        rgb_to_xyz: Matrix3x3::zero(),
+        white: Illuminant::D50,
+        curves: [ToneCurve::Gamma(2.2), ToneCurve::Gamma(2.2), ToneCurve::Gamma(2.2)],
+    };
+    assert!(singular.validate().is_err());
 }
 
-#[test]
-fn decmc1() {
-    assert!(compare_de(DEMethod::DECMC(1.0, 1.0), 17.4901, &[20.0, 30.0, 40.0], &[30.0, 40.0, 50.0]).is_ok());
+// Build a minimal matrix/TRC ICC profile from tags
+#[cfg(feature = "icc")]
+fn icc_profile(tags: &[(&[u8; 4], Vec<u8>)]) -> Vec<u8> {
+    let mut header = vec![0_u8; 128];
+    header[16..20].copy_from_slice(b"RGB ");
+    header[20..24].copy_from_slice(b"XYZ ");
+    header[36..40].copy_from_slice(b"acsp");
+
+    let mut table = (tags.len() as u32).to_be_bytes().to_vec();
+    let mut data = Vec::new();
+    let mut offset = 128 + 4 + tags.len() * 12;
+    for (signature, tag) in tags {
+        table.extend_from_slice(&signature[..]);
+        table.extend_from_slice(&(offset as u32).to_be_bytes());
+        table.extend_from_slice(&(tag.len() as u32).to_be_bytes());
+        data.extend_from_slice(tag);
+        offset += tag.len();
+    }
+
+    let mut profile = header;
+    profile.extend(table);
+    profile.extend(data);
+    let size = profile.len() as u32;
+    profile[0..4].copy_from_slice(&size.to_be_bytes());
+    profile
+}
+
+#[cfg(feature = "icc")]
+fn icc_s15_fixed16(values: &[f32]) -> Vec<u8> {
+    values.iter().flat_map(|v| ((v * 65536.0).round() as i32).to_be_bytes()).collect()
+}
+
+#[cfg(feature = "icc")]
+fn icc_xyz(xyz: [f32; 3]) -> Vec<u8> {
+    let mut tag = b"XYZ \0\0\0\0".to_vec();
+    tag.extend(icc_s15_fixed16(&xyz));
+    tag
 }
 
 #[test]
-fn decmc2() {
-    assert!(compare_de(DEMethod::DECMC(2.0, 1.0), 10.0731, &[20.0, 30.0, 40.0], &[30.0, 40.0, 50.0]).is_ok());
+#[cfg(feature = "icc")]
+fn icc_profile_srgb() {
+    // The colorants of sRGB adapted to D50 with Bradford, as in the sRGB ICC profile
+    let cat = ChromaticAdaptationTransform::new(
+        ChromaticAdaptationMethod::Bradford, Illuminant::D65, Illuminant::D50
+    );
+    let m = *cat.matrix() * RgbSystem::Srgb.rgb_to_xyz();
+    let colorant = |col: usize| icc_xyz([m[(0, col)], m[(1, col)], m[(2, col)]]);
+
+    let mut para = b"para\0\0\0\0\0\x03\0\0".to_vec();
+    para.extend(icc_s15_fixed16(&[2.4, 1.0 / 1.055, 0.055 / 1.055, 1.0 / 12.92, 0.04045]));
+
+    let mut tags: Vec<(&[u8; 4], Vec<u8>)> = vec![
+        (b"wtpt", icc_xyz([0.9642, 1.0, 0.8249])),
+        (b"rXYZ", colorant(0)),
+        (b"gXYZ", colorant(1)),
+        (b"bXYZ", colorant(2)),
+        (b"rTRC", para.clone()),
+        (b"gTRC", para.clone()),
+        (b"bTRC", para),
+    ];
+
+    let profile = IccProfile::from_bytes(&icc_profile(&tags)).unwrap();
+    assert_eq!(profile.media_white_point().round_to(4), XyzValue { x: 0.9642, y: 1.0, z: 0.8249 });
+
+    let system = profile.rgb_system();
+    assert_eq!(system.white_point(), Illuminant::D50);
+    assert_eq!(RgbSystem::from_icc(&icc_profile(&tags)).unwrap(), system);
+
+    let bradford = Some(ChromaticAdaptationMethod::Bradford);
+    for rgb in &[[1.0, 1.0, 1.0], [1.0, 0.0, 0.0], [0.2, 0.4, 0.6], [0.02, 0.01, 0.0]] {
+        let rgb = RgbNominalValue { r: rgb[0], g: rgb[1], b: rgb[2] };
+        let icc = LabValue::from_rgb(rgb, system.clone(), bradford);
+        assert!(icc.delta(LabValue::from_rgb(rgb, RgbSystem::Srgb, bradford), DE2000).value() < &0.01);
+    }
+
+    // Gamma and sampled curves
+    let gamma_22 = |i: usize| (i as f32 / 1023.0).powf(2.2);
+    let mut curv = b"curv\0\0\0\0".to_vec();
+    curv.extend(1024_u32.to_be_bytes());
+    curv.extend((0..1024).flat_map(|i| ((gamma_22(i) * 65535.0).round() as u16).to_be_bytes()));
+    tags[4].1 = curv;
+    tags[5].1 = b"curv\0\0\0\0\0\0\0\x01\x01\xcd".to_vec(); // u8Fixed8 1.8
+    tags[6].1 = b"curv\0\0\0\0\0\0\0\0".to_vec();
+
+    match RgbSystem::from_icc(&icc_profile(&tags)).unwrap() {
+        RgbSystem::Custom { curves: [ToneCurve::Sampled(r), ToneCurve::Gamma(g), ToneCurve::Gamma(b)], .. } => {
+            assert_eq!(r.len(), 1024);
+            assert_eq!(ToneCurve::Sampled(r.clone()).linearize(512.0 / 1023.0), r[512]);
+            assert_eq!(g, 461.0 / 256.0);
+            assert_eq!(b, 1.0);
+        }
+        other => panic!("{:?}", other),
+    }
+
+    // Malformed profiles
+    assert!(RgbSystem::from_icc(&[]).is_err());
+    assert!(RgbSystem::from_icc(&icc_profile(&tags)[..200]).is_err());
+    assert!(RgbSystem::from_icc(&icc_profile(&tags[1..])).is_ok());
+    assert!(RgbSystem::from_icc(&icc_profile(&tags[..6])).is_err());
+
+    let mut cmyk = icc_profile(&tags);
+    cmyk[16..20].copy_from_slice(b"CMYK");
+    assert!(RgbSystem::from_icc(&cmyk).is_err());
+
+    tags[1].1 = icc_xyz([0.0, 0.0, 0.0]);
+    assert!(RgbSystem::from_icc(&icc_profile(&tags)).is_err());
 }
 
 #[test]
-fn de1976_test_set() {
-    let set = &[
-        (0.0000,   &[0.0000,  0.0000,    0.0000  ], &[0.0000,    0.0000,    0.0000  ]),
-        (5.0000,   &[0.0000,  0.0000,    0.0000  ], &[0.0000,    3.0000,    4.0000  ]),
-        (5.0000,   &[0.0000,  0.0000,    0.0000  ], &[0.0000,   -3.0000,   -4.0000  ]),
-        (50.0000,  &[0.0000,  0.0000,    0.0000  ], &[0.0000,   -30.0000,  -40.0000 ]),
-        (181.0193, &[0.0000,  0.0000,    0.0000  ], &[0.0000,    128.0000,  128.0000]),
-        (362.0387, &[0.0000, -128.0000, -128.0000], &[0.0000,    128.0000,  128.0000]),
-        (375.5955, &[0.0000, -128.0000, -128.0000], &[100.0000,  128.0000,  128.0000])
+#[cfg(feature = "icc")]
+fn icc_profile_srgb_curv() {
+    // The sRGB profile shipped with most systems stores its curves as 1024-entry curv tables
+    let cat = ChromaticAdaptationTransform::new(
+        ChromaticAdaptationMethod::Bradford, Illuminant::D65, Illuminant::D50
+    );
+    let m = *cat.matrix() * RgbSystem::Srgb.rgb_to_xyz();
+    let colorant = |col: usize| icc_xyz([m[(0, col)], m[(1, col)], m[(2, col)]]);
+
+    let mut curv = b"curv\0\0\0\0".to_vec();
+    curv.extend(1024_u32.to_be_bytes());
+    curv.extend((0..1024).flat_map(|i| {
+        let linear = RgbSystem::Srgb.linearize(i as f32 / 1023.0);
+        ((linear * 65535.0).round() as u16).to_be_bytes()
+    }));
+
+    let tags: Vec<(&[u8; 4], Vec<u8>)> = vec![
+        (b"wtpt", icc_xyz([0.9642, 1.0, 0.8249])),
+        (b"rXYZ", colorant(0)),
+        (b"gXYZ", colorant(1)),
+        (b"bXYZ", colorant(2)),
+        (b"rTRC", curv.clone()),
+        (b"gTRC", curv.clone()),
+        (b"bTRC", curv),
     ];
+    let system = RgbSystem::from_icc(&icc_profile(&tags)).unwrap();
 
-    for (expected, reference, sample) in set.iter() {
-        assert!(compare_de(DEMethod::DE1976, *expected, reference, sample).is_ok());
+    // A fitted gamma misses sRGB by more than 2 ΔE in the midtones. What is left is the rounding
+    // of the 16-bit samples, which shows most near black.
+    let bradford = Some(ChromaticAdaptationMethod::Bradford);
+    let steps = [0.0, 0.002, 0.01, 0.03, 0.05, 0.1, 0.2, 0.35, 0.5, 0.65, 0.8, 0.95, 1.0];
+    for &r in &steps {
+        for &g in &steps {
+            for &b in &[0.0, 0.04, 0.5, 1.0] {
+                let rgb = RgbNominalValue { r, g, b };
+                let icc = LabValue::from_rgb(rgb, system.clone(), bradford);
+                let srgb = LabValue::from_rgb(rgb, RgbSystem::Srgb, bradford);
+                let de = *icc.delta(srgb, DE2000).value();
+                assert!(de < 0.02, "{:?}: {}", rgb, de);
+
+                // The table is inverted back to the same nominal values
+                let back = RgbNominalValue::from_lab(icc, system.clone(), bradford);
+                assert_eq!(back.round_to(3), rgb.round_to(3));
+            }
+        }
     }
 }
 
-// Tests taken from Table 1: "CIEDE2000 total color difference test data" of
-// "The CIEDE2000 Color-Difference Formula: Implementation Notes,
-// Supplementary Test Data, and Mathematical Observations" by Gaurav Sharma,
-// Wencheng Wu and Edul N. Dalal.
-//
-// http://www.ece.rochester.edu/~gsharma/papers/CIEDE2000CRNAFeb05.pdf
+#[cfg(feature = "rgb")]
+#[test]
+fn tone_curve_sampled() {
+    let curve = ToneCurve::Sampled(vec![0.0, 0.1, 0.1, 0.4, 1.0].into());
+    assert_eq!(curve.linearize(0.0), 0.0);
+    assert_eq!(curve.linearize(0.375), 0.1);
+    assert_eq!(curve.linearize(0.625), 0.25);
+    assert_eq!(curve.linearize(1.0), 1.0);
+    assert_eq!(curve.linearize(-0.5), 0.0);
+    assert_eq!(curve.linearize(1.5), 1.0);
+
+    // Flat runs invert to their start, and values outside the table are clipped
+    assert_eq!(curve.compand(0.1), 0.25);
+    assert_eq!(curve.compand(0.25), 0.625);
+    assert_eq!(curve.compand(-0.1), 0.0);
+    assert_eq!(curve.compand(2.0), 1.0);
+    for v in &[0.0, 0.05, 0.2, 0.55, 0.9, 1.0] {
+        assert!((curve.compand(curve.linearize(*v)) - v).abs() < 1e-6, "{}", v);
+    }
 
+    let custom = |curve: ToneCurve| RgbSystem::Custom {
+        rgb_to_xyz: RgbSystem::Srgb.rgb_to_xyz(),
+        white: Illuminant::D65,
+        curves: [curve.clone(), curve.clone(), curve],
+    };
+    assert!(custom(curve).validate().is_ok());
+    assert!(matches!(custom(ToneCurve::Sampled(vec![0.0].into())).validate(), Err(ValueError::BadFormat)));
+    assert!(matches!(custom(ToneCurve::Sampled(vec![0.0, 0.5, 0.4, 1.0].into())).validate(), Err(ValueError::BadFormat)));
+    assert!(matches!(custom(ToneCurve::Sampled(vec![0.0, f32::NAN].into())).validate(), Err(ValueError::NotFinite)));
+}
+
+// Encode a null terminated UTF-16BE string
+#[cfg(feature = "swatches")]
+fn utf16_be(s: &str) -> Vec<u8> {
+    s.encode_utf16().chain(Some(0)).flat_map(u16::to_be_bytes).collect()
+}
+
+#[cfg(feature = "swatches")]
 #[test]
-fn de2000_test_set() {
-    let set = &[
-        (0.0000,   &[0.0000,   0.0000,   0.0000 ], &[0.0000,   0.0000,   0.0000 ]),
-        (0.0000,   &[99.5000,  0.0050,  -0.0100 ], &[99.5000,  0.0050,  -0.0100 ]),
-        (100.0000, &[100.0000, 0.0050,  -0.0100 ], &[0.0000,   0.0000,   0.0000 ]),
-        (2.0425,   &[50.0000,  2.6772,  -79.7751], &[50.0000,  0.0000,  -82.7485]),
-        (2.8615,   &[50.0000,  3.1571,  -77.2803], &[50.0000,  0.0000,  -82.7485]),
-        (3.4412,   &[50.0000,  2.8361,  -74.0200], &[50.0000,  0.0000,  -82.7485]),
-        (1.0000,   &[50.0000, -1.3802,  -84.2814], &[50.0000,  0.0000,  -82.7485]),
-        (1.0000,   &[50.0000, -1.1848,  -84.8006], &[50.0000,  0.0000,  -82.7485]),
-        (1.0000,   &[50.0000, -0.9009,  -85.5211], &[50.0000,  0.0000,  -82.7485]),
-        (2.3669,   &[50.0000,  0.0000,   0.0000 ], &[50.0000, -1.0000,   2.0000 ]),
-        (2.3669,   &[50.0000, -1.0000,   2.0000 ], &[50.0000,  0.0000,   0.0000 ]),
-        (7.1792,   &[50.0000,  2.4900,  -0.0010 ], &[50.0000, -2.4900,   0.0009 ]),
-        (7.1792,   &[50.0000,  2.4900,  -0.0010 ], &[50.0000, -2.4900,   0.0010 ]),
-        (7.2195,   &[50.0000,  2.4900,  -0.0010 ], &[50.0000, -2.4900,   0.0011 ]),
-        (7.2195,   &[50.0000,  2.4900,  -0.0010 ], &[50.0000, -2.4900,   0.0012 ]),
-        (4.8045,   &[50.0000, -0.0010,   2.4900 ], &[50.0000,  0.0009,  -2.4900 ]),
-        (4.7461,   &[50.0000, -0.0010,   2.4900 ], &[50.0000,  0.0011,  -2.4900 ]),
-        (4.3065,   &[50.0000,  2.5000,   0.0000 ], &[50.0000,  0.0000,  -2.5000 ]),
-        (27.1492,  &[50.0000,  2.5000,   0.0000 ], &[73.0000,  25.0000, -18.0000]),
-        (22.8977,  &[50.0000,  2.5000,   0.0000 ], &[61.0000, -5.0000,   29.0000]),
-        (31.9030,  &[50.0000,  2.5000,   0.0000 ], &[56.0000, -27.0000, -3.0000 ]),
-        (19.4535,  &[50.0000,  2.5000,   0.0000 ], &[58.0000,  24.0000,  15.0000]),
-        (1.0000,   &[50.0000,  2.5000,   0.0000 ], &[50.0000,  3.1736,   0.5854 ]),
-        (1.0000,   &[50.0000,  2.5000,   0.0000 ], &[50.0000,  3.2972,   0.0000 ]),
-        (1.0000,   &[50.0000,  2.5000,   0.0000 ], &[50.0000,  1.8634,   0.5757 ]),
-        (1.0000,   &[50.0000,  2.5000,   0.0000 ], &[50.0000,  3.2592,   0.3350 ]),
-        (1.2644,   &[60.2574, -34.0099,  36.2677], &[60.4626, -34.1751,  39.4387]),
-        (1.2630,   &[63.0109, -31.0961, -5.8663 ], &[62.8187, -29.7946, -4.0864 ]),
-        (1.8731,   &[61.2901,  3.7196,  -5.3901 ], &[61.4292,  2.2480,  -4.9620 ]),
-        (1.8645,   &[35.0830, -44.1164,  3.7933 ], &[35.0232, -40.0716,  1.5901 ]),
-        (2.0373,   &[22.7233,  20.0904, -46.6940], &[23.0331, 14.9730,  -42.5619]),
-        (1.4146,   &[36.4612,  47.8580,  18.3852], &[36.2715,  50.5065,  21.2231]),
-        (1.4441,   &[90.8027, -2.0831,   1.4410 ], &[91.1528, -1.6435,   0.0447 ]),
-        (1.5381,   &[90.9257, -0.5406,  -0.9208 ], &[88.6381, -0.8985,   -0.7239]),
-        (0.6377,   &[6.7747,  -0.2908,  -2.4247 ], &[5.8714,  -0.0985,   -2.2286]),
-        (0.9082,   &[2.0776,   0.0795,  -1.1350 ], &[0.9033,  -0.0636,   -0.5514])
+fn swatch_ase() {
+    let entry = |name: &str, model: &[u8; 4], values: &[f32]| {
+        let mut block = ((name.encode_utf16().count() + 1) as u16).to_be_bytes().to_vec();
+        block.extend(utf16_be(name));
+        block.extend(model);
+        block.extend(values.iter().flat_map(|v| v.to_be_bytes()));
+        block.extend(2_u16.to_be_bytes());
+
+        let mut bytes = 1_u16.to_be_bytes().to_vec();
+        bytes.extend((block.len() as u32).to_be_bytes());
+        bytes.extend(block);
+        bytes
+    };
+
+    let group_start = {
+        let mut block = 6_u16.to_be_bytes().to_vec();
+        block.extend(utf16_be("Brand"));
+        let mut bytes = 0xC001_u16.to_be_bytes().to_vec();
+        bytes.extend((block.len() as u32).to_be_bytes());
+        bytes.extend(block);
+        bytes
+    };
+
+    let blocks = [
+        group_start,
+        entry("Red", b"RGB ", &[1.0, 0.0, 0.0]),
+        entry("Mid Gray", b"LAB ", &[0.5, 0.0, -1.5]),
+        [0xC0, 0x02, 0, 0, 0, 0].to_vec(),
+        entry("Käse", b"Gray", &[0.25]),
+        entry("Cyan", b"CMYK", &[1.0, 0.0, 0.0, 0.0]),
     ];
 
-    for (expected, reference, sample) in set.iter() {
-        assert!(compare_de(DEMethod::DE2000, *expected, reference, sample).is_ok())
+    let mut ase = b"ASEF\x00\x01\x00\x00".to_vec();
+    ase.extend((blocks.len() as u32).to_be_bytes());
+    blocks.iter().for_each(|b| ase.extend(b));
+
+    let swatches = swatch::read_ase(&ase).unwrap();
+    let names: Vec<&str> = swatches.iter().map(|s| s.name.as_str()).collect();
+    assert_eq!(names, ["Red", "Mid Gray", "Käse", "Cyan"]);
+
+    use swatch::SwatchColor;
+    assert_eq!(swatches[0].color, SwatchColor::Rgb(RgbNominalValue { r: 1.0, g: 0.0, b: 0.0 }));
+    assert_eq!(swatches[1].color, SwatchColor::Lab(LabValue { l: 50.0, a: 0.0, b: -1.5 }));
+    assert_eq!(swatches[2].color, SwatchColor::Gray(0.25));
+    assert_eq!(swatches[3].color, SwatchColor::Cmyk([1.0, 0.0, 0.0, 0.0]));
+
+    let red = swatches[0].color.to_lab(RgbSystem::Srgb).unwrap();
+    assert!(red.delta(LabValue::new(54.29, 80.8, 69.89).unwrap(), DE2000).value() < &0.1);
+    assert_eq!(swatches[1].color.to_lab(RgbSystem::Srgb), Some(LabValue { l: 50.0, a: 0.0, b: -1.5 }));
+    assert!(swatches[3].color.to_lab(RgbSystem::Srgb).is_none());
+
+    assert!(swatch::read_ase(&ase[..ase.len() - 1]).is_err());
+    assert!(swatch::read_ase(b"ASEX\x00\x01\x00\x00\x00\x00\x00\x00").is_err());
+    let mut bad = b"ASEF\x00\x01\x00\x00\x00\x00\x00\x01".to_vec();
+    bad.extend(entry("Too Red", b"RGB ", &[1.5, 0.0, 0.0]));
+    assert!(swatch::read_ase(&bad).is_err());
+}
+
+#[cfg(feature = "swatches")]
+#[test]
+fn swatch_aco() {
+    use swatch::SwatchColor;
+
+    let colors: &[(u16, [u16; 4], &str)] = &[
+        (0, [0xFFFF, 0x0000, 0x0000, 0], "Red"),
+        (1, [0x5555, 0xFFFF, 0xFFFF, 0], "Green"),
+        (7, [5000, (-1500_i16) as u16, 2500, 0], "Lab"),
+        (8, [10000, 0, 0, 0], "Black"),
+        (3, [0, 1, 0, 0], "Pantone"),
+        (2, [0, 0xFFFF, 0xFFFF, 0xFFFF], "Cyan"),
+    ];
+
+    let color = |&(space, values, _): &(u16, [u16; 4], &str)| -> Vec<u8> {
+        let mut bytes = space.to_be_bytes().to_vec();
+        bytes.extend(values.iter().flat_map(|v| v.to_be_bytes()));
+        bytes
+    };
+
+    let mut v1 = 1_u16.to_be_bytes().to_vec();
+    v1.extend((colors.len() as u16).to_be_bytes());
+    colors.iter().for_each(|c| v1.extend(color(c)));
+
+    let mut aco = v1.clone();
+    aco.extend(2_u16.to_be_bytes());
+    aco.extend((colors.len() as u16).to_be_bytes());
+    for c in colors {
+        aco.extend(color(c));
+        aco.extend(((c.2.len() + 1) as u32).to_be_bytes());
+        aco.extend(utf16_be(c.2));
+    }
+
+    let swatches = swatch::read_aco(&aco).unwrap();
+    let names: Vec<&str> = swatches.iter().map(|s| s.name.as_str()).collect();
+    assert_eq!(names, ["Red", "Green", "Lab", "Black", "Cyan"]);
+
+    assert_eq!(swatches[0].color, SwatchColor::Rgb(RgbNominalValue { r: 1.0, g: 0.0, b: 0.0 }));
+    assert_eq!(swatches[1].color, SwatchColor::Rgb(RgbNominalValue { r: 0.0, g: 1.0, b: 0.0 }));
+    assert_eq!(swatches[2].color, SwatchColor::Lab(LabValue { l: 50.0, a: -15.0, b: 25.0 }));
+    assert_eq!(swatches[3].color, SwatchColor::Gray(0.0));
+    assert_eq!(swatches[4].color, SwatchColor::Cmyk([1.0, 0.0, 0.0, 0.0]));
+
+    // Version 1 only has no names
+    let swatches = swatch::read_aco(&v1).unwrap();
+    assert_eq!(swatches.len(), 5);
+    assert!(swatches.iter().all(|s| s.name.is_empty()));
+
+    assert!(swatch::read_aco(&aco[..aco.len() - 2]).is_err());
+    assert!(swatch::read_aco(&v1[..v1.len() - 1]).is_err());
+    assert!(swatch::read_aco(&[0, 2, 0, 0]).is_err());
+}
+
+#[cfg(feature = "swatches")]
+#[test]
+fn swatch_gpl() {
+    use swatch::{Swatch, SwatchColor};
+
+    let gpl = "GIMP Palette\nName: Brand\nColumns: 2\n#\n# Comment\n255   0   0\tBrand Red\n\n  0 128 255 Sky\n 17  17  17\n";
+    let swatches = swatch::read_gpl(gpl).unwrap();
+    let names: Vec<&str> = swatches.iter().map(|s| s.name.as_str()).collect();
+    assert_eq!(names, ["Brand Red", "Sky", ""]);
+    assert_eq!(swatches[1].color, SwatchColor::Rgb(RgbValue::new(0, 128, 255).into()));
+
+    let mut out = Vec::new();
+    swatch::write_gpl(&mut out, "Brand", &swatches, RgbSystem::Srgb).unwrap();
+    let written = String::from_utf8(out).unwrap();
+    assert_eq!(
+        written,
+        "GIMP Palette\nName: Brand\n#\n255   0   0\tBrand Red\n  0 128 255\tSky\n 17  17  17\t\n"
+    );
+    assert_eq!(swatch::read_gpl(&written).unwrap(), swatches);
+
+    // Lab and gray colors are converted
+    let converted = [
+        Swatch { name: "White".into(), color: SwatchColor::Lab(LabValue::new(100.0, 0.0, 0.0).unwrap()) },
+        Swatch { name: "Black".into(), color: SwatchColor::Gray(0.0) },
+    ];
+    let mut out = Vec::new();
+    swatch::write_gpl(&mut out, "", &converted, RgbSystem::Srgb).unwrap();
+    assert!(String::from_utf8(out).unwrap().ends_with("255 255 255\tWhite\n  0   0   0\tBlack\n"));
+
+    let cmyk = [Swatch { name: "Cyan".into(), color: SwatchColor::Cmyk([1.0, 0.0, 0.0, 0.0]) }];
+    assert!(matches!(
+        swatch::write_gpl(Vec::new(), "", &cmyk, RgbSystem::Srgb),
+        Err(Error::UnsupportedConversion)
+    ));
+
+    assert!(swatch::read_gpl("255 0 0 Red\n").is_err());
+    assert!(swatch::read_gpl("GIMP Palette\n255 0 Red\n").is_err());
+    assert!(swatch::read_gpl("GIMP Palette\n256 0 0 Red\n").is_err());
+}
+
+#[cfg(feature = "cgats")]
+#[test]
+fn cgats_read() {
+    use cgats::Cgats;
+
+    let s = "CGATS.17\n# A comment\nORIGINATOR \"Proof Lab\"\nDESCRIPTOR \"Quoted, with spaces\"\n\
+             NUMBER_OF_FIELDS 5\nBEGIN_DATA_FORMAT\nSAMPLE_ID SAMPLE_NAME\nXYZ_X XYZ_Y XYZ_Z\n\
+             END_DATA_FORMAT\nNUMBER_OF_SETS 2\nBEGIN_DATA\n1 \"Paper white\" 96.42 100 82.51\n\
+             2 Black 0 0 0\nEND_DATA\n";
+    let cgats = Cgats::from_str(s).unwrap();
+    assert_eq!(cgats.keyword("ORIGINATOR"), Some("Proof Lab"));
+    assert_eq!(cgats.keyword("DESCRIPTOR"), Some("Quoted, with spaces"));
+    assert_eq!(cgats.fields, ["SAMPLE_ID", "SAMPLE_NAME", "XYZ_X", "XYZ_Y", "XYZ_Z"]);
+    assert_eq!(cgats.rows[0][1], "Paper white");
+
+    // XYZ is scaled and converted to Lab
+    let patches = cgats.patches().unwrap();
+    assert_eq!(patches[0].id, "1");
+    assert_eq!(patches[0].color.round_to(2), LabValue { l: 100.0, a: 0.0, b: 0.0 });
+
+    // Decimal commas, and row numbers without a SAMPLE_ID
+    let s = "CGATS.17\nBEGIN_DATA_FORMAT\nLAB_L LAB_A LAB_B\nEND_DATA_FORMAT\nBEGIN_DATA\n50,5 0 -1,25\nEND_DATA\n";
+    let patches = Cgats::from_str(s).unwrap().patches().unwrap();
+    assert_eq!(patches[0].id, "1");
+    assert_eq!(patches[0].color, LabValue { l: 50.5, a: 0.0, b: -1.25 });
+
+    let bad = &[
+        "",
+        "CGATS.17\nORIGINATOR x\n",
+        "CGATS.17\nBEGIN_DATA_FORMAT\nLAB_L LAB_A LAB_B\nEND_DATA_FORMAT\nBEGIN_DATA\n50 0\nEND_DATA\n",
+        "CGATS.17\nBEGIN_DATA_FORMAT\nSAMPLE_ID\nEND_DATA_FORMAT\nBEGIN_DATA\n\"A1\nEND_DATA\n",
+    ];
+    for s in bad {
+        assert!(Cgats::from_str(s).is_err(), "{:?}", s);
     }
+
+    let no_color = "CGATS.17\nBEGIN_DATA_FORMAT\nSAMPLE_ID RGB_R RGB_G RGB_B\nEND_DATA_FORMAT\nBEGIN_DATA\n1 0 0 0\nEND_DATA\n";
+    assert!(Cgats::from_str(no_color).unwrap().patches().is_err());
+    let out_of_range = "CGATS.17\nBEGIN_DATA_FORMAT\nLAB_L LAB_A LAB_B\nEND_DATA_FORMAT\nBEGIN_DATA\n101 0 0\nEND_DATA\n";
+    assert!(Cgats::from_str(out_of_range).unwrap().patches().is_err());
 }
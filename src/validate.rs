@@ -2,33 +2,157 @@ use super::*;
 
 /// Trait to validate whether a type has appropriate values
 pub trait Validate where Self: Sized {
-    /// Return `Err()` if the values are invalid
+    /// Return `Err()` if the values are invalid. Values that are NaN or infinite return
+    /// [`ValueError::NotFinite`], and values out of range return [`ValueError::OutOfBounds`].
     fn validate(self) -> ValueResult<Self>;
+
+    /// Like [`Validate::validate`], but with the ranges of a [`RangeProfile`] instead of the
+    /// default ones. Types without ranges are only validated.
+    /// ```
+    /// use deltae::*;
+    ///
+    /// // A saturated yellow measured by a spectrophotometer
+    /// let yellow = LabValue { l: 90.0, a: -5.0, b: 135.0 };
+    /// assert!(yellow.validate().is_err());
+    /// assert_eq!(yellow.validate_with(&RangeProfile::EXTENDED).unwrap(), yellow);
+    ///
+    /// // XYZ on the 0-100 scale
+    /// let xyz = XyzValue { x: 95.047, y: 100.0, z: 108.883 };
+    /// assert!(xyz.validate_with(&RangeProfile::EXTENDED).is_ok());
+    /// ```
+    fn validate_with(self, profile: &RangeProfile) -> ValueResult<Self> {
+        let _ = profile;
+        self.validate()
+    }
+
+    /// Like [`Validate::validate`], but values out of range by no more than `epsilon` are clamped
+    /// into range instead of returning `Err()`. This accepts values that are only out of range
+    /// because of rounding in a conversion. Types without ranges to clamp to are only validated.
+    /// ```
+    /// use deltae::*;
+    ///
+    /// let lab = LabValue { l: 100.0001, a: -0.5, b: 128.00002 };
+    /// assert!(lab.validate().is_err());
+    /// assert_eq!(lab.validate_clamped(0.001).unwrap(), LabValue { l: 100.0, a: -0.5, b: 128.0 });
+    /// assert!(LabValue { l: 100.1, a: 0.0, b: 0.0 }.validate_clamped(0.001).is_err());
+    /// ```
+    fn validate_clamped(self, epsilon: f32) -> ValueResult<Self> {
+        let _ = epsilon;
+        self.validate()
+    }
+}
+
+pub(crate) const RANGE_PCT: std::ops::RangeInclusive<f32> = 0.0..=100.0;
+pub(crate) const RANGE_I8: std::ops::RangeInclusive<f32> = -128.0..=128.0;
+pub(crate) const RANGE_CHROMA: std::ops::RangeInclusive<f32> = 0.0..=181.01933;
+pub(crate) const RANGE_360: std::ops::RangeInclusive<f32> = 0.0..=360.0;
+pub(crate) const RANGE_01: std::ops::RangeInclusive<f32> = 0.0..=1.0;
+
+/// # RangeProfile
+///
+/// The ranges that [`Validate::validate_with`] accepts for each component. The hue angle of an
+/// [`LchValue`] is always in `0.0..=360.0`.
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct RangeProfile {
+    /// Lightness (L\*) of [`LabValue`] and [`LchValue`]
+    pub lightness: std::ops::RangeInclusive<f32>,
+    /// The a\* and b\* components of [`LabValue`]
+    pub ab: std::ops::RangeInclusive<f32>,
+    /// Chroma (C\*) of [`LchValue`]
+    pub chroma: std::ops::RangeInclusive<f32>,
+    /// The X, Y, and Z components of [`XyzValue`]
+    pub xyz: std::ops::RangeInclusive<f32>,
+    /// The components of [`RgbNominalValue`]
+    pub rgb: std::ops::RangeInclusive<f32>,
+}
+
+impl RangeProfile {
+    /// The ranges of [`Validate::validate`]: a\* and b\* in `-128.0..=128.0`, and XYZ on the 0-1
+    /// scale
+    pub const STRICT: RangeProfile = RangeProfile {
+        lightness: RANGE_PCT,
+        ab: RANGE_I8,
+        chroma: RANGE_CHROMA,
+        xyz: RANGE_01,
+        rgb: RANGE_01,
+    };
+
+    /// Ranges for instrument data: a\* and b\* in `-200.0..=200.0`, which includes saturated
+    /// yellows and greens beyond `±128`, and XYZ up to `150.0`, which includes white on the 0-100
+    /// scale. Validation does not rescale values, and conversions from [`XyzValue`] expect the 0-1
+    /// scale.
+    pub const EXTENDED: RangeProfile = RangeProfile {
+        lightness: RANGE_PCT,
+        ab: -200.0..=200.0,
+        chroma: 0.0..=282.8427,
+        xyz: 0.0..=150.0,
+        rgb: RANGE_01,
+    };
+}
+
+impl Default for RangeProfile {
+    fn default() -> RangeProfile {
+        RangeProfile::STRICT
+    }
 }
 
-const RANGE_PCT: std::ops::RangeInclusive<f32> = 0.0..=100.0;
-const RANGE_I8: std::ops::RangeInclusive<f32> = -128.0..=128.0;
-const RANGE_CHROMA: std::ops::RangeInclusive<f32> = 0.0..=181.01933;
-const RANGE_360: std::ops::RangeInclusive<f32> = 0.0..=360.0;
-const RANGE_01: std::ops::RangeInclusive<f32> = 0.0..=1.0;
+// NaN and infinity are never valid, and are reported before any range
+fn finite(values: &[f32]) -> ValueResult<()> {
+    if values.iter().all(|v| v.is_finite()) {
+        Ok(())
+    } else {
+        Err(ValueError::NotFinite)
+    }
+}
+
+// The value clamped into the range, if it is no more than epsilon outside of it. Values that are
+// not finite are rejected by the callers first, so that they are always `NotFinite`.
+fn clamp_within(value: f32, range: &std::ops::RangeInclusive<f32>, epsilon: f32) -> ValueResult<f32> {
+    let (min, max) = (*range.start(), *range.end());
+    if value >= min - epsilon && value <= max + epsilon {
+        Ok(value.clamp(min, max))
+    } else {
+        Err(ValueError::OutOfBounds)
+    }
+}
 
 impl Validate for LabValue {
     fn validate(self) -> ValueResult<Self> {
-        if RANGE_PCT.contains(&self.l)
-            && RANGE_I8.contains(&self.a)
-            && RANGE_I8.contains(&self.b)
+        self.validate_with(&RangeProfile::STRICT)
+    }
+
+    fn validate_with(self, profile: &RangeProfile) -> ValueResult<Self> {
+        finite(&[self.l, self.a, self.b])?;
+        if profile.lightness.contains(&self.l)
+            && profile.ab.contains(&self.a)
+            && profile.ab.contains(&self.b)
         {
             Ok(self)
         } else {
             Err(ValueError::OutOfBounds)
         }
     }
+
+    fn validate_clamped(self, epsilon: f32) -> ValueResult<Self> {
+        finite(&[self.l, self.a, self.b])?;
+        Ok(LabValue {
+            l: clamp_within(self.l, &RANGE_PCT, epsilon)?,
+            a: clamp_within(self.a, &RANGE_I8, epsilon)?,
+            b: clamp_within(self.b, &RANGE_I8, epsilon)?,
+        })
+    }
 }
 
 impl Validate for LchValue {
     fn validate(self) -> ValueResult<Self> {
-        if RANGE_PCT.contains(&self.l)
-            && RANGE_CHROMA.contains(&self.c)
+        self.validate_with(&RangeProfile::STRICT)
+    }
+
+    fn validate_with(self, profile: &RangeProfile) -> ValueResult<Self> {
+        finite(&[self.l, self.c, self.h])?;
+        if profile.lightness.contains(&self.l)
+            && profile.chroma.contains(&self.c)
             && RANGE_360.contains(&self.h)
         {
             Ok(self)
@@ -36,17 +160,186 @@ impl Validate for LchValue {
             Err(ValueError::OutOfBounds)
         }
     }
+
+    fn validate_clamped(self, epsilon: f32) -> ValueResult<Self> {
+        finite(&[self.l, self.c, self.h])?;
+        Ok(LchValue {
+            l: clamp_within(self.l, &RANGE_PCT, epsilon)?,
+            c: clamp_within(self.c, &RANGE_CHROMA, epsilon)?,
+            h: clamp_within(self.h, &RANGE_360, epsilon)?,
+        })
+    }
 }
 
 impl Validate for XyzValue {
     fn validate(self) -> ValueResult<Self> {
-        if RANGE_01.contains(&self.x)
-            && RANGE_01.contains(&self.y)
-            && RANGE_01.contains(&self.z)
+        self.validate_with(&RangeProfile::STRICT)
+    }
+
+    fn validate_with(self, profile: &RangeProfile) -> ValueResult<Self> {
+        finite(&[self.x, self.y, self.z])?;
+        if profile.xyz.contains(&self.x)
+            && profile.xyz.contains(&self.y)
+            && profile.xyz.contains(&self.z)
+        {
+            Ok(self)
+        } else {
+            Err(ValueError::OutOfBounds)
+        }
+    }
+
+    fn validate_clamped(self, epsilon: f32) -> ValueResult<Self> {
+        finite(&[self.x, self.y, self.z])?;
+        Ok(XyzValue {
+            x: clamp_within(self.x, &RANGE_01, epsilon)?,
+            y: clamp_within(self.y, &RANGE_01, epsilon)?,
+            z: clamp_within(self.z, &RANGE_01, epsilon)?,
+        })
+    }
+}
+
+#[cfg(feature = "rgb")]
+impl Validate for RgbNominalValue {
+    fn validate(self) -> ValueResult<Self> {
+        self.validate_with(&RangeProfile::STRICT)
+    }
+
+    fn validate_with(self, profile: &RangeProfile) -> ValueResult<Self> {
+        finite(&[self.r, self.g, self.b])?;
+        if profile.rgb.contains(&self.r)
+            && profile.rgb.contains(&self.g)
+            && profile.rgb.contains(&self.b)
         {
             Ok(self)
         } else {
             Err(ValueError::OutOfBounds)
         }
     }
+
+    fn validate_clamped(self, epsilon: f32) -> ValueResult<Self> {
+        finite(&[self.r, self.g, self.b])?;
+        Ok(RgbNominalValue {
+            r: clamp_within(self.r, &RANGE_01, epsilon)?,
+            g: clamp_within(self.g, &RANGE_01, epsilon)?,
+            b: clamp_within(self.b, &RANGE_01, epsilon)?,
+        })
+    }
+}
+
+#[cfg(feature = "rgb")]
+impl Validate for HslValue {
+    fn validate(self) -> ValueResult<Self> {
+        finite(&[self.h, self.s, self.l])?;
+        if RANGE_360.contains(&self.h) && RANGE_01.contains(&self.s) && RANGE_01.contains(&self.l) {
+            Ok(self)
+        } else {
+            Err(ValueError::OutOfBounds)
+        }
+    }
+}
+
+#[cfg(feature = "rgb")]
+impl Validate for HsvValue {
+    fn validate(self) -> ValueResult<Self> {
+        finite(&[self.h, self.s, self.v])?;
+        if RANGE_360.contains(&self.h) && RANGE_01.contains(&self.s) && RANGE_01.contains(&self.v) {
+            Ok(self)
+        } else {
+            Err(ValueError::OutOfBounds)
+        }
+    }
+}
+
+impl Validate for Color {
+    fn validate(self) -> ValueResult<Self> {
+        match self {
+            Color::Lab(lab) => lab.validate().map(Color::Lab),
+            Color::Lch(lch) => lch.validate().map(Color::Lch),
+            Color::Xyz(xyz) => xyz.validate().map(Color::Xyz),
+            #[cfg(feature = "rgb")]
+            Color::Rgb(_) => Ok(self),
+        }
+    }
+
+    fn validate_with(self, profile: &RangeProfile) -> ValueResult<Self> {
+        match self {
+            Color::Lab(lab) => lab.validate_with(profile).map(Color::Lab),
+            Color::Lch(lch) => lch.validate_with(profile).map(Color::Lch),
+            Color::Xyz(xyz) => xyz.validate_with(profile).map(Color::Xyz),
+            #[cfg(feature = "rgb")]
+            Color::Rgb(_) => Ok(self),
+        }
+    }
+
+    fn validate_clamped(self, epsilon: f32) -> ValueResult<Self> {
+        match self {
+            Color::Lab(lab) => lab.validate_clamped(epsilon).map(Color::Lab),
+            Color::Lch(lch) => lch.validate_clamped(epsilon).map(Color::Lch),
+            Color::Xyz(xyz) => xyz.validate_clamped(epsilon).map(Color::Xyz),
+            #[cfg(feature = "rgb")]
+            Color::Rgb(_) => Ok(self),
+        }
+    }
+}
+
+#[cfg(feature = "illuminants")]
+impl Validate for ChromaticAdaptationMethod {
+    fn validate(self) -> ValueResult<Self> {
+        match self {
+            ChromaticAdaptationMethod::Custom(matrix) if matrix.iter().any(|v| !v.is_finite()) => {
+                Err(ValueError::NotFinite)
+            }
+            ChromaticAdaptationMethod::Custom(matrix) if !matrix.is_invertible() => {
+                Err(ValueError::SingularMatrix)
+            }
+            _ => Ok(self),
+        }
+    }
+}
+
+// A sampled tone curve needs two samples to interpolate between, and must not decrease to be
+// inverted. A parametric curve divides by its gamma and by `a`, and needs a finite breakpoint.
+#[cfg(feature = "rgb")]
+fn validate_curve(curve: &ToneCurve) -> ValueResult<()> {
+    match curve {
+        ToneCurve::Parametric { g, a, d, .. } => {
+            if *g > 0.0 && *a != 0.0 && d.is_finite() {
+                Ok(())
+            } else {
+                Err(ValueError::OutOfBounds)
+            }
+        }
+        ToneCurve::Sampled(table) => {
+            finite(table)?;
+            if table.len() < 2 || table.windows(2).any(|w| w[1] < w[0]) {
+                Err(ValueError::BadFormat)
+            } else {
+                Ok(())
+            }
+        }
+        _ => Ok(()),
+    }
+}
+
+#[cfg(feature = "rgb")]
+impl Validate for RgbSystem {
+    fn validate(self) -> ValueResult<Self> {
+        match self {
+            RgbSystem::Custom { .. } | RgbSystem::Primaries { .. } => {
+                let rgb_to_xyz = self.rgb_to_xyz();
+                if rgb_to_xyz.iter().any(|v| !v.is_finite()) {
+                    return Err(ValueError::NotFinite);
+                } else if !rgb_to_xyz.is_invertible() {
+                    return Err(ValueError::SingularMatrix);
+                }
+
+                if let RgbSystem::Custom { curves, .. } = &self {
+                    curves.iter().try_for_each(validate_curve)?;
+                }
+
+                Ok(self)
+            }
+            _ => Ok(self),
+        }
+    }
 }
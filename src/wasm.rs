@@ -0,0 +1,82 @@
+//! JavaScript bindings for WebAssembly, built with
+//! [wasm-bindgen](https://rustwasm.github.io/docs/wasm-bindgen/). Requires the `wasm` feature.
+//!
+//! The bindings take and return plain numbers, so they can be used without wrapper objects.
+//! Colors are returned as a `Float32Array` of their three components, and DeltaE methods are named
+//! as they parse into a [`DEMethod`]. Invalid values throw an `Error` with the message of the
+//! [`Error`](crate::Error).
+//!
+//! ```js
+//! import { deltaE, hexToLab, labToLch } from "deltae";
+//!
+//! const [l, a, b] = hexToLab("#663399");
+//! const de = deltaE(l, a, b, 50.0, 40.0, -50.0, "de2000");
+//! const [_, c, h] = labToLch(l, a, b);
+//! ```
+
+use std::str::FromStr;
+
+use wasm_bindgen::prelude::*;
+
+use crate::*;
+
+/// The DeltaE between a reference and a sample Lab color by a named method, such as `"de2000"`,
+/// `"de1976"`, or `"cmc(2:1)"`
+#[wasm_bindgen(js_name = deltaE)]
+pub fn delta_e(
+    reference_l: f32,
+    reference_a: f32,
+    reference_b: f32,
+    sample_l: f32,
+    sample_a: f32,
+    sample_b: f32,
+    method: &str,
+) -> Result<f32, JsError> {
+    let reference = LabValue::new(reference_l, reference_a, reference_b)?;
+    let sample = LabValue::new(sample_l, sample_a, sample_b)?;
+    let method = DEMethod::from_str(method)?;
+
+    Ok(*reference.delta(sample, method).value())
+}
+
+/// Convert Lab to LCh, returning `[l, c, h]`
+#[wasm_bindgen(js_name = labToLch)]
+pub fn lab_to_lch(l: f32, a: f32, b: f32) -> Result<Vec<f32>, JsError> {
+    let lch = LchValue::from(LabValue::new(l, a, b)?);
+    Ok(vec![lch.l, lch.c, lch.h])
+}
+
+/// Convert LCh to Lab, returning `[l, a, b]`
+#[wasm_bindgen(js_name = lchToLab)]
+pub fn lch_to_lab(l: f32, c: f32, h: f32) -> Result<Vec<f32>, JsError> {
+    let lab = LabValue::from(LchValue::new(l, c, h)?);
+    Ok(vec![lab.l, lab.a, lab.b])
+}
+
+/// Convert Lab relative to D50 to XYZ, returning `[x, y, z]`
+#[wasm_bindgen(js_name = labToXyz)]
+pub fn lab_to_xyz(l: f32, a: f32, b: f32) -> Result<Vec<f32>, JsError> {
+    let xyz = XyzValue::from(LabValue::new(l, a, b)?);
+    Ok(vec![xyz.x, xyz.y, xyz.z])
+}
+
+/// Convert XYZ relative to D50 to Lab, returning `[l, a, b]`
+#[wasm_bindgen(js_name = xyzToLab)]
+pub fn xyz_to_lab(x: f32, y: f32, z: f32) -> Result<Vec<f32>, JsError> {
+    let lab = LabValue::from(XyzValue::new(x, y, z)?);
+    Ok(vec![lab.l, lab.a, lab.b])
+}
+
+/// Convert an sRGB color to Lab, returning `[l, a, b]`. The color is parsed like an
+/// [`RgbValue`], such as `"#663399"`, `"#639"`, or `"102, 51, 153"`.
+#[wasm_bindgen(js_name = hexToLab)]
+pub fn hex_to_lab(rgb: &str) -> Result<Vec<f32>, JsError> {
+    let lab = LabValue::from(RgbValue::from_str(rgb)?);
+    Ok(vec![lab.l, lab.a, lab.b])
+}
+
+/// Convert Lab to the nearest 8-bit sRGB color, formatted as `#rrggbb`
+#[wasm_bindgen(js_name = labToHex)]
+pub fn lab_to_hex(l: f32, a: f32, b: f32) -> Result<String, JsError> {
+    Ok(format!("{:x}", RgbValue::from(LabValue::new(l, a, b)?)))
+}
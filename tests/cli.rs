@@ -0,0 +1,193 @@
+// Integration tests of the deltae command line application
+
+use std::io::Write;
+use std::process::{Command, Output, Stdio};
+
+// Run deltae with arguments, writing `stdin` to its standard input
+fn deltae(args: &[&str], stdin: &str) -> Output {
+    let mut child = Command::new(env!("CARGO_BIN_EXE_deltae"))
+        .args(args)
+        .current_dir(env!("CARGO_MANIFEST_DIR"))
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .spawn()
+        .unwrap();
+
+    child.stdin.take().unwrap().write_all(stdin.as_bytes()).unwrap();
+    child.wait_with_output().unwrap()
+}
+
+// The standard output of a successful run
+fn stdout(args: &[&str], stdin: &str) -> String {
+    let output = deltae(args, stdin);
+    assert!(output.status.success(), "{}", String::from_utf8_lossy(&output.stderr));
+    String::from_utf8(output.stdout).unwrap()
+}
+
+// The standard error of a failed run
+fn stderr(args: &[&str], stdin: &str) -> String {
+    let output = deltae(args, stdin);
+    assert!(!output.status.success());
+    String::from_utf8(output.stderr).unwrap()
+}
+
+#[test]
+fn compare() {
+    assert_eq!(stdout(&["95.08, -0.17, -10.81", "89.73, 1.88, -6.96"], ""), "DE2000: 5.316941\n");
+    assert_eq!(stdout(&["-m", "1976", "50,0,0", "51,0.5,-0.5"], ""), "DE1976: 1.2247449\n");
+    assert!(stderr(&["50,0", "50,0,0"], "").contains("Error"));
+    assert!(stdout(&["--version"], "").starts_with("deltae "));
+}
+
+#[test]
+fn compare_stdin() {
+    // Pairs of colors, skipping empty lines
+    let pairs = "50,0,0\t51,0.5,-0.5\n\n95.08,-0.17,-10.81\t89.73,1.88,-6.96\n";
+    assert_eq!(stdout(&[], pairs), "DE2000: 1.333737\nDE2000: 5.316941\n");
+    assert_eq!(stdout(&["-", "-"], pairs), "DE2000: 1.333737\nDE2000: 5.316941\n");
+
+    // A color from each line compared to a fixed color, on either side
+    assert_eq!(stdout(&["-", "50,0,0"], "50,0,0\n51,0,0\n"), "DE2000: 0\nDE2000: 0.9991673\n");
+    assert_eq!(stdout(&["50,0,0", "-"], "51,0,0\n"), "DE2000: 0.9991673\n");
+
+    // Errors name the line
+    assert!(stderr(&[], "50,0,0\t51,0,0\n50,0,0\n").contains("stdin line 2: expected REFERENCE<TAB>SAMPLE"));
+    assert!(stderr(&["-", "50,0,0"], "\nbad\n").contains("stdin line 2"));
+}
+
+#[test]
+fn compare_color_types() {
+    let gray = "DE2000: 0.034439053\n";
+    assert_eq!(stdout(&["--type1", "rgb", "50,0,0", "#777777"], ""), gray);
+    assert_eq!(stdout(&["-c", "rgb", "--type0", "lab", "50,0,0", "#777777"], ""), gray);
+
+    // Prefixes override the types of the arguments and of stdin
+    assert_eq!(stdout(&["lab:50,0,0", "rgb:#777777"], ""), gray);
+    assert_eq!(stdout(&["-c", "rgb", "lab:50,0,0", "#777777"], ""), gray);
+    assert_eq!(stdout(&[], "lab:50,0,0\trgb:#777777\n"), gray);
+    assert_eq!(stdout(&["lch:50,0,0", "50,0,0"], ""), "DE2000: 0\n");
+}
+
+#[test]
+fn compare_output() {
+    let header = "reference_L,reference_a,reference_b,sample_L,sample_a,sample_b,method,delta_e,delta_l,delta_a,delta_b,delta_c,delta_h";
+    let row = "50,0,0,51,0.5,-0.5,DE2000,1.333737,1,0.5,-0.5,0.70710677,-0";
+
+    assert_eq!(stdout(&["-o", "csv", "50,0,0", "51,0.5,-0.5"], ""), format!("{}\n{}\n", header, row));
+    assert_eq!(
+        stdout(&["-o", "tsv", "50,0,0", "51,0.5,-0.5"], ""),
+        format!("{}\n{}\n", header.replace(',', "\t"), row.replace(',', "\t")),
+    );
+
+    // The header is printed once for a batch from stdin
+    let csv = stdout(&["--output", "csv"], "50,0,0\t51,0.5,-0.5\n50,0,0\t51,0.5,-0.5\n");
+    assert_eq!(csv, format!("{}\n{}\n{}\n", header, row, row));
+
+    // One JSON object per line, with the fields in the order of the CSV header
+    let json = stdout(&["-o", "json"], "50,0,0\t51,0.5,-0.5\n95.08,-0.17,-10.81\t89.73,1.88,-6.96\n");
+    let lines: Vec<&str> = json.lines().collect();
+    assert_eq!(lines.len(), 2);
+    assert_eq!(
+        lines[0],
+        r#"{"reference_L":50,"reference_a":0,"reference_b":0,"sample_L":51,"sample_a":0.5,"sample_b":-0.5,"method":"DE2000","delta_e":1.333737,"delta_l":1,"delta_a":0.5,"delta_b":-0.5,"delta_c":0.70710677,"delta_h":-0}"#,
+    );
+    let value: serde_json::Value = serde_json::from_str(lines[1]).unwrap();
+    assert_eq!(value["method"], "DE2000");
+    assert_eq!(value["delta_e"].as_f64().unwrap() as f32, 5.316941);
+}
+
+#[test]
+fn compare_swatch() {
+    // Truecolor blocks of the reference and sample before the value
+    let swatch = stdout(&["-s", "50,0,0", "51,0,0"], "");
+    assert!(swatch.starts_with("\x1b[48;2;119;119;119m    \x1b[0m \x1b[48;2;121;121;121m    \x1b[0m "));
+    assert!(swatch.ends_with(" DE2000: 0.9991673\n"));
+
+    // The swatches are in the RGB system of the conditions, from Lab relative to the illuminant
+    assert!(stdout(&["-s", "50,60,0", "51,0,0"], "").starts_with("\x1b[48;2;206;63;122m"));
+    assert!(stdout(&["-s", "-r", "displayp3", "50,60,0", "51,0,0"], "").starts_with("\x1b[48;2;190;74;121m"));
+    assert!(stdout(&["-s", "-i", "d65", "50,0,0", "51,0,0"], "").starts_with("\x1b[48;2;119;119;119m"));
+
+    // Only plain output has swatches
+    assert!(!stdout(&["-s", "-o", "csv", "50,0,0", "51,0,0"], "").contains('\x1b'));
+}
+
+#[test]
+fn convert() {
+    assert_eq!(stdout(&["convert", "-f", "rgb", "-t", "lab", "#FF0000"], ""), "[L:54.291756, a:80.814835, b:69.88545]\n");
+    assert_eq!(stdout(&["convert", "-t", "rgb", "-"], "50,0,0\n\n51,0,0\n").lines().count(), 2);
+    assert!(stderr(&["convert", "-t", "rgb", "-"], "50,0,0\nbad\n").contains("stdin line 2"));
+}
+
+#[test]
+fn stats() {
+    // By position, each row is compared to the row of the same index
+    let by_position = stdout(&["stats", "tests/data/reference.csv", "tests/data/sample.csv"], "");
+    assert_eq!(
+        by_position,
+        "\
+method:          DE2000
+count:           3
+mean:            36.49546
+max:             44.049343
+std dev:         5.4369807
+95th percentile: 43.04057
+worst:
+  red: 44.049343
+  white: 33.961605
+  gray: 31.47543
+",
+    );
+
+    let by_id = stdout(&["stats", "--by-id", "-w", "2", "tests/data/reference.csv", "tests/data/sample.csv"], "");
+    assert_eq!(
+        by_id,
+        "\
+method:          DE2000
+count:           3
+mean:            2.9010916
+max:             5.316941
+std dev:         1.7332889
+95th percentile: 4.990506
+worst:
+  white: 5.316941
+  red: 2.052597
+",
+    );
+
+    assert!(stderr(&["stats", "tests/data/missing.csv", "tests/data/sample.csv"], "").contains("tests/data/missing.csv"));
+}
+
+#[test]
+fn cgats() {
+    let table = stdout(&["cgats", "--sort", "delta", "tests/data/reference.cgats", "tests/data/sample.cgats"], "");
+    assert_eq!(
+        table,
+        "\
+SAMPLE_ID\tDE\tDL\tDa\tDb\tDC\tDH
+A1\t5.316941\t-5.3499985\t2.05\t3.8500004\t-3.6018991\t2.4599464
+A2\t2.052597\t-1.2900009\t-2.800003\t2.1100006\t-0.6819916\t3.4390457
+A3\t1.333737\t1\t0.5\t-0.5\t0.70710677\t-0
+
+method:          DE2000
+count:           3
+mean:            2.9010916
+max:             5.316941
+std dev:         1.7332889
+95th percentile: 4.990506
+",
+    );
+
+    // The CGATS patches are the rows of the CSV files, paired by ID
+    let by_id = stdout(&["stats", "--by-id", "tests/data/reference.csv", "tests/data/sample.csv"], "");
+    assert!(by_id.starts_with(&table[table.find("method:").unwrap()..]));
+
+    assert!(stderr(&["cgats", "tests/data/reference.cgats", "tests/data/reference.csv"], "").contains("tests/data/reference.csv"));
+}
+
+#[test]
+fn completions_and_man() {
+    assert!(stdout(&["completions", "bash"], "").contains("deltae"));
+    assert!(stdout(&["man"], "").starts_with(".ie"));
+}